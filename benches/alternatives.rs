@@ -0,0 +1,169 @@
+use std::str::FromStr;
+
+use aoc23::{
+    fourteenth::{alternatives::DensePlatform, bits::BitPlatform, Platform, CYCLE},
+    generate::{grid, springs},
+    rng::stream,
+    sixteenth::Contraption,
+    thirteenth::{alternatives::BitGrid, parse_grids},
+    twelfth::Springs,
+    Direction, Part,
+};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+const SIZE: usize = 100;
+
+/// Random `SIZE`x`SIZE` puzzle input made from `chars`, similar in size to the real inputs. The
+/// numbers come from the crate's stream for `subsystem`, so `AOC23_SEED` repeats them
+fn generate(chars: &[char], subsystem: &str) -> String {
+    grid(&mut stream(subsystem), chars, SIZE, SIZE)
+}
+
+fn inputs(group: &str, sample: &'static str, chars: &[char]) -> [(&'static str, String); 2] {
+    [
+        ("sample", sample.to_string()),
+        ("generated", generate(chars, &format!("bench {group}"))),
+    ]
+}
+
+fn tilt(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fourteenth/cycle");
+    let inputs = inputs(
+        "fourteenth/cycle",
+        include_str!("../sample/fourteenth.txt"),
+        &['.', '.', '.', 'O', 'O', '#'],
+    );
+    for (name, input) in inputs.iter() {
+        let platform = Platform::from_str(input).expect("parsing");
+        group.bench_with_input(BenchmarkId::new("hashmap", name), &platform, |b, p| {
+            b.iter_batched_ref(
+                || p.clone(),
+                |p| CYCLE.into_iter().for_each(|dir| p.tilt(dir)),
+                criterion::BatchSize::SmallInput,
+            )
+        });
+        let dense = DensePlatform::from(&platform);
+        group.bench_with_input(BenchmarkId::new("dense", name), &dense, |b, p| {
+            b.iter_batched_ref(
+                || p.clone(),
+                |p| CYCLE.into_iter().for_each(|dir| p.tilt(dir)),
+                criterion::BatchSize::SmallInput,
+            )
+        });
+        let bits = BitPlatform::try_from(&platform).expect("bits");
+        group.bench_with_input(BenchmarkId::new("bitset", name), &bits, |b, p| {
+            b.iter_batched_ref(
+                || p.clone(),
+                |p| CYCLE.into_iter().for_each(|dir| p.tilt(dir)),
+                criterion::BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn energize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sixteenth/energize");
+    // Advancing all beams one step at a time is slow on the generated input
+    group.sample_size(10);
+    let inputs = inputs(
+        "sixteenth/energize",
+        include_str!("../sample/sixteenth.txt"),
+        &['.', '.', '.', '.', '.', '.', '.', '.', '/', '\\', '|', '-'],
+    );
+    let entry = (Direction::Right, 0);
+    for (name, input) in inputs.iter() {
+        group.bench_with_input(BenchmarkId::new("beams", name), input, |b, input| {
+            b.iter(|| {
+                let mut contraption = Contraption::from_str(input).expect("parsing");
+                contraption.set_entry(entry).expect("entry");
+                contraption.run_to_equilibrium().expect("running");
+                contraption.energized_cells().len()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("sweep", name), input, |b, input| {
+            b.iter(|| {
+                let contraption = Contraption::from_str(input).expect("parsing");
+                contraption.sweep(entry)
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("energize", name), input, |b, input| {
+            b.iter(|| {
+                let contraption = Contraption::from_str(input).expect("parsing");
+                contraption.energize(entry)
+            })
+        });
+    }
+    group.finish();
+}
+
+fn best_entry(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sixteenth/best_entry");
+    group.sample_size(10);
+    let inputs = inputs(
+        "sixteenth/best_entry",
+        include_str!("../sample/sixteenth.txt"),
+        &['.', '.', '.', '.', '.', '.', '.', '.', '/', '\\', '|', '-'],
+    );
+    for (name, input) in inputs.iter() {
+        let contraption = Contraption::from_str(input).expect("parsing");
+        group.bench_with_input(BenchmarkId::new("sweep", name), &contraption, |b, c| {
+            b.iter(|| c.sweep_best_entry())
+        });
+        group.bench_with_input(BenchmarkId::new("runs", name), &contraption, |b, c| {
+            b.iter(|| c.best_entry())
+        });
+    }
+    group.finish();
+}
+
+fn arrangements(c: &mut Criterion) {
+    let mut group = c.benchmark_group("twelfth/arrangements");
+    // Short enough reports for the enumeration to finish
+    let generated = springs(&mut stream("bench twelfth/arrangements"), SIZE, 16);
+    let inputs = [
+        ("sample", include_str!("../sample/twelfth.txt").to_string()),
+        ("generated", generated),
+    ];
+    for (name, input) in inputs.iter() {
+        let springs = Springs::from_str(input).expect("parsing");
+        group.bench_with_input(BenchmarkId::new("enumerate", name), &springs, |b, s| {
+            b.iter(|| {
+                s.reports()
+                    .map(|r| r.enumerate_arrangements().expect("few unknowns"))
+                    .sum::<usize>()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("memoized", name), &springs, |b, s| {
+            b.iter(|| s.reports().map(|r| r.arrangements()).sum::<usize>())
+        });
+    }
+    group.finish();
+}
+
+fn mirror(c: &mut Criterion) {
+    let mut group = c.benchmark_group("thirteenth/mirror");
+    let inputs = inputs(
+        "thirteenth/mirror",
+        include_str!("../sample/thirteenth.txt"),
+        &['.', '#'],
+    );
+    for (name, input) in inputs.iter() {
+        let grids = parse_grids(input).expect("parsing");
+        for part in [Part::One, Part::Two] {
+            let id = BenchmarkId::new(format!("ndarray/{part:?}"), name);
+            group.bench_with_input(id, &grids, |b, grids| {
+                b.iter(|| grids.iter().map(|g| g.mirror(part)).collect::<Vec<_>>())
+            });
+            let bits = grids.iter().map(BitGrid::from).collect::<Vec<_>>();
+            let id = BenchmarkId::new(format!("bitset/{part:?}"), name);
+            group.bench_with_input(id, &bits, |b, bits| {
+                b.iter(|| bits.iter().map(|g| g.mirror(part)).collect::<Vec<_>>())
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, tilt, energize, best_entry, mirror, arrangements);
+criterion_main!(benches);
@@ -0,0 +1,62 @@
+use std::iter::successors;
+
+use aoc23::{
+    rng::stream,
+    thirteenth::{parse_grids, total_score},
+    Part,
+};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use enum_iterator::all;
+use rand::Rng;
+use rayon::ThreadPoolBuilder;
+
+/// Far more grids than the real inputs have, for the work to be worth spreading over the cores
+const GRIDS: usize = 10_000;
+
+/// `GRIDS` random grids of ash & rocks, sized like the ones in the real inputs. Most of them have
+/// no mirror at all, so each fold gets checked. They come from the crate's stream for the bench,
+/// so `AOC23_SEED` repeats them
+fn generate() -> String {
+    let mut rng = stream("bench thirteenth/total_score");
+    (0..GRIDS)
+        .map(|_| {
+            let (rows, cols) = (rng.gen_range(5..=17), rng.gen_range(5..=17));
+            (0..rows)
+                .map(|_| {
+                    (0..cols)
+                        .map(|_| if rng.gen_bool(0.5) { '#' } else { '.' })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn score(c: &mut Criterion) {
+    let grids = parse_grids(&generate()).expect("parsing");
+    let mut group = c.benchmark_group("thirteenth/total_score");
+    group.sample_size(10);
+    // Powers of two up to all cores
+    let cores = std::thread::available_parallelism().map_or(1, usize::from);
+    let jobs = successors(Some(1), |jobs| Some(jobs * 2))
+        .take_while(|jobs| *jobs < cores)
+        .chain([cores]);
+    for jobs in jobs {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build()
+            .expect("thread pool");
+        for part in all::<Part>() {
+            let id = BenchmarkId::new(format!("{part:?}"), jobs);
+            group.bench_with_input(id, &grids, |b, grids| {
+                b.iter(|| pool.install(|| total_score(grids, part)))
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, score);
+criterion_main!(benches);
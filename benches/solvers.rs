@@ -0,0 +1,45 @@
+use std::path::Path;
+
+use aoc23::{solvers, Part, Solver, DEFAULT_STRATEGY};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use enum_iterator::all;
+
+/// Personal puzzle input of a day if there is one, its sample otherwise
+fn input(solver: &dyn Solver) -> Option<(&'static str, String)> {
+    ["input", "sample"].into_iter().find_map(|dir| {
+        let input = std::fs::read_to_string(solver.input(Path::new(dir))).ok()?;
+        Some((dir, aoc23::normalize_input(&input)))
+    })
+}
+
+fn solve(c: &mut Criterion) {
+    for (day, solver) in solvers() {
+        let Some((kind, input)) = input(solver.as_ref()) else {
+            eprintln!("Skipping day {day}, it has neither input nor sample");
+            continue;
+        };
+        let mut group = c.benchmark_group(solver.name());
+        group.sample_size(10);
+        for part in all::<Part>() {
+            for strategy in solver.strategy_names() {
+                let id = match strategy {
+                    DEFAULT_STRATEGY => format!("{part:?}"),
+                    strategy => format!("{part:?}/{strategy}"),
+                };
+                // Some can't solve the sample, e.g. Day 21's extrapolation, or are too slow for
+                // the personal input, e.g. Day 18's flood fill
+                if let Err(e) = solver.solve_with(strategy, part, &input) {
+                    eprintln!("Skipping day {day} part {part:?} by {strategy}: {e:#}");
+                    continue;
+                }
+                group.bench_with_input(BenchmarkId::new(id, kind), &input, |b, input| {
+                    b.iter(|| solver.solve_with(strategy, part, input).expect("solving"))
+                });
+            }
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, solve);
+criterion_main!(benches);
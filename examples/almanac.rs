@@ -0,0 +1,19 @@
+//! Day 5: following a seed through all maps of the almanac and a location back to its seed
+
+use aoc23::{fifth::Almanac, Part};
+
+const INPUT: &str = include_str!("../sample/fifth.txt");
+
+fn main() -> anyhow::Result<()> {
+    let (almanac, seeds) = Almanac::parse(Part::One, INPUT)?;
+    for (resource, ranges) in almanac.trace(&seeds[..1]) {
+        println!("{resource:?}: {ranges:?}");
+    }
+
+    let location = almanac.best_location(&seeds);
+    println!(
+        "Best location {location} comes from seed(s) {:?}",
+        almanac.seed_for_location(location)
+    );
+    Ok(())
+}
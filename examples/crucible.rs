@@ -0,0 +1,54 @@
+//! Shortest paths with `aoc23::search`: least heat loss of a crucible, which must turn after at
+//! most three blocks (Day 17)
+
+use aoc23::{search::dijkstra, Coord, Direction};
+use enum_iterator::all;
+
+const CITY: &str = "2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533";
+
+/// Position, heading & how many blocks the crucible moved straight into that heading
+type Crucible = (Coord, Option<Direction>, u32);
+
+fn main() {
+    let city = CITY
+        .lines()
+        .map(|line| line.chars().filter_map(|c| c.to_digit(10)).collect())
+        .collect::<Vec<Vec<u32>>>();
+    let heat_loss = |c: Coord| -> Option<u32> {
+        city.get(usize::try_from(c.y).ok()?)?
+            .get(usize::try_from(c.x).ok()?)
+            .copied()
+    };
+    let goal = Coord::new(city[0].len() as i32 - 1, city.len() as i32 - 1);
+
+    let successors = |(pos, dir, run): &Crucible| {
+        all::<Direction>()
+            .filter(|next| *dir != Some(next.opposite()))
+            .filter(|next| *dir != Some(*next) || *run < 3)
+            .filter_map(|next| {
+                let pos = *pos + Coord::from(next);
+                let run = if *dir == Some(next) { run + 1 } else { 1 };
+                Some(((pos, Some(next), run), heat_loss(pos)?))
+            })
+            .collect::<Vec<_>>()
+    };
+    let start = (Coord::new(0, 0), None, 0);
+    match dijkstra(start, successors, |(pos, _, _)| *pos == goal) {
+        Some((path, loss)) => {
+            println!("Least heat loss is {loss} over {} blocks", path.len() - 1)
+        }
+        None => println!("The crucible can't reach the factory"),
+    }
+}
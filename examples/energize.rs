@@ -0,0 +1,28 @@
+//! Day 16: how many cells a beam energizes, once in one go and once step by step
+
+use std::str::FromStr;
+
+use aoc23::sixteenth::{Contraption, PART_ONE_ENTRY};
+
+const INPUT: &str = include_str!("../sample/sixteenth.txt");
+
+fn main() -> anyhow::Result<()> {
+    let mut contraption = Contraption::from_str(INPUT)?;
+    println!(
+        "Entering at {:?} energizes {} cells",
+        PART_ONE_ENTRY,
+        contraption.energize(PART_ONE_ENTRY)
+    );
+
+    contraption.set_entry(PART_ONE_ENTRY)?;
+    let steps = contraption.run_to_equilibrium()?;
+    println!(
+        "Beams came to rest after {steps} steps with {} energized cells",
+        contraption.energized_cells().len()
+    );
+
+    if let Some((entry, cells)) = contraption.best_entry() {
+        println!("Best is entering at {entry:?} with {cells} energized cells");
+    }
+    Ok(())
+}
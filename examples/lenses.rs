@@ -0,0 +1,22 @@
+//! Day 15: putting lenses into the boxes of the HASHMAP by hand
+
+use aoc23::fifteenth::HashMap;
+
+fn main() {
+    let mut hashmap = HashMap::default();
+    hashmap.insert("rn", 1);
+    hashmap.insert("cm", 2);
+    hashmap.insert("qp", 3);
+    hashmap.remove("qp");
+    hashmap.insert("rn", 4);
+
+    for (box_, slot, label, focal_length) in &hashmap {
+        println!("Box {box_}, slot {slot}: {label} {focal_length}");
+    }
+    println!(
+        "{} lenses in {} boxes, focusing power {}",
+        hashmap.len(),
+        hashmap.boxes().count(),
+        hashmap.focal_power()
+    );
+}
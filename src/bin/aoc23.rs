@@ -0,0 +1,436 @@
+use std::{
+    io::Write,
+    panic::{catch_unwind, set_hook, take_hook},
+    path::{Path, PathBuf},
+    sync::mpsc::channel,
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Result};
+use aoc23::{
+    cache::{record, report_runs, runs, Run},
+    fixtures::{self, FIXTURES_DIR},
+    input::{fetch, read, INPUT_DIR},
+    minimize::minimize,
+    render::{diagram, render, Format},
+    report::{diff, Entry, Report, Summary, REGRESSION_THRESHOLD},
+    rng, solve, solve_with, solvers, Day, Part, DEFAULT_STRATEGY,
+};
+use clap::{Parser, Subcommand, ValueEnum};
+use enum_iterator::all;
+use notify::{RecursiveMode, Watcher};
+
+/// Editors tend to write a file in several steps, wait for them all before solving again
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Solve any day of the library, e.g. `aoc23 16 two`, or use one of the tools working across days
+#[derive(Debug, Parser)]
+#[clap(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+struct Options {
+    #[clap(subcommand)]
+    command: Option<Command>,
+
+    /// Day to solve, e.g. `16`, `16th` or `sixteenth`
+    #[clap(required = true)]
+    day: Option<Day>,
+
+    /// Which part of the day to solve
+    #[clap(required = true, value_enum)]
+    part: Option<Part>,
+
+    /// Path to the file with the input data, `sample/<day>.txt` by default, e.g. `sample/fifth.txt`
+    #[clap(short, long)]
+    input: Option<PathBuf>,
+
+    /// Way of solving the day, see `aoc23 strategies <day>`
+    #[clap(short, long, default_value = DEFAULT_STRATEGY)]
+    strategy: String,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Solve both parts of all days and print answers & timings as JSON. Like `all`, this logs
+    /// the solves next to the inputs for `summary`
+    Report {
+        /// Directory with one `<day>.txt` input file per day, e.g. `fifth.txt`
+        #[clap(short, long, default_value = "input")]
+        dir: PathBuf,
+    },
+
+    /// Solve both parts of all days and print answers & timings as a table, failing if any day
+    /// fails
+    All {
+        /// Directory with one `<day>.txt` input file per day, e.g. `fifth.txt`
+        #[clap(short, long, default_value = "input")]
+        dir: PathBuf,
+    },
+
+    /// Sum up the logged solves of each day: stars, fastest time & last run, and totals below
+    Summary {
+        /// Directory with the inputs and the log of their solves
+        #[clap(short, long, default_value = INPUT_DIR)]
+        dir: PathBuf,
+
+        /// Further JSON reports to include, dated by when they got written
+        #[clap(long)]
+        report: Vec<PathBuf>,
+    },
+
+    /// List the ways of solving a day, for `--strategy`
+    Strategies {
+        /// Day to list the strategies of, e.g. `18` or `eighteenth`
+        day: Day,
+    },
+
+    /// Solve both parts of all days with an input in `dir` by all their strategies, failing if any
+    /// of them disagree
+    Verify {
+        /// Directory with one `<day>.txt` input file per day, e.g. `fifth.txt`
+        #[clap(short, long, default_value = "input")]
+        dir: PathBuf,
+    },
+
+    /// Download the personal input of a day, using the session cookie in `$AOC_SESSION` or
+    /// `~/.config/aoc23/session`
+    Fetch {
+        /// Day to download, e.g. `14` or `fourteenth`
+        #[clap(short, long)]
+        day: Day,
+
+        /// Directory to cache the input in, as `<day>.txt`, e.g. `fifth.txt`
+        #[clap(long, default_value = INPUT_DIR)]
+        dir: PathBuf,
+
+        /// Download the input again, even if it is cached already
+        #[clap(long)]
+        force: bool,
+    },
+
+    /// Compare two JSON reports, flagging changed answers and slowdowns
+    Diff {
+        /// Report of the version to compare against
+        #[clap(long)]
+        old: PathBuf,
+
+        /// Report of the version under test
+        #[clap(long)]
+        new: PathBuf,
+
+        /// Relative slowdown, above which a solve counts as regression
+        #[clap(long, default_value_t = REGRESSION_THRESHOLD)]
+        threshold: f64,
+    },
+
+    /// Draw the parsed input of one day to stdout, without solving it
+    Show {
+        /// Day to work on, e.g. `14` or `fourteenth`
+        day: Day,
+
+        /// Directory with one `<day>.txt` input file per day, e.g. `fifth.txt`
+        #[clap(short, long, default_value = "input")]
+        dir: PathBuf,
+
+        #[clap(short, long, value_enum, default_value_t)]
+        format: Format,
+    },
+
+    /// Solve one part of a day again whenever its input file changes
+    Watch {
+        /// Day to work on, e.g. `14` or `fourteenth`
+        day: Day,
+
+        #[clap(value_enum)]
+        part: Part,
+
+        /// Directory with one `<day>.txt` input file per day, e.g. `fifth.txt`
+        #[clap(short, long, default_value = "input")]
+        dir: PathBuf,
+    },
+
+    /// Explain why one of two hands of Day 7 outranks the other
+    Explain {
+        /// Day to explain, only `7` can explain itself so far
+        day: Day,
+
+        /// Two hands separated by whitespace, e.g. `"KTJJT T55J5"`
+        #[clap(long)]
+        hands: String,
+
+        /// In part two `J`s are jokers instead of jacks
+        #[clap(short, long, value_enum, default_value_t)]
+        part: Part,
+    },
+
+    /// Shrink the input of a day to a minimal one, on which the solver still fails
+    Minimize {
+        /// Day to work on, e.g. `14` or `fourteenth`
+        day: Day,
+
+        #[clap(value_enum)]
+        part: Part,
+
+        /// Directory with one `<day>.txt` input file per day, e.g. `fifth.txt`
+        #[clap(short, long, default_value = "input")]
+        dir: PathBuf,
+
+        /// What counts as failing
+        #[clap(short, long, value_enum, default_value_t)]
+        until: Failure,
+    },
+
+    /// Draw the generated fixtures again and write the answers of all fixtures next to them, for
+    /// the tests to check the solvers against
+    Fixtures {
+        /// Directory with the fixtures
+        #[clap(short, long, default_value = FIXTURES_DIR)]
+        dir: PathBuf,
+    },
+}
+
+/// Ways a solver can fail, which the minimized input must keep showing
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Failure {
+    /// The solver panics
+    #[default]
+    Panic,
+    /// The solver panics or returns an error, e.g. because the input can't be parsed
+    Error,
+}
+
+fn fails(day: Day, part: Part, failure: Failure, input: &str) -> bool {
+    match catch_unwind(|| solve(day, part, input)) {
+        Err(_) => true,
+        Ok(result) => failure == Failure::Error && result.is_err(),
+    }
+}
+
+fn input_path(dir: &Path, day: Day) -> Result<PathBuf> {
+    solvers()
+        .get(&day)
+        .map(|solver| solver.input(dir))
+        .ok_or(anyhow!("Day {day} is not part of the library"))
+}
+
+/// Solves one part of a day and logs the solve next to the input, unless it was solved by another
+/// than the default strategy, which would skew the fastest times of `summary`
+fn solve_file(day: Day, part: Part, path: &Path, strategy: &str) -> Result<()> {
+    let input = aoc23::normalize_input(&read(path, day.number())?);
+    let start = Instant::now();
+    let answer = solve_with(day, strategy, part, &input)?;
+    let seconds = start.elapsed().as_secs_f64();
+    println!("Day {day} part {part:?}: {answer} ({seconds:.3}s)");
+    if strategy == DEFAULT_STRATEGY {
+        let dir = path.parent().unwrap_or(Path::new("."));
+        let entry = Entry {
+            day,
+            part,
+            answer,
+            seconds,
+        };
+        record(dir, [Run::now(entry)])?;
+    }
+    Ok(())
+}
+
+fn watch(day: Day, part: Part, dir: PathBuf) -> Result<()> {
+    let path = input_path(&dir, day)?;
+    let (tx, rx) = channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    // Watch the whole directory, since editors often replace the file instead of writing into it
+    watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+    eprintln!("Watching {path:?}, press Ctrl+C to stop");
+
+    let mut changed = true;
+    loop {
+        if changed {
+            sleep(DEBOUNCE);
+            rx.try_iter().for_each(drop);
+            if let Err(e) = solve_file(day, part, &path, DEFAULT_STRATEGY) {
+                eprintln!("Day {day} part {part:?}: {e:#}");
+            }
+        }
+        let event = rx.recv()??;
+        changed = (event.kind.is_modify() || event.kind.is_create())
+            && event
+                .paths
+                .iter()
+                .any(|p| p.file_name() == path.file_name());
+    }
+}
+
+/// Solves both parts of all days with an input in `dir`, collecting the solves which failed instead
+/// of stopping at the first one. The successful ones get logged in `dir`
+fn report(dir: PathBuf) -> Result<(Report, Vec<anyhow::Error>)> {
+    let mut report = Report::default();
+    let mut failures = Vec::new();
+    for (day, solver) in solvers() {
+        let path = solver.input(&dir);
+        if !path.exists() {
+            eprintln!("Skipping day {day}, {path:?} does not exist");
+            continue;
+        }
+        let input = aoc23::normalize_input(&std::fs::read_to_string(path)?);
+        for part in all::<Part>() {
+            let start = Instant::now();
+            match solver.solve(part, &input) {
+                Ok(answer) => report.entries.push(Entry {
+                    day,
+                    part,
+                    answer,
+                    seconds: start.elapsed().as_secs_f64(),
+                }),
+                Err(e) => failures.push(e.context(format!("Day {day} part {part:?}"))),
+            }
+        }
+    }
+    record(&dir, report.entries.iter().cloned().map(Run::now))?;
+    Ok((report, failures))
+}
+
+/// Answers of all strategies differing from the default one, as errors. Strategies failing to
+/// solve at all only get reported, since some of them are meant for small inputs only
+fn verify(dir: PathBuf) -> Result<Vec<anyhow::Error>> {
+    let mut disagreements = Vec::new();
+    for (day, solver) in solvers() {
+        let path = solver.input(&dir);
+        if !path.exists() || solver.strategies().is_empty() {
+            continue;
+        }
+        let input = aoc23::normalize_input(&std::fs::read_to_string(path)?);
+        for part in all::<Part>() {
+            let expected = solver.solve(part, &input)?;
+            for strategy in solver.strategies() {
+                match solver.solve_with(strategy.name, part, &input) {
+                    Ok(answer) if answer == expected => {
+                        println!("Day {day} part {part:?} by {}: {answer}", strategy.name)
+                    }
+                    Ok(answer) => disagreements.push(anyhow!(
+                        "Day {day} part {part:?} by {}: {answer} instead of {expected}",
+                        strategy.name
+                    )),
+                    Err(e) => eprintln!("Day {day} part {part:?} by {}: {e:#}", strategy.name),
+                }
+            }
+        }
+    }
+    Ok(disagreements)
+}
+
+fn read_report(path: PathBuf) -> Result<Report> {
+    Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+}
+
+fn main() -> Result<()> {
+    let args = Options::parse();
+    let Some(command) = args.command else {
+        let (Some(day), Some(part)) = (args.day, args.part) else {
+            return Err(anyhow!(
+                "Either a day & part to solve or a command is needed"
+            ));
+        };
+        let path = match args.input {
+            Some(path) => path,
+            None => input_path(Path::new("sample"), day)?,
+        };
+        return solve_file(day, part, &path, &args.strategy);
+    };
+    match command {
+        Command::Report { dir } => {
+            let (report, failures) = report(dir)?;
+            if let Some(e) = failures.into_iter().next() {
+                return Err(e);
+            }
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        Command::All { dir } => {
+            let (report, failures) = report(dir)?;
+            print!("{report}");
+            for e in &failures {
+                eprintln!("{e:#}");
+            }
+            if !failures.is_empty() {
+                return Err(anyhow!("{} solves failed", failures.len()));
+            }
+        }
+        Command::Summary { dir, report } => {
+            let mut runs = runs(&dir)?;
+            for path in report {
+                runs.extend(report_runs(path)?);
+            }
+            print!("{}", Summary::new(&runs));
+        }
+        Command::Strategies { day } => {
+            let solver = solvers()
+                .remove(&day)
+                .ok_or(anyhow!("Day {day} is not part of the library"))?;
+            println!("{DEFAULT_STRATEGY}");
+            for strategy in solver.strategies() {
+                println!("{}: {}", strategy.name, strategy.about);
+            }
+        }
+        Command::Verify { dir } => {
+            let disagreements = verify(dir)?;
+            for e in &disagreements {
+                eprintln!("{e:#}");
+            }
+            if !disagreements.is_empty() {
+                return Err(anyhow!("{} answers disagree", disagreements.len()));
+            }
+        }
+        Command::Fetch { day, dir, force } => println!("{}", fetch(day, &dir, force)?.display()),
+        Command::Diff {
+            old,
+            new,
+            threshold,
+        } => {
+            let (old, new) = (read_report(old)?, read_report(new)?);
+            let changes = diff(&old, &new, threshold);
+            for change in &changes {
+                println!("{change}");
+            }
+            let failures = changes.iter().filter(|c| c.is_failure()).count();
+            if failures > 0 {
+                return Err(anyhow!("{failures} solves changed for the worse"));
+            }
+            println!("No changed answers or regressions");
+        }
+        Command::Show { day, dir, format } => {
+            let path = input_path(&dir, day)?;
+            let input = aoc23::normalize_input(&std::fs::read_to_string(path)?);
+            std::io::stdout().write_all(&render(&*diagram(day, &input)?, format)?)?;
+        }
+        Command::Watch { day, part, dir } => watch(day, part, dir)?,
+        Command::Explain { day, hands, part } => match day {
+            #[cfg(feature = "day07")]
+            Day::Seventh => println!("{}", aoc23::seventh::explain(&hands, part)?),
+            _ => {
+                return Err(anyhow!(
+                    "Day {day} has nothing to explain ({hands:?}, {part:?})"
+                ))
+            }
+        },
+        Command::Minimize {
+            day,
+            part,
+            dir,
+            until,
+        } => {
+            let input = aoc23::normalize_input(&std::fs::read_to_string(input_path(&dir, day)?)?);
+            let structure = solvers()
+                .get(&day)
+                .map(|solver| solver.structure())
+                .unwrap_or_default();
+            // Don't flood the terminal with the messages of all the expected panics
+            set_hook(Box::new(|_| {}));
+            let minimal = minimize(&input, structure, |input| fails(day, part, until, input));
+            drop(take_hook());
+            print!("{}", minimal?);
+        }
+        Command::Fixtures { dir } => {
+            rng::install(fixtures::SEED)?;
+            println!("{}", fixtures::write(&dir)?.display());
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+use aoc23::{
+    eighteenth::{flood_fill, lagoon_size, DigPlan},
+    timing::Timings,
+    Part,
+};
+use clap::Parser;
+
+/// Day 18: Lavaduct Lagoon
+#[derive(Debug, Parser)]
+struct Options {
+    /// Path to the file with the input data
+    #[clap(short, long, default_value = "sample/eighteenth.txt")]
+    input: String,
+
+    /// Which part of the day to solve
+    part: Part,
+
+    /// Count the cells by flood filling around the trench instead, slow but good to cross-check
+    /// the shoelace formula on small plans
+    #[clap(short, long)]
+    flood_fill: bool,
+
+    /// Print how long each phase of the solve took
+    #[clap(short, long)]
+    time: bool,
+
+    /// Together with `--time` also print what each phase allocated (needs `alloc-stats` feature)
+    #[clap(short, long)]
+    verbose: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Options::parse();
+    let input = aoc23::normalize_input(&aoc23::input::read(&args.input, 18)?);
+    let mut timings = Timings::new(args.time, args.verbose);
+    let plan = timings.measure("parse", || DigPlan::from_str(&input))?;
+    let instructions = plan.instructions(args.part)?;
+    let solution = if args.flood_fill {
+        timings.measure("flood fill", || flood_fill(&instructions))? as i64
+    } else {
+        timings.measure("shoelace", || lagoon_size(&instructions))?
+    };
+    println!("Solution part {:?}: {solution}", args.part);
+    eprint!("{timings}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc23::eighteenth::solve;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Part::One, 62)]
+    #[case(Part::Two, 952408144115)]
+    fn sample(#[case] part: Part, #[case] expected: i64) {
+        let input = include_str!("../../sample/eighteenth.txt");
+        assert_eq!(solve(part, input).expect("solving"), expected);
+    }
+}
@@ -1,10 +1,10 @@
-use std::{hash::Hasher, str::FromStr};
-
 use anyhow::{anyhow, Result};
-use aoc23::{
-    fifteenth::{animation, HashMap, HASH},
-    Part,
-};
+
+#[cfg(feature = "animations")]
+use aoc23::fifteenth::{animation, HashMap};
+#[cfg(feature = "animations")]
+use aoc23::{camera::CameraArgs, capture::CaptureArgs, demo::DemoArgs, Presentation};
+use aoc23::{fifteenth::solve, Part};
 use clap::Parser;
 
 /// Day 15: Lens Library
@@ -24,38 +24,50 @@ struct Options {
     /// How fast shall the animation run initially
     #[clap(short, long, default_value_t = 1.5)]
     frequency: f32,
+
+    #[cfg(feature = "animations")]
+    #[clap(flatten)]
+    camera: CameraArgs,
+
+    #[cfg(feature = "animations")]
+    #[clap(flatten)]
+    demo: DemoArgs,
+
+    #[cfg(feature = "animations")]
+    #[clap(flatten)]
+    capture: CaptureArgs,
 }
 
 fn main() -> Result<()> {
     let args = Options::parse();
-    let input = std::fs::read_to_string(args.input)?;
+    let input = aoc23::normalize_input(&aoc23::input::read(&args.input, 15)?);
     let solution = match args.part {
         Part::One if args.animate => return Err(anyhow!("Part one cannot be animated")),
-        Part::One => input
-            .lines()
-            .map(|line| {
-                line.split(',')
-                    .map(|chunk| chunk.bytes().collect::<HASH>().finish())
-                    .sum::<u64>()
-            })
-            .sum::<u64>(),
-        Part::Two => {
-            if args.animate {
-                animation::run(args.frequency, HashMap::default(), &input);
+        Part::One => solve(Part::One, &input)?,
+        Part::Two if args.animate => {
+            #[cfg(not(feature = "animations"))]
+            return Err(aoc23::animations_unavailable());
+            #[cfg(feature = "animations")]
+            {
+                let presentation = Presentation {
+                    camera: args.camera.settings()?,
+                    demo: args.demo.script()?,
+                    recording: args.capture.recording()?,
+                };
+                animation::run(args.frequency, HashMap::default(), &input, presentation);
                 0
-            } else {
-                let facility = HashMap::from_str(&input)?;
-                facility.focal_power()
             }
         }
+        Part::Two => solve(Part::Two, &input)?,
     };
     println!("Solution part {:?}: {solution}", args.part);
     Ok(())
 }
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use aoc23::fifteenth::{HashMap, HASH};
     use rstest::rstest;
+    use std::{hash::Hasher, str::FromStr};
 
     #[rstest]
     fn sample_a_hash() {
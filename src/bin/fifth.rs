@@ -1,7 +1,8 @@
-use aoc23::{
-    fifth::{animation, Almanac},
-    Part,
-};
+#[cfg(feature = "animations")]
+use aoc23::fifth::animation;
+#[cfg(feature = "animations")]
+use aoc23::{camera::CameraArgs, capture::CaptureArgs, demo::DemoArgs, Presentation};
+use aoc23::{fifth::Almanac, timing::Timings, Part};
 
 use anyhow::Result;
 use clap::Parser;
@@ -23,17 +24,57 @@ struct Options {
     /// How often to execute each step (Hz)
     #[clap(short, long, default_value_t = 1.)]
     frequency: f32,
+
+    /// Find the best location by trying each one from 0 upwards & mapping it back to the seeds
+    #[clap(short, long)]
+    scan: bool,
+
+    /// Print how long each phase of the solve took
+    #[clap(short, long)]
+    time: bool,
+
+    /// Together with `--time` also print what each phase allocated (needs `alloc-stats` feature)
+    #[clap(short, long)]
+    verbose: bool,
+
+    #[cfg(feature = "animations")]
+    #[clap(flatten)]
+    camera: CameraArgs,
+
+    #[cfg(feature = "animations")]
+    #[clap(flatten)]
+    demo: DemoArgs,
+
+    #[cfg(feature = "animations")]
+    #[clap(flatten)]
+    capture: CaptureArgs,
 }
 
 fn main() -> Result<()> {
     let args = Options::parse();
-    let input = std::fs::read_to_string(args.input)?;
-    let (almanac, seeds) = Almanac::parse(args.part, &input)?;
-    let solution = almanac.best_location(&seeds);
+    let input = aoc23::normalize_input(&aoc23::input::read(&args.input, 5)?);
+    let mut timings = Timings::new(args.time, args.verbose);
+    let (almanac, seeds) = timings.measure("parse", || Almanac::parse(args.part, &input))?;
+    let solution = if args.scan {
+        timings.measure("scan locations", || almanac.scan_best_location(&seeds))
+    } else {
+        timings.measure("best location", || almanac.best_location(&seeds))
+    };
     println!("Solution part {:?}: {solution}", args.part);
+    eprint!("{timings}");
 
     if args.animate {
-        animation::run(almanac, &seeds, args.frequency);
+        #[cfg(not(feature = "animations"))]
+        return Err(aoc23::animations_unavailable());
+        #[cfg(feature = "animations")]
+        {
+            let presentation = Presentation {
+                camera: args.camera.settings()?,
+                demo: args.demo.script()?,
+                recording: args.capture.recording()?,
+            };
+            animation::run(almanac, &seeds, args.frequency, presentation);
+        }
     }
     Ok(())
 }
@@ -1,4 +1,9 @@
-use aoc23::{mouse, toggle_running, Part, Running, Scroll, Tick};
+use aoc23::{
+    camera::CameraArgs,
+    capture::{CaptureArgs, CapturePlugin},
+    demo::DemoArgs,
+    AocCameraPlugin, AocControlPlugin, Part, Presentation, Running, Tick,
+};
 use bevy::{prelude::*, sprite::Anchor};
 use clap::Parser;
 
@@ -90,17 +95,9 @@ impl From<&Box> for Transform {
 }
 
 #[derive(Resource)]
-struct File(String);
+struct Input(String);
 
-fn setup(mut commands: Commands, file: Res<File>) {
-    commands.spawn((
-        Scroll(1.),
-        Camera2dBundle {
-            transform: Transform::from_xyz(200., 0., 0.),
-            ..default()
-        },
-    ));
-    let input = std::fs::read_to_string(&file.0).expect(&file.0);
+fn setup(mut commands: Commands, input: Res<Input>) {
     let line_scale = 1.05;
     let style = TextStyle {
         font_size: FONT_SIZE,
@@ -108,7 +105,7 @@ fn setup(mut commands: Commands, file: Res<File>) {
         ..default()
     };
     let mut digits = Vec::new();
-    for (i, line) in input.lines().enumerate() {
+    for (i, line) in input.0.lines().enumerate() {
         commands
             .spawn((
                 Line(line.to_string()),
@@ -259,11 +256,10 @@ fn box_color(mut query: Query<(&Box, &mut Sprite)>) {
 
 fn digit_setter(mut query: Query<(&Digit, &mut Text)>, boxes: Query<&Box>) {
     for (digit, mut text) in query.iter_mut() {
-        match boxes
-            .get(digit.0 .0)
-            .expect("Digit to reference an Entity with a `Box` component")
-            .state
-        {
+        let Ok(bx) = boxes.get(digit.0 .0) else {
+            continue;
+        };
+        match bx.state {
             State::Found(d) => {
                 text.sections[0].value = format!("{d}");
                 text.sections[0].style.color = Color::WHITE;
@@ -282,21 +278,11 @@ fn sum_setter(mut query: Query<(&Sum, &mut Text)>, digits: Query<&Digit>, boxes:
         let sum = sum
             .0
             .iter()
-            .map(|id| {
-                digits
-                    .get(*id)
-                    .expect("Sum to reference an Entity with a `Digit` component")
-                    .0
-            })
-            .map(|digit| {
-                match boxes
-                    .get(digit.0)
-                    .expect("Digit to reference an Entity with a `Box` component")
-                    .state
-                {
-                    State::Found(i) => i * digit.1,
-                    _ => 0,
-                }
+            .filter_map(|id| digits.get(*id).ok())
+            .filter_map(|Digit((id, weight))| Some((boxes.get(*id).ok()?, weight)))
+            .map(|(bx, weight)| match bx.state {
+                State::Found(i) => i * weight,
+                _ => 0,
             })
             .sum::<u32>();
         if sum == 0 {
@@ -316,34 +302,53 @@ struct Options {
     /// How often to execute each step (Hz)
     #[clap(short, long, default_value_t = 1.)]
     frequency: f32,
+
+    #[clap(flatten)]
+    camera: CameraArgs,
+
+    #[clap(flatten)]
+    demo: DemoArgs,
+
+    #[clap(flatten)]
+    capture: CaptureArgs,
 }
 
-fn main() {
+fn main() -> anyhow::Result<()> {
     let args = Options::parse();
-    App::new()
-        .add_plugins(DefaultPlugins)
-        .insert_resource(File(args.input))
-        .insert_resource(Tick::new(args.frequency))
-        .insert_resource(Running::default())
-        .add_systems(Startup, setup)
-        .add_systems(
-            Update,
-            (
-                update,
-                toggle_running,
-                mouse,
-                box_movement,
-                box_color,
-                digit_setter,
-                sum_setter,
-            ),
-        )
-        .run()
+    let presentation = Presentation {
+        camera: args.camera.settings()?,
+        demo: args.demo.script()?,
+        recording: args.capture.recording()?,
+    };
+    let input = aoc23::normalize_input(&aoc23::input::read(&args.input, 1)?);
+    let plugins = DefaultPlugins.build().add(presentation);
+    app(input, args.frequency, plugins)
+        .add_plugins(CapturePlugin)
+        .run();
+    Ok(())
+}
+
+fn app(input: String, frequency: f32, plugins: impl PluginGroup) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        plugins,
+        AocCameraPlugin::new(1.).at(Vec3::new(200., 0., 0.)),
+        AocControlPlugin,
+    ))
+    .insert_resource(Input(input))
+    .insert_resource(Tick::new(frequency))
+    .add_systems(Startup, setup)
+    .add_systems(
+        Update,
+        (update, box_movement, box_color, digit_setter, sum_setter),
+    );
+    app
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc23::HeadlessPlugins;
 
     #[test]
     fn solution_a() {
@@ -356,4 +361,16 @@ mod tests {
         let sample = include_str!("../../sample/first-b.txt");
         assert_eq!(281, calibration(sample, Part::Two));
     }
+
+    #[test]
+    fn smoke() {
+        let mut app = app(
+            include_str!("../../sample/first-a.txt").to_owned(),
+            1.,
+            HeadlessPlugins,
+        );
+        for _ in 0..3 {
+            app.update();
+        }
+    }
 }
@@ -1,11 +1,16 @@
 use std::{fmt::Debug, str::FromStr};
 
 use aoc23::{
-    cycle,
-    fourteenth::{animation, Platform, CYCLE, NORTH},
+    fourteenth::{bits::BitPlatform, Platform, Tilting, CYCLE, NORTH, SPIN_CYCLES},
+    timing::Timings,
     Part,
 };
 
+#[cfg(feature = "animations")]
+use aoc23::fourteenth::animation;
+#[cfg(feature = "animations")]
+use aoc23::{camera::CameraArgs, capture::CaptureArgs, demo::DemoArgs, Presentation};
+
 use anyhow::Result;
 use clap::Parser;
 
@@ -23,52 +28,80 @@ struct Options {
     #[clap(short, long)]
     animate: bool,
 
-    /// In the animation what is the maximum load you expect for one column of rocks?
-    #[clap(short, long, default_value_t = 30.)]
-    max_load: f32,
+    /// Print how long each phase of the solve took
+    #[clap(short, long)]
+    time: bool,
+
+    /// Together with `--time` also print what each phase allocated (needs `alloc-stats` feature)
+    #[clap(short, long)]
+    verbose: bool,
+
+    /// Animate the solution in the terminal instead of a window
+    #[clap(long, conflicts_with = "animate")]
+    tui: bool,
+
+    /// How often to tilt in the terminal (Hz)
+    #[clap(long, default_value_t = 4.)]
+    frequency: f32,
+
+    #[cfg(feature = "animations")]
+    #[clap(flatten)]
+    camera: CameraArgs,
+
+    #[cfg(feature = "animations")]
+    #[clap(flatten)]
+    demo: DemoArgs,
+
+    #[cfg(feature = "animations")]
+    #[clap(flatten)]
+    capture: CaptureArgs,
 }
 
 fn main() -> Result<()> {
     let args = Options::parse();
-    let input = std::fs::read_to_string(args.input)?;
-    let mut platform = Platform::from_str(&input)?;
+    let input = aoc23::normalize_input(&aoc23::input::read(&args.input, 14)?);
+    let mut timings = Timings::new(args.time, args.verbose);
+    let mut platform = timings.measure("parse", || Platform::from_str(&input))?;
 
     if args.animate {
-        animation::run(platform, args.max_load);
-        return Ok(());
+        #[cfg(not(feature = "animations"))]
+        return Err(aoc23::animations_unavailable());
+        #[cfg(feature = "animations")]
+        {
+            let presentation = Presentation {
+                camera: args.camera.settings()?,
+                demo: args.demo.script()?,
+                recording: args.capture.recording()?,
+            };
+            animation::run(platform, presentation);
+            return Ok(());
+        }
     }
 
-    let mut states = Vec::new();
+    if args.tui {
+        let directions = match args.part {
+            Part::One => vec![NORTH],
+            Part::Two => CYCLE.repeat(platform.equivalent_cycles(SPIN_CYCLES as u64) as usize),
+        };
+        return aoc23::tui::run(Tilting::new(platform, directions), args.frequency);
+    }
 
     let solution = match args.part {
-        Part::One => {
+        Part::One => timings.measure("tilt", || {
             platform.tilt(NORTH);
             platform.total_north_load()
-        }
+        }),
         Part::Two => {
-            let until = loop {
-                for dir in CYCLE.iter() {
-                    platform.tilt(*dir);
-                }
-                states.push(platform.total_north_load());
-
-                if let Some((mu, lambda)) = cycle(states.iter()) {
-                    break ((1_000_000_000 - mu) % lambda) + mu;
-                }
-            };
-
-            // Reset
-            platform = Platform::from_str(&input)?;
-            for _ in 0..until {
-                for dir in CYCLE.iter() {
-                    platform.tilt(*dir);
-                }
-            }
-            platform.total_north_load()
+            let mut bits = timings.measure("bit masks", || BitPlatform::try_from(&platform))?;
+            timings.measure("spin", || {
+                bits.run_cycles(SPIN_CYCLES);
+                bits.total_north_load()
+            })
         }
     };
 
     println!("Solution part {:?} {solution}", args.part);
+    eprint!("{timings}");
 
     Ok(())
 }
@@ -77,7 +110,8 @@ fn main() -> Result<()> {
 mod tests {
     use super::*;
     use aoc23::{
-        fourteenth::{EAST, NORTH, SOUTH, WEST},
+        fourteenth::{EAST, SOUTH, WEST},
+        tui::Stepper,
         Coord,
     };
     use rstest::rstest;
@@ -208,4 +242,41 @@ mod tests {
             "Platform:\n{platform}\n\nExpected\n{expected}"
         );
     }
+
+    #[rstest]
+    fn tilting_steps_through_directions() {
+        let input = include_str!("../../sample/fourteenth.txt");
+        let platform = Platform::from_str(input).expect("parsing");
+        let mut tilting = Tilting::new(platform.clone(), CYCLE);
+        let steps = std::iter::from_fn(|| tilting.step().then_some(())).count();
+        assert!(!tilting.step());
+
+        // Each step rolls the rocks by one cell, in the direction of the tilt the platform is at
+        let mut expected = platform;
+        let tilt_steps = CYCLE
+            .iter()
+            .map(|dir| {
+                let steps = expected.tilt_steps(*dir).count();
+                expected.tilt(*dir);
+                steps
+            })
+            .sum::<usize>();
+        assert_eq!(steps, tilt_steps);
+        assert!(tilting.frame().starts_with(&format!(
+            "{expected}\nNorth load: {}",
+            expected.total_north_load()
+        )));
+    }
+
+    #[cfg(feature = "serialize")]
+    #[rstest]
+    fn json_round_trip() {
+        let input = include_str!("../../sample/fourteenth.txt");
+        let platform = Platform::from_str(input).expect("parsing");
+        let json = serde_json::to_string(&platform).expect("serializing");
+        assert_eq!(
+            serde_json::from_str::<Platform>(&json).expect("deserializing"),
+            platform
+        );
+    }
 }
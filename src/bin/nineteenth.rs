@@ -0,0 +1,53 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+use aoc23::{nineteenth::System, timing::Timings, Part};
+use clap::Parser;
+
+/// Day 19: Aplenty
+#[derive(Debug, Parser)]
+struct Options {
+    /// Path to the file with the input data
+    #[clap(short, long, default_value = "sample/nineteenth.txt")]
+    input: String,
+
+    /// Which part of the day to solve
+    part: Part,
+
+    /// Print how long each phase of the solve took
+    #[clap(short, long)]
+    time: bool,
+
+    /// Together with `--time` also print what each phase allocated (needs `alloc-stats` feature)
+    #[clap(short, long)]
+    verbose: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Options::parse();
+    let input = aoc23::normalize_input(&aoc23::input::read(&args.input, 19)?);
+    let mut timings = Timings::new(args.time, args.verbose);
+    let system = timings.measure("parse", || System::from_str(&input))?;
+    let solution = match args.part {
+        Part::One => timings.measure("route", || system.accepted_ratings())?,
+        Part::Two => timings.measure("split", || system.accepted_combinations())?,
+    };
+    println!("Solution part {:?}: {solution}", args.part);
+    eprint!("{timings}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc23::nineteenth::solve;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Part::One, 19114)]
+    #[case(Part::Two, 167409079868000)]
+    fn sample(#[case] part: Part, #[case] expected: u64) {
+        let input = include_str!("../../sample/nineteenth.txt");
+        assert_eq!(solve(part, input).expect("solving"), expected);
+    }
+}
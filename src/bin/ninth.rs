@@ -1,11 +1,6 @@
-use aoc23::Part;
-
+use anyhow::Result;
+use aoc23::{ninth::histories, timing::Timings, Part};
 use clap::Parser;
-use itertools::Itertools;
-use num::Zero;
-use std::ops::Sub;
-use std::str::FromStr;
-use std::{fmt::Debug, rc::Rc, sync::Mutex};
 
 /// Day 9: Mirage Maintenance
 #[derive(Debug, Parser)]
@@ -16,131 +11,31 @@ struct Options {
 
     /// Which part of the day to solve
     part: Part,
-}
-
-fn main() -> anyhow::Result<()> {
-    let args = Options::parse();
-    let input = std::fs::read_to_string(&args.input)?;
-
-    let solution = predict::<i64>(&input, args.part)
-        .map(|history| history.sum::<i64>())
-        .sum::<i64>();
-    println!("Solution part {:?}: {solution:?}", args.part);
-    Ok(())
-}
-
-fn predict<'a, T>(input: &'a str, part: Part) -> impl Iterator<Item = PredictIter<'a, T>>
-where
-    T: Copy + Sub<Output = T> + Zero + 'a + FromStr,
-{
-    input
-        .lines()
-        .map(|line| {
-            line.split_whitespace()
-                .filter_map(|item| item.parse::<T>().ok())
-        })
-        .map(move |values| match part {
-            Part::One => PredictIter::new(values.rev()),
-            Part::Two => PredictIter::new(values),
-        })
-}
 
-#[derive(Clone)]
-struct PredictIter<'a, T> {
-    first: Option<T>,
-    diff: DiffIter<'a, T>,
-}
+    /// Print how long each phase of the solve took
+    #[clap(short, long)]
+    time: bool,
 
-impl<'a, T> PredictIter<'a, T>
-where
-    T: Copy + Sub<Output = T> + Zero + 'a,
-{
-    fn new<I: Iterator<Item = T> + 'a + Clone>(iter: I) -> Self {
-        let mut iter = iter.peekable();
-        let first = iter.peek().copied();
-        Self {
-            first,
-            diff: DiffIter::new(iter),
-        }
-    }
+    /// Together with `--time` also print what each phase allocated (needs `alloc-stats` feature)
+    #[clap(short, long)]
+    verbose: bool,
 }
 
-impl<'a, T> Iterator for PredictIter<'a, T>
-where
-    T: Copy + Sub<Output = T> + PartialEq<T> + Zero + Debug + 'a,
-{
-    type Item = T;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        self.first.take().or_else(|| {
-            let mut iter = self.diff.clone().peekable();
-            let n = iter.peek().copied();
-            self.diff = DiffIter::new(iter);
-            n
-        })
-    }
-}
-
-#[derive(Clone)]
-struct DiffIter<'a, T> {
-    values: Rc<Mutex<dyn Iterator<Item = T> + 'a>>,
-}
-impl<'a, T> DiffIter<'a, T>
-where
-    T: Copy + Sub<Output = T> + Zero + 'a,
-{
-    fn new<I: Iterator<Item = T> + 'a + Clone>(iter: I) -> Self {
-        Self {
-            values: Rc::new(Mutex::new(iter.tuple_windows().map(|(a, b)| a - b))),
-        }
-    }
-}
-impl<'a, T> Iterator for DiffIter<'a, T>
-where
-    T: Sub<Output = T> + Copy,
-{
-    type Item = T;
-    fn next(&mut self) -> Option<Self::Item> {
-        self.values.lock().expect("mutex").next()
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    use rstest::rstest;
-
-    #[rstest]
-    #[case(0, vec![15, 3, 0, 0, 0, 0])]
-    #[case(1, vec![21, 6, 1, 0, 0, 0])]
-    #[case(2, vec![45, 15, 6, 2, 0, 0])]
-    fn sample_a_manual(#[case] line: usize, #[case] expectation: Vec<i32>) {
-        let input = include_str!("../../sample/ninth.txt");
-        let oasis = predict(input, Part::One)
-            .nth(line)
-            .expect("input to contain line number {line}");
-        assert_eq!(expectation, oasis.collect::<Vec<i32>>());
-    }
-
-    #[rstest]
-    fn sample_a() {
-        let input = include_str!("../../sample/ninth.txt");
-        let oasis = predict::<i32>(input, Part::One)
-            .map(|history| history.sum::<i32>())
-            .sum::<i32>();
-        assert_eq!(114, oasis);
-    }
-
-    #[rstest]
-    #[case(0, vec![ 0, -3, 0, 0, 0, 0])]
-    #[case(1, vec![ 1, -2, 1, 0, 0, 0])]
-    #[case(2, vec![10, -3, 0, -2, 0, 0])]
-    fn sample_b_manual(#[case] line: usize, #[case] expectation: Vec<i32>) {
-        let input = include_str!("../../sample/ninth.txt");
-        let oasis = predict(input, Part::Two)
-            .nth(line)
-            .expect("input to contain line number {line}");
-        assert_eq!(expectation, oasis.collect::<Vec<i32>>())
-    }
+fn main() -> Result<()> {
+    let args = Options::parse();
+    let input = aoc23::normalize_input(&aoc23::input::read(&args.input, 9)?);
+    let mut timings = Timings::new(args.time, args.verbose);
+    let histories = timings.measure("parse", || histories(&input))?;
+    let solution = timings.measure("extrapolate", || {
+        histories
+            .iter()
+            .map(|history| match args.part {
+                Part::One => history.next_value(),
+                Part::Two => history.previous_value(),
+            })
+            .sum::<i64>()
+    });
+    println!("Solution part {:?}: {solution}", args.part);
+    eprint!("{timings}");
+    Ok(())
 }
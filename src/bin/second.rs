@@ -1,10 +1,15 @@
-use std::str::FromStr;
+use anyhow::Context;
 
+#[cfg(feature = "animations")]
+use aoc23::second::animation;
+#[cfg(feature = "animations")]
+use aoc23::{camera::CameraArgs, capture::CaptureArgs, demo::DemoArgs, Presentation};
 use aoc23::{
-    second::{animation, Color, Game, BAG},
+    second::{solve, Game, BAG},
     Part,
 };
 use clap::Parser;
+use std::str::FromStr;
 
 /// Day 2: Cube Conundrum
 #[derive(Debug, Parser)]
@@ -16,6 +21,14 @@ struct Options {
     /// Which part of the day to solve
     part: Part,
 
+    /// Explain why impossible games are impossible (part one only)
+    #[clap(short, long)]
+    verbose: bool,
+
+    /// Fail on colors drawn more than once within a round, instead of summing them up
+    #[clap(long)]
+    strict: bool,
+
     /// Should the solution be animated?
     #[clap(short, long)]
     animate: bool,
@@ -23,39 +36,58 @@ struct Options {
     /// How often to execute each step (Hz)
     #[clap(short, long, default_value_t = 1.)]
     frequency: f32,
-}
 
-fn possible_game_ids(input: &str) -> impl Iterator<Item = u32> + '_ {
-    input
-        .lines()
-        .filter_map(|line| Game::from_str(line).ok())
-        .filter(|game| game.possible(&BAG))
-        .map(|game| game.id())
-}
-fn powers(input: &str) -> impl Iterator<Item = u32> + '_ {
-    input
-        .lines()
-        .filter_map(|line| Game::from_str(line).ok())
-        .map(|game| game.fewest())
-        .map(|f| {
-            f.get(&Color::Red).unwrap_or(&0)
-                * f.get(&Color::Green).unwrap_or(&0)
-                * f.get(&Color::Blue).unwrap_or(&0)
-        })
+    #[cfg(feature = "animations")]
+    #[clap(flatten)]
+    camera: CameraArgs,
+
+    #[cfg(feature = "animations")]
+    #[clap(flatten)]
+    demo: DemoArgs,
+
+    #[cfg(feature = "animations")]
+    #[clap(flatten)]
+    capture: CaptureArgs,
 }
 
 fn main() -> anyhow::Result<()> {
     let args = Options::parse();
-    let input = std::fs::read_to_string(args.input)?;
+    let input = aoc23::normalize_input(&aoc23::input::read(&args.input, 2)?);
+
+    if args.strict {
+        for (i, line) in input.lines().enumerate() {
+            Game::strict(line).with_context(|| format!("Line {} of the input", i + 1))?;
+        }
+    }
+
+    if args.verbose && args.part == Part::One {
+        for game in input.lines().map(Game::from_str) {
+            let game = game?;
+            if let Some((round, color, needed, available)) = game.first_violation(&BAG) {
+                println!(
+                    "Game {id} is impossible: round {round} draws {needed} {color:?}, but the bag has only {available}",
+                    id = game.id(),
+                    round = round + 1,
+                );
+            }
+        }
+    }
 
-    let answer = match args.part {
-        Part::One => possible_game_ids(&input).sum::<u32>(),
-        Part::Two => powers(&input).sum(),
-    };
+    let answer = solve(args.part, &input)?;
     println!("Solution Part {:?}: {answer}", args.part);
 
     if args.animate {
-        animation::run(&input, args.frequency, args.part);
+        #[cfg(not(feature = "animations"))]
+        return Err(aoc23::animations_unavailable());
+        #[cfg(feature = "animations")]
+        {
+            let presentation = Presentation {
+                camera: args.camera.settings()?,
+                demo: args.demo.script()?,
+                recording: args.capture.recording()?,
+            };
+            animation::run(&input, args.frequency, args.part, presentation);
+        }
     }
 
     Ok(())
@@ -63,7 +95,7 @@ fn main() -> anyhow::Result<()> {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use aoc23::second::{possible_game_ids, powers};
 
     #[test]
     fn sample_part_one() {
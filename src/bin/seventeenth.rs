@@ -0,0 +1,60 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use aoc23::{
+    seventeenth::{City, CRUCIBLE, ULTRA_CRUCIBLE},
+    timing::Timings,
+    Part,
+};
+use clap::Parser;
+
+/// Day 17: Clumsy Crucible
+#[derive(Debug, Parser)]
+struct Options {
+    /// Path to the file with the input data
+    #[clap(short, long, default_value = "sample/seventeenth.txt")]
+    input: String,
+
+    /// Which part of the day to solve
+    part: Part,
+
+    /// Print how long each phase of the solve took
+    #[clap(short, long)]
+    time: bool,
+
+    /// Together with `--time` also print what each phase allocated (needs `alloc-stats` feature)
+    #[clap(short, long)]
+    verbose: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Options::parse();
+    let input = aoc23::normalize_input(&aoc23::input::read(&args.input, 17)?);
+    let mut timings = Timings::new(args.time, args.verbose);
+    let city = timings.measure("parse", || City::from_str(&input))?;
+    let runs = match args.part {
+        Part::One => CRUCIBLE,
+        Part::Two => ULTRA_CRUCIBLE,
+    };
+    let solution = timings
+        .measure("search", || city.least_heat_loss(runs))
+        .ok_or(anyhow!("No crucible can reach the factory"))?;
+    println!("Solution part {:?}: {solution}", args.part);
+    eprint!("{timings}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc23::seventeenth::solve;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Part::One, 102)]
+    #[case(Part::Two, 94)]
+    fn sample(#[case] part: Part, #[case] expected: u32) {
+        let input = include_str!("../../sample/seventeenth.txt");
+        assert_eq!(solve(part, input).expect("solving"), expected);
+    }
+}
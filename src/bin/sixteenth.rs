@@ -2,13 +2,17 @@
 
 use std::{fmt::Debug, str::FromStr};
 
-use anyhow::anyhow;
+#[cfg(feature = "animations")]
+use aoc23::sixteenth::animation;
+#[cfg(feature = "animations")]
+use aoc23::{camera::CameraArgs, capture::CaptureArgs, demo::DemoArgs, Presentation};
 use aoc23::{
-    sixteenth::{animation, Contraption, PART_ONE_ENTRY},
-    Direction, Part,
+    rng::RngArgs,
+    sixteenth::{Contraption, PART_ONE_ENTRY},
+    timing::Timings,
+    Part,
 };
 use clap::Parser;
-use rayon::{iter::repeat as par_repeat, prelude::*};
 
 /// Day 16: The Floor Will Be Lava
 #[derive(Debug, Parser)]
@@ -26,67 +30,90 @@ struct Options {
 
     #[clap(long, short, default_value_t = 50.)]
     frequency: f32,
+
+    /// In the animation, how many seconds newly energized cells take to fade to their beam's color
+    #[clap(long, default_value_t = 4.)]
+    trail: f32,
+
+    /// Print how long each phase of the solve took
+    #[clap(short, long)]
+    time: bool,
+
+    /// Together with `--time` also print what each phase allocated (needs `alloc-stats` feature)
+    #[clap(short, long)]
+    verbose: bool,
+
+    /// Animate the solution in the terminal instead of a window
+    #[clap(long, conflicts_with = "animate")]
+    tui: bool,
+
+    #[cfg(feature = "animations")]
+    #[clap(flatten)]
+    camera: CameraArgs,
+
+    #[cfg(feature = "animations")]
+    #[clap(flatten)]
+    demo: DemoArgs,
+
+    #[cfg(feature = "animations")]
+    #[clap(flatten)]
+    capture: CaptureArgs,
+
+    #[clap(flatten)]
+    rng: RngArgs,
 }
 
 fn main() -> anyhow::Result<()> {
     let args = Options::parse();
-    let input = std::fs::read_to_string(args.input)?;
+    args.rng.install()?;
+    let input = aoc23::normalize_input(&aoc23::input::read(&args.input, 16)?);
 
-    let mut contraption = Contraption::from_str(&input)?;
-    match args.part {
-        Part::One => contraption.set_entry(PART_ONE_ENTRY)?,
+    let mut timings = Timings::new(args.time, args.verbose);
+    let mut contraption = timings.measure("parse", || Contraption::from_str(&input))?;
+    let entry = match args.part {
+        Part::One => PART_ONE_ENTRY,
         Part::Two => {
-            let best_entry = par_repeat(Direction::Right)
-                .zip(0..contraption.nrows())
-                .chain(par_repeat(Direction::Up).zip(0..contraption.ncols()))
-                .chain(
-                    par_repeat(Direction::Left)
-                        .zip(0..contraption.nrows())
-                        .rev(),
-                )
-                .chain(
-                    par_repeat(Direction::Down)
-                        .zip(0..contraption.ncols())
-                        .rev(),
-                )
-                .map(|entry| {
-                    let mut contraption = Contraption::from_str(&input).expect("parsing");
-                    contraption.set_entry(entry).unwrap();
-
-                    while !contraption.is_in_equilibrium() {
-                        contraption.advance(0.);
-                    }
-                    (entry, contraption.energized_cells().len())
-                })
-                .max_by_key(|(_, energized_cells)| *energized_cells)
-                .ok_or(anyhow!("No best entry found"))?;
+            let best_entry = timings
+                .measure("best entry", || contraption.best_entry())
+                .ok_or(anyhow::anyhow!("No best entry found"))?;
             println!(
                 "Found best entry at {:?} leading to {} energized cells",
                 best_entry.0, best_entry.1
             );
-
-            contraption.reset();
-            contraption.set_entry(best_entry.0)?;
+            best_entry.0
         }
     };
+    contraption.set_entry(entry)?;
 
-    if args.animate {
-        animation::run(contraption, args.frequency);
-        return Ok(());
+    if args.tui {
+        return aoc23::tui::run(contraption, args.frequency);
     }
 
-    while !contraption.is_in_equilibrium() {
-        contraption.advance(0.);
+    if args.animate {
+        #[cfg(not(feature = "animations"))]
+        return Err(aoc23::animations_unavailable());
+        #[cfg(feature = "animations")]
+        {
+            let presentation = Presentation {
+                camera: args.camera.settings()?,
+                demo: args.demo.script()?,
+                recording: args.capture.recording()?,
+            };
+            animation::run(contraption, args.frequency, args.trail, presentation);
+            return Ok(());
+        }
     }
 
-    let solution = contraption.energized_cells().len();
+    let solution = timings.measure("energize", || contraption.energize(entry));
     println!("Solution: {solution}");
+    eprint!("{timings}");
 
     Ok(())
 }
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc23::{sixteenth::best_entry, Direction};
     use rstest::rstest;
 
     #[rstest]
@@ -135,7 +162,7 @@ mod tests {
             contraption.nrows()
         );
         while !contraption.is_in_equilibrium() {
-            contraption.advance(0.);
+            contraption.advance().expect("advancing");
             println!("{contraption:?}");
             println!(
                 "Beams: {:?}",
@@ -155,31 +182,8 @@ mod tests {
     #[rstest]
     fn sample_b() {
         let input = include_str!("../../sample/sixteenth.txt");
-        let contraption = Contraption::from_str(input).expect("parsing");
-        let best_entry = par_repeat(Direction::Right)
-            .zip(0..contraption.nrows())
-            .chain(par_repeat(Direction::Up).zip(0..contraption.ncols()))
-            .chain(
-                par_repeat(Direction::Left)
-                    .zip(0..contraption.nrows())
-                    .rev(),
-            )
-            .chain(
-                par_repeat(Direction::Down)
-                    .zip(0..contraption.ncols())
-                    .rev(),
-            )
-            .map(|entry| {
-                let mut contraption = Contraption::from_str(input).expect("parsing");
-                contraption.set_entry(entry).unwrap();
-
-                while !contraption.is_in_equilibrium() {
-                    contraption.advance(0.);
-                }
-                (entry, contraption.energized_cells().len())
-            })
-            .max_by_key(|(_, energized_cells)| *energized_cells);
-
-        assert_eq!(Some(((Direction::Down, 3), 51)), best_entry);
+        let best_entry = best_entry(input).expect("best entry");
+
+        assert_eq!(((Direction::Down, 3), 51), best_entry);
     }
 }
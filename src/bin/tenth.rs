@@ -1,16 +1,20 @@
+#[cfg(feature = "animations")]
+use aoc23::ten::animation;
+#[cfg(feature = "animations")]
+use aoc23::{camera::CameraArgs, capture::CaptureArgs, demo::DemoArgs, Presentation};
 use aoc23::{
-    ten::{animation, Maze},
+    ten::{Maze, Tracing},
     Part,
 };
 
 use clap::Parser;
-use std::{fmt::Debug, str::FromStr};
+use std::{fmt::Debug, path::PathBuf, str::FromStr};
 
 /// Day 10: Pipe Maze
 #[derive(Debug, Parser)]
 struct Options {
     /// Path to the file with the input data
-    #[clap(short, long, default_value = "sample/tenth-b.txt")]
+    #[clap(short, long, default_value = "sample/tenth.txt")]
     input: String,
 
     /// Which part of the day to solve
@@ -20,9 +24,9 @@ struct Options {
     #[clap(short, long)]
     verbose: bool,
 
-    /// Invert the "inside" of the search
+    /// Write a PNG of the loop, colored by each tile's distance from the start
     #[clap(long)]
-    invert: bool,
+    heatmap: Option<PathBuf>,
 
     /// Should the solution be animated?
     #[clap(short, long)]
@@ -31,11 +35,27 @@ struct Options {
     /// How often to execute each step (Hz)
     #[clap(short, long, default_value_t = 5.)]
     frequency: f32,
+
+    /// Animate the solution in the terminal instead of a window
+    #[clap(long, conflicts_with = "animate")]
+    tui: bool,
+
+    #[cfg(feature = "animations")]
+    #[clap(flatten)]
+    camera: CameraArgs,
+
+    #[cfg(feature = "animations")]
+    #[clap(flatten)]
+    demo: DemoArgs,
+
+    #[cfg(feature = "animations")]
+    #[clap(flatten)]
+    capture: CaptureArgs,
 }
 
 fn main() -> anyhow::Result<()> {
     let args = Options::parse();
-    let input = std::fs::read_to_string(&args.input)?;
+    let input = aoc23::normalize_input(&aoc23::input::read(&args.input, 10)?);
     let mut maze = Maze::from_str(&input)?;
     let solution = match args.part {
         Part::One => {
@@ -43,20 +63,42 @@ fn main() -> anyhow::Result<()> {
             maze.path().len() / 2
         }
         Part::Two => {
-            maze.calculate_path();
-            maze.calculate_inside(args.invert);
+            maze.calculate_inside();
             maze.inside().len()
         }
     };
 
     if args.verbose {
         println!("{maze:?}");
+        println!(
+            "Junk pipes: {}, dead ends: {}",
+            maze.junk_pipes().len(),
+            maze.dead_ends().len()
+        );
     }
 
     println!("Solution part {:?}: {solution}", args.part);
 
+    if let Some(path) = &args.heatmap {
+        std::fs::write(path, aoc23::render::png(&maze.heat_map())?)?;
+    }
+
+    if args.tui {
+        return aoc23::tui::run(Tracing::from(maze), args.frequency);
+    }
+
     if args.animate {
-        animation::run(maze, args.frequency);
+        #[cfg(not(feature = "animations"))]
+        return Err(aoc23::animations_unavailable());
+        #[cfg(feature = "animations")]
+        {
+            let presentation = Presentation {
+                camera: args.camera.settings()?,
+                demo: args.demo.script()?,
+                recording: args.capture.recording()?,
+            };
+            animation::run(maze, args.frequency, presentation);
+        }
     }
     Ok(())
 }
@@ -65,6 +107,7 @@ fn main() -> anyhow::Result<()> {
 mod tests {
     use super::*;
 
+    use aoc23::{ten::Pipe, tui::Stepper};
     use rstest::rstest;
 
     #[rstest]
@@ -78,16 +121,100 @@ mod tests {
     }
 
     #[rstest]
-    #[case(include_str!("../../sample/tenth-a.txt"), false, 1)]
-    #[case(include_str!("../../sample/tenth-b.txt"), false, 1)]
-    #[case(include_str!("../../sample/tenth-c.txt"), false, 4)]
-    #[case(include_str!("../../sample/tenth-d.txt"), false, 4)]
-    #[case(include_str!("../../sample/tenth-e.txt"), true, 8)]
-    #[case(include_str!("../../sample/tenth-f.txt"), false, 35)]
-    fn sample_b(#[case] s: &str, #[case] ccw: bool, #[case] expected_inside_area: usize) {
+    #[case(include_str!("../../sample/tenth-a.txt"), Pipe::SE)]
+    #[case(include_str!("../../sample/tenth-b.txt"), Pipe::SE)]
+    #[case(".....\n.F-7.\n.|.|.\n.L-S.\n.....", Pipe::NW)]
+    #[case(".....\n.F-7.\n.S.|.\n.L-J.\n.....", Pipe::NS)]
+    fn infers_start_pipe(#[case] s: &str, #[case] expected: Pipe) {
+        let maze = Maze::from_str(s).expect("parsing");
+        assert_eq!(maze.infer_start_pipe(), Some(expected));
+    }
+
+    #[rstest]
+    #[case(".....\n.F-7.\n.|.|.\n.L-S.\n.....")]
+    #[case(".....\n.F-7.\n.S.|.\n.L-J.\n.....")]
+    fn starts_in_any_direction(#[case] s: &str) {
+        let mut maze = Maze::from_str(s).expect("parsing");
+        maze.calculate_inside();
+        assert_eq!(maze.path().len(), 8);
+        assert_eq!(maze.inside().len(), 1);
+    }
+
+    #[rstest]
+    #[case(include_str!("../../sample/tenth-a.txt"))]
+    #[case(include_str!("../../sample/tenth-b.txt"))]
+    #[case(include_str!("../../sample/tenth-f.txt"))]
+    fn distance_field_peaks_at_part_one(#[case] s: &str) {
+        let mut maze = Maze::from_str(s).expect("parsing");
+        maze.calculate_path();
+        let distances = maze.distance_field();
+        assert_eq!(distances.len(), maze.path().len());
+        assert_eq!(distances.values().min(), Some(&0));
+        assert_eq!(distances.values().max(), Some(&(maze.path().len() / 2)));
+    }
+
+    #[rstest]
+    #[case(include_str!("../../sample/tenth-a.txt"))]
+    #[case(include_str!("../../sample/tenth-b.txt"))]
+    fn walkers_meet_on_farthest_tile(#[case] s: &str) {
+        let mut maze = Maze::from_str(s).expect("parsing");
+        maze.calculate_path();
+        let walk = maze.bidirectional_walk().collect::<Vec<_>>();
+        assert_eq!(walk.len(), maze.path().len() / 2);
+        let (a, b) = walk.last().expect("walking");
+        assert_eq!(a, b);
+        assert!(walk[..walk.len() - 1].iter().all(|(a, b)| a != b));
+    }
+
+    #[rstest]
+    #[case(include_str!("../../sample/tenth-a.txt"))]
+    #[case(include_str!("../../sample/tenth-f.txt"))]
+    fn tracing_walks_to_farthest_tile(#[case] s: &str) {
+        let mut maze = Maze::from_str(s).expect("parsing");
+        maze.calculate_path();
+        let farthest = maze.path().len() / 2;
+        let mut tracing = Tracing::from(maze);
+        let steps = std::iter::from_fn(|| tracing.step().then_some(())).count();
+        assert_eq!(steps, farthest);
+        assert!(tracing.frame().contains(&format!("Farthest: {farthest}")));
+    }
+
+    #[rstest]
+    #[case(include_str!("../../sample/tenth-a.txt"), 1)]
+    #[case(include_str!("../../sample/tenth-b.txt"), 1)]
+    #[case(include_str!("../../sample/tenth-c.txt"), 4)]
+    #[case(include_str!("../../sample/tenth-d.txt"), 4)]
+    #[case(include_str!("../../sample/tenth-e.txt"), 8)]
+    #[case(include_str!("../../sample/tenth-f.txt"), 35)]
+    fn sample_b(#[case] s: &str, #[case] expected_inside_area: usize) {
         let mut maze = Maze::from_str(s).expect("parsing");
-        maze.calculate_inside(ccw);
+        maze.calculate_inside();
         println!("{maze:?}");
         assert_eq!(expected_inside_area, maze.inside().len());
+        assert_eq!(expected_inside_area, maze.inside_area_shoelace());
+    }
+
+    #[rstest]
+    #[case(include_str!("../../sample/tenth-b.txt"), 7, 7)]
+    #[case(include_str!("../../sample/tenth-c.txt"), 0, 0)]
+    #[case(".....\n.S-7.\n.|.|.\n.L-J.\nF7...\nLJ.-.", 5, 1)]
+    fn finds_junk_pipes(#[case] s: &str, #[case] junk: usize, #[case] dead_ends: usize) {
+        let mut maze = Maze::from_str(s).expect("parsing");
+        maze.calculate_path();
+        let junk_pipes = maze.junk_pipes();
+        assert_eq!(junk_pipes.len(), junk);
+        assert_eq!(maze.dead_ends().len(), dead_ends);
+        assert!(maze.dead_ends().is_subset(&junk_pipes));
+    }
+
+    #[cfg(feature = "serialize")]
+    #[rstest]
+    fn json_round_trip() {
+        let input = include_str!("../../sample/tenth-c.txt");
+        let mut maze = Maze::from_str(input).expect("parsing");
+        maze.calculate_inside();
+        let json = serde_json::to_string(&maze).expect("serializing");
+        let parsed = serde_json::from_str::<Maze>(&json).expect("deserializing");
+        assert_eq!(format!("{parsed:?}"), format!("{maze:?}"));
     }
 }
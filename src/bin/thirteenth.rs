@@ -1,11 +1,14 @@
-use std::{fmt::Debug, str::FromStr};
+use std::fmt::Debug;
 
+#[cfg(feature = "animations")]
+use aoc23::thirteenth::animation;
+#[cfg(feature = "animations")]
+use aoc23::{camera::CameraArgs, capture::CaptureArgs, demo::DemoArgs, Presentation};
 use aoc23::{
-    thirteenth::{animation, Grid, Reflection},
+    thirteenth::{parse_grids, total_score},
     Part,
 };
 
-use anyhow::Result;
 use clap::Parser;
 
 /// Day 13: Point of Incidence
@@ -25,47 +28,47 @@ struct Options {
     /// How often to execute each step (Hz)
     #[clap(short, long, default_value_t = 2.)]
     frequency: f32,
+
+    /// How many threads to score the grids on (0 for one per core)
+    #[clap(short, long, default_value_t = 0)]
+    jobs: usize,
+
+    #[cfg(feature = "animations")]
+    #[clap(flatten)]
+    camera: CameraArgs,
+
+    #[cfg(feature = "animations")]
+    #[clap(flatten)]
+    demo: DemoArgs,
+
+    #[cfg(feature = "animations")]
+    #[clap(flatten)]
+    capture: CaptureArgs,
 }
 
 fn main() -> anyhow::Result<()> {
     let args = Options::parse();
-    let input = std::fs::read_to_string(args.input)?;
-    let mut grids = input
-        .split("\n\n")
-        .map(Grid::from_str)
-        .collect::<Result<Vec<_>>>()?;
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(args.jobs)
+        .build_global()?;
+    let input = aoc23::normalize_input(&aoc23::input::read(&args.input, 13)?);
+    let grids = parse_grids(&input)?;
+    let solution = total_score(&grids, args.part);
 
-    let mut lefts = 0;
-    let mut aboves = 0;
-
-    if args.part == Part::Two {
-        for grid in grids.iter_mut() {
-            let (_index, fold, dir) = [Reflection::Horizontal, Reflection::Vertical]
-                .into_iter()
-                .flat_map(|r| grid.find_smudge(r))
-                .next()
-                .expect("a smudge");
-            match dir {
-                Reflection::Horizontal => aboves += fold,
-                Reflection::Vertical => lefts += fold,
-            }
-        }
-    } else {
-        for (dir, x) in grids.iter().flat_map(|grid| {
-            grid.fold_line(Reflection::Horizontal)
-                .or(grid.fold_line(Reflection::Vertical))
-        }) {
-            match dir {
-                Reflection::Vertical => lefts += x,
-                Reflection::Horizontal => aboves += x,
-            }
-        }
-    }
-    let solution = lefts + 100 * aboves;
     println!("Solution part {:?}: {solution}", args.part);
 
     if args.animate {
-        animation::run(grids, args.part, args.frequency);
+        #[cfg(not(feature = "animations"))]
+        return Err(aoc23::animations_unavailable());
+        #[cfg(feature = "animations")]
+        {
+            let presentation = Presentation {
+                camera: args.camera.settings()?,
+                demo: args.demo.script()?,
+                recording: args.capture.recording()?,
+            };
+            animation::run(grids, args.part, args.frequency, presentation);
+        }
     }
 
     Ok(())
@@ -74,6 +77,9 @@ fn main() -> anyhow::Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use anyhow::Result;
+    use aoc23::thirteenth::{Grid, Reflection};
+    use std::str::FromStr;
 
     use rstest::rstest;
 
@@ -277,30 +283,58 @@ mod tests {
         );
     }
 
+    #[rstest]
+    fn fixing_smudge_yields_its_fold() {
+        let input = include_str!("../../sample/thirteenth.txt");
+        for grid in parse_grids(input).expect("parsing") {
+            for direction in [Reflection::Horizontal, Reflection::Vertical] {
+                let Some(((row, col), fold, _)) = grid.find_smudge(direction) else {
+                    continue;
+                };
+                let fixed = grid.toggled(row, col);
+                assert!(!grid.reflects(fold, direction), "\n{grid:?}");
+                assert!(fixed.reflects(fold, direction), "\n{fixed:?}");
+                assert_eq!(fixed.toggled(row, col), grid);
+            }
+        }
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(4)]
+    fn scores_on_any_number_of_threads(#[case] jobs: usize) {
+        let grids = parse_grids(include_str!("../../sample/thirteenth.txt")).expect("parsing");
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build()
+            .expect("thread pool");
+        assert_eq!(pool.install(|| total_score(&grids, Part::One)), 405);
+        assert_eq!(pool.install(|| total_score(&grids, Part::Two)), 400);
+    }
+
     #[rstest]
     fn sample_b() {
         let input = include_str!("../../sample/thirteenth.txt");
 
-        let mut grids = input
+        let grids = input
             .split("\n\n")
             .map(Grid::from_str)
             .collect::<Result<Vec<_>>>()
             .unwrap();
 
-        let mut lefts = 0;
-        let mut aboves = 0;
-        for grid in grids.iter_mut() {
-            let (_index, fold, dir) = [Reflection::Horizontal, Reflection::Vertical]
-                .into_iter()
-                .flat_map(|r| grid.find_smudge(r))
-                .next()
-                .expect("a smudge");
-            match dir {
-                Reflection::Vertical => lefts += fold,
-                Reflection::Horizontal => aboves += fold,
-            };
-        }
+        assert_eq!(400, total_score(&grids, Part::Two));
+    }
 
-        assert_eq!(400, lefts + 100 * aboves);
+    #[cfg(feature = "serialize")]
+    #[rstest]
+    fn json_round_trip() {
+        let input = include_str!("../../sample/thirteenth.txt");
+        for grid in parse_grids(input).expect("parsing") {
+            let json = serde_json::to_string(&grid).expect("serializing");
+            assert_eq!(
+                serde_json::from_str::<Grid>(&json).expect("deserializing"),
+                grid
+            );
+        }
     }
 }
@@ -0,0 +1,53 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use aoc23::{
+    timing::Timings,
+    twentyfifth::{Wiring, WIRES},
+};
+use clap::Parser;
+
+/// Day 25: Snowverload
+#[derive(Debug, Parser)]
+struct Options {
+    /// Path to the file with the input data
+    #[clap(short, long, default_value = "sample/twentyfifth.txt")]
+    input: String,
+
+    /// Print how long each phase of the solve took
+    #[clap(short, long)]
+    time: bool,
+
+    /// Together with `--time` also print what each phase allocated (needs `alloc-stats` feature)
+    #[clap(short, long)]
+    verbose: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Options::parse();
+    let input = aoc23::normalize_input(&aoc23::input::read(&args.input, 25)?);
+    let mut timings = Timings::new(args.time, args.verbose);
+    let wiring = timings.measure("parse", || Wiring::from_str(&input))?;
+    let cut = timings
+        .measure("cut", || wiring.min_cut(WIRES))
+        .ok_or(anyhow!("No {WIRES} wires split the components in two"))?;
+    for (a, b) in &cut.wires {
+        println!("Disconnect {}/{}", wiring.name(*a), wiring.name(*b));
+    }
+    let solution = cut.group.len() * (wiring.len() - cut.group.len());
+    println!("Solution: {solution}");
+    eprint!("{timings}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc23::{twentyfifth::solve, Part};
+    use rstest::rstest;
+
+    #[rstest]
+    fn sample() {
+        let input = include_str!("../../sample/twentyfifth.txt");
+        assert_eq!(solve(Part::One, input).expect("solving"), 54);
+    }
+}
@@ -0,0 +1,67 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+use aoc23::{
+    timing::Timings,
+    twentyfirst::{Garden, INFINITE_STEPS, STEPS},
+    Part,
+};
+use clap::Parser;
+
+/// Day 21: Step Counter
+#[derive(Debug, Parser)]
+struct Options {
+    /// Path to the file with the input data
+    #[clap(short, long, default_value = "sample/twentyfirst.txt")]
+    input: String,
+
+    /// Which part of the day to solve
+    part: Part,
+
+    /// How many steps to walk instead of the part's own
+    #[clap(short, long)]
+    steps: Option<u64>,
+
+    /// Print how long each phase of the solve took
+    #[clap(short, long)]
+    time: bool,
+
+    /// Together with `--time` also print what each phase allocated (needs `alloc-stats` feature)
+    #[clap(short, long)]
+    verbose: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Options::parse();
+    let input = aoc23::normalize_input(&aoc23::input::read(&args.input, 21)?);
+    let mut timings = Timings::new(args.time, args.verbose);
+    let garden = timings.measure("parse", || Garden::from_str(&input))?;
+    let solution = match args.part {
+        Part::One => {
+            let steps = args.steps.unwrap_or(STEPS);
+            timings.measure("walk", || garden.walk(steps, false))
+        }
+        Part::Two => {
+            let steps = args.steps.unwrap_or(INFINITE_STEPS);
+            timings.measure("extrapolate", || garden.reachable(steps))
+        }
+    };
+    println!("Solution part {:?}: {solution}", args.part);
+    eprint!("{timings}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(6, 16)]
+    #[case(100, 6536)]
+    fn sample(#[case] steps: u64, #[case] expected: u64) {
+        let input = include_str!("../../sample/twentyfirst.txt");
+        let garden = Garden::from_str(input).expect("parsing");
+        assert_eq!(garden.reachable(steps), expected);
+    }
+}
@@ -0,0 +1,72 @@
+use anyhow::{anyhow, Result};
+use aoc23::{
+    timing::Timings,
+    twentyfourth::{crossings, parse, throw, TEST_AREA},
+    Part,
+};
+use clap::Parser;
+
+/// Day 24: Never Tell Me The Odds
+#[derive(Debug, Parser)]
+struct Options {
+    /// Path to the file with the input data
+    #[clap(short, long, default_value = "sample/twentyfourth.txt")]
+    input: String,
+
+    /// Which part of the day to solve
+    part: Part,
+
+    /// Lower bound of the test area in X & Y, e.g. `7` for the sample
+    #[clap(long, default_value_t = *TEST_AREA.start())]
+    min: i128,
+
+    /// Upper bound of the test area in X & Y, e.g. `27` for the sample
+    #[clap(long, default_value_t = *TEST_AREA.end())]
+    max: i128,
+
+    /// Print how long each phase of the solve took
+    #[clap(short, long)]
+    time: bool,
+
+    /// Together with `--time` also print what each phase allocated (needs `alloc-stats` feature)
+    #[clap(short, long)]
+    verbose: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Options::parse();
+    let input = aoc23::normalize_input(&aoc23::input::read(&args.input, 24)?);
+    let mut timings = Timings::new(args.time, args.verbose);
+    let hailstones = timings.measure("parse", || parse(&input))?;
+    let solution = match args.part {
+        Part::One => {
+            let area = args.min..=args.max;
+            timings.measure("crossings", || crossings(&hailstones, &area)) as i128
+        }
+        Part::Two => {
+            let rock = timings
+                .measure("throw", || throw(&hailstones))
+                .ok_or(anyhow!("No throw hits all hailstones"))?;
+            rock.position.0.iter().sum()
+        }
+    };
+    println!("Solution part {:?}: {solution}", args.part);
+    eprint!("{timings}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc23::twentyfourth::SAMPLE_TEST_AREA;
+    use rstest::rstest;
+
+    #[rstest]
+    fn sample() {
+        let input = include_str!("../../sample/twentyfourth.txt");
+        let hailstones = parse(input).expect("parsing");
+        assert_eq!(crossings(&hailstones, &SAMPLE_TEST_AREA), 2);
+        let rock = throw(&hailstones).expect("throwing");
+        assert_eq!(rock.position.0.iter().sum::<i128>(), 47);
+    }
+}
@@ -0,0 +1,54 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+use aoc23::{timing::Timings, twentysecond::Snapshot, Part};
+use clap::Parser;
+
+/// Day 22: Sand Slabs
+#[derive(Debug, Parser)]
+struct Options {
+    /// Path to the file with the input data
+    #[clap(short, long, default_value = "sample/twentysecond.txt")]
+    input: String,
+
+    /// Which part of the day to solve
+    part: Part,
+
+    /// Print how long each phase of the solve took
+    #[clap(short, long)]
+    time: bool,
+
+    /// Together with `--time` also print what each phase allocated (needs `alloc-stats` feature)
+    #[clap(short, long)]
+    verbose: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Options::parse();
+    let input = aoc23::normalize_input(&aoc23::input::read(&args.input, 22)?);
+    let mut timings = Timings::new(args.time, args.verbose);
+    let snapshot = timings.measure("parse", || Snapshot::from_str(&input))?;
+    let stack = timings.measure("settle", || snapshot.settle());
+    let solution = match args.part {
+        Part::One => timings.measure("disintegrate", || stack.disintegrable()),
+        Part::Two => timings.measure("chain reactions", || stack.falls()),
+    };
+    println!("Solution part {:?}: {solution}", args.part);
+    eprint!("{timings}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc23::twentysecond::solve;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Part::One, 5)]
+    #[case(Part::Two, 7)]
+    fn sample(#[case] part: Part, #[case] expected: usize) {
+        let input = include_str!("../../sample/twentysecond.txt");
+        assert_eq!(solve(part, input).expect("solving"), expected);
+    }
+}
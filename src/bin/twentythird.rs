@@ -0,0 +1,54 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use aoc23::{timing::Timings, twentythird::Trails, Part};
+use clap::Parser;
+
+/// Day 23: A Long Walk
+#[derive(Debug, Parser)]
+struct Options {
+    /// Path to the file with the input data
+    #[clap(short, long, default_value = "sample/twentythird.txt")]
+    input: String,
+
+    /// Which part of the day to solve
+    part: Part,
+
+    /// Print how long each phase of the solve took
+    #[clap(short, long)]
+    time: bool,
+
+    /// Together with `--time` also print what each phase allocated (needs `alloc-stats` feature)
+    #[clap(short, long)]
+    verbose: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Options::parse();
+    let input = aoc23::normalize_input(&aoc23::input::read(&args.input, 23)?);
+    let mut timings = Timings::new(args.time, args.verbose);
+    let trails = timings.measure("parse", || Trails::from_str(&input))?;
+    let slippery = args.part == Part::One;
+    let junctions = timings.measure("contract", || trails.contract(slippery))?;
+    let solution = timings
+        .measure("longest path", || junctions.longest_path(0, 1))
+        .ok_or(anyhow!("There is no hike from the start to the end"))?;
+    println!("Solution part {:?}: {solution}", args.part);
+    eprint!("{timings}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc23::twentythird::solve;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Part::One, 94)]
+    #[case(Part::Two, 154)]
+    fn sample(#[case] part: Part, #[case] expected: u32) {
+        let input = include_str!("../../sample/twentythird.txt");
+        assert_eq!(solve(part, input).expect("solving"), expected);
+    }
+}
@@ -0,0 +1,110 @@
+//! Log of the solves of the inputs in a directory, next to them in [`RUNS_FILE`], which
+//! `aoc23 summary` sums up into stars, fastest times & last runs per day
+
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::report::{Entry, Report};
+
+/// Log of the solves of the inputs in a directory, one JSON [`Run`] per line
+pub const RUNS_FILE: &str = "runs.jsonl";
+
+/// One solve of an input and when it happened
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Run {
+    #[serde(flatten)]
+    pub entry: Entry,
+    /// Seconds since the unix epoch
+    pub timestamp: u64,
+}
+
+impl Run {
+    /// The solve of `entry`, as finished just now
+    pub fn now(entry: Entry) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since| since.as_secs())
+            .unwrap_or_default();
+        Self { entry, timestamp }
+    }
+}
+
+/// Appends `runs` to the log in `dir`
+pub fn record(dir: &Path, runs: impl IntoIterator<Item = Run>) -> Result<()> {
+    let path = dir.join(RUNS_FILE);
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Opening {path:?}"))?;
+    for run in runs {
+        writeln!(file, "{}", serde_json::to_string(&run)?)?;
+    }
+    Ok(())
+}
+
+/// All runs logged in `dir`, oldest first. Empty if nothing got recorded there yet
+pub fn runs(dir: &Path) -> Result<Vec<Run>> {
+    let path = dir.join(RUNS_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    std::fs::read_to_string(&path)?
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line).with_context(|| format!("Line {} of {path:?}", i + 1))
+        })
+        .collect()
+}
+
+/// The solves of the JSON report at `path`, as runs at the time it got written
+pub fn report_runs(path: PathBuf) -> Result<Vec<Run>> {
+    let timestamp = std::fs::metadata(&path)?
+        .modified()?
+        .duration_since(UNIX_EPOCH)?
+        .as_secs();
+    let report: Report = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+    Ok(report
+        .entries
+        .into_iter()
+        .map(|entry| Run { entry, timestamp })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Day, Part};
+    use rstest::rstest;
+
+    #[rstest]
+    fn records_runs() {
+        let dir = std::env::temp_dir().join("aoc23-recorded-runs");
+        std::fs::create_dir_all(&dir).expect("creating");
+        let _ = std::fs::remove_file(dir.join(RUNS_FILE));
+        assert!(runs(&dir).expect("no runs").is_empty());
+
+        let run = |part, seconds| {
+            Run::now(Entry {
+                day: Day::Fifth,
+                part,
+                answer: "35".to_owned(),
+                seconds,
+            })
+        };
+        let first = vec![run(Part::One, 0.5)];
+        let second = vec![run(Part::One, 0.25), run(Part::Two, 1.)];
+        record(&dir, first.clone()).expect("recording");
+        record(&dir, second.clone()).expect("recording");
+        assert_eq!(runs(&dir).expect("runs"), [first, second].concat());
+    }
+}
@@ -0,0 +1,446 @@
+//! Zooming & panning the camera of the animations with the mouse or touch gestures, tunable per
+//! user through a TOML config file and/or command line flags
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use bevy::{
+    input::{
+        mouse::{MouseMotion, MouseWheel},
+        touch::Touches,
+        touchpad::TouchpadMagnify,
+    },
+    prelude::*,
+    window::PrimaryWindow,
+};
+use clap::{Args, ValueEnum};
+use serde::{Deserialize, Serialize};
+
+use crate::crt::CrtPlugin;
+
+#[derive(Debug, Component)]
+pub struct Scroll(pub f32);
+
+/// Mouse buttons, which pan the camera while held down
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Pan {
+    #[default]
+    LeftOrRight,
+    Middle,
+}
+
+impl Pan {
+    fn buttons(self) -> &'static [MouseButton] {
+        match self {
+            Self::LeftOrRight => &[MouseButton::Left, MouseButton::Right],
+            Self::Middle => &[MouseButton::Middle],
+        }
+    }
+}
+
+/// Optional file in the working directory with the [`CameraSettings`], e.g. `invert_scroll = true`
+/// and `pan = "middle"`
+pub const FILE: &str = "camera.toml";
+
+/// How [`mouse`] moves the camera. Comes from [`FILE`], unless inserted before adding the
+/// [`AocCameraPlugin`], e.g. by [`crate::Presentation`]
+#[derive(Debug, Clone, Copy, PartialEq, Resource, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CameraSettings {
+    /// How fast the zoom catches up with the scroll wheel
+    pub zoom_speed: f32,
+    /// How much one notch of the scroll wheel zooms
+    pub sensitivity: f32,
+    /// Scrolling up zooms in instead of out
+    pub invert_scroll: bool,
+    /// Keep the point below the cursor in place while zooming, instead of the window's center
+    pub zoom_to_cursor: bool,
+    pub pan: Pan,
+    /// Overlay scanlines & a vignette like on an old CRT screen, see [`crate::crt`]
+    pub crt: bool,
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        Self {
+            zoom_speed: 4.,
+            sensitivity: 0.1,
+            invert_scroll: false,
+            zoom_to_cursor: true,
+            pan: Pan::default(),
+            crt: false,
+        }
+    }
+}
+
+impl CameraSettings {
+    /// Settings from the TOML file at `path`, or the default ones if there is none
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path).with_context(|| format!("Reading {path:?}"))?;
+        toml::from_str(&content).with_context(|| format!("Parsing {path:?}"))
+    }
+}
+
+/// Command line flags for the [`CameraSettings`], to `#[clap(flatten)]` into a binary's options.
+/// Flags override the config file
+#[derive(Debug, Default, Args)]
+pub struct CameraArgs {
+    /// TOML file with the camera settings, e.g. `invert_scroll = true`
+    #[clap(long, default_value = FILE)]
+    camera: PathBuf,
+
+    /// How much one notch of the scroll wheel zooms
+    #[clap(long)]
+    zoom_sensitivity: Option<f32>,
+
+    /// Scrolling up zooms in instead of out
+    #[clap(long)]
+    invert_scroll: bool,
+
+    /// Zoom towards the window's center instead of the cursor
+    #[clap(long)]
+    zoom_to_center: bool,
+
+    /// Mouse buttons, which pan the camera while held down
+    #[clap(long, value_enum)]
+    pan: Option<Pan>,
+
+    /// Overlay scanlines & a vignette like on an old CRT screen, e.g. for recording demo clips
+    #[clap(long)]
+    crt: bool,
+}
+
+impl CameraArgs {
+    pub fn settings(&self) -> anyhow::Result<CameraSettings> {
+        let mut settings = CameraSettings::load(&self.camera)?;
+        if let Some(sensitivity) = self.zoom_sensitivity {
+            settings.sensitivity = sensitivity;
+        }
+        settings.invert_scroll |= self.invert_scroll;
+        settings.zoom_to_cursor &= !self.zoom_to_center;
+        if let Some(pan) = self.pan {
+            settings.pan = pan;
+        }
+        settings.crt |= self.crt;
+        Ok(settings)
+    }
+}
+
+/// A `position` in the window relative to its center with y pointing up, i.e. the direction of
+/// world space
+fn offset(window: &Window, position: Vec2) -> Vec2 {
+    let offset = position - Vec2::new(window.width(), window.height()) / 2.;
+    Vec2::new(offset.x, -offset.y)
+}
+
+fn cursor_offset(window: &Window) -> Option<Vec2> {
+    Some(offset(window, window.cursor_position()?))
+}
+
+/// Two fingers moving on a touch screen
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Pinch {
+    /// Change of [`Scroll`], negative when the fingers spread apart to zoom in
+    zoom: f32,
+    /// How far the fingers moved together, in window coordinates like [`MouseMotion`]
+    pan: Vec2,
+    /// Middle between the fingers, in window coordinates
+    center: Vec2,
+}
+
+impl Pinch {
+    fn new(previous: [Vec2; 2], current: [Vec2; 2]) -> Self {
+        let middle = |[a, b]: [Vec2; 2]| (a + b) / 2.;
+        let before = previous[0].distance(previous[1]);
+        let after = current[0].distance(current[1]);
+        Self {
+            zoom: if before > f32::EPSILON && after > f32::EPSILON {
+                (before / after).ln()
+            } else {
+                0.
+            },
+            pan: middle(current) - middle(previous),
+            center: middle(current),
+        }
+    }
+
+    /// Pinch of the first two fingers on the screen, if there are exactly two
+    fn read(touches: &Touches) -> Option<Self> {
+        let [a, b] = touches.iter().collect::<Vec<_>>().try_into().ok()?;
+        Some(Self::new(
+            [a.previous_position(), b.previous_position()],
+            [a.position(), b.position()],
+        ))
+    }
+}
+
+/// Where the camera has to move, for the world point shown at `cursor` (see [`cursor_offset`]) to
+/// stay there, when its scale changes from `from` to `to`
+fn zoom_shift(cursor: Vec2, from: f32, to: f32) -> Vec2 {
+    cursor * (from - to)
+}
+
+/// Zooms & pans the camera with the mouse, with two fingers on a touch screen, or by pinching on a
+/// touchpad
+#[allow(clippy::too_many_arguments)]
+pub fn mouse(
+    time: Res<Time>,
+    settings: Res<CameraSettings>,
+    mouse: Res<Input<MouseButton>>,
+    touches: Res<Touches>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut motion: EventReader<MouseMotion>,
+    mut scroll: EventReader<MouseWheel>,
+    mut magnify: EventReader<TouchpadMagnify>,
+    mut query: Query<(&mut Scroll, &mut Transform), With<Camera>>,
+) {
+    let pressed = mouse.any_pressed(settings.pan.buttons().iter().copied());
+    let motion = motion.read().map(|ev| ev.delta).sum::<Vec2>();
+    let mut pan = if pressed { motion } else { Vec2::ZERO };
+    let mut delta = scroll.read().map(|ev| ev.y).sum::<f32>();
+    if settings.invert_scroll {
+        delta = -delta;
+    }
+    let mut zoom = delta * settings.sensitivity - magnify.read().map(|ev| ev.0).sum::<f32>();
+    let window = windows.get_single().ok();
+    let mut anchor = window.and_then(cursor_offset);
+    if let Some(pinch) = Pinch::read(&touches) {
+        zoom += pinch.zoom;
+        pan += pinch.pan;
+        anchor = window.map(|window| offset(window, pinch.center));
+    }
+    let anchor = anchor.filter(|_| settings.zoom_to_cursor);
+
+    for (mut scroll, mut tf) in query.iter_mut() {
+        scroll.0 += zoom;
+        let from = tf.scale.x;
+        let to = from + settings.zoom_speed * (scroll.0.exp() - from) * time.delta_seconds();
+        tf.scale = Vec3::splat(to);
+        if let Some(anchor) = anchor {
+            tf.translation += zoom_shift(anchor, from, to).extend(0.);
+        }
+        tf.translation += Vec3::new(-pan.x, pan.y, 0.) * to;
+    }
+}
+
+/// Spawns the 2D camera of an animation with a [`Scroll`] and lets the user move it with [`mouse`].
+/// The [`CameraSettings`] come from [`FILE`], unless inserted before adding this plugin. Adds the
+/// [`CrtPlugin`], if they ask for it
+#[derive(Debug, Clone, Copy)]
+pub struct AocCameraPlugin {
+    scroll: f32,
+    translation: Option<Vec3>,
+}
+
+impl AocCameraPlugin {
+    /// Camera zoomed out to `exp(scroll)`, looking at the origin
+    pub fn new(scroll: f32) -> Self {
+        Self {
+            scroll,
+            translation: None,
+        }
+    }
+
+    /// Looks at `translation` instead of the origin
+    pub fn at(self, translation: Vec3) -> Self {
+        Self {
+            translation: Some(translation),
+            ..self
+        }
+    }
+}
+
+impl Plugin for AocCameraPlugin {
+    fn build(&self, app: &mut App) {
+        let Self {
+            scroll,
+            translation,
+        } = *self;
+        app.add_systems(Startup, move |mut cmd: Commands| {
+            let mut camera = Camera2dBundle::default();
+            if let Some(translation) = translation {
+                camera.transform.translation = translation;
+            }
+            cmd.spawn((Scroll(scroll), camera));
+        })
+        .add_systems(Update, mouse);
+        if !app.world.contains_resource::<CameraSettings>() {
+            let settings = CameraSettings::load(FILE).unwrap_or_else(|e| {
+                error!("{e:#}, using the default camera settings");
+                CameraSettings::default()
+            });
+            app.insert_resource(settings);
+        }
+        if app.world.resource::<CameraSettings>().crt {
+            app.add_plugins(CrtPlugin);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HeadlessPlugins, Presentation};
+    use bevy::{input::mouse::MouseScrollUnit, time::TimeUpdateStrategy, window::WindowResolution};
+    use rstest::rstest;
+    use std::time::Duration;
+
+    #[rstest]
+    fn plugin_spawns_scrollable_camera() {
+        let mut app = App::new();
+        app.add_plugins((
+            HeadlessPlugins,
+            AocCameraPlugin::new(0.5).at(Vec3::new(1., 2., 3.)),
+        ));
+        app.update();
+        let (scroll, tf) = app
+            .world
+            .query_filtered::<(&Scroll, &Transform), With<Camera>>()
+            .single(&app.world);
+        assert_eq!(scroll.0, 0.5);
+        assert_eq!(tf.translation, Vec3::new(1., 2., 3.));
+    }
+
+    #[rstest]
+    fn plugin_keeps_presented_settings() {
+        let camera = CameraSettings {
+            invert_scroll: true,
+            ..Default::default()
+        };
+        let mut app = App::new();
+        app.add_plugins((
+            HeadlessPlugins,
+            Presentation {
+                camera,
+                ..Default::default()
+            },
+            AocCameraPlugin::new(0.),
+        ));
+        assert_eq!(*app.world.resource::<CameraSettings>(), camera);
+    }
+
+    #[rstest]
+    fn flags_override_config_file() {
+        let path = std::env::temp_dir().join("aoc23-camera-settings.toml");
+        std::fs::write(&path, "sensitivity = 0.5\npan = \"middle\"\n").expect("writing");
+        let args = CameraArgs {
+            camera: path,
+            invert_scroll: true,
+            zoom_to_center: true,
+            pan: Some(Pan::LeftOrRight),
+            crt: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            args.settings().expect("settings"),
+            CameraSettings {
+                sensitivity: 0.5,
+                invert_scroll: true,
+                zoom_to_cursor: false,
+                pan: Pan::LeftOrRight,
+                crt: true,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[rstest]
+    #[case(Vec2::ZERO, 1., 2.)]
+    #[case(Vec2::new(100., -50.), 1., 2.)]
+    #[case(Vec2::new(-30., 20.), 2., 0.5)]
+    fn zooming_keeps_cursor_in_place(#[case] cursor: Vec2, #[case] from: f32, #[case] to: f32) {
+        let camera = Vec2::new(7., 3.);
+        let world = camera + cursor * from;
+        let moved = camera + zoom_shift(cursor, from, to);
+        assert!((moved + cursor * to - world).length() < 1e-4);
+    }
+
+    #[rstest]
+    #[case([Vec2::ZERO, Vec2::X], [Vec2::ZERO, Vec2::X * 2.], -2f32.ln(), Vec2::X / 2.)]
+    #[case([Vec2::ZERO, Vec2::X * 4.], [Vec2::X, Vec2::X * 3.], 2f32.ln(), Vec2::ZERO)]
+    #[case([Vec2::ZERO, Vec2::Y], [Vec2::ONE, Vec2::ONE + Vec2::Y], 0., Vec2::ONE)]
+    #[case([Vec2::ONE; 2], [Vec2::ONE; 2], 0., Vec2::ZERO)]
+    fn pinching_zooms_and_pans(
+        #[case] previous: [Vec2; 2],
+        #[case] current: [Vec2; 2],
+        #[case] zoom: f32,
+        #[case] pan: Vec2,
+    ) {
+        let pinch = Pinch::new(previous, current);
+        assert!((pinch.zoom - zoom).abs() < 1e-6, "{pinch:?}");
+        assert_eq!(pinch.pan, pan);
+    }
+
+    #[rstest]
+    #[case(1.)]
+    #[case(-3.)]
+    fn scrolling_keeps_world_below_cursor(#[case] notches: f32) {
+        let mut app = App::new();
+        app.add_plugins(HeadlessPlugins)
+            .insert_resource(CameraSettings::default())
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )))
+            .add_systems(Update, mouse);
+        let mut window = Window {
+            resolution: WindowResolution::new(800., 600.),
+            ..default()
+        };
+        window.set_cursor_position(Some(Vec2::new(650., 100.)));
+        let cursor = cursor_offset(&window).expect("cursor");
+        let window = app.world.spawn((window, PrimaryWindow)).id();
+        let camera = app
+            .world
+            .spawn((
+                Scroll(0.),
+                Camera::default(),
+                Transform::from_xyz(7., 3., 0.),
+            ))
+            .id();
+        let below = |app: &App| {
+            let tf = app.world.get::<Transform>(camera).expect("camera");
+            tf.translation.truncate() + cursor * tf.scale.x
+        };
+        let world = below(&app);
+
+        app.world.send_event(MouseWheel {
+            unit: MouseScrollUnit::Line,
+            x: 0.,
+            y: notches,
+            window,
+        });
+        for _ in 0..10 {
+            app.update();
+        }
+        let tf = app.world.get::<Transform>(camera).expect("camera");
+        assert_ne!(tf.scale.x, 1., "Camera did not zoom");
+        assert!((below(&app) - world).length() < 1e-3);
+    }
+
+    #[rstest]
+    fn magnifying_touchpad_zooms_in() {
+        let mut app = App::new();
+        app.add_plugins(HeadlessPlugins)
+            .insert_resource(CameraSettings::default())
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )))
+            .add_systems(Update, mouse);
+        let camera = app
+            .world
+            .spawn((Scroll(0.), Camera::default(), Transform::default()))
+            .id();
+        app.world.send_event(TouchpadMagnify(0.5));
+        for _ in 0..10 {
+            app.update();
+        }
+        assert_eq!(app.world.get::<Scroll>(camera).expect("scroll").0, -0.5);
+        let tf = app.world.get::<Transform>(camera).expect("camera");
+        assert!(tf.scale.x < 1., "Camera did not zoom in: {tf:?}");
+    }
+}
@@ -0,0 +1,180 @@
+//! Recording the frames of an animation as numbered PNGs, optionally assembled into a GIF once its
+//! window closes
+
+use std::{
+    fs::File,
+    iter::once,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use bevy::{
+    app::AppExit, prelude::*, render::view::screenshot::ScreenshotManager, window::PrimaryWindow,
+};
+use clap::Args;
+use image::{
+    codecs::gif::{GifEncoder, Repeat},
+    Delay, DynamicImage, Frame,
+};
+
+/// How long the last frame of a GIF is shown, since there is no next one to wait for
+const LAST_DELAY: Duration = Duration::from_millis(100);
+
+/// Command line flags for recording, to `#[clap(flatten)]` into a binary's options
+#[derive(Debug, Default, Args)]
+pub struct CaptureArgs {
+    /// Save every rendered frame as numbered PNG into this directory
+    #[clap(long)]
+    record: Option<PathBuf>,
+
+    /// Also assemble the recorded frames into `animation.gif` once the window closes
+    #[clap(long, requires = "record")]
+    gif: bool,
+}
+
+impl CaptureArgs {
+    /// Where to record to, if `--record` was given. Creates the directory already
+    pub fn recording(&self) -> anyhow::Result<Option<Recording>> {
+        let Some(dir) = &self.record else {
+            return Ok(None);
+        };
+        std::fs::create_dir_all(dir)?;
+        Ok(Some(Recording {
+            dir: dir.clone(),
+            gif: self.gif,
+        }))
+    }
+}
+
+/// Where [`CapturePlugin`] saves the frames to
+#[derive(Debug, Clone, Resource)]
+pub struct Recording {
+    pub dir: PathBuf,
+    pub gif: bool,
+}
+
+/// Records the frames of the primary window, if a [`Recording`] is inserted before adding this
+/// plugin, e.g. by [`crate::Presentation`]. Does nothing otherwise
+#[derive(Debug)]
+pub struct CapturePlugin;
+
+impl Plugin for CapturePlugin {
+    fn build(&self, app: &mut App) {
+        if !app.world.contains_resource::<Recording>() {
+            return;
+        }
+        app.init_resource::<Frames>()
+            .add_systems(Last, (capture, assemble));
+    }
+}
+
+/// Number of a frame, when it was taken and what it shows
+type Shot = (usize, Duration, DynamicImage);
+
+#[derive(Default, Resource)]
+struct Frames {
+    requested: usize,
+    /// Screenshots arrive asynchronously, only kept around for the GIF
+    shots: Arc<Mutex<Vec<Shot>>>,
+}
+
+fn frame_path(dir: &Path, frame: usize) -> PathBuf {
+    dir.join(format!("frame-{frame:05}.png"))
+}
+
+fn capture(
+    time: Res<Time>,
+    recording: Res<Recording>,
+    mut frames: ResMut<Frames>,
+    screenshots: Option<ResMut<ScreenshotManager>>,
+    windows: Query<Entity, With<PrimaryWindow>>,
+) {
+    let (Some(mut screenshots), Ok(window)) = (screenshots, windows.get_single()) else {
+        return;
+    };
+    let (frame, elapsed) = (frames.requested, time.elapsed());
+    let path = frame_path(&recording.dir, frame);
+    let shots = recording.gif.then(|| frames.shots.clone());
+    let requested = screenshots.take_screenshot(window, move |image| {
+        let image = match image.try_into_dynamic() {
+            Ok(image) => image,
+            Err(e) => {
+                error!("Converting frame {frame}: {e}");
+                return;
+            }
+        };
+        if let Err(e) = image.to_rgb8().save(&path) {
+            error!("Saving {path:?}: {e}");
+        }
+        if let Some(shots) = shots {
+            shots
+                .lock()
+                .expect("poisoned")
+                .push((frame, elapsed, image));
+        }
+    });
+    if requested.is_ok() {
+        frames.requested += 1;
+    }
+}
+
+fn assemble(recording: Res<Recording>, frames: Res<Frames>, mut exit: EventReader<AppExit>) {
+    if exit.read().next().is_none() || !recording.gif {
+        return;
+    }
+    let mut shots = std::mem::take(&mut *frames.shots.lock().expect("poisoned"));
+    let path = recording.dir.join("animation.gif");
+    match gif(&path, &mut shots) {
+        Ok(()) => info!("Saved {} frames to {path:?}", shots.len()),
+        Err(e) => error!("Assembling {path:?}: {e}"),
+    }
+}
+
+/// Encodes `shots` into a looping GIF, showing each one until the next was taken
+fn gif(path: &Path, shots: &mut [Shot]) -> anyhow::Result<()> {
+    shots.sort_by_key(|(frame, _, _)| *frame);
+    let delays = shots
+        .windows(2)
+        .map(|pair| pair[1].1.saturating_sub(pair[0].1))
+        .chain(once(LAST_DELAY))
+        .collect::<Vec<_>>();
+    let mut encoder = GifEncoder::new(File::create(path)?);
+    encoder.set_repeat(Repeat::Infinite)?;
+    for ((_, _, image), delay) in shots.iter().zip(delays) {
+        let delay = Delay::from_saturating_duration(delay);
+        encoder.encode_frame(Frame::from_parts(image.to_rgba8(), 0, 0, delay))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{codecs::gif::GifDecoder, AnimationDecoder, Rgba, RgbaImage};
+    use rstest::rstest;
+
+    #[rstest]
+    fn gif_shows_frames_in_order() {
+        let path = std::env::temp_dir().join("aoc23-capture.gif");
+        let shot = |frame: usize, shade: u8| {
+            let image = RgbaImage::from_pixel(4, 3, Rgba([shade, shade, shade, 255]));
+            let elapsed = Duration::from_millis(50 * frame as u64);
+            (frame, elapsed, DynamicImage::ImageRgba8(image))
+        };
+        let mut shots = vec![shot(2, 200), shot(0, 0), shot(1, 100)];
+        gif(&path, &mut shots).expect("encoding");
+
+        let decoder = GifDecoder::new(File::open(&path).expect("opening")).expect("decoding");
+        let frames = decoder.into_frames().collect_frames().expect("frames");
+        let shades = frames
+            .iter()
+            .map(|frame| frame.buffer().get_pixel(0, 0).0[0])
+            .collect::<Vec<_>>();
+        assert_eq!(shades, vec![0, 100, 200]);
+        assert_eq!(
+            frames[0].delay(),
+            Delay::from_saturating_duration(Duration::from_millis(50))
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//! Retro look for the animations: scanlines & a vignette, as if shown on an old CRT screen. Purely
+//! cosmetic, e.g. for recording demo clips, and enabled with `--crt` (see [`CameraSettings`])
+//!
+//! [`CameraSettings`]: crate::camera::CameraSettings
+
+use bevy::{
+    asset::load_internal_asset,
+    prelude::*,
+    render::render_resource::{AsBindGroup, ShaderRef},
+    sprite::{Material2d, Material2dPlugin, MaterialMesh2dBundle},
+    window::PrimaryWindow,
+};
+
+const SHADER: Handle<Shader> = Handle::weak_from_u128(0x6372_745f_7363_616e_6c69_6e65_7332_3032);
+
+/// Translucent overlay, which darkens the gaps between the scanlines and the screen's corners
+#[derive(Debug, Clone, Asset, TypePath, AsBindGroup)]
+pub struct CrtMaterial {
+    /// Pixels from one scanline to the next
+    #[uniform(0)]
+    pub period: f32,
+    /// How dark the gaps between the scanlines get, from 0 (not at all) to 1 (black)
+    #[uniform(1)]
+    pub darkness: f32,
+    /// How dark the corners get
+    #[uniform(2)]
+    pub vignette: f32,
+}
+
+impl Default for CrtMaterial {
+    fn default() -> Self {
+        Self {
+            period: 3.,
+            darkness: 0.35,
+            vignette: 0.6,
+        }
+    }
+}
+
+impl Material2d for CrtMaterial {
+    fn fragment_shader() -> ShaderRef {
+        SHADER.into()
+    }
+}
+
+/// Marks the quad in front of each camera, which carries the [`CrtMaterial`]
+#[derive(Debug, Component)]
+pub struct Crt;
+
+/// Covers every camera with a [`CrtMaterial`] overlay, which follows it around and always fills
+/// the whole window
+#[derive(Debug, Clone, Copy)]
+pub struct CrtPlugin;
+
+impl Plugin for CrtPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(app, SHADER, "crt.wgsl", Shader::from_wgsl);
+        app.add_plugins(Material2dPlugin::<CrtMaterial>::default())
+            .add_systems(Update, (cover, fit));
+    }
+}
+
+fn cover(
+    mut cmd: Commands,
+    cameras: Query<Entity, Added<Camera>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<CrtMaterial>>,
+) {
+    for camera in cameras.iter() {
+        let overlay = MaterialMesh2dBundle {
+            mesh: meshes.add(shape::Quad::new(Vec2::ONE).into()).into(),
+            material: materials.add(CrtMaterial::default()),
+            // Just in front of the camera, so that it covers everything else
+            transform: Transform::from_xyz(0., 0., -1.),
+            ..default()
+        };
+        // Without visibility of its own, the camera would hide the overlay
+        cmd.entity(camera)
+            .insert(VisibilityBundle::default())
+            .with_children(|parent| {
+                parent.spawn((Crt, overlay));
+            });
+    }
+}
+
+/// Stretches the (unit sized) overlays to the window, the cameras' zoom scales them along
+fn fit(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut overlays: Query<&mut Transform, With<Crt>>,
+) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    for mut tf in overlays.iter_mut() {
+        tf.scale = Vec3::new(window.width(), window.height(), 1.);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HeadlessPlugins;
+    use bevy::window::WindowResolution;
+
+    #[test]
+    fn overlay_covers_the_window() {
+        let mut app = App::new();
+        app.add_plugins((HeadlessPlugins, CrtPlugin));
+        app.world.spawn((
+            Window {
+                resolution: WindowResolution::new(800., 600.),
+                ..default()
+            },
+            PrimaryWindow,
+        ));
+        let camera = app.world.spawn(Camera2dBundle::default()).id();
+        app.update();
+        app.update();
+
+        let (parent, tf) = app
+            .world
+            .query_filtered::<(&Parent, &Transform), With<Crt>>()
+            .single(&app.world);
+        assert_eq!(parent.get(), camera);
+        assert_eq!(tf.scale, Vec3::new(800., 600., 1.));
+    }
+}
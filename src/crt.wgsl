@@ -0,0 +1,16 @@
+// Darkens the gaps between scanlines and the corners of the screen, drawn on a quad covering it
+#import bevy_sprite::mesh2d_vertex_output::VertexOutput
+
+@group(1) @binding(0) var<uniform> period: f32;
+@group(1) @binding(1) var<uniform> darkness: f32;
+@group(1) @binding(2) var<uniform> vignette: f32;
+
+const TAU: f32 = 6.283185307;
+
+@fragment
+fn fragment(mesh: VertexOutput) -> @location(0) vec4<f32> {
+    // The fragment's position is in pixels, so the lines stay equally thick when zooming
+    let gap = 0.5 - 0.5 * cos(mesh.position.y * TAU / period);
+    let corner = smoothstep(0.35, 0.75, length(mesh.uv - vec2<f32>(0.5)));
+    return vec4<f32>(0.0, 0.0, 0.0, clamp(darkness * gap + vignette * corner, 0.0, 1.0));
+}
@@ -0,0 +1,215 @@
+//! The days of Advent of Code, so that they are told apart by more than a number or a name: the
+//! registry of [`crate::solvers`], the `aoc23` command line and the reports all key by [`Day`]
+
+use std::{fmt::Display, str::FromStr};
+
+use anyhow::{anyhow, bail, Context};
+use enum_iterator::{all, Sequence};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+const NAMES: [&str; 25] = [
+    "first",
+    "second",
+    "third",
+    "fourth",
+    "fifth",
+    "sixth",
+    "seventh",
+    "eighth",
+    "ninth",
+    "tenth",
+    "eleventh",
+    "twelfth",
+    "thirteenth",
+    "fourteenth",
+    "fifteenth",
+    "sixteenth",
+    "seventeenth",
+    "eighteenth",
+    "nineteenth",
+    "twentieth",
+    "twentyfirst",
+    "twentysecond",
+    "twentythird",
+    "twentyfourth",
+    "twentyfifth",
+];
+
+/// Shortest start of a name, which [`Day::from_str`] accepts for the whole one
+const MIN_PREFIX: usize = 3;
+
+/// Serialized as its number, so that e.g. the days of reports stay plain numbers in JSON
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Sequence, Serialize, Deserialize,
+)]
+#[serde(into = "u8", try_from = "u8")]
+pub enum Day {
+    First,
+    Second,
+    Third,
+    Fourth,
+    Fifth,
+    Sixth,
+    Seventh,
+    Eighth,
+    Ninth,
+    Tenth,
+    Eleventh,
+    Twelfth,
+    Thirteenth,
+    Fourteenth,
+    Fifteenth,
+    Sixteenth,
+    Seventeenth,
+    Eighteenth,
+    Nineteenth,
+    Twentieth,
+    TwentyFirst,
+    TwentySecond,
+    TwentyThird,
+    TwentyFourth,
+    TwentyFifth,
+}
+
+impl Day {
+    /// Day of December, from 1 to 25
+    pub fn number(&self) -> u8 {
+        *self as u8 + 1
+    }
+
+    /// Name of the day's binary and input file, e.g. `fifth` for day 5
+    pub fn name(&self) -> &'static str {
+        NAMES[*self as usize]
+    }
+}
+
+impl TryFrom<u8> for Day {
+    type Error = anyhow::Error;
+
+    fn try_from(number: u8) -> Result<Self, Self::Error> {
+        all::<Day>()
+            .nth(usize::from(number).wrapping_sub(1))
+            .ok_or(anyhow!("There is no day {number} in Advent of Code"))
+    }
+}
+
+impl From<Day> for u8 {
+    fn from(day: Day) -> Self {
+        day.number()
+    }
+}
+
+/// Its number, so that e.g. `Day {day}` reads like before
+impl Display for Day {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.number(), f)
+    }
+}
+
+/// Numbers like `5`, `05` or `5th`, names like `fifth`, `Fifth` or `twenty-first`, both optionally
+/// after `day`, or an unambiguous start of a name like `fourte`
+impl FromStr for Day {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let key = s
+            .to_lowercase()
+            .chars()
+            .filter(|c| !matches!(c, ' ' | '-' | '_'))
+            .collect::<String>();
+        let key = key.strip_prefix("day").unwrap_or(&key);
+
+        let digits = key.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+        if !digits.is_empty() && ["", "st", "nd", "rd", "th"].contains(&&key[digits.len()..]) {
+            let number = digits
+                .parse::<u8>()
+                .with_context(|| format!("There is no day {s:?} in Advent of Code"))?;
+            return Self::try_from(number);
+        }
+
+        if let Some(day) = all::<Day>().find(|day| day.name() == key) {
+            return Ok(day);
+        }
+        let candidates = all::<Day>()
+            .filter(|day| key.len() >= MIN_PREFIX && day.name().starts_with(key))
+            .collect::<Vec<_>>();
+        match candidates[..] {
+            [day] => Ok(day),
+            [] => bail!("There is no day {s:?} in Advent of Code"),
+            _ => bail!(
+                "{s:?} could be any of {}",
+                candidates.iter().map(Day::name).join(", ")
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("1", Day::First)]
+    #[case("01", Day::First)]
+    #[case("21st", Day::TwentyFirst)]
+    #[case("2nd", Day::Second)]
+    #[case("first", Day::First)]
+    #[case("Fifteenth", Day::Fifteenth)]
+    #[case("twenty-first", Day::TwentyFirst)]
+    #[case("Twenty Fifth", Day::TwentyFifth)]
+    #[case("day 5", Day::Fifth)]
+    #[case("day05", Day::Fifth)]
+    #[case("Day-twelfth", Day::Twelfth)]
+    #[case("fourte", Day::Fourteenth)]
+    #[case("tenth", Day::Tenth)]
+    fn parses(#[case] input: &str, #[case] expected: Day) {
+        assert_eq!(input.parse::<Day>().expect("parsing"), expected);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("0")]
+    #[case("26")]
+    #[case("300")]
+    #[case("5x")]
+    #[case("fi")]
+    #[case("fif")]
+    #[case("twentyf")]
+    #[case("thirtieth")]
+    fn rejects(#[case] input: &str) {
+        assert!(input.parse::<Day>().is_err(), "{input}");
+    }
+
+    #[rstest]
+    fn numbers_and_names_round_trip() {
+        for day in all::<Day>() {
+            assert_eq!(Day::try_from(day.number()).expect("number"), day);
+            assert_eq!(day.name().parse::<Day>().expect("name"), day);
+            assert_eq!(day.to_string().parse::<Day>().expect("display"), day);
+        }
+        assert_eq!(all::<Day>().count(), 25);
+        assert_eq!(Day::TwentyFifth.number(), 25);
+    }
+
+    #[rstest]
+    fn names_match_solvers() {
+        for (day, solver) in crate::solvers() {
+            assert_eq!(day.name(), solver.name());
+        }
+    }
+
+    #[rstest]
+    fn serializes_as_number() {
+        assert_eq!(
+            serde_json::to_string(&Day::Sixteenth).expect("serializing"),
+            "16"
+        );
+        assert_eq!(
+            serde_json::from_str::<Day>("7").expect("deserializing"),
+            Day::Seventh
+        );
+        assert!(serde_json::from_str::<Day>("26").is_err());
+    }
+}
@@ -0,0 +1,276 @@
+//! Hands-free presentations of the animations: a [`Script`] of camera moves, pauses and speed
+//! ramps, read from a RON file (see the ones in `demos/`) and played back with `--demo <file>`
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+use bevy::{app::AppExit, prelude::*};
+use clap::Args;
+use serde::{Deserialize, Serialize};
+
+use crate::{camera::Scroll, lerp, Running, Tick};
+
+/// Something a [`Script`] does, one after the other
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Step {
+    /// Do nothing for this many seconds
+    Wait(f32),
+    /// Stop the animation for this many seconds, e.g. to look at a milestone
+    Pause(f32),
+    /// Set the frequency of the animation's [`Tick`] right away
+    Speed(f32),
+    /// Change the frequency of the animation's [`Tick`] gradually to `to` within `seconds`
+    Ramp { to: f32, seconds: f32 },
+    /// Glide the camera to look at `x`/`y` zoomed to `exp(scroll)` within `seconds`
+    Camera {
+        x: f32,
+        y: f32,
+        scroll: f32,
+        seconds: f32,
+    },
+    /// End the animation
+    Quit,
+}
+
+/// Steps of a demo, e.g. `[Speed(2.0), Camera(x: 0, y: 0, scroll: 1, seconds: 3), Quit]`. Played
+/// by [`crate::AocControlPlugin`], if inserted before adding it, e.g. by [`crate::Presentation`]
+#[derive(Debug, Default, Clone, PartialEq, Resource, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Script(pub Vec<Step>);
+
+impl Script {
+    pub fn load(path: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let path = path.into();
+        let content =
+            std::fs::read_to_string(&path).with_context(|| format!("Reading {path:?}"))?;
+        ron::from_str(&content).with_context(|| format!("Parsing {path:?}"))
+    }
+}
+
+/// Command line flag for a demo [`Script`], to `#[clap(flatten)]` into a binary's options
+#[derive(Debug, Default, Args)]
+pub struct DemoArgs {
+    /// RON file with camera moves, pauses and speed ramps to play, e.g. `demos/sixteenth.ron`
+    #[clap(long)]
+    demo: Option<PathBuf>,
+}
+
+impl DemoArgs {
+    /// The script given on the command line, if any
+    pub fn script(&self) -> anyhow::Result<Option<Script>> {
+        self.demo.as_ref().map(Script::load).transpose()
+    }
+}
+
+/// State of the animation when the current step started, to glide from
+#[derive(Debug, Clone, Copy)]
+struct Origin {
+    translation: Vec2,
+    scroll: f32,
+    frequency: f32,
+}
+
+#[derive(Debug, Resource)]
+struct Demo {
+    script: Script,
+    step: usize,
+    elapsed: f32,
+    origin: Option<Origin>,
+}
+
+/// Plays a [`Script`] from start to end
+#[derive(Debug, Clone)]
+pub struct DemoPlugin(pub Script);
+
+impl Plugin for DemoPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Demo {
+            script: self.0.clone(),
+            step: 0,
+            elapsed: 0.,
+            origin: None,
+        })
+        .add_systems(Update, play);
+    }
+}
+
+/// Progress from 0 to 1 after `elapsed` of `seconds`, steps without any duration are done at once
+fn progress(elapsed: f32, seconds: f32) -> f32 {
+    if seconds <= 0. {
+        1.
+    } else {
+        (elapsed / seconds).min(1.)
+    }
+}
+
+fn play(
+    time: Res<Time>,
+    mut demo: ResMut<Demo>,
+    mut running: ResMut<Running>,
+    mut tick: Option<ResMut<Tick>>,
+    mut cameras: Query<(&mut Scroll, &mut Transform), With<Camera>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    let Some(step) = demo.script.0.get(demo.step).cloned() else {
+        return;
+    };
+    let origin = *demo.origin.get_or_insert_with(|| {
+        let (translation, scroll) = cameras
+            .iter()
+            .next()
+            .map_or((Vec2::ZERO, 0.), |(scroll, tf)| {
+                (tf.translation.truncate(), scroll.0)
+            });
+        Origin {
+            translation,
+            scroll,
+            frequency: tick.as_ref().map_or(1., |tick| tick.frequency()),
+        }
+    });
+    demo.elapsed += time.delta_seconds();
+
+    let done = match step {
+        Step::Wait(seconds) => demo.elapsed >= seconds,
+        Step::Pause(seconds) => {
+            running.0 = demo.elapsed >= seconds;
+            running.0
+        }
+        Step::Speed(frequency) => {
+            if let Some(tick) = tick.as_mut() {
+                tick.set_frequency(frequency);
+            }
+            true
+        }
+        Step::Ramp { to, seconds } => {
+            let t = progress(demo.elapsed, seconds);
+            if let Some(tick) = tick.as_mut() {
+                // Exponentially, so that each doubling takes equally long
+                tick.set_frequency(origin.frequency * (to / origin.frequency).powf(t));
+            }
+            t >= 1.
+        }
+        Step::Camera {
+            x,
+            y,
+            scroll,
+            seconds,
+        } => {
+            let t = progress(demo.elapsed, seconds);
+            for (mut s, mut tf) in cameras.iter_mut() {
+                let xy = origin.translation.lerp(Vec2::new(x, y), t);
+                tf.translation = xy.extend(tf.translation.z);
+                s.0 = lerp(origin.scroll, scroll, t);
+            }
+            t >= 1.
+        }
+        Step::Quit => {
+            exit.send(AppExit);
+            true
+        }
+    };
+    if done {
+        demo.step += 1;
+        demo.elapsed = 0.;
+        demo.origin = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HeadlessPlugins;
+    use bevy::time::TimeUpdateStrategy;
+    use rstest::rstest;
+    use std::{path::Path, time::Duration};
+
+    fn app(script: Script) -> App {
+        let mut app = App::new();
+        app.add_plugins((HeadlessPlugins, DemoPlugin(script)))
+            .insert_resource(Running(true))
+            .insert_resource(Tick::new(1.))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )));
+        app
+    }
+
+    #[rstest]
+    fn parses_ron() {
+        let script = ron::from_str::<Script>(
+            "[Wait(1.0), Ramp(to: 8.0, seconds: 2.0), Camera(x: 1.0, y: 2.0, scroll: 0.5, seconds: 1.0), Quit]",
+        )
+        .expect("parsing");
+        assert_eq!(script.0.len(), 4);
+        assert_eq!(
+            script.0[1],
+            Step::Ramp {
+                to: 8.,
+                seconds: 2.
+            }
+        );
+    }
+
+    /// Each binary offering `--demo` ships a script of the same name in `demos/`, which parses
+    #[rstest]
+    fn every_demo_day_has_a_script() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let mut days = 0;
+        for entry in std::fs::read_dir(root.join("src/bin")).expect("listing binaries") {
+            let path = entry.expect("binary").path();
+            let Ok(source) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            if !source.contains("DemoArgs") {
+                continue;
+            }
+            let name = path.file_stem().expect("binary name");
+            let script = root.join("demos").join(name).with_extension("ron");
+            Script::load(&script).unwrap_or_else(|e| panic!("{e:#}"));
+            days += 1;
+        }
+        assert!(days > 0);
+    }
+
+    #[rstest]
+    fn pauses_speeds_up_and_quits() {
+        let mut app = app(Script(vec![Step::Speed(4.), Step::Pause(0.5), Step::Quit]));
+        for _ in 0..3 {
+            app.update();
+        }
+        assert_eq!(app.world.resource::<Tick>().frequency(), 4.);
+        assert!(!app.world.resource::<Running>().inner());
+        let quit = (0..20).any(|_| {
+            app.update();
+            !app.world.resource::<Events<AppExit>>().is_empty()
+        });
+        assert!(quit);
+        assert!(app.world.resource::<Running>().inner());
+    }
+
+    #[rstest]
+    fn glides_camera_and_ramps_speed() {
+        let mut app = app(Script(vec![
+            Step::Camera {
+                x: 10.,
+                y: 20.,
+                scroll: 1.,
+                seconds: 0.5,
+            },
+            Step::Ramp {
+                to: 4.,
+                seconds: 0.5,
+            },
+        ]));
+        let camera = app
+            .world
+            .spawn((Scroll(0.), Camera::default(), Transform::default()))
+            .id();
+        for _ in 0..20 {
+            app.update();
+        }
+        let tf = app.world.get::<Transform>(camera).expect("camera");
+        assert_eq!(tf.translation.truncate(), Vec2::new(10., 20.));
+        assert_eq!(app.world.get::<Scroll>(camera).expect("scroll").0, 1.);
+        assert!((app.world.resource::<Tick>().frequency() - 4.).abs() < 1e-4);
+    }
+}
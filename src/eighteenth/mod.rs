@@ -0,0 +1,268 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, bail};
+use enum_iterator::all;
+use nom::Finish;
+
+use self::parser::instructions;
+use crate::{anyhowing, Coord, Direction, Part, Solver, Strategy};
+
+mod parser;
+
+/// Bounding boxes with more cells than this are too big for [`flood_fill`]
+pub const FLOOD_FILL_LIMIT: i64 = 1 << 24;
+
+/// One step of the dig plan: which way & how many meters to dig, and the color of the trench
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub direction: Direction,
+    pub distance: u32,
+    pub color: u32,
+}
+
+impl Instruction {
+    /// The actual instruction hidden in the color (part two): its first five hex digits are the
+    /// distance, the last one the direction
+    pub fn decode(&self) -> anyhow::Result<Self> {
+        let direction = match self.color & 0xf {
+            0 => Direction::Right,
+            1 => Direction::Down,
+            2 => Direction::Left,
+            3 => Direction::Up,
+            d => bail!("Unknown direction {d} in color #{:06x}", self.color),
+        };
+        Ok(Self {
+            direction,
+            distance: self.color >> 4,
+            color: self.color,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigPlan {
+    instructions: Vec<Instruction>,
+}
+
+impl FromStr for DigPlan {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self {
+            instructions: instructions(s).finish().map_err(anyhowing)?.1,
+        })
+    }
+}
+
+impl DigPlan {
+    /// The instructions as written down (part one) or as decoded from their colors (part two)
+    pub fn instructions(&self, part: Part) -> anyhow::Result<Vec<Instruction>> {
+        match part {
+            Part::One => Ok(self.instructions.clone()),
+            Part::Two => self.instructions.iter().map(Instruction::decode).collect(),
+        }
+    }
+
+    pub fn lagoon_size(&self, part: Part) -> anyhow::Result<i64> {
+        lagoon_size(&self.instructions(part)?)
+    }
+}
+
+/// Corners of the trench dug along `instructions`, starting at the origin. The last one is back at
+/// the origin, if the trench is closed
+pub fn corners(instructions: &[Instruction]) -> Vec<(i64, i64)> {
+    instructions
+        .iter()
+        .scan((0, 0), |(x, y), instruction| {
+            let step = Coord::from(instruction.direction);
+            *x += step.x as i64 * instruction.distance as i64;
+            *y += step.y as i64 * instruction.distance as i64;
+            Some((*x, *y))
+        })
+        .collect()
+}
+
+/// Cubic meters of lava the lagoon dug along `instructions` holds. The shoelace formula gives the
+/// area `A` enclosed by the centers of the `b` trench cells, Pick's theorem then the cells inside
+/// as `A - b/2 + 1`, so together with the trench `A + b/2 + 1`
+pub fn lagoon_size(instructions: &[Instruction]) -> anyhow::Result<i64> {
+    let corners = corners(instructions);
+    match corners.last() {
+        None | Some((0, 0)) => {}
+        Some(end) => bail!("Trench does not end where it started, but at {end:?}"),
+    }
+    let twice_area = corners
+        .iter()
+        .zip(corners.iter().cycle().skip(1))
+        .map(|((x1, y1), (x2, y2))| x1 * y2 - x2 * y1)
+        .sum::<i64>()
+        .abs();
+    let boundary = instructions
+        .iter()
+        .map(|instruction| instruction.distance as i64)
+        .sum::<i64>();
+    Ok(twice_area / 2 + boundary / 2 + 1)
+}
+
+/// Same as [`lagoon_size`], but digging out cell by cell and filling everything outside of the
+/// trench, to cross-check the fast way on small plans
+pub fn flood_fill(instructions: &[Instruction]) -> anyhow::Result<usize> {
+    let corners = corners(instructions);
+    match corners.last() {
+        None | Some((0, 0)) => {}
+        Some(end) => bail!("Trench does not end where it started, but at {end:?}"),
+    }
+    // One cell of margin around the corners, so that the outside is connected all around
+    let (min, max) = corners.iter().fold(
+        ((-1, -1), (1, 1)),
+        |(min, max): ((i64, i64), (i64, i64)), (x, y)| {
+            (
+                (min.0.min(*x - 1), min.1.min(*y - 1)),
+                (max.0.max(*x + 1), max.1.max(*y + 1)),
+            )
+        },
+    );
+    let (ncols, nrows) = (max.0 - min.0 + 1, max.1 - min.1 + 1);
+    if ncols * nrows > FLOOD_FILL_LIMIT {
+        bail!("Too big to flood fill: {ncols}x{nrows}");
+    }
+    let (min, max) = (
+        Coord::new(min.0 as i32, min.1 as i32),
+        Coord::new(max.0 as i32, max.1 as i32),
+    );
+
+    let mut pos = Coord::zero();
+    let mut trench = HashSet::from([pos]);
+    for instruction in instructions {
+        for _ in 0..instruction.distance {
+            pos += Coord::from(instruction.direction);
+            trench.insert(pos);
+        }
+    }
+
+    let mut outside = HashSet::from([min]);
+    let mut open = VecDeque::from([min]);
+    while let Some(c) = open.pop_front() {
+        for dir in all::<Direction>() {
+            let next = c + Coord::from(dir);
+            let inside_box =
+                min.x <= next.x && next.x <= max.x && min.y <= next.y && next.y <= max.y;
+            if inside_box && !trench.contains(&next) && outside.insert(next) {
+                open.push_back(next);
+            }
+        }
+    }
+    Ok((ncols * nrows) as usize - outside.len())
+}
+
+pub fn solve(part: Part, input: &str) -> anyhow::Result<i64> {
+    DigPlan::from_str(input)?
+        .lagoon_size(part)
+        .map_err(|e| anyhow!("Digging the lagoon: {e}"))
+}
+
+/// Day 18: Lavaduct Lagoon
+#[derive(Debug, Clone, Copy)]
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    fn name(&self) -> &'static str {
+        "eighteenth"
+    }
+
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::One, input).map(|x| x.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::Two, input).map(|x| x.to_string())
+    }
+
+    fn strategies(&self) -> Vec<Strategy> {
+        vec![Strategy {
+            name: "flood-fill",
+            about: "Dig out the trench cell by cell and fill around it, only for small plans",
+            solve: |part, input| {
+                let instructions = DigPlan::from_str(input)?.instructions(part)?;
+                Ok(flood_fill(&instructions)?.to_string())
+            },
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Part::One, 62)]
+    #[case(Part::Two, 952408144115)]
+    fn sample(#[case] part: Part, #[case] expected: i64) {
+        let input = include_str!("../../sample/eighteenth.txt");
+        assert_eq!(solve(part, input).expect("solving"), expected);
+    }
+
+    #[rstest]
+    #[case(include_str!("../../sample/eighteenth.txt"), 62)]
+    #[case("R 2 (#000000)\nD 2 (#000000)\nL 2 (#000000)\nU 2 (#000000)", 9)]
+    #[case("D 1 (#000000)\nU 1 (#000000)", 2)]
+    #[case(
+        "R 4 (#000000)
+         D 4 (#000000)
+         L 2 (#000000)
+         U 2 (#000000)
+         L 2 (#000000)
+         U 2 (#000000)",
+        21
+    )]
+    fn flood_fill_matches_shoelace(#[case] input: &str, #[case] expected: usize) {
+        let plan = DigPlan::from_str(input).expect("parsing");
+        let instructions = plan.instructions(Part::One).expect("instructions");
+        assert_eq!(flood_fill(&instructions).expect("flood fill"), expected);
+        assert_eq!(
+            lagoon_size(&instructions).expect("shoelace"),
+            expected as i64
+        );
+    }
+
+    #[rstest]
+    #[case(0x70c710, Direction::Right, 461937)]
+    #[case(0x0dc571, Direction::Down, 56407)]
+    #[case(0x7a21e3, Direction::Up, 500254)]
+    fn decodes_colors(#[case] color: u32, #[case] direction: Direction, #[case] distance: u32) {
+        let instruction = Instruction {
+            direction: Direction::Left,
+            distance: 1,
+            color,
+        };
+        let decoded = instruction.decode().expect("decoding");
+        assert_eq!((decoded.direction, decoded.distance), (direction, distance));
+    }
+
+    #[rstest]
+    fn open_trenches_are_refused() {
+        let plan = DigPlan::from_str("R 2 (#000000)\nD 2 (#000000)").expect("parsing");
+        assert!(plan.lagoon_size(Part::One).is_err());
+        assert!(flood_fill(&plan.instructions(Part::One).expect("instructions")).is_err());
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("X 2 (#000000)")]
+    #[case("R -2 (#000000)")]
+    #[case("R 2 (#00000)")]
+    #[case("R 2 (#00000g)")]
+    fn invalid_plans(#[case] input: &str) {
+        assert!(DigPlan::from_str(input).is_err());
+    }
+
+    #[rstest]
+    fn invalid_direction_in_color() {
+        let plan = DigPlan::from_str("R 2 (#000004)").expect("parsing");
+        assert!(plan.lagoon_size(Part::Two).is_err());
+    }
+}
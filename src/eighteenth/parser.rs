@@ -0,0 +1,52 @@
+use nom::{
+    bytes::complete::{tag, take_while_m_n},
+    character::complete::{char, line_ending, multispace0},
+    multi::separated_list1,
+    sequence::{delimited, tuple},
+    IResult, Parser as NomParser,
+};
+use nom_supreme::ParserExt;
+
+use super::Instruction;
+use crate::{
+    parse::common::{integer, ws},
+    Direction,
+};
+
+pub(crate) fn direction(s: &str) -> IResult<&str, Direction> {
+    char('U')
+        .value(Direction::Up)
+        .or(char('R').value(Direction::Right))
+        .or(char('D').value(Direction::Down))
+        .or(char('L').value(Direction::Left))
+        .parse(s)
+}
+
+/// Six hex digits in parentheses, e.g. `(#70c710)`
+pub(crate) fn color(s: &str) -> IResult<&str, u32> {
+    delimited(
+        tag("(#"),
+        take_while_m_n(6, 6, |c: char| c.is_ascii_hexdigit())
+            .map_res(|hex| u32::from_str_radix(hex, 16)),
+        char(')'),
+    )
+    .parse(s)
+}
+
+pub(crate) fn instruction(s: &str) -> IResult<&str, Instruction> {
+    tuple((ws(direction), ws(integer), ws(color)))
+        .map(|(direction, distance, color)| Instruction {
+            direction,
+            distance,
+            color,
+        })
+        .parse(s)
+}
+
+pub(crate) fn instructions(s: &str) -> IResult<&str, Vec<Instruction>> {
+    multispace0
+        .precedes(separated_list1(line_ending, instruction))
+        .terminated(multispace0)
+        .all_consuming()
+        .parse(s)
+}
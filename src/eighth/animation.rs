@@ -0,0 +1,456 @@
+use std::collections::{HashMap, VecDeque};
+
+use bevy::{prelude::*, sprite::Anchor};
+use itertools::Itertools;
+
+use crate::{
+    lerp, rect,
+    reset::ResetPlugin,
+    summary::AnimationFinished,
+    widgets::{BigNumber, ScorePopup, WidgetsPlugin},
+    AocCameraPlugin, AocControlPlugin, Part, Presentation, Running, Tick,
+};
+
+use super::{Direction, Map};
+
+pub fn run(map: &Map, part: Part, frequency: f32, presentation: Presentation) {
+    let plugins = DefaultPlugins.build().add(presentation);
+    app(map, part, frequency, plugins).run()
+}
+
+pub fn app(map: &Map, part: Part, frequency: f32, plugins: impl PluginGroup) -> App {
+    let mut app = App::new();
+    let graph = Graph::new(map, part);
+    let state = GameState::new(&graph, part);
+    let width = graph
+        .positions
+        .iter()
+        .map(|position| position.x)
+        .fold(0., f32::max);
+    let camera = AocCameraPlugin::new(1.).at(Vec3::new(width / 2., 0., 0.));
+    app.add_plugins((plugins, camera, AocControlPlugin, WidgetsPlugin))
+        .insert_resource(graph)
+        .insert_resource(state)
+        .insert_resource(Tick::new(frequency))
+        .add_plugins(ResetPlugin::<GameState>::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, (update, ghost_mover, edge_drawer, statistics));
+    app
+}
+
+const FONT_SIZE: f32 = 24.;
+const NODE_SIZE: f32 = 12.;
+/// Distance between two layers of the graph
+const LAYER_GAP: f32 = 120.;
+/// Distance between two nodes within the same layer
+const NODE_GAP: f32 = 40.;
+const MOTION: f32 = 10.;
+const START_COLOR: Color = Color::GREEN;
+const END_COLOR: Color = Color::GOLD;
+const EDGE_COLOR: Color = Color::Rgba {
+    red: 0.5,
+    green: 0.5,
+    blue: 0.5,
+    alpha: 0.3,
+};
+
+/// The network, laid out in layers by how many steps each node is away from the nearest start
+#[derive(Debug, Resource)]
+struct Graph {
+    names: Vec<String>,
+    positions: Vec<Vec2>,
+    /// Left & right neighbour of each node, if it has any
+    edges: Vec<Option<(usize, usize)>>,
+    starts: Vec<usize>,
+    ends: Vec<bool>,
+    instructions: Vec<Direction>,
+}
+
+impl Graph {
+    fn new(map: &Map, part: Part) -> Self {
+        let names = map
+            .names
+            .iter()
+            .map(|(_, name)| name)
+            .sorted()
+            .collect_vec();
+        let index = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (*name, i))
+            .collect::<HashMap<_, _>>();
+        let edges = names
+            .iter()
+            .map(|name| {
+                let (left, right) = map.network.get(&map.find(name)?)?;
+                Some((index[map.name(*left)], index[map.name(*right)]))
+            })
+            .collect::<Vec<_>>();
+        let starts = map
+            .starts
+            .iter()
+            .map(|start| index[map.name(*start)])
+            .collect_vec();
+        let ends = names
+            .iter()
+            .map(|name| match part {
+                Part::One => *name == "ZZZ",
+                Part::Two => name.ends_with('Z'),
+            })
+            .collect();
+
+        // Breadth first from all starts at once, nodes no ghost can reach come last
+        let mut layers = vec![None; names.len()];
+        let mut order = Vec::with_capacity(names.len());
+        let mut queue = starts
+            .iter()
+            .map(|start| (*start, 0))
+            .collect::<VecDeque<_>>();
+        while let Some((node, layer)) = queue.pop_front() {
+            if layers[node].is_some() {
+                continue;
+            }
+            layers[node] = Some(layer);
+            order.push(node);
+            if let Some((left, right)) = edges[node] {
+                queue.extend([(left, layer + 1), (right, layer + 1)]);
+            }
+        }
+        let unreachable = layers.iter().flatten().max().map_or(0, |max| max + 1);
+        order.extend((0..names.len()).filter(|node| layers[*node].is_none()));
+
+        let mut positions = vec![Vec2::ZERO; names.len()];
+        for (layer, nodes) in &order
+            .into_iter()
+            .group_by(|node| layers[*node].unwrap_or(unreachable))
+        {
+            let nodes = nodes.collect_vec();
+            let middle = (nodes.len() as f32 - 1.) / 2.;
+            for (i, node) in nodes.into_iter().enumerate() {
+                positions[node] =
+                    Vec2::new(layer as f32 * LAYER_GAP, (middle - i as f32) * NODE_GAP);
+            }
+        }
+
+        Self {
+            names: names.into_iter().map(str::to_owned).collect(),
+            positions,
+            edges,
+            starts,
+            ends,
+            instructions: map.instructions.clone(),
+        }
+    }
+
+    fn color(&self, node: usize) -> Color {
+        if self.starts.contains(&node) {
+            START_COLOR
+        } else if self.ends[node] {
+            END_COLOR
+        } else {
+            Color::GRAY
+        }
+    }
+}
+
+#[derive(Debug, Clone, Resource)]
+struct GameState {
+    part: Part,
+    steps: usize,
+    /// Node each ghost is on right now
+    ghosts: Vec<usize>,
+    /// Step, in which each ghost reached an end node for the first time
+    first_end: Vec<Option<usize>>,
+    /// Steps each ghost needs from one end node to the next
+    cycles: Vec<Option<usize>>,
+    done: bool,
+}
+
+impl GameState {
+    fn new(graph: &Graph, part: Part) -> Self {
+        let n = graph.starts.len();
+        Self {
+            part,
+            steps: 0,
+            ghosts: graph.starts.clone(),
+            first_end: vec![None; n],
+            cycles: vec![None; n],
+            done: n == 0,
+        }
+    }
+
+    /// Least common multiple of all cycles found so far
+    fn lcm(&self) -> Option<usize> {
+        self.cycles
+            .iter()
+            .flatten()
+            .copied()
+            .reduce(num::integer::lcm)
+    }
+}
+
+#[derive(Debug, Component)]
+struct Ghost(usize);
+
+#[derive(Debug, Component)]
+struct Statistics;
+
+fn ghost_color(ghost: usize, n: usize) -> Color {
+    Color::hsl(360. * ghost as f32 / n.max(1) as f32, 0.8, 0.6)
+}
+
+fn setup(mut cmd: Commands, graph: Res<Graph>) {
+    for (node, (name, position)) in graph.names.iter().zip(&graph.positions).enumerate() {
+        let color = graph.color(node);
+        cmd.spawn(rect(
+            position.x, position.y, 0., NODE_SIZE, NODE_SIZE, color,
+        ));
+        cmd.spawn(Text2dBundle {
+            text: Text::from_section(
+                name,
+                TextStyle {
+                    font_size: FONT_SIZE / 2.,
+                    color,
+                    ..default()
+                },
+            ),
+            transform: Transform::from_xyz(position.x, position.y + NODE_SIZE, 1.),
+            text_anchor: Anchor::BottomCenter,
+            ..default()
+        });
+    }
+
+    for (ghost, start) in graph.starts.iter().enumerate() {
+        let position = graph.positions[*start];
+        let color = ghost_color(ghost, graph.starts.len());
+        let size = NODE_SIZE * 1.5;
+        cmd.spawn((
+            Ghost(ghost),
+            rect(position.x, position.y, 2., size, size, color),
+        ));
+    }
+
+    let height = graph
+        .positions
+        .iter()
+        .map(|position| position.y)
+        .fold(0., f32::max);
+    cmd.spawn((
+        Statistics,
+        Text2dBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font_size: FONT_SIZE,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ),
+            transform: Transform::from_xyz(0., height + 2. * NODE_GAP, 3.),
+            text_anchor: Anchor::BottomLeft,
+            ..default()
+        },
+    ));
+}
+
+fn update(
+    mut cmd: Commands,
+    running: Res<Running>,
+    time: Res<Time>,
+    mut timer: ResMut<Tick>,
+    mut state: ResMut<GameState>,
+    graph: Res<Graph>,
+    mut finished: EventWriter<AnimationFinished>,
+) {
+    if !running.inner() || state.done {
+        return;
+    }
+    if !timer.inner().tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let dir = graph.instructions[state.steps % graph.instructions.len()];
+    state.steps += 1;
+    let steps = state.steps;
+    for ghost in 0..state.ghosts.len() {
+        // Ghosts walking off the map just stay where they are
+        let Some((left, right)) = graph.edges[state.ghosts[ghost]] else {
+            continue;
+        };
+        let node = match dir {
+            Direction::L => left,
+            Direction::R => right,
+        };
+        state.ghosts[ghost] = node;
+        if !graph.ends[node] || state.cycles[ghost].is_some() {
+            continue;
+        }
+        let first_end = state.first_end[ghost];
+        match first_end {
+            None => state.first_end[ghost] = Some(steps),
+            Some(first) => {
+                state.cycles[ghost] = Some(steps - first);
+                cmd.spawn(ScorePopup::bundle(
+                    format!("{}", steps - first),
+                    TextStyle {
+                        font_size: FONT_SIZE,
+                        color: ghost_color(ghost, state.ghosts.len()),
+                        ..default()
+                    },
+                    graph.positions[node].extend(4.),
+                    NODE_GAP,
+                ));
+            }
+        }
+    }
+
+    state.done = match state.part {
+        Part::One => state.first_end.iter().all(Option::is_some),
+        Part::Two => state.cycles.iter().all(Option::is_some),
+    };
+    if state.done {
+        let answer = match state.part {
+            Part::One => Some(steps),
+            Part::Two => state.lcm(),
+        };
+        if let Some(answer) = answer {
+            finished.send(
+                AnimationFinished::new(BigNumber::new(answer as i128), steps)
+                    .with("Ghosts", state.ghosts.len()),
+            );
+        }
+    }
+}
+
+fn ghost_mover(
+    time: Res<Time>,
+    graph: Res<Graph>,
+    state: Res<GameState>,
+    mut ghosts: Query<(&Ghost, &mut Transform)>,
+) {
+    let dt = time.delta_seconds();
+    for (ghost, mut tf) in ghosts.iter_mut() {
+        let target = graph.positions[state.ghosts[ghost.0]];
+        tf.translation.x = lerp(tf.translation.x, target.x, MOTION * dt);
+        tf.translation.y = lerp(tf.translation.y, target.y, MOTION * dt);
+    }
+}
+
+fn edge_drawer(graph: Res<Graph>, state: Res<GameState>, mut gizmos: Gizmos) {
+    for (node, edges) in graph.edges.iter().enumerate() {
+        let Some((left, right)) = edges else {
+            continue;
+        };
+        for next in [left, right] {
+            gizmos.line_2d(graph.positions[node], graph.positions[*next], EDGE_COLOR);
+        }
+    }
+
+    // Highlight the way each ghost is about to take
+    if state.done {
+        return;
+    }
+    let dir = graph.instructions[state.steps % graph.instructions.len()];
+    for (ghost, node) in state.ghosts.iter().enumerate() {
+        let Some((left, right)) = graph.edges[*node] else {
+            continue;
+        };
+        let next = match dir {
+            Direction::L => left,
+            Direction::R => right,
+        };
+        gizmos.line_2d(
+            graph.positions[*node],
+            graph.positions[next],
+            ghost_color(ghost, state.ghosts.len()),
+        );
+    }
+}
+
+fn statistics(
+    graph: Res<Graph>,
+    state: Res<GameState>,
+    mut text: Query<&mut Text, With<Statistics>>,
+) {
+    let dir = graph.instructions[state.steps % graph.instructions.len()];
+    let mut lines = vec![format!("Steps: {} (next {dir:?})", state.steps)];
+    for (ghost, start) in graph.starts.iter().enumerate() {
+        let mut line = format!(
+            "{} @ {}",
+            graph.names[*start], graph.names[state.ghosts[ghost]]
+        );
+        if state.part == Part::Two {
+            let cycle = state.cycles[ghost].map_or("---".to_owned(), |cycle| cycle.to_string());
+            line += &format!(": cycle {cycle}");
+        }
+        lines.push(line);
+    }
+    if let Some(lcm) = state.lcm() {
+        let cycles = state.cycles.iter().flatten().join(", ");
+        lines.push(format!("LCM({cycles}) = {lcm}"));
+    }
+    for mut text in text.iter_mut() {
+        text.sections[0].value = lines.join("\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HeadlessPlugins;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    const GHOSTS: &str = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+";
+
+    #[test]
+    fn smoke() {
+        let input = include_str!("../../sample/eighth.txt");
+        let map = Map::new(input, Part::One).expect("parsing");
+        let mut app = app(&map, Part::One, 5., HeadlessPlugins);
+        for _ in 0..3 {
+            app.update();
+        }
+    }
+
+    #[test]
+    fn layers_start_at_ghosts() {
+        let map = Map::new(GHOSTS, Part::Two).expect("parsing");
+        let graph = Graph::new(&map, Part::Two);
+        let x = |name: &str| {
+            let node = graph.names.iter().position(|n| n == name).expect("node");
+            graph.positions[node].x / LAYER_GAP
+        };
+        assert_eq!(
+            ["11A", "22A", "11B", "22B", "XXX", "11Z", "22C", "22Z"].map(x),
+            [0., 0., 1., 1., 1., 2., 2., 3.]
+        );
+    }
+
+    #[test]
+    fn finds_cycles_and_lcm() {
+        let map = Map::new(GHOSTS, Part::Two).expect("parsing");
+        let mut app = app(&map, Part::Two, 100., HeadlessPlugins);
+        app.insert_resource(Running(true))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )));
+        for _ in 0..50 {
+            app.update();
+        }
+        let state = app.world.resource::<GameState>();
+        assert!(state.done);
+        assert_eq!(state.cycles, vec![Some(2), Some(3)]);
+        assert_eq!(state.lcm(), Some(6));
+    }
+}
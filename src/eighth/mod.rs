@@ -0,0 +1,434 @@
+#[cfg(feature = "animations")]
+pub mod animation;
+
+use crate::{
+    anyhowing,
+    graph::{self, Attributes, Edge, Style},
+    intern::Interner,
+    parse::common::ws,
+    Part, Solver,
+};
+
+use anyhow::{anyhow, Result};
+use itertools::Itertools;
+use nom::{
+    character::complete::{alphanumeric1, char, line_ending, multispace1},
+    multi::{many_till, separated_list1},
+    sequence::{separated_pair, tuple},
+    Finish, IResult, Parser as NomParser,
+};
+use nom_supreme::ParserExt;
+use std::{
+    collections::{HashMap, HashSet},
+    iter::{successors, Cycle},
+    vec::IntoIter,
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Direction {
+    L,
+    R,
+}
+
+/// Id of a node's name in the [`Map`]'s [`Interner`]
+pub type Node = u32;
+type Instructions = Cycle<IntoIter<Direction>>;
+type Network = HashMap<Node, (Node, Node)>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Map {
+    /// Nodes the ghosts start on, sorted by name
+    pub starts: Vec<Node>,
+    names: Interner,
+    network: Network,
+    instructions: Vec<Direction>,
+}
+
+impl Map {
+    pub fn new(s: &str, part: Part) -> Result<Self> {
+        let (instructions, lines) = parse_map(s).finish().map_err(anyhowing)?.1;
+        let mut names = Interner::new();
+        let network = lines
+            .into_iter()
+            .map(|(node, (left, right))| {
+                let node = names.intern(node);
+                (node, (names.intern(left), names.intern(right)))
+            })
+            .collect::<Network>();
+        let starts = network
+            .keys()
+            .copied()
+            .filter(|&node| match part {
+                Part::One => names.resolve(node) == "AAA",
+                Part::Two => names.resolve(node).ends_with('A'),
+            })
+            .sorted_by_key(|&node| names.resolve(node))
+            .collect();
+        Ok(Map {
+            instructions,
+            names,
+            network,
+            starts,
+        })
+    }
+
+    /// Name of `node`
+    pub fn name(&self, node: Node) -> &str {
+        self.names.resolve(node)
+    }
+
+    /// Node called `name`, if it appears on the map at all
+    pub fn find(&self, name: &str) -> Option<Node> {
+        self.names.get(name)
+    }
+
+    fn is_end(&self, node: Node) -> bool {
+        self.name(node).ends_with('Z')
+    }
+
+    /// Node reached from `node` by going `dir`, if `node` is on the map at all
+    fn next(&self, node: Node, dir: Direction) -> Option<Node> {
+        let (left, right) = self.network.get(&node)?;
+        Some(match dir {
+            Direction::L => *left,
+            Direction::R => *right,
+        })
+    }
+
+    /// Nodes a single ghost visits from `start` on (including it), until it walks off the map
+    pub fn walk(&self, start: Node) -> impl Iterator<Item = Node> + '_ {
+        let mut instructions = self.instructions.iter().copied().cycle();
+        successors(Some(start), move |node| {
+            self.next(*node, instructions.next()?)
+        })
+    }
+
+    /// Steps, after which any ghost must have repeated itself: by then it has been on every node
+    /// at every instruction
+    fn horizon(&self) -> usize {
+        (self.network.len() + 1) * self.instructions.len()
+    }
+
+    /// Steps from `AAA` to `ZZZ`
+    pub fn steps(&self) -> Result<usize> {
+        let no_way = || anyhow!("There is no way from AAA to ZZZ");
+        let start = self.find("AAA").ok_or_else(no_way)?;
+        let end = self.find("ZZZ").ok_or_else(no_way)?;
+        self.walk(start)
+            .take(self.horizon() + 1)
+            .position(|node| node == end)
+            .ok_or_else(no_way)
+    }
+
+    /// Steps the ghost starting on `start` needs from one end node to the next
+    pub fn cycle_length(&self, start: Node) -> Option<usize> {
+        let mut ends = self
+            .walk(start)
+            .take(2 * self.horizon() + 1)
+            .positions(|node| self.is_end(node));
+        let first = ends.next()?;
+        Some(ends.next()? - first)
+    }
+
+    /// [`Map::cycle_length`] of each ghost, in the order of [`Map::starts`]
+    pub fn cycle_lengths(&self) -> Result<Vec<usize>> {
+        self.starts
+            .iter()
+            .copied()
+            .map(|start| {
+                self.cycle_length(start).ok_or(anyhow!(
+                    "Ghost starting on {} never cycles through an end",
+                    self.name(start)
+                ))
+            })
+            .collect()
+    }
+
+    /// Edges the ghosts take from their starts up to their first end node
+    fn traversed(&self) -> HashSet<Edge> {
+        let mut edges = HashSet::new();
+        for start in &self.starts {
+            let nodes = self.walk(*start).take(self.horizon() + 1);
+            let dirs = self.instructions.iter().copied().cycle();
+            for ((from, to), dir) in nodes.tuple_windows().zip(dirs) {
+                edges.insert(Edge::new(from, to).labelled(format!("{dir:?}")));
+                if self.is_end(to) {
+                    break;
+                }
+            }
+        }
+        edges
+    }
+
+    /// The network in Graphviz' DOT language, with the start nodes as green boxes, the end nodes
+    /// as red double circles and the edges the ghosts take to their first end in blue
+    pub fn to_dot(&self) -> String {
+        let edges = self
+            .network
+            .iter()
+            .sorted()
+            .flat_map(|(&node, &(left, right))| {
+                [(Direction::L, left), (Direction::R, right)]
+                    .map(|(dir, to)| Edge::new(node, to).labelled(format!("{dir:?}")))
+            });
+        graph::to_dot(&self.names, edges, &self.traversed(), self)
+    }
+}
+
+impl Style for Map {
+    fn node(&self, label: &str) -> Attributes {
+        if self.starts.iter().any(|start| self.name(*start) == label) {
+            vec![("shape", "box".into()), ("color", "green".into())]
+        } else if label.ends_with('Z') {
+            vec![("shape", "doublecircle".into()), ("color", "red".into())]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct MapIter {
+    yielded_start: bool,
+    state: Vec<Node>,
+    names: Interner,
+    network: Network,
+    instructions: Instructions,
+}
+
+impl IntoIterator for Map {
+    type Item = Vec<Node>;
+    type IntoIter = MapIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        MapIter {
+            yielded_start: false,
+            state: self.starts,
+            names: self.names,
+            instructions: self.instructions.into_iter().cycle(),
+            network: self.network,
+        }
+    }
+}
+
+impl Iterator for MapIter {
+    type Item = Vec<Node>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self
+            .state
+            .iter()
+            .all(|node| self.names.resolve(*node).ends_with('Z'))
+        {
+            // All ghosts found an end node
+            return None;
+        }
+        if !self.yielded_start {
+            self.yielded_start = true;
+            return Some(self.state.clone());
+        }
+
+        let dir = self.instructions.next()?;
+        for node in self.state.iter_mut() {
+            // simulation
+            let (left, right) = self.network.get(node)?;
+            *node = match dir {
+                Direction::L => *left,
+                Direction::R => *right,
+            };
+        }
+
+        Some(self.state.clone())
+    }
+}
+
+fn instructions(s: &str) -> IResult<&str, Vec<Direction>> {
+    let left = char('L').value(Direction::L);
+    let right = char('R').value(Direction::R);
+    many_till(left.or(right), multispace1)
+        .map(|(dirs, _)| dirs)
+        .parse(s)
+}
+
+fn node(s: &str) -> IResult<&str, &str> {
+    alphanumeric1(s)
+}
+/// Each node's name with the names of its left & right neighbour, in the order of the input
+type Lines<'a> = Vec<(&'a str, (&'a str, &'a str))>;
+fn network(s: &str) -> IResult<&str, Lines<'_>> {
+    separated_list1(
+        line_ending,
+        separated_pair(
+            node,
+            ws(char('=')),
+            char('(')
+                .precedes(separated_pair(node, ws(char(',')), node))
+                .terminated(char(')')),
+        ),
+    )
+    .parse(s)
+}
+fn parse_map(s: &str) -> IResult<&str, (Vec<Direction>, Lines<'_>)> {
+    tuple((instructions, network)).parse(s)
+}
+
+pub fn solve(part: Part, input: &str) -> Result<usize> {
+    let map = Map::new(input, part)?;
+    match part {
+        Part::One => map.steps(),
+        // Each ghost loops through its end node with a fixed period, which (in the puzzle inputs)
+        // equals the steps to reach it first. So all of them meet on end nodes after the LCM
+        Part::Two => map
+            .cycle_lengths()?
+            .into_iter()
+            .reduce(num::integer::lcm)
+            .ok_or(anyhow!("There are no ghosts on the map")),
+    }
+}
+
+/// Day 8: Haunted Wasteland
+#[derive(Debug, Clone, Copy)]
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    fn name(&self) -> &'static str {
+        "eighth"
+    }
+
+    fn part_one(&self, input: &str) -> Result<String> {
+        solve(Part::One, input).map(|x| x.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> Result<String> {
+        solve(Part::Two, input).map(|x| x.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indoc::indoc;
+    use rstest::rstest;
+
+    const NETWORK_SIMPLE: &str = indoc! {"LR
+        AAA = (BBB, CCC)
+    "};
+    const NETWORK_THREE_NODES: &str = indoc! {"LR
+        AAA = (BBB, CCC)
+        BBB = (KJL, ABC)
+        CCC = (ZZZ, FOO)
+    "};
+    const NETWORK_SEVEN_NODES: &str = indoc! {"RL
+       AAA = (BBB, CCC)
+       BBB = (DDD, EEE)
+       CCC = (ZZZ, GGG)
+       DDD = (DDD, DDD)
+       EEE = (EEE, EEE)
+       GGG = (GGG, GGG)
+       ZZZ = (ZZZ, ZZZ)
+     "};
+    const NETWORK_SAMPLE: &str = include_str!("../../sample/eighth.txt");
+
+    #[rstest]
+    #[case(NETWORK_SIMPLE, vec![("AAA", ("BBB", "CCC"))])]
+    #[case(NETWORK_THREE_NODES, vec![
+            ("AAA", ("BBB", "CCC")),
+            ("BBB", ("KJL", "ABC")),
+            ("CCC", ("ZZZ", "FOO")),
+        ])
+    ]
+    fn map_from_str(#[case] map: &str, #[case] expected_network: Vec<(&str, (&str, &str))>) {
+        let map = Map::new(map, Part::One).expect("parsing");
+        for (name, (l, r)) in expected_network {
+            let node = map.find(name);
+            assert!(
+                node.and_then(|node| map.network.get(&node)).is_some(),
+                "Expected node {name} to be present in network"
+            );
+            let (left, right) = map.network[&node.unwrap()];
+            assert_eq!((l, r), (map.name(left), map.name(right)))
+        }
+    }
+
+    #[rstest]
+    #[case(NETWORK_SEVEN_NODES, vec!["AAA", "CCC", "ZZZ"])]
+    #[case(NETWORK_SAMPLE, vec!["AAA", "BBB", "AAA", "BBB", "AAA", "BBB", "ZZZ"])]
+    fn sample_a(#[case] map: &str, #[case] expected_path: Vec<&str>) {
+        let map = Map::new(map, Part::One).expect("parsing");
+        assert_eq!(map.steps().expect("steps"), expected_path.len() - 1);
+        assert_eq!(
+            expected_path,
+            map.clone()
+                .into_iter()
+                .flatten()
+                .map(|node| map.name(node))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    const NETWORK_SEVEN_NODES2: &str = indoc! {"LR
+        11A = (11B, XXX)
+        11B = (XXX, 11Z)
+        11Z = (11B, XXX)
+        22A = (22B, XXX)
+        22B = (22C, 22C)
+        22C = (22Z, 22Z)
+        22Z = (22B, 22B)
+        XXX = (XXX, XXX)
+     "};
+
+    #[rstest]
+    #[case(NETWORK_SEVEN_NODES2, vec![
+        vec!["11A", "11B", "11Z", "11B", "11Z", "11B", "11Z"],
+        vec!["22A", "22B", "22C", "22Z", "22B", "22C", "22Z"],
+    ])]
+    fn sample_b(#[case] map: &str, #[case] expected_paths: Vec<Vec<&str>>) {
+        let map = Map::new(map, Part::Two).expect("parsing");
+        let paths = map
+            .clone()
+            .into_iter()
+            .map(|nodes| nodes.into_iter().map(|node| map.name(node)).collect())
+            .collect::<Vec<Vec<_>>>();
+        assert_eq!(transpose(expected_paths), paths);
+    }
+
+    #[rstest]
+    fn cycles_meet_at_lcm() {
+        let map = Map::new(NETWORK_SEVEN_NODES2, Part::Two).expect("parsing");
+        assert_eq!(map.cycle_lengths().expect("cycles"), vec![2, 3]);
+        assert_eq!(solve(Part::Two, NETWORK_SEVEN_NODES2).expect("solving"), 6);
+    }
+
+    #[rstest]
+    fn dead_ends_never_cycle() {
+        let map = Map::new(NETWORK_SEVEN_NODES, Part::Two).expect("parsing");
+        assert_eq!(map.cycle_length(map.find("AAA").expect("AAA")), Some(1));
+        let map = Map::new(NETWORK_SIMPLE, Part::Two).expect("parsing");
+        assert!(map.cycle_lengths().is_err());
+    }
+
+    #[rstest]
+    fn dot_highlights_the_path() {
+        let map = Map::new(NETWORK_SEVEN_NODES, Part::One).expect("parsing");
+        let dot = map.to_dot();
+        assert!(dot.starts_with("digraph {\n"));
+        assert!(dot.contains("    \"AAA\" [shape=box, color=green];\n"));
+        assert!(dot.contains("    \"ZZZ\" [shape=doublecircle, color=red];\n"));
+        assert!(dot.contains("    \"BBB\";\n"));
+        assert!(dot.contains("    \"AAA\" -> \"CCC\" [label=R, color=blue, penwidth=2];\n"));
+        assert!(dot.contains("    \"CCC\" -> \"ZZZ\" [label=L, color=blue, penwidth=2];\n"));
+        assert!(dot.contains("    \"AAA\" -> \"BBB\" [label=L];\n"));
+        assert_eq!(dot.matches(" -> ").count(), 14);
+    }
+
+    fn transpose<T>(v: Vec<Vec<T>>) -> Vec<Vec<T>>
+    where
+        T: Clone,
+    {
+        assert!(!v.is_empty());
+        (0..v[0].len())
+            .map(|i| v.iter().map(|inner| inner[i].clone()).collect::<Vec<T>>())
+            .collect()
+    }
+}
@@ -0,0 +1,316 @@
+use std::collections::BTreeSet;
+
+use bevy::{prelude::*, sprite::Anchor};
+
+use crate::{
+    rect,
+    reset::ResetPlugin,
+    summary::AnimationFinished,
+    widgets::{ScorePopup, WidgetsPlugin},
+    AocCameraPlugin, AocControlPlugin, Presentation, Running, Tick,
+};
+
+use super::{Coord, Universe};
+
+pub fn run(universe: Universe, frequency: f32, presentation: Presentation) {
+    let plugins = DefaultPlugins.build().add(presentation);
+    app(universe, frequency, plugins).run()
+}
+
+pub fn app(universe: Universe, frequency: f32, plugins: impl PluginGroup) -> App {
+    let mut app = App::new();
+    let routes = Routes::new(&universe);
+    let shape = universe.sky.shape();
+    let (nrows, ncols) = (shape[0] as f32, shape[1] as f32);
+    let camera = AocCameraPlugin::new(1.).at(Vec3::new(ncols * TILE / 2., -nrows * TILE / 2., 0.));
+    app.add_plugins((plugins, camera, AocControlPlugin, WidgetsPlugin))
+        .insert_resource(universe)
+        .insert_resource(routes)
+        .insert_resource(GameState::default())
+        .insert_resource(Tick::new(frequency))
+        .add_plugins(ResetPlugin::<GameState>::default())
+        .add_systems(Startup, setup)
+        .add_systems(
+            Update,
+            (
+                update,
+                galaxy_mover,
+                gap_stretcher,
+                route_drawer,
+                distance_counter,
+            ),
+        );
+    app
+}
+
+const TILE: f32 = 32.;
+const FONT_SIZE: f32 = 40.;
+/// How many ticks the empty rows & columns take to stretch
+const EXPANSION_TICKS: usize = 10;
+/// Largest expansion drawn, e.g. part two's million times would not fit on any screen. The
+/// distances still count the real one
+const MAX_DRAWN_EXPANSION: u64 = 5;
+/// How many of the latest routes stay visible, fading out
+const TRAIL: usize = 10;
+
+#[derive(Debug, Default, Clone, Resource)]
+struct GameState {
+    progress: usize,
+}
+
+impl GameState {
+    /// Share of the (drawn) expansion done so far, from 0 to 1
+    fn expansion(&self) -> f32 {
+        self.progress.min(EXPANSION_TICKS) as f32 / EXPANSION_TICKS as f32
+    }
+
+    /// Number of galaxy pairs, whose route has been drawn so far
+    fn routes(&self, routes: &Routes) -> usize {
+        self.progress
+            .saturating_sub(EXPANSION_TICKS)
+            .min(routes.pairs.len())
+    }
+}
+
+/// [`Universe::pairwise_distances`], computed once, with the running total of the distances
+#[derive(Debug, Resource)]
+struct Routes {
+    pairs: Vec<(Coord, Coord, u64)>,
+    totals: Vec<u64>,
+}
+
+impl Routes {
+    fn new(universe: &Universe) -> Self {
+        let pairs = universe.pairwise_distances().collect::<Vec<_>>();
+        let totals = pairs
+            .iter()
+            .scan(0, |total, (_, _, dist)| {
+                *total += dist;
+                Some(*total)
+            })
+            .collect();
+        Self { pairs, totals }
+    }
+
+    /// Sum of the first `n` distances
+    fn total(&self, n: usize) -> u64 {
+        n.checked_sub(1)
+            .map_or(0, |i| self.totals.get(i).copied().unwrap_or_default())
+    }
+}
+
+#[derive(Debug, Component)]
+struct Galaxy(Coord);
+
+/// Stripe over an empty column (`Column`) or row (`Row`) of the universe
+#[derive(Debug, Component)]
+enum Gap {
+    Column(i64),
+    Row(i64),
+}
+
+#[derive(Debug, Component)]
+struct DistanceLen;
+
+/// How many extra tiles each empty row & column is drawn with, after `t` (0..1) of the expansion
+fn stretch(universe: &Universe, t: f32) -> f32 {
+    (universe.expansion.min(MAX_DRAWN_EXPANSION) - 1) as f32 * t
+}
+
+/// Position of row/column `i` (in tiles), if each of the `empty` ones before it grew by `stretch`
+fn offset(empty: &BTreeSet<i64>, i: i64, stretch: f32) -> f32 {
+    i as f32 + stretch * empty.range(..i).count() as f32
+}
+
+fn position(universe: &Universe, coord: Coord, stretch: f32) -> Vec2 {
+    Vec2::new(
+        offset(&universe.horizontal, coord.x, stretch),
+        -offset(&universe.vertical, coord.y, stretch),
+    ) * TILE
+}
+
+fn setup(mut cmd: Commands, universe: Res<Universe>) {
+    let gap = Color::rgba(0.3, 0.3, 0.6, 0.3);
+    for x in &universe.horizontal {
+        cmd.spawn((Gap::Column(*x), rect(0., 0., 0., TILE, TILE, gap)));
+    }
+    for y in &universe.vertical {
+        cmd.spawn((Gap::Row(*y), rect(0., 0., 0., TILE, TILE, gap)));
+    }
+
+    for galaxy in universe.galaxies() {
+        let size = TILE / 2.;
+        cmd.spawn((Galaxy(galaxy), rect(0., 0., 1., size, size, Color::GOLD)));
+    }
+
+    let style = TextStyle {
+        font_size: FONT_SIZE,
+        color: Color::GOLD,
+        ..default()
+    };
+    cmd.spawn((
+        DistanceLen,
+        Text2dBundle {
+            text: Text::from_sections([
+                TextSection::new("Distance: ", style.clone()),
+                TextSection::new("---", style),
+            ]),
+            transform: Transform::from_xyz(0., TILE, 2.),
+            text_anchor: Anchor::BottomLeft,
+            ..default()
+        },
+    ));
+}
+
+fn update(
+    running: Res<Running>,
+    time: Res<Time>,
+    mut timer: ResMut<Tick>,
+    mut state: ResMut<GameState>,
+    routes: Res<Routes>,
+    universe: Res<Universe>,
+    mut cmd: Commands,
+    mut finished: EventWriter<AnimationFinished>,
+) {
+    if !running.inner() {
+        return;
+    }
+    if !timer.inner().tick(time.delta()).just_finished() {
+        return;
+    }
+    if state.progress >= EXPANSION_TICKS + routes.pairs.len() {
+        return;
+    }
+
+    state.progress += 1;
+
+    // Pop up the distance of the route, which has just been drawn
+    let n = state.routes(&routes);
+    if n == 0 || state.progress != EXPANSION_TICKS + n {
+        return;
+    }
+    let (_, b, dist) = routes.pairs[n - 1];
+    let style = TextStyle {
+        font_size: FONT_SIZE / 2.,
+        color: Color::GOLD,
+        ..default()
+    };
+    let at = position(&universe, b, stretch(&universe, 1.));
+    cmd.spawn(ScorePopup::bundle(
+        format!("+{dist}"),
+        style,
+        at.extend(3.),
+        TILE,
+    ));
+    if n == routes.pairs.len() {
+        finished.send(AnimationFinished::new(routes.total(n), state.progress).with("Pairs", n));
+    }
+}
+
+fn galaxy_mover(
+    universe: Res<Universe>,
+    state: Res<GameState>,
+    mut galaxies: Query<(&Galaxy, &mut Transform)>,
+) {
+    let stretch = stretch(&universe, state.expansion());
+    for (galaxy, mut tf) in galaxies.iter_mut() {
+        let at = position(&universe, galaxy.0, stretch);
+        tf.translation.x = at.x;
+        tf.translation.y = at.y;
+    }
+}
+
+fn gap_stretcher(
+    universe: Res<Universe>,
+    state: Res<GameState>,
+    mut gaps: Query<(&Gap, &mut Sprite, &mut Transform)>,
+) {
+    let stretch = stretch(&universe, state.expansion());
+    let shape = universe.sky.shape();
+    let height = shape[0] as f32 + stretch * universe.vertical.len() as f32;
+    let width = shape[1] as f32 + stretch * universe.horizontal.len() as f32;
+    for (gap, mut sprite, mut tf) in gaps.iter_mut() {
+        // Gaps span the whole universe and grow from their top/left edge onwards
+        let (center, size) = match gap {
+            Gap::Column(x) => (
+                Vec2::new(
+                    offset(&universe.horizontal, *x, stretch) + stretch / 2.,
+                    -(height - 1.) / 2.,
+                ),
+                Vec2::new(1. + stretch, height),
+            ),
+            Gap::Row(y) => (
+                Vec2::new(
+                    (width - 1.) / 2.,
+                    -offset(&universe.vertical, *y, stretch) - stretch / 2.,
+                ),
+                Vec2::new(width, 1. + stretch),
+            ),
+        };
+        sprite.custom_size = Some(size * TILE);
+        tf.translation.x = center.x * TILE;
+        tf.translation.y = center.y * TILE;
+    }
+}
+
+fn route_drawer(
+    universe: Res<Universe>,
+    routes: Res<Routes>,
+    state: Res<GameState>,
+    mut gizmos: Gizmos,
+) {
+    let n = state.routes(&routes);
+    let stretch = stretch(&universe, 1.);
+    for (i, (a, b, _)) in routes.pairs[..n].iter().enumerate().rev().take(TRAIL) {
+        let alpha = 1. - (n - 1 - i) as f32 / TRAIL as f32;
+        // Manhattan route: first along the row of `a`, then along the column of `b`
+        let corner = Coord::new(b.x, a.y);
+        gizmos.linestrip_2d(
+            [*a, corner, *b].map(|c| position(&universe, c, stretch)),
+            Color::ORANGE.with_a(alpha),
+        );
+    }
+}
+
+fn distance_counter(
+    state: Res<GameState>,
+    routes: Res<Routes>,
+    mut text: Query<&mut Text, With<DistanceLen>>,
+) {
+    let n = state.routes(&routes);
+    if n == 0 {
+        return;
+    }
+    for mut text in text.iter_mut() {
+        text.sections[1].value = format!("{} ({n}/{} pairs)", routes.total(n), routes.pairs.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HeadlessPlugins;
+    use std::str::FromStr;
+
+    fn universe() -> Universe {
+        let mut universe =
+            Universe::from_str(include_str!("../../sample/eleventh.txt")).expect("parsing");
+        universe.expand(2);
+        universe
+    }
+
+    #[test]
+    fn smoke() {
+        let mut app = app(universe(), 5., HeadlessPlugins);
+        for _ in 0..3 {
+            app.update();
+        }
+    }
+
+    #[test]
+    fn routes_total_to_part_one() {
+        let routes = Routes::new(&universe());
+        assert_eq!(routes.total(0), 0);
+        assert_eq!(routes.total(routes.pairs.len()), 374);
+    }
+}
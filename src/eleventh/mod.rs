@@ -0,0 +1,191 @@
+#[cfg(feature = "animations")]
+pub mod animation;
+
+use std::{collections::BTreeSet, fmt::Debug, str::FromStr};
+
+#[cfg(feature = "animations")]
+use bevy::prelude::Resource;
+use euclid::Vector2D;
+use itertools::Itertools;
+use ndarray::prelude::*;
+
+use crate::{Part, Solver};
+
+/// Image of the sky with its galaxies, whose empty rows & columns grow by an expansion factor
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "animations", derive(Resource))]
+pub struct Universe {
+    sky: Array2<char>,
+    /// How many rows & columns each empty one becomes, see [`Universe::expand`]
+    expansion: u64,
+    /// Indices of the columns without any galaxy
+    horizontal: BTreeSet<i64>,
+    /// Indices of the rows without any galaxy
+    vertical: BTreeSet<i64>,
+}
+
+pub type Coord = Vector2D<i64, euclid::UnknownUnit>;
+
+impl Debug for Universe {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let shape = self.sky.shape();
+        for y in 0..shape[0] {
+            for x in 0..shape[1] {
+                write!(f, "{}", self.sky[[y, x]])?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Universe {
+    fn new(sky: Array2<char>) -> Self {
+        let empty = |axis| {
+            sky.axis_iter(axis)
+                .enumerate()
+                .filter(|(_, line)| line.iter().all(|c| *c != GALAXY))
+                .map(|(i, _)| i as i64)
+                .collect::<BTreeSet<_>>()
+        };
+        Self {
+            expansion: 1,
+            vertical: empty(Axis(0)),
+            horizontal: empty(Axis(1)),
+            sky,
+        }
+    }
+
+    /// Distance between `a` & `b`, if each empty row & column counts `factor` times
+    fn manhattan(&self, a: &Coord, b: &Coord, factor: u64) -> u64 {
+        let (start, end) = (a.min(*b), a.max(*b));
+        let empty = self.horizontal.range(start.x..end.x).count()
+            + self.vertical.range(start.y..end.y).count();
+        let d = (*a - *b).abs();
+        (d.x + d.y) as u64 + empty as u64 * (factor - 1)
+    }
+
+    /// Let each empty row & column grow to `factor` rows & columns
+    pub fn expand(&mut self, factor: u64) {
+        self.expansion = factor;
+    }
+
+    /// Positions of all galaxies, before any expansion
+    pub fn galaxies(&self) -> impl Iterator<Item = Coord> + '_ + Clone {
+        self.sky
+            .indexed_iter()
+            .filter(|(_, c)| **c == GALAXY)
+            .map(|((x, y), _)| Coord::new(y as i64, x as i64))
+    }
+
+    /// Distances between all pairs of galaxies, after each empty row & column grew to `factor`
+    /// rows & columns
+    pub fn shortest_paths_with_expansion(
+        &self,
+        factor: u64,
+    ) -> impl Iterator<Item = (Coord, Coord, u64)> + '_ {
+        self.galaxies()
+            .tuple_combinations()
+            .map(move |(a, b)| (a, b, self.manhattan(&a, &b, factor)))
+    }
+
+    /// Distances between all pairs of galaxies, in the universe as far as it [`Universe::expand`]ed
+    pub fn pairwise_distances(&self) -> impl Iterator<Item = (Coord, Coord, u64)> + '_ {
+        self.shortest_paths_with_expansion(self.expansion)
+    }
+}
+
+impl FromStr for Universe {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let nrows = s.lines().count();
+        let ncols = s
+            .lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or_default();
+        let mut sky = Array2::from_elem((nrows, ncols), VOID);
+
+        for (coord, _) in s
+            .lines()
+            .enumerate()
+            .flat_map(|(y, line)| line.chars().enumerate().map(move |(x, c)| ([y, x], c)))
+            .filter(|(_, c)| *c == '#')
+        {
+            sky[coord] = GALAXY;
+        }
+        Ok(Universe::new(sky))
+    }
+}
+
+pub fn solve(part: Part, input: &str) -> anyhow::Result<u64> {
+    let mut universe = Universe::from_str(input)?;
+    universe.expand(match part {
+        Part::One => 2,
+        Part::Two => 1_000_000,
+    });
+    Ok(universe.pairwise_distances().map(|(_, _, dist)| dist).sum())
+}
+
+/// Day 11: Cosmic Expansion
+#[derive(Debug, Clone, Copy)]
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    fn name(&self) -> &'static str {
+        "eleventh"
+    }
+
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::One, input).map(|x| x.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::Two, input).map(|x| x.to_string())
+    }
+}
+
+const VOID: char = '·';
+const GALAXY: char = '●';
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(2, 374)]
+    #[case(10, 1030)]
+    #[case(100, 8410)]
+    #[case(1_000_000, 82000210)]
+    fn sample(#[case] expansion: u64, #[case] expected_path_len: u64) {
+        let input = include_str!("../../sample/eleventh.txt");
+        let mut universe = Universe::from_str(input).expect("parsing");
+
+        universe.expand(expansion);
+        assert_eq!(
+            expected_path_len,
+            universe
+                .pairwise_distances()
+                .map(|(_, _, dist)| dist)
+                .sum::<u64>(),
+            "{universe:?}"
+        );
+    }
+
+    #[rstest]
+    fn non_square_universe() {
+        let universe = Universe::from_str("#..#\n....\n").expect("parsing");
+        assert_eq!(universe.horizontal, BTreeSet::from([1, 2]));
+        assert_eq!(universe.vertical, BTreeSet::from([1]));
+        assert_eq!(
+            universe
+                .shortest_paths_with_expansion(10)
+                .map(|(_, _, dist)| dist)
+                .collect::<Vec<_>>(),
+            vec![21]
+        );
+    }
+}
@@ -0,0 +1,94 @@
+//! Writing tables of results (e.g. a day's rankings) as JSON or CSV, to sort & filter them with
+//! other tools
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Export {
+    Json,
+    Csv,
+}
+
+/// One line of an exported table
+pub trait Row: Serialize {
+    /// Names of the columns
+    const HEADER: &'static [&'static str];
+
+    /// Values of the columns, in the same order as [`Row::HEADER`]
+    fn fields(&self) -> Vec<String>;
+}
+
+/// Quote `field` if it would otherwise break the CSV
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+pub fn csv<R: Row>(rows: &[R]) -> String {
+    let lines = std::iter::once(R::HEADER.join(",")).chain(rows.iter().map(|row| {
+        row.fields()
+            .iter()
+            .map(|field| escape(field))
+            .collect::<Vec<_>>()
+            .join(",")
+    }));
+    lines.map(|line| line + "\n").collect()
+}
+
+pub fn export<R: Row>(rows: &[R], format: Export) -> anyhow::Result<String> {
+    Ok(match format {
+        Export::Json => serde_json::to_string_pretty(rows)? + "\n",
+        Export::Csv => csv(rows),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[derive(Serialize)]
+    struct Pair {
+        name: &'static str,
+        value: u32,
+    }
+
+    impl Row for Pair {
+        const HEADER: &'static [&'static str] = &["name", "value"];
+        fn fields(&self) -> Vec<String> {
+            vec![self.name.to_owned(), self.value.to_string()]
+        }
+    }
+
+    #[rstest]
+    fn csv_quotes_separators() {
+        let rows = [
+            Pair {
+                name: "plain",
+                value: 1,
+            },
+            Pair {
+                name: "a, \"b\"",
+                value: 2,
+            },
+        ];
+        assert_eq!(csv(&rows), "name,value\nplain,1\n\"a, \"\"b\"\"\",2\n");
+    }
+
+    #[rstest]
+    fn json_has_one_object_per_row() {
+        let rows = [Pair {
+            name: "plain",
+            value: 1,
+        }];
+        let json = export(&rows, Export::Json).expect("exporting");
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&json).expect("parsing"),
+            serde_json::json!([{"name": "plain", "value": 1}])
+        );
+    }
+}
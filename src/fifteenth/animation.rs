@@ -9,22 +9,38 @@ use itertools::Itertools;
 use lazy_static::lazy_static;
 
 use crate::{
-    arc_segment, fifteenth::N, frequency_increaser, lerp, lerphsl, toggle_running, ArcSegment,
-    Running, Tick,
+    arc_segment,
+    capture::CapturePlugin,
+    fifteenth::N,
+    keybindings::{Action, Keybindings},
+    lerp, lerphsl,
+    reset::ResetPlugin,
+    summary::AnimationFinished,
+    AocCameraPlugin, AocControlPlugin, ArcSegment, Presentation, Running, Tick,
 };
 
 use super::{parser::instructions, HashMap, Instruction, Operation};
 
-pub fn run(frequency: f32, hashmap: HashMap, input: &str) {
-    App::new()
-        .add_plugins(DefaultPlugins)
+pub fn run(frequency: f32, hashmap: HashMap, input: &str, presentation: Presentation) {
+    let plugins = DefaultPlugins.build().add(presentation);
+    app(frequency, hashmap, input, plugins)
+        .add_plugins(CapturePlugin)
+        .run()
+}
+
+pub fn app(frequency: f32, hashmap: HashMap, input: &str, plugins: impl PluginGroup) -> App {
+    let mut app = App::new();
+    app.add_plugins((plugins, AocCameraPlugin::new(0.), AocControlPlugin))
         .insert_resource(Tick::new(frequency))
-        .insert_resource(Running::default())
         .insert_resource(hashmap)
         .insert_resource(Instructions {
             list: instructions(input).expect("Input to be parseable").1,
             cursor: 0,
         })
+        .add_plugins((
+            ResetPlugin::<HashMap>::default(),
+            ResetPlugin::<Instructions>::default(),
+        ))
         .add_systems(Startup, setup)
         .add_systems(
             Update,
@@ -35,11 +51,9 @@ pub fn run(frequency: f32, hashmap: HashMap, input: &str) {
                 update_instruction_transparency,
                 move_instruction_list,
                 rotate_circle,
-                frequency_increaser,
-                toggle_running,
             ),
-        )
-        .run()
+        );
+    app
 }
 
 const LENS_SIZE: f32 = RADIUS / 6.;
@@ -58,7 +72,7 @@ lazy_static! {
         ..default()
     };
 }
-#[derive(Debug, Resource)]
+#[derive(Debug, Clone, Resource)]
 struct Instructions {
     list: Vec<Instruction>,
     cursor: usize,
@@ -98,8 +112,6 @@ fn setup(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
-    cmd.spawn(Camera2dBundle::default());
-
     cmd.spawn_empty()
         .insert(SpatialBundle::default())
         .insert(Circle)
@@ -216,13 +228,10 @@ fn update_instruction_transparency(
     mut texts: Query<&mut Text, With<InstructionList>>,
     instructions: Res<Instructions>,
 ) {
-    for (i, section) in texts
-        .get_single_mut()
-        .unwrap()
-        .sections
-        .iter_mut()
-        .enumerate()
-    {
+    let Ok(mut text) = texts.get_single_mut() else {
+        return;
+    };
+    for (i, section) in text.sections.iter_mut().enumerate() {
         let t = 2. * (instructions.cursor as f32 - i as f32) / VISIBLE_INSTRUCTIONS as f32;
         section.style.color.set_a(1. - t.abs());
     }
@@ -234,7 +243,9 @@ fn move_instruction_list(
     mut texts: Query<&mut Transform, With<InstructionList>>,
     instructions: Res<Instructions>,
 ) {
-    let mut tf = texts.get_single_mut().unwrap();
+    let Ok(mut tf) = texts.get_single_mut() else {
+        return;
+    };
     tf.translation.y = lerp(
         tf.translation.y,
         instructions.cursor as f32 * FONT_SIZE + INSTRUCTION_LIST_OFFSET_Y,
@@ -250,18 +261,15 @@ fn rotate_circle(time: Res<Time>, mut circles: Query<&mut Transform, With<Circle
 
 fn update(
     keys: Res<Input<KeyCode>>,
+    bindings: Res<Keybindings>,
     running: Res<Running>,
     time: Res<Time>,
     mut timer: ResMut<Tick>,
-    mut exit: ResMut<Events<bevy::app::AppExit>>,
     mut catalogue: ResMut<HashMap>,
     mut instructions: ResMut<Instructions>,
+    mut finished: EventWriter<AnimationFinished>,
 ) {
-    if keys.just_pressed(KeyCode::Q) {
-        exit.send(bevy::app::AppExit);
-    }
-
-    let trigger = keys.just_released(KeyCode::Tab)
+    let trigger = bindings.released(&keys, Action::Step)
         || running.inner() && timer.inner().tick(time.delta()).just_finished();
 
     if !trigger {
@@ -271,7 +279,45 @@ fn update(
     if let Some(instruction) = instructions.next() {
         // println!(">> {instruction:?}");
         catalogue.process(instruction.clone());
+        if instructions.cursor == instructions.list.len() {
+            finished.send(
+                AnimationFinished::new(catalogue.focal_power(), instructions.cursor)
+                    .with("Lenses", catalogue.len()),
+            );
+        }
     } else {
-        println!("Processessed all instructions =)");
+        info!("Processed all instructions");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HeadlessPlugins;
+
+    #[test]
+    fn smoke() {
+        let input = include_str!("../../sample/fifteenth.txt");
+        let mut app = app(1.5, HashMap::default(), input, HeadlessPlugins);
+        for _ in 0..3 {
+            app.update();
+        }
+    }
+
+    #[test]
+    fn smoke_without_instruction_list() {
+        let input = include_str!("../../sample/fifteenth.txt");
+        let mut app = app(1.5, HashMap::default(), input, HeadlessPlugins);
+        app.update();
+
+        let lists = app
+            .world
+            .query_filtered::<Entity, With<InstructionList>>()
+            .iter(&app.world)
+            .collect::<Vec<_>>();
+        for id in lists {
+            app.world.despawn(id);
+        }
+        app.update();
     }
 }
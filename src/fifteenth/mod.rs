@@ -1,7 +1,8 @@
 use std::{array, fmt::Display, hash::Hasher, iter::repeat, str::FromStr};
 
-use crate::anyhowing;
+use crate::{anyhowing, Part, Solver};
 use anyhow::Result;
+#[cfg(feature = "animations")]
 use bevy::ecs::system::Resource;
 use derive_more::{Add, AsRef, From, Into, Sum};
 use itertools::izip;
@@ -9,6 +10,7 @@ use nom::Finish;
 
 use self::parser::instructions;
 
+#[cfg(feature = "animations")]
 pub mod animation;
 mod parser;
 
@@ -19,7 +21,8 @@ type Instruction = (Label, Operation);
 
 pub(crate) const N: usize = 256;
 
-#[derive(Debug, Resource)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "animations", derive(Resource))]
 pub struct HashMap([Box; N]);
 
 impl FromIterator<Instruction> for HashMap {
@@ -52,14 +55,53 @@ impl FromStr for HashMap {
 
 impl HashMap {
     pub fn focal_power(&self) -> u64 {
+        self.iter()
+            .map(|(box_, slot, _, focal_length)| (1 + box_ as u64) * slot as u64 * focal_length)
+            .sum()
+    }
+
+    /// Puts the lens `label` into its box, or changes the focal length of the one already in there
+    pub fn insert(&mut self, label: impl Into<Label>, focal_length: FocalLength) {
+        let label = label.into();
+        let lenses = &mut self.0[hash(&label)];
+        match lenses.iter_mut().find(|(l, _)| label == *l) {
+            Some(lens) => lens.1 = focal_length,
+            None => lenses.push((label, focal_length)),
+        }
+    }
+
+    /// Takes the lens `label` out of its box, returning its focal length if it was in there
+    pub fn remove(&mut self, label: &str) -> Option<FocalLength> {
+        let lenses = &mut self.0[hash(label)];
+        let slot = lenses.iter().position(|(l, _)| l == label)?;
+        Some(lenses.remove(slot).1)
+    }
+
+    /// Number of lenses in all boxes
+    pub fn len(&self) -> usize {
+        self.0.iter().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(Vec::is_empty)
+    }
+
+    /// All lenses as box index, slot (counting from 1), label & focal length
+    pub fn iter(&self) -> impl Iterator<Item = (u8, usize, &str, FocalLength)> {
+        self.boxes().flat_map(|(box_, lenses)| {
+            izip!(repeat(box_), 1.., lenses).map(|(box_, slot, (label, focal_length))| {
+                (box_, slot, label.as_str(), *focal_length)
+            })
+        })
+    }
+
+    /// Boxes with at least one lens, by index
+    pub fn boxes(&self) -> impl Iterator<Item = (u8, &[(Label, FocalLength)])> {
         self.0
             .iter()
             .enumerate()
-            .flat_map(|(box_, lenses)| {
-                izip!(repeat(1 + box_ as u64), 1.., lenses)
-                    .map(|(box_nr, slot, (_, focal_length))| box_nr * slot * focal_length)
-            })
-            .sum()
+            .filter(|(_, lenses)| !lenses.is_empty())
+            .map(|(box_, lenses)| (box_ as u8, lenses.as_slice()))
     }
 
     pub fn get(&self, key: &str) -> impl Iterator<Item = &(Label, FocalLength)> {
@@ -72,19 +114,22 @@ impl HashMap {
     pub(crate) fn process(&mut self, (label, operation): Instruction) {
         match operation {
             Operation::Remove => {
-                self.0[hash(&label)].retain(|lens| lens.0 != label);
-            }
-            Operation::Insert(fl) => {
-                let item = &mut self.0[hash(&label)];
-                match item.iter_mut().find(|(l, _)| label == *l) {
-                    Some(lens) => lens.1 = fl,
-                    None => item.push((label, fl)),
-                }
+                self.remove(&label);
             }
+            Operation::Insert(fl) => self.insert(label, fl),
         };
     }
 }
 
+impl<'a> IntoIterator for &'a HashMap {
+    type Item = (u8, usize, &'a str, FocalLength);
+    type IntoIter = std::boxed::Box<dyn Iterator<Item = Self::Item> + 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        std::boxed::Box::new(self.iter())
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub(crate) enum Operation {
     Remove,
@@ -100,7 +145,7 @@ impl Display for Operation {
     }
 }
 
-#[derive(Debug, Default, PartialEq, Eq, From, Into, Add, Sum, AsRef)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, From, Into, Add, Sum, AsRef)]
 #[allow(clippy::upper_case_acronyms)]
 pub struct HASH(u8);
 
@@ -141,10 +186,40 @@ where
     }
 }
 
+pub fn solve(part: Part, input: &str) -> Result<u64> {
+    Ok(match part {
+        Part::One => input
+            .split([',', '\n'])
+            .map(str::trim)
+            .filter(|step| !step.is_empty())
+            .map(|step| step.bytes().collect::<HASH>().finish())
+            .sum(),
+        Part::Two => HashMap::from_str(input)?.focal_power(),
+    })
+}
+
+/// Day 15: Lens Library
+#[derive(Debug, Clone, Copy)]
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    fn name(&self) -> &'static str {
+        "fifteenth"
+    }
+
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::One, input).map(|x| x.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::Two, input).map(|x| x.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::fifteenth::parser::instruction;
+    use crate::fifteenth::parser::{instruction, instructions};
     use nom::IResult;
     use rstest::rstest;
 
@@ -156,4 +231,70 @@ mod tests {
     fn sample_b_parsing(#[case] input: &str, #[case] expected: IResult<&str, (String, Operation)>) {
         assert_eq!(expected, instruction(input));
     }
+
+    #[rstest]
+    #[case("rn=1,cm-\n", 2)]
+    #[case("rn=1,cm-,\n", 2)]
+    #[case("rn=1,\ncm-", 2)]
+    #[case("rn=1\ncm-\r\n", 2)]
+    #[case("rn=1,\r\n,cm-,qp=3,\n\n", 3)]
+    fn instructions_across_lines(#[case] input: &str, #[case] expected: usize) {
+        let (rest, list) = instructions(input).expect("parsing");
+        assert_eq!(rest, "");
+        assert_eq!(list.len(), expected);
+    }
+
+    #[rstest]
+    #[case("rn=1,cm?")]
+    #[case("rn=1\n42")]
+    fn rejects_unparsable_rest(#[case] input: &str) {
+        assert!(instructions(input).is_err());
+    }
+
+    #[rstest]
+    fn lenses_of_sample() {
+        let hashmap =
+            HashMap::from_str(include_str!("../../sample/fifteenth.txt")).expect("parsing");
+        assert_eq!(hashmap.len(), 5);
+        assert_eq!(
+            hashmap.boxes().map(|(box_, _)| box_).collect::<Vec<_>>(),
+            vec![0, 3]
+        );
+        assert_eq!(
+            hashmap.iter().collect::<Vec<_>>(),
+            vec![
+                (0, 1, "rn", 1),
+                (0, 2, "cm", 2),
+                (3, 1, "ot", 7),
+                (3, 2, "ab", 5),
+                (3, 3, "pc", 6),
+            ]
+        );
+        assert_eq!((&hashmap).into_iter().count(), hashmap.len());
+    }
+
+    #[rstest]
+    fn insert_replaces_and_remove_takes_out() {
+        let mut hashmap = HashMap::default();
+        assert!(hashmap.is_empty());
+        hashmap.insert("rn", 1);
+        hashmap.insert("cm", 2);
+        hashmap.insert("rn", 3);
+        assert_eq!(
+            hashmap.get("rn").collect::<Vec<_>>(),
+            vec![&("rn".to_owned(), 3), &("cm".to_owned(), 2)]
+        );
+        assert_eq!(hashmap.remove("rn"), Some(3));
+        assert_eq!(hashmap.remove("rn"), None);
+        assert_eq!(hashmap.len(), 1);
+    }
+
+    #[rstest]
+    #[case(Part::One, 1320)]
+    #[case(Part::Two, 145)]
+    fn wrapped_input_solves_alike(#[case] part: Part, #[case] expected: u64) {
+        let input = include_str!("../../sample/fifteenth.txt");
+        let wrapped = input.replace(',', ",\n").replacen(",\n", "\n", 3);
+        assert_eq!(solve(part, &wrapped).expect("solving"), expected);
+    }
 }
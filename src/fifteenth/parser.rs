@@ -1,19 +1,19 @@
 use nom::{
-    character::complete::{alpha1, char, digit1},
-    multi::separated_list1,
+    branch::alt,
+    character::complete::{alpha1, char, multispace0, multispace1},
+    multi::{many1_count, separated_list1},
     sequence::tuple,
     IResult, Parser as NomParser,
 };
 use nom_supreme::ParserExt;
 
 use super::{Label, Operation};
+use crate::parse::common::integer;
 
 pub(crate) fn operation(s: &str) -> IResult<&str, Operation> {
     char('-')
         .value(Operation::Remove)
-        .or(char('=')
-            .precedes(digit1.map_res(str::parse))
-            .map(Operation::Insert))
+        .or(char('=').precedes(integer).map(Operation::Insert))
         .parse(s)
 }
 
@@ -24,6 +24,17 @@ pub(crate) fn instruction(s: &str) -> IResult<&str, (Label, Operation)> {
     tuple((label, operation)).parse(s)
 }
 
+/// Commas and/or line breaks, since editors may wrap the (single line) input between any two steps
+fn separator(s: &str) -> IResult<&str, ()> {
+    many1_count(alt((char(',').value(()), multispace1.value(()))))
+        .value(())
+        .parse(s)
+}
+
 pub(crate) fn instructions(s: &str) -> IResult<&str, Vec<(Label, Operation)>> {
-    separated_list1(char(','), instruction).parse(s)
+    multispace0
+        .precedes(separated_list1(separator, instruction))
+        .terminated(separator.opt())
+        .all_consuming()
+        .parse(s)
 }
@@ -1,54 +1,81 @@
 use super::{propagate_once, Almanac, Mapping, Resource as R};
-use crate::{mouse, rect, toggle_running, Running, Scroll, Tick};
+use crate::{
+    capture::CapturePlugin,
+    keybindings::{Action, Keybindings},
+    rect,
+    reset::ResetPlugin,
+    summary::AnimationFinished,
+    widgets::{BigNumber, WidgetsPlugin},
+    AocCameraPlugin, AocControlPlugin, Presentation, Running, Tick,
+};
 
 use std::{iter::once, ops::Range};
 
 use bevy::prelude::*;
 use enum_iterator::{all, next};
 
-pub fn run(almanac: Almanac, seeds: &[Range<i128>], frequency: f32) {
-    App::new()
-        .add_plugins(DefaultPlugins)
-        .insert_resource(ClearColor(Color::WHITE))
-        .insert_resource(GameState::default())
-        .insert_resource(almanac)
-        .insert_resource(Seeds(seeds.to_vec()))
-        .insert_resource(Tick::new(frequency))
-        .insert_resource(Running::default())
-        .add_systems(Startup, setup)
-        .add_systems(
-            Update,
-            (
-                update,
-                mouse,
-                toggle_running,
-                range_mover,
-                range_shower,
-                seed_mover,
-                label_mover,
-            ),
-        )
+pub fn run(almanac: Almanac, seeds: &[Range<i128>], frequency: f32, presentation: Presentation) {
+    let plugins = DefaultPlugins.build().add(presentation);
+    app(almanac, seeds, frequency, plugins)
+        .add_plugins(CapturePlugin)
         .run()
 }
 
+pub fn app(
+    almanac: Almanac,
+    seeds: &[Range<i128>],
+    frequency: f32,
+    plugins: impl PluginGroup,
+) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        plugins,
+        AocCameraPlugin::new(0.1).at(Vec3::new(400., 0., 0.)),
+        AocControlPlugin,
+        WidgetsPlugin,
+    ))
+    .insert_resource(ClearColor(Color::WHITE))
+    .insert_resource(GameState::default())
+    .insert_resource(almanac)
+    .insert_resource(Seeds(seeds.to_vec()))
+    .insert_resource(Tick::new(frequency))
+    .add_plugins(ResetPlugin::<GameState>::default())
+    .add_systems(Startup, setup)
+    .add_systems(
+        Update,
+        (update, range_mover, range_shower, seed_mover, label_mover),
+    );
+    app
+}
+
 const RANGE_COLOR: Color = Color::Rgba {
     red: 0.,
     green: 1.,
     blue: 1.,
     alpha: 1.,
 };
+const SELECTED_COLOR: Color = Color::rgb(0., 0.4, 1.);
+/// Value at the right end of each number line
+const SCALE: i128 = 100;
+/// How far the selected seed moves per key press
+const NUDGE: i128 = SCALE / 20;
 const MOVE_SPEED: f32 = 5.;
 const SHOW_SPEED: f32 = 15.;
 const ROWHEIGHT: f32 = 75.;
 const ROWLEN: f32 = 500.;
 const FONT_SIZE: f32 = 26.;
 
-#[derive(Default, Debug, Resource)]
+#[derive(Default, Debug, Clone, Resource)]
 struct GameState {
     res: R,
     step: Step,
     i: usize,
-    selection: i32,
+    /// Which of the seeds is selected to move around, in the order they were spawned
+    selection: Option<usize>,
+    /// Whether the seed leading to the best location is highlighted already
+    done: bool,
+    /// Ticks taken until now
+    steps: usize,
 }
 
 #[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
@@ -69,15 +96,12 @@ struct RangeComponent((Range<i128>, R));
 #[derive(Debug, Component)]
 struct Highlight;
 
+/// Text telling where the selected seed ends up
+#[derive(Debug, Component)]
+struct SelectionInfo;
+
 fn setup(mut cmd: Commands, seeds: Res<Seeds>, assets: Res<AssetServer>) {
     let grey = Color::rgb(0.3, 0.3, 0.3);
-    cmd.spawn((
-        Scroll(0.1),
-        Camera2dBundle {
-            transform: Transform::from_xyz(400., 0., 0.),
-            ..default()
-        },
-    ));
     for (y, path) in [
         "seed.png",
         "soil.png",
@@ -126,9 +150,10 @@ fn setup(mut cmd: Commands, seeds: Res<Seeds>, assets: Res<AssetServer>) {
     let seed = seeds.0.iter().min_by_key(|r| r.start).unwrap();
     cmd.spawn((
         R::Seed,
+        BigNumber::new(seed.start),
         Text2dBundle {
             text: Text::from_section(
-                format!("{}", seed.start),
+                BigNumber::new(seed.start).to_string(),
                 TextStyle {
                     font_size: FONT_SIZE,
                     color: Color::BLACK,
@@ -140,11 +165,26 @@ fn setup(mut cmd: Commands, seeds: Res<Seeds>, assets: Res<AssetServer>) {
             ..default()
         },
     ));
+    cmd.spawn((
+        SelectionInfo,
+        Text2dBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font_size: FONT_SIZE,
+                    color: SELECTED_COLOR,
+                    ..default()
+                },
+            ),
+            transform: Transform::from_xyz(ROWLEN / 2., row_y(R::Seed) + ROWHEIGHT, 5.),
+            ..default()
+        },
+    ));
 }
 
 fn row_x(range: &Range<i128>) -> f32 {
     let len = (range.end - range.start) as f32;
-    (range.start as f32 + len / 2.) / 100. * ROWLEN
+    (range.start as f32 + len / 2.) / SCALE as f32 * ROWLEN
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -160,7 +200,7 @@ fn spawn_range(
     comps: impl Bundle,
 ) {
     let len = (range.end - range.start) as f32;
-    let (w, h) = (len / 100. * ROWLEN, h * ROWHEIGHT / 2.);
+    let (w, h) = (len / SCALE as f32 * ROWLEN, h * ROWHEIGHT / 2.);
     cmd.spawn((
         RangeComponent((range.clone(), res)),
         rect(x, y, z, w, h, color),
@@ -185,8 +225,7 @@ fn row_y(res: R) -> f32 {
 fn range_mover(time: Res<Time>, mut query: Query<(&RangeComponent, &mut Transform)>) {
     for (c, mut tf) in query.iter_mut() {
         let (range, res) = &c.0;
-        let len = (range.end - range.start) as f32;
-        let (x, y) = ((range.start as f32 + len / 2.) / 100. * ROWLEN, row_y(*res));
+        let (x, y) = (row_x(range), row_y(*res));
         tf.translation.x += (x - tf.translation.x) * MOVE_SPEED * time.delta_seconds();
         tf.translation.y += (y - tf.translation.y) * MOVE_SPEED * time.delta_seconds();
     }
@@ -226,59 +265,101 @@ fn range_shower(
 
 fn seed_mover(
     keys: Res<Input<KeyCode>>,
+    bindings: Res<Keybindings>,
+    almanac: Res<Almanac>,
     mut state: ResMut<GameState>,
-    mut highlight: Query<&mut RangeComponent, Without<Highlight>>,
-    mut sprites: Query<&mut Sprite, (Without<Highlight>, With<RangeComponent>)>,
+    mut ranges: Query<(Entity, &mut RangeComponent, &mut Sprite), Without<Highlight>>,
+    mut info: Query<&mut Text, With<SelectionInfo>>,
 ) {
-    if keys.just_released(KeyCode::Key1) {
-        state.selection = 1;
-        println!("Selecting Seed #1")
+    let mut seeds = ranges
+        .iter_mut()
+        .filter(|(_, c, _)| c.0 .1 == R::Seed)
+        .collect::<Vec<_>>();
+    // Spawn order, so that moving a seed past another one keeps it selected
+    seeds.sort_by_key(|(id, _, _)| *id);
+    let n = seeds.len();
+
+    let selection = state.selection;
+    if bindings.released(&keys, Action::Cycle) {
+        let backwards = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+        state.selection = cycle(state.selection, n, backwards);
     }
-    if keys.just_released(KeyCode::Key2) {
-        state.selection = 2;
-        println!("Selecting Seed #2")
+    for i in (0..4).filter(|i| *i < n) {
+        if bindings.released(&keys, Action::Select(i)) {
+            state.selection = Some(i);
+        }
     }
-    if keys.just_released(KeyCode::Key3) {
-        state.selection = 3;
-        println!("Selecting Seed #3")
+    let selection = match state.selection.filter(|i| *i < n) {
+        Some(i) if state.selection != selection => {
+            info!("Selecting Seed #{}", i + 1);
+            Some(i)
+        }
+        i => i,
+    };
+
+    for (i, (_, _, sprite)) in seeds.iter_mut().enumerate() {
+        sprite.color = if Some(i) == selection {
+            SELECTED_COLOR
+        } else {
+            RANGE_COLOR
+        };
     }
-    if keys.just_released(KeyCode::Key4) {
-        state.selection = 4;
-        println!("Selecting Seed #4")
+    let Ok(mut text) = info.get_single_mut() else {
+        return;
+    };
+    let Some((_, range, _)) = selection.and_then(|i| seeds.get_mut(i)) else {
+        text.sections[0].value.clear();
+        return;
+    };
+    let range = &mut range.0 .0;
+    if bindings.released(&keys, Action::Left) {
+        range.start -= NUDGE;
+        range.end -= NUDGE;
     }
-    for (i, mut sprite) in sprites.iter_mut().enumerate() {
-        if i + 1 == state.selection as usize {
-            sprite.color = Color::hex("#00ffff").unwrap();
-        } else {
-            sprite.color = RANGE_COLOR;
-        }
+    if bindings.released(&keys, Action::Right) {
+        range.start += NUDGE;
+        range.end += NUDGE;
     }
-    if let Some(mut range) = highlight.iter_mut().nth((state.selection - 1) as usize) {
-        if keys.just_released(KeyCode::H) {
-            range.0 .0.start -= 5;
-            range.0 .0.end -= 5;
-        }
+    let location = almanac
+        .map_seed_through(range.start)
+        .last()
+        .map_or(range.start, |(_, location)| *location);
+    text.sections[0].value = format!(
+        "Seed {} → location {}",
+        BigNumber::new(range.start),
+        BigNumber::new(location)
+    );
+}
 
-        if keys.just_released(KeyCode::L) {
-            range.0 .0.start += 5;
-            range.0 .0.end += 5;
-        }
+/// Selection after the next (or previous, going `backwards`) of `n` things, the first (or last)
+/// one if none was selected yet
+fn cycle(selection: Option<usize>, n: usize, backwards: bool) -> Option<usize> {
+    if n == 0 {
+        return None;
     }
+    Some(match (selection.filter(|i| *i < n), backwards) {
+        (None, false) => 0,
+        (None, true) => n - 1,
+        (Some(i), false) => (i + 1) % n,
+        (Some(i), true) => (i + n - 1) % n,
+    })
 }
 
 fn label_mover(
     time: Res<Time>,
-    mut texts: Query<(&mut Text, &mut Transform)>,
+    mut labels: Query<(&mut BigNumber, &mut Transform)>,
     ranges: Query<&RangeComponent, Without<Highlight>>,
 ) {
-    if let Some((mut text, mut tf)) = texts.iter_mut().next() {
+    if let Some((mut number, mut tf)) = labels.iter_mut().next() {
         if let Some((range, res)) = ranges
             .iter()
             .map(|c| c.0.clone())
             .min_by_key(|(range, _)| range.start)
         {
             let dt = time.delta_seconds();
-            text.sections[0].value = format!("{}", range.start);
+            if number.value != range.start {
+                number.value = range.start;
+            }
             tf.translation.x +=
                 (row_x(&(range.start - 2..range.start + 1)) - tf.translation.x) * MOVE_SPEED * dt;
             tf.translation.y += (row_y(res) + 20. - tf.translation.y) * MOVE_SPEED * dt;
@@ -286,14 +367,37 @@ fn label_mover(
     }
 }
 
+/// Marks the seed, which the best location maps back to
+fn highlight_best_seed(cmd: &mut Commands, almanac: &Almanac, seeds: &[Range<i128>]) {
+    let location = almanac.best_location(seeds);
+    let Some(seed) = almanac
+        .seed_for_location(location)
+        .into_iter()
+        .find(|seed| seeds.iter().any(|r| r.contains(seed)))
+    else {
+        return;
+    };
+    info!("Seed {seed} leads to the best location {location}");
+    cmd.spawn(rect(
+        row_x(&(seed..seed + 1)),
+        row_y(R::Seed),
+        7.,
+        3.,
+        ROWHEIGHT / 1.5,
+        Color::GOLD,
+    ));
+}
+
 fn update(
     time: Res<Time>,
     query: Query<(Entity, &mut RangeComponent), Without<Highlight>>,
     mut cmd: Commands,
     almanac: Res<Almanac>,
+    seeds: Res<Seeds>,
     running: Res<Running>,
     mut state: ResMut<GameState>,
     mut timer: ResMut<Tick>,
+    mut finished: EventWriter<AnimationFinished>,
 ) {
     if !running.inner() {
         return;
@@ -302,8 +406,19 @@ fn update(
     let nextres = next(&state.res);
     if nextres.is_none() {
         // Done
+        if !state.done {
+            state.done = true;
+            highlight_best_seed(&mut cmd, &almanac, &seeds.0);
+            finished.send(
+                AnimationFinished::new(almanac.best_location(&seeds.0), state.steps)
+                    .with("Seed ranges", seeds.0.len()),
+            );
+        }
         return;
     }
+    if tick {
+        state.steps += 1;
+    }
     let (thisres, nextres) = (state.res, nextres.unwrap());
 
     let takeover = Mapping::takeover();
@@ -316,7 +431,7 @@ fn update(
     let is_takeover = t == &takeover;
     state.step = match state.step {
         Step::ShowMapping if tick => {
-            println!(
+            debug!(
                 "A) Show mapping {r:?} #{i}: {t:?}",
                 r = nextres,
                 i = state.i
@@ -354,7 +469,7 @@ fn update(
                 .collect::<Vec<_>>();
             let (olds, news) = propagate_once(&rs, t);
 
-            println!(
+            debug!(
                 "B) moving slices {r:?} #{i}: {olds:?} -> {news:?}",
                 r = nextres,
                 i = state.i
@@ -397,14 +512,12 @@ fn update(
             }
         }
         Step::PrepareNext => {
-            println!("D)  prepare next {r:?} #{i}", r = nextres, i = state.i);
+            debug!("D)  prepare next {r:?} #{i}", r = nextres, i = state.i);
             state.i += 1;
             if state.i >= ts.len() {
                 state.res = nextres;
                 state.i = 0;
-                println!("--------------------------------------");
-                println!("{thisres:?} -> {nextres:?}");
-                println!("--------------------------------------")
+                debug!("{thisres:?} -> {nextres:?}");
             }
 
             let t = ts[state.i];
@@ -418,3 +531,80 @@ fn update(
         x => x,
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HeadlessPlugins, Part};
+    use bevy::ecs::event::Events;
+    use rstest::rstest;
+
+    #[test]
+    fn smoke() {
+        let input = include_str!("../../sample/fifth.txt");
+        let (almanac, seeds) = Almanac::parse(Part::Two, input).expect("parsing");
+        let mut app = app(almanac, &seeds, 1., HeadlessPlugins);
+        for _ in 0..3 {
+            app.update();
+        }
+    }
+
+    #[test]
+    fn highlights_best_seed() {
+        let input = include_str!("../../sample/fifth.txt");
+        let (almanac, seeds) = Almanac::parse(Part::Two, input).expect("parsing");
+        let mut app = app(almanac, &seeds, 1., HeadlessPlugins);
+        app.insert_resource(Running(true)).update();
+        app.world.resource_mut::<GameState>().res = R::Location;
+        app.update();
+        assert!(app.world.resource::<GameState>().done);
+        let golden = app
+            .world
+            .query::<&Sprite>()
+            .iter(&app.world)
+            .filter(|sprite| sprite.color == Color::GOLD)
+            .count();
+        assert_eq!(golden, 1);
+
+        let events = app.world.resource::<Events<AnimationFinished>>();
+        let finished = events
+            .get_reader()
+            .read(events)
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(finished.len(), 1);
+        assert_eq!(finished[0].answer, "46");
+    }
+
+    #[rstest]
+    #[case(None, 3, false, Some(0))]
+    #[case(None, 3, true, Some(2))]
+    #[case(Some(2), 3, false, Some(0))]
+    #[case(Some(0), 3, true, Some(2))]
+    #[case(Some(4), 7, false, Some(5))]
+    #[case(Some(4), 2, false, Some(0))]
+    #[case(Some(1), 0, false, None)]
+    fn cycles_through_any_number_of_seeds(
+        #[case] selection: Option<usize>,
+        #[case] n: usize,
+        #[case] backwards: bool,
+        #[case] expected: Option<usize>,
+    ) {
+        assert_eq!(cycle(selection, n, backwards), expected);
+    }
+
+    #[test]
+    fn shows_location_of_selected_seed() {
+        let input = include_str!("../../sample/fifth.txt");
+        let (almanac, seeds) = Almanac::parse(Part::One, input).expect("parsing");
+        let mut app = app(almanac, &seeds, 1., HeadlessPlugins);
+        app.update();
+        app.world.resource_mut::<GameState>().selection = Some(1);
+        app.update();
+        let text = app
+            .world
+            .query_filtered::<&Text, With<SelectionInfo>>()
+            .single(&app.world);
+        assert_eq!(text.sections[0].value, "Seed 14 → location 43");
+    }
+}
@@ -1,19 +1,25 @@
+#[cfg(feature = "animations")]
 pub mod animation;
 mod parser;
 
 use std::{collections::HashMap, fmt::Debug, iter::once, ops::Range, str::FromStr};
 
 use crate::{
-    fifth::parser::{parse_almanac, parse_seeds_individual, parse_seeds_ranges},
-    Part,
+    fifth::parser::{parse_almanac, parse_seed_individual, parse_seed_range, parse_seeds},
+    minimize::Structure,
+    Part, Solver,
 };
 
 use anyhow::{anyhow, Result};
+#[cfg(feature = "animations")]
 use bevy::prelude::{Component, Resource as BevyResource};
-use enum_iterator::{all, Sequence};
-use nom::{bytes::complete::tag, sequence::preceded, Finish};
+use enum_iterator::{all, reverse_all, Sequence};
+use nom::Finish;
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub(crate) struct Mapping {
     range: Range<i128>,
     offset: i128,
@@ -33,8 +39,10 @@ impl Mapping {
     }
 }
 
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Sequence, Component)]
-pub(crate) enum Resource {
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Sequence)]
+#[cfg_attr(feature = "animations", derive(Component))]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum Resource {
     #[default]
     Seed,
     Soil,
@@ -46,7 +54,9 @@ pub(crate) enum Resource {
     Location,
 }
 
-#[derive(Debug, BevyResource)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "animations", derive(BevyResource))]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct Almanac(HashMap<Resource, Vec<Mapping>>);
 
 impl FromStr for Almanac {
@@ -58,11 +68,11 @@ impl FromStr for Almanac {
 
 impl Almanac {
     pub fn parse(part: Part, s: &str) -> Result<(Self, Vec<Range<i128>>)> {
-        let parser = match part {
-            Part::One => parse_seeds_individual,
-            Part::Two => parse_seeds_ranges,
+        let seed = match part {
+            Part::One => parse_seed_individual,
+            Part::Two => parse_seed_range,
         };
-        let (s, seeds) = preceded(tag("seeds: "), parser)(s).map_err(|e| anyhow!("{e}"))?;
+        let (s, seeds) = parse_seeds(s, seed).map_err(|e| anyhow!("{e}"))?;
         let almanac = Self::from_str(s)?;
         Ok((almanac, seeds))
     }
@@ -74,16 +84,76 @@ impl Almanac {
     }
 
     pub fn best_location(&self, seeds: &[Range<i128>]) -> i128 {
-        all::<Resource>()
-            .filter(|r| *r != Resource::Seed)
-            .fold(seeds.to_vec(), |ranges, resource| {
-                propagate(&ranges, self.mappings(resource))
-            })
+        let (_, locations) = self
+            .trace(seeds)
+            .pop()
+            .expect("Trace to end at the locations");
+        locations
             .iter()
             .map(|r| r.start)
             .min()
             .expect("Seeds not to be empty")
     }
+
+    /// Ranges of each resource, which the `seeds` map to, from the seeds themselves up to their
+    /// locations
+    pub fn trace(&self, seeds: &[Range<i128>]) -> Vec<(Resource, Vec<Range<i128>>)> {
+        all::<Resource>().filter(|r| *r != Resource::Seed).fold(
+            vec![(Resource::Seed, seeds.to_vec())],
+            |mut trace, resource| {
+                let (_, ranges) = trace.last().expect("Trace to start with the seeds");
+                let next = propagate(ranges, self.mappings(resource));
+                trace.push((resource, next));
+                trace
+            },
+        )
+    }
+
+    /// Value of each resource, which a single `seed` maps to, from the seed itself up to its
+    /// location
+    #[allow(clippy::single_range_in_vec_init)]
+    pub fn map_seed_through(&self, seed: i128) -> Vec<(Resource, i128)> {
+        self.trace(&[seed..seed + 1])
+            .into_iter()
+            .map(|(resource, ranges)| {
+                let value = ranges
+                    .first()
+                    .expect("A seed to map to a single value")
+                    .start;
+                (resource, value)
+            })
+            .collect()
+    }
+
+    /// Seeds ending up at `location`: usually one, but none if no mapping leads there and several
+    /// if different values get mapped onto the same one
+    #[allow(clippy::single_range_in_vec_init)]
+    pub fn seed_for_location(&self, location: i128) -> Vec<i128> {
+        let mut seeds = reverse_all::<Resource>()
+            .filter(|r| *r != Resource::Seed)
+            .fold(vec![location..location + 1], |ranges, resource| {
+                reverse_propagate(&ranges, self.mappings(resource))
+            })
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        seeds.sort();
+        seeds.dedup();
+        seeds
+    }
+
+    /// Same as [`Almanac::best_location`], but trying each location from 0 upwards, until one
+    /// leads back to any of the `seeds`
+    pub fn scan_best_location(&self, seeds: &[Range<i128>]) -> i128 {
+        assert!(!seeds.is_empty(), "Seeds not to be empty");
+        (0..)
+            .find(|location| {
+                self.seed_for_location(*location)
+                    .iter()
+                    .any(|seed| seeds.iter().any(|r| r.contains(seed)))
+            })
+            .expect("Some location to lead back to the seeds")
+    }
 }
 
 pub(crate) fn propagate_once(
@@ -142,6 +212,60 @@ pub(crate) fn propagate(rs: &[Range<i128>], ts: &[Mapping]) -> Vec<Range<i128>>
         .collect()
 }
 
+/// Which values `ts` map into the ranges `rs`, i.e. [`propagate`] backwards
+pub(crate) fn reverse_propagate(rs: &[Range<i128>], ts: &[Mapping]) -> Vec<Range<i128>> {
+    let mapped = rs.iter().flat_map(|r| {
+        ts.iter().filter_map(move |t| {
+            let start = r.start.max(t.range.start + t.offset);
+            let end = r.end.min(t.range.end + t.offset);
+            (start < end).then(|| start - t.offset..end - t.offset)
+        })
+    });
+    // Values outside of all mappings are taken over as they are
+    let kept = ts.iter().fold(rs.to_vec(), |ranges, t| {
+        ranges
+            .into_iter()
+            .flat_map(|r| {
+                [
+                    r.start..r.end.min(t.range.start),
+                    r.start.max(t.range.end)..r.end,
+                ]
+                .into_iter()
+                .filter(|r| !r.is_empty())
+            })
+            .collect()
+    });
+    mapped.chain(kept).collect()
+}
+
+pub fn solve(part: Part, input: &str) -> Result<i128> {
+    let (almanac, seeds) = Almanac::parse(part, input)?;
+    Ok(almanac.best_location(&seeds))
+}
+
+/// Day 5: If You Give A Seed A Fertilizer
+#[derive(Debug, Clone, Copy)]
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    fn name(&self) -> &'static str {
+        "fifth"
+    }
+
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::One, input).map(|x| x.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::Two, input).map(|x| x.to_string())
+    }
+
+    /// The seeds and each map are separated by blank lines
+    fn structure(&self) -> Structure {
+        Structure::Blocks
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -169,6 +293,81 @@ mod tests {
         assert_eq!(location, almanac.best_location(&[seed]));
     }
 
+    #[rstest]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn trace_sample() {
+        let input = include_str!("../../sample/fifth.txt");
+        let (almanac, _) = Almanac::parse(Part::One, input).unwrap();
+        let trace = almanac.trace(&[79..80]);
+        assert_eq!(
+            trace
+                .iter()
+                .map(|(resource, _)| *resource)
+                .collect::<Vec<_>>(),
+            all::<Resource>().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            trace
+                .into_iter()
+                .map(|(_, ranges)| ranges)
+                .collect::<Vec<_>>(),
+            [79, 81, 81, 81, 74, 78, 78, 82].map(|x| vec![x..x + 1])
+        );
+    }
+
+    #[rstest]
+    #[case(79, 82)]
+    #[case(14, 43)]
+    #[case(55, 86)]
+    #[case(13, 35)]
+    fn map_seed_through_sample(#[case] seed: i128, #[case] location: i128) {
+        let input = include_str!("../../sample/fifth.txt");
+        let (almanac, _) = Almanac::parse(Part::One, input).unwrap();
+        let values = almanac.map_seed_through(seed);
+        assert_eq!(values.first(), Some(&(Resource::Seed, seed)));
+        assert_eq!(values.last(), Some(&(Resource::Location, location)));
+        assert_eq!(values.len(), all::<Resource>().count());
+    }
+
+    #[rstest]
+    #[case(82, vec![79])]
+    #[case(43, vec![14])]
+    #[case(86, vec![55])]
+    #[case(35, vec![13])]
+    #[case(46, vec![82])]
+    #[case(0, vec![70])]
+    fn seed_for_location_sample(#[case] location: i128, #[case] seeds: Vec<i128>) {
+        let input = include_str!("../../sample/fifth.txt");
+        let (almanac, _) = Almanac::parse(Part::One, input).unwrap();
+        assert_eq!(seeds, almanac.seed_for_location(location));
+    }
+
+    #[rstest]
+    #[case(Part::One, 35)]
+    #[case(Part::Two, 46)]
+    fn scan_like_best_location(#[case] part: Part, #[case] location: i128) {
+        let input = include_str!("../../sample/fifth.txt");
+        let (almanac, seeds) = Almanac::parse(part, input).unwrap();
+        assert_eq!(location, almanac.scan_best_location(&seeds));
+        assert_eq!(location, almanac.best_location(&seeds));
+    }
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn reverse_propagate_inverts_propagate() {
+        let ts = [Mapping::new(98..100, -48), Mapping::new(50..98, 2)];
+        let mut x = reverse_propagate(&[45..55, 99..101], &ts);
+        x.sort_by_key(|r| r.start);
+        assert_eq!(x, vec![45..50, 50..53, 97..98, 98..100, 100..101]);
+        for value in x.into_iter().flatten() {
+            let y = propagate(&[value..value + 1], &ts);
+            assert!(
+                [45..55, 99..101].iter().any(|r| r.contains(&y[0].start)),
+                "{value} -> {y:?}"
+            );
+        }
+    }
+
     #[test]
     fn sample_b_manual() {
         let x = vec![55..68, 79..93];
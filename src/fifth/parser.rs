@@ -1,9 +1,10 @@
 use super::{Almanac, Mapping, Resource};
+use crate::parse::common::{integer, labelled_list};
 
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{anychar, i128, line_ending, space1},
+    character::complete::{anychar, line_ending, multispace0, space1},
     combinator::map,
     multi::{many_till, separated_list1},
     sequence::{preceded, separated_pair, terminated, tuple},
@@ -11,23 +12,36 @@ use nom::{
 };
 use std::ops::Range;
 
-pub(crate) fn parse_seeds_individual(s: &str) -> IResult<&str, Vec<Range<i128>>> {
-    separated_list1(space1, map(i128, |x| x..(x + 1)))(s)
+pub(crate) fn parse_seed_individual(s: &str) -> IResult<&str, Range<i128>> {
+    map(integer, |x: i128| x..(x + 1))(s)
 }
 
-pub(crate) fn parse_seeds_ranges(s: &str) -> IResult<&str, Vec<Range<i128>>> {
-    separated_list1(
-        space1,
-        map(separated_pair(i128, space1, i128), |(a, b)| a..(a + b)),
+pub(crate) fn parse_seed_range(s: &str) -> IResult<&str, Range<i128>> {
+    map(
+        separated_pair(integer, space1, integer),
+        |(a, b): (i128, i128)| a..(a + b),
     )(s)
 }
 
-fn parse_mapping(s: &str) -> IResult<&str, Mapping> {
-    tuple((terminated(i128, space1), terminated(i128, space1), i128))
-        .map(|(dest, src, len)| Mapping::new(src..(src + len), dest - src))
+pub(crate) fn parse_seeds(
+    s: &str,
+    seed: fn(&str) -> IResult<&str, Range<i128>>,
+) -> IResult<&str, Vec<Range<i128>>> {
+    terminated(labelled_list(tag("seeds"), seed), multispace0)
+        .map(|(_, seeds)| seeds)
         .parse(s)
 }
 
+fn parse_mapping(s: &str) -> IResult<&str, Mapping> {
+    tuple((
+        integer,
+        preceded(space1, integer),
+        preceded(space1, integer),
+    ))
+    .map(|(dest, src, len): (i128, i128, i128)| Mapping::new(src..(src + len), dest - src))
+    .parse(s)
+}
+
 fn parse_header(s: &str) -> IResult<&str, Resource> {
     preceded(
         many_till(anychar, tag("-to-")),
@@ -0,0 +1,148 @@
+//! The corpus of small inputs in `fixtures/`, covering edge cases the samples don't, with the
+//! answers of the solvers in `fixtures/answers.txt`. Most of them are written by hand, the ones of
+//! [`GENERATED`] are drawn from [`crate::rng::stream`]s seeded with [`SEED`] by `aoc23 fixtures`
+
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context};
+use enum_iterator::all;
+use rand::rngs::StdRng;
+
+use crate::{
+    generate::{bricks, grid, hailstones},
+    normalize_input,
+    rng::stream,
+    solvers, Day, Part,
+};
+
+/// Directory of the fixtures, relative to the crate
+pub const FIXTURES_DIR: &str = "fixtures";
+
+/// File in [`FIXTURES_DIR`] with one line per fixture: its day, file & the answers of both parts
+pub const ANSWERS_FILE: &str = "answers.txt";
+
+/// Seed all generated fixtures get drawn with
+pub const SEED: u64 = 2023;
+
+/// Stands for a part failing to solve in [`ANSWERS_FILE`], e.g. for gardens which can't be
+/// extrapolated
+pub const FAILS: &str = "-";
+
+const HEADER: &str = "\
+# Small inputs covering edge cases the samples do not, e.g. single-row grids, empty regions or
+# duplicate hands. The \"generated\" ones are random grids, bricks or hailstones. Regenerate them
+# and these answers with `aoc23 fixtures`. A `-` instead of an answer means solving that part has
+# to fail, e.g. for gardens which can't be extrapolated
+# day file part-one part-two
+";
+
+/// A fixture drawn at random, as `<day>-<name>.txt`, e.g. `fourteenth-generated-1.txt`
+#[derive(Debug, Clone, Copy)]
+pub struct Generated {
+    pub day: Day,
+    pub name: &'static str,
+    draw: fn(&mut StdRng) -> String,
+}
+
+impl Generated {
+    pub fn file(&self) -> String {
+        format!("{}-{}.txt", self.day.name(), self.name)
+    }
+
+    /// Draws the fixture from a stream of its own, so that adding one leaves the others as they are
+    pub fn draw(&self) -> String {
+        (self.draw)(&mut stream(&format!("fixture {}", self.file())))
+    }
+}
+
+pub const GENERATED: [Generated; 8] = [
+    Generated {
+        day: Day::Fourteenth,
+        name: "generated-1",
+        draw: |rng| grid(rng, &['.', '.', '.', 'O', 'O', '#'], 6, 6),
+    },
+    Generated {
+        day: Day::Fourteenth,
+        name: "generated-2",
+        draw: |rng| grid(rng, &['.', '.', '.', 'O', 'O', '#'], 6, 6),
+    },
+    Generated {
+        day: Day::Sixteenth,
+        name: "generated-1",
+        draw: |rng| grid(rng, &MIRRORS, 6, 6),
+    },
+    Generated {
+        day: Day::Sixteenth,
+        name: "generated-2",
+        draw: |rng| grid(rng, &MIRRORS, 6, 6),
+    },
+    Generated {
+        day: Day::Seventeenth,
+        name: "generated-1",
+        draw: |rng| grid(rng, &['1', '2', '3', '4', '5', '6', '7', '8', '9'], 9, 7),
+    },
+    // Mostly cheap blocks, so that the cheapest path winds around the few expensive ones
+    Generated {
+        day: Day::Seventeenth,
+        name: "generated-2",
+        draw: |rng| grid(rng, &['1', '1', '1', '2', '2', '3', '4', '5', '9'], 12, 5),
+    },
+    Generated {
+        day: Day::TwentySecond,
+        name: "generated-1",
+        draw: |rng| bricks(rng, 10),
+    },
+    Generated {
+        day: Day::TwentyFourth,
+        name: "generated-1",
+        draw: |rng| hailstones(rng, 5),
+    },
+];
+
+/// Mostly empty space, as in the real inputs
+const MIRRORS: [char; 12] = ['.', '.', '.', '.', '.', '.', '.', '.', '/', '\\', '|', '-'];
+
+/// Draws the [`GENERATED`] fixtures into `dir`, then solves all fixtures in there and writes their
+/// answers into its [`ANSWERS_FILE`]. The seed of the process has to be [`SEED`] for the generated
+/// fixtures to come out the same again
+pub fn write(dir: &Path) -> anyhow::Result<PathBuf> {
+    for fixture in GENERATED {
+        let path = dir.join(fixture.file());
+        std::fs::write(&path, fixture.draw()).with_context(|| format!("Writing {path:?}"))?;
+    }
+
+    let mut fixtures = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let file = entry?.file_name().to_string_lossy().into_owned();
+        if !file.ends_with(".txt") || file == ANSWERS_FILE {
+            continue;
+        }
+        let day = file.split('-').next().unwrap_or_default().parse::<Day>()?;
+        fixtures.push((day, file));
+    }
+    fixtures.sort();
+
+    let solvers = solvers();
+    let mut answers = HEADER.to_owned();
+    for (day, file) in fixtures {
+        let solver = solvers
+            .get(&day)
+            .ok_or(anyhow!("Day {day} is not part of the library, for {file}"))?;
+        let input = normalize_input(&std::fs::read_to_string(dir.join(&file))?);
+        write!(answers, "{day} {file}")?;
+        for part in all::<Part>() {
+            let answer = solver
+                .solve(part, &input)
+                .unwrap_or_else(|_| FAILS.to_owned());
+            write!(answers, " {answer}")?;
+        }
+        writeln!(answers)?;
+    }
+
+    let path = dir.join(ANSWERS_FILE);
+    std::fs::write(&path, answers).with_context(|| format!("Writing {path:?}"))?;
+    Ok(path)
+}
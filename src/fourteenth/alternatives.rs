@@ -0,0 +1,133 @@
+//! Other strategies to tilt a [`Platform`], which lost against the `HashMap` based one and the
+//! [`BitPlatform`], but are kept around to compare against in the benchmarks
+//!
+//! [`BitPlatform`]: super::bits::BitPlatform
+
+use std::collections::HashSet;
+
+use super::{Platform, Rock, EAST, NORTH, SOUTH, WEST};
+use crate::Coord;
+
+/// Row-major `Vec` of all cells, in which round rocks roll to the next free slot of each line
+#[derive(Debug, Clone)]
+pub struct DensePlatform {
+    cells: Vec<Rock>,
+    nrows: i32,
+    ncols: i32,
+}
+
+impl From<&Platform> for DensePlatform {
+    fn from(platform: &Platform) -> Self {
+        let cells = (0..platform.nrows())
+            .flat_map(|y| (0..platform.ncols()).map(move |x| Coord::new(x, y)))
+            .map(|c| platform.get(c))
+            .collect();
+        Self {
+            cells,
+            nrows: platform.nrows(),
+            ncols: platform.ncols(),
+        }
+    }
+}
+
+impl DensePlatform {
+    /// Index of the `inner`th cell of line `outer`, counted from the side the rocks roll towards
+    fn index(&self, dir: Coord, outer: i32, inner: i32) -> usize {
+        let (x, y) = if dir == NORTH {
+            (outer, inner)
+        } else if dir == SOUTH {
+            (outer, self.nrows - 1 - inner)
+        } else if dir == WEST {
+            (inner, outer)
+        } else if dir == EAST {
+            (self.ncols - 1 - inner, outer)
+        } else {
+            panic!("Only N,S,W or E directions supported")
+        };
+        (y * self.ncols + x) as usize
+    }
+
+    pub fn tilt(&mut self, dir: Coord) {
+        let (outer, inner) = if dir == NORTH || dir == SOUTH {
+            (self.ncols, self.nrows)
+        } else {
+            (self.nrows, self.ncols)
+        };
+        for o in 0..outer {
+            let mut free = 0;
+            for i in 0..inner {
+                let idx = self.index(dir, o, i);
+                match self.cells[idx] {
+                    Rock::None => {}
+                    Rock::Square => free = i + 1,
+                    Rock::Round => {
+                        self.cells[idx] = Rock::None;
+                        let target = self.index(dir, o, free);
+                        self.cells[target] = Rock::Round;
+                        free += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn total_north_load(&self) -> i32 {
+        self.round_rocks()
+            .into_iter()
+            .map(|coord| self.nrows - coord.y)
+            .sum()
+    }
+
+    pub fn round_rocks(&self) -> HashSet<Coord> {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, rock)| rock == &&Rock::Round)
+            .map(|(i, _)| Coord::new(i as i32 % self.ncols, i as i32 / self.ncols))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fourteenth::{bits::BitPlatform, CYCLE};
+    use rstest::rstest;
+    use std::str::FromStr;
+
+    #[rstest]
+    #[case(NORTH)]
+    #[case(WEST)]
+    #[case(SOUTH)]
+    #[case(EAST)]
+    fn all_strategies_tilt_alike(#[case] dir: Coord) {
+        let mut platform =
+            Platform::from_str(include_str!("../../sample/fourteenth.txt")).expect("parsing");
+        let mut dense = DensePlatform::from(&platform);
+        let mut bits = BitPlatform::try_from(&platform).expect("bits");
+        for _ in 0..3 {
+            platform.tilt(dir);
+            dense.tilt(dir);
+            bits.tilt(dir);
+            assert_eq!(platform.round_rocks(), dense.round_rocks());
+            assert_eq!(platform.round_rocks(), bits.round_rocks());
+        }
+    }
+
+    #[rstest]
+    fn all_strategies_cycle_alike() {
+        let mut platform =
+            Platform::from_str(include_str!("../../sample/fourteenth.txt")).expect("parsing");
+        let mut dense = DensePlatform::from(&platform);
+        let mut bits = BitPlatform::try_from(&platform).expect("bits");
+        for _ in 0..3 {
+            for dir in CYCLE {
+                platform.tilt(dir);
+                dense.tilt(dir);
+                bits.tilt(dir);
+            }
+            assert_eq!(platform.total_north_load(), dense.total_north_load());
+            assert_eq!(platform.total_north_load(), bits.total_north_load());
+        }
+    }
+}
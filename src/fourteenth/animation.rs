@@ -1,3 +1,5 @@
+use std::f32::consts::FRAC_PI_2;
+
 use bevy::{
     prelude::*,
     sprite::{Anchor, MaterialMesh2dBundle},
@@ -5,11 +7,19 @@ use bevy::{
 use bevy_rapier2d::prelude::*;
 use enum_iterator::{next_cycle, Sequence};
 use itertools::Itertools;
-use lazy_static::lazy_static;
 
-use crate::{in_states, lerp, mouse, rect, Coord, Scroll};
+use crate::{
+    capture::CapturePlugin,
+    in_states, lerp,
+    picking::{CellInfo, PickingPlugin},
+    rect,
+    reset::ResetPlugin,
+    summary::AnimationFinished,
+    widgets::{BarChart, WidgetsPlugin},
+    AocCameraPlugin, AocControlPlugin, Coord, Presentation, Running,
+};
 
-use super::{Platform, Rock};
+use super::{Platform, Rock, EAST, NORTH, SOUTH, WEST};
 
 const SIZE: f32 = 100.;
 const GAP: f32 = 0.01 * SIZE;
@@ -17,54 +27,67 @@ const SETTLED_THRESHOLD: f32 = 0.005 * SIZE;
 const STIFFNESS: f32 = 5000.;
 const DAMPING: f32 = 5.;
 const FONT_SIZE: f32 = 40.;
+/// Length of the bars showing the largest load on a wall
+const BAR_LENGTH: f32 = 3. * SIZE;
 
-lazy_static! {
-    static ref STYLE: TextStyle = TextStyle {
-        font_size: FONT_SIZE,
-        color: Color::WHITE,
-        ..default()
-    };
+pub fn run(platform: Platform, presentation: Presentation) {
+    let plugins = DefaultPlugins.build().add(presentation);
+    app(platform, plugins).add_plugins(CapturePlugin).run()
 }
 
-pub fn run(platform: Platform, max_load: f32) {
-    App::new()
-        .add_plugins(DefaultPlugins)
-        .add_plugins(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(100.))
-        // .add_plugins(RapierDebugRenderPlugin::default())
-        .insert_resource(platform)
-        .insert_resource(TotalLoad::default())
-        .insert_resource(MaxLoad(max_load))
-        .add_state::<Tilt>()
-        .add_state::<Motion>()
-        .add_state::<Simulation>()
-        .add_systems(Startup, setup)
-        .add_systems(
-            Update,
-            (
-                update,
-                mouse,
-                stress_test_n,
-                stress_test_s,
-                stress_test_w,
-                stress_test_e,
-                track_ball_columns,
-                update_total,
-                detect_pause_play,
-            ),
-        )
-        .add_systems(OnEnter(Simulation::Paused), disable_gravity)
-        .add_systems(OnEnter(Simulation::Playing), enable_gravity)
-        .add_systems(
-            Update,
-            (
-                detect_settlement.run_if(in_state(Motion::Moving)),
-                detect_movement.run_if(in_state(Motion::Settled)),
-                stabilize_on_rows.run_if(in_states(&[Tilt::East, Tilt::West])),
-                stabilize_on_colums.run_if(in_states(&[Tilt::North, Tilt::South])),
-            ),
-        )
-        .add_systems(OnExit(Motion::Moving), change_gravity)
-        .run()
+pub fn app(platform: Platform, plugins: impl PluginGroup) -> App {
+    let center = Vec3::new(
+        platform.ncols() as f32 * SIZE / 2.,
+        platform.nrows() as f32 * SIZE / 2.,
+        0.,
+    );
+    let mut app = App::new();
+    app.add_plugins((
+        plugins,
+        AocCameraPlugin::new(1.).at(center),
+        AocControlPlugin,
+        WidgetsPlugin,
+    ))
+    .add_plugins(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(100.))
+    .add_plugins(PickingPlugin::<Platform>::default())
+    // .add_plugins(RapierDebugRenderPlugin::default())
+    .insert_resource(platform)
+    .insert_resource(TotalLoad::default())
+    .insert_resource(Tilts::default())
+    .add_state::<Tilt>()
+    .add_state::<Motion>()
+    .add_state::<Simulation>()
+    .add_plugins((
+        ResetPlugin::<Platform>::default(),
+        ResetPlugin::<TotalLoad>::default(),
+        ResetPlugin::<Tilts>::default(),
+    ))
+    .add_systems(Startup, setup)
+    .add_systems(
+        Update,
+        (
+            stress_test,
+            track_ball_columns,
+            update_total,
+            detect_pause_play,
+        ),
+    )
+    .add_systems(OnEnter(Simulation::Paused), disable_gravity)
+    .add_systems(OnEnter(Simulation::Playing), enable_gravity)
+    .add_systems(
+        Update,
+        (
+            detect_settlement.run_if(in_state(Motion::Moving)),
+            detect_movement.run_if(in_state(Motion::Settled)),
+            stabilize_on_rows.run_if(in_states(&[Tilt::East, Tilt::West])),
+            stabilize_on_colums.run_if(in_states(&[Tilt::North, Tilt::South])),
+        ),
+    )
+    .add_systems(
+        OnExit(Motion::Moving),
+        (change_gravity, sync_platform, count_tilts),
+    );
+    app
 }
 
 #[derive(Debug, Component)]
@@ -74,14 +97,9 @@ struct Support;
 #[derive(Debug, Component)]
 struct Total;
 
-#[derive(Debug, Component, PartialEq, Eq)]
-struct Index((i32, i32));
-
-impl From<Vec3> for Index {
-    fn from(v: Vec3) -> Self {
-        Self(((v.x / SIZE).round() as i32, (v.y / SIZE).round() as i32))
-    }
-}
+/// Bar chart of the loads on the wall in this direction
+#[derive(Debug, Component)]
+struct Wall(Coord);
 
 #[derive(Default, Debug, States, Hash, PartialEq, Eq, Clone)]
 enum Motion {
@@ -106,11 +124,12 @@ enum Tilt {
     East,
 }
 
-#[derive(Debug, Default, Resource)]
+#[derive(Debug, Default, Clone, Resource)]
 struct TotalLoad(i32);
 
-#[derive(Debug, Default, Resource)]
-struct MaxLoad(f32);
+/// How many times the balls came to rest after the platform tilted
+#[derive(Debug, Default, Clone, Resource)]
+struct Tilts(usize);
 
 impl From<&Tilt> for Vec2 {
     fn from(d: &Tilt) -> Self {
@@ -129,18 +148,8 @@ fn setup(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
-    cmd.spawn(Camera2dBundle {
-        transform: Transform::from_xyz(
-            platform.ncols as f32 * SIZE / 2.,
-            platform.nrows as f32 * SIZE / 2.,
-            0.,
-        ),
-        ..default()
-    })
-    .insert(Scroll(1.));
-
-    for (x, y) in (-1..=platform.ncols).cartesian_product(-1..=platform.nrows) {
-        match platform.get(Coord::new(x, platform.nrows - 1 - y)) {
+    for (x, y) in (-1..=platform.ncols()).cartesian_product(-1..=platform.nrows()) {
+        match platform.get(Coord::new(x, platform.nrows() - 1 - y)) {
             Rock::None => continue,
             Rock::Round => {
                 let radius = (SIZE - GAP) / 2.;
@@ -184,54 +193,39 @@ fn setup(
                     Color::DARK_GRAY,
                 ))
                 .insert(Collider::cuboid(SIZE / 2., SIZE / 2.))
-                .insert(Index((x, y)))
                 .insert(Support);
             }
         }
     }
 
-    // North support
-    for i in 0..platform.ncols {
-        let position = Vec3::new(i as f32 * SIZE, platform.nrows as f32 * SIZE, 5.);
-        cmd.spawn(Text2dBundle {
-            text: Text::from_section("-", STYLE.clone()).with_alignment(TextAlignment::Center),
-            transform: Transform::from_translation(position),
-            ..default()
-        })
-        .insert(Index::from(position));
-    }
-
-    // South support
-    for i in 0..platform.ncols {
-        let position = Vec3::new(i as f32 * SIZE, -1. * SIZE, 5.);
-        cmd.spawn(Text2dBundle {
-            text: Text::from_section("-", STYLE.clone()).with_alignment(TextAlignment::Center),
-            transform: Transform::from_translation(position),
-            ..default()
-        })
-        .insert(Index::from(position));
-    }
-
-    // West support
-    for i in 0..platform.nrows {
-        let position = Vec3::new(-1. * SIZE, i as f32 * SIZE, 5.);
-        cmd.spawn(Text2dBundle {
-            text: Text::from_section("-", STYLE.clone()).with_alignment(TextAlignment::Center),
-            transform: Transform::from_translation(position),
-            ..default()
-        })
-        .insert(Index::from(position));
-    }
-
-    // East support
-    for i in 0..platform.nrows {
-        let position = Vec3::new(platform.ncols as f32 * SIZE, i as f32 * SIZE, 5.);
-        cmd.spawn(Text2dBundle {
-            text: Text::from_section("-", STYLE.clone()).with_alignment(TextAlignment::Center),
-            transform: Transform::from_translation(position),
-            ..default()
-        })
-        .insert(Index::from(position));
+    // Bars grow away from the platform, one per column (north & south) or row (west & east)
+    let (w, h) = (
+        platform.ncols() as f32 * SIZE,
+        platform.nrows() as f32 * SIZE,
+    );
+    let flip = Vec3::new(1., -1., 1.);
+    let walls = [
+        (NORTH, Transform::from_xyz(0., h - SIZE / 2., 5.)),
+        (
+            SOUTH,
+            Transform::from_xyz(0., -SIZE / 2., 5.).with_scale(flip),
+        ),
+        (
+            WEST,
+            Transform::from_xyz(-SIZE / 2., h - SIZE, 5.)
+                .with_rotation(Quat::from_rotation_z(-FRAC_PI_2))
+                .with_scale(flip),
+        ),
+        (
+            EAST,
+            Transform::from_xyz(w - SIZE / 2., h - SIZE, 5.)
+                .with_rotation(Quat::from_rotation_z(-FRAC_PI_2)),
+        ),
+    ];
+    for (dir, tf) in walls {
+        let (mut chart, spatial) = BarChart::bundle(BAR_LENGTH, SIZE, tf);
+        chart.scale = Some(platform.max_possible_line_load(dir) as f32);
+        cmd.spawn((Wall(dir), chart, spatial));
     }
 
     cmd.spawn(Text2dBundle {
@@ -255,8 +249,8 @@ fn setup(
         ])
         .with_alignment(TextAlignment::Center),
         transform: Transform::from_xyz(
-            (platform.nrows - 1) as f32 * SIZE / 2.,
-            (platform.ncols + 2) as f32 * SIZE,
+            (platform.nrows() - 1) as f32 * SIZE / 2.,
+            h + BAR_LENGTH + SIZE,
             0.,
         ),
         text_anchor: Anchor::Center,
@@ -289,18 +283,66 @@ fn detect_movement(
     }
 }
 
-fn detect_pause_play(
-    keys: Res<Input<KeyCode>>,
-    state: Res<State<Simulation>>,
-    mut next: ResMut<NextState<Simulation>>,
+/// The simulation follows [`Running`], which the space bar toggles
+fn detect_pause_play(running: Res<Running>, mut next: ResMut<NextState<Simulation>>) {
+    if running.is_changed() {
+        next.set(match running.inner() {
+            true => Simulation::Playing,
+            false => Simulation::Paused,
+        });
+    }
+}
+
+/// Row & column of the platform cell at the world `position`
+fn cell(platform: &Platform, position: Vec2) -> Coord {
+    Coord::new(
+        (position.x / SIZE).round() as i32,
+        platform.nrows() - 1 - (position.y / SIZE).round() as i32,
+    )
+}
+
+/// Let the platform know where the balls came to rest, so that it explains the current cells
+fn sync_platform(balls: Query<&Transform, With<Ball>>, mut platform: ResMut<Platform>) {
+    let rocks = balls
+        .iter()
+        .map(|tf| cell(&platform, tf.translation.truncate()))
+        .collect::<Vec<_>>();
+    platform.set_round_rocks(rocks);
+}
+
+impl CellInfo for Platform {
+    fn pick(&self, position: Vec2) -> Option<Coord> {
+        let cell = cell(self, position);
+        self.rocks.contains(cell).then_some(cell)
+    }
+
+    fn explain(&self, cell: Coord) -> Vec<String> {
+        let rock = match self.get(cell) {
+            Rock::Round => format!("Round rock, load {}", self.nrows() - cell.y),
+            Rock::Square => String::from("Cube-shaped rock"),
+            Rock::None => String::from("Empty space"),
+        };
+        vec![
+            rock,
+            format!("Column load {}", self.column_north_load(cell.x)),
+        ]
+    }
+}
+
+/// Once the balls rest against the north wall for the first time, the load is part one's answer
+fn count_tilts(
+    current: Res<State<Tilt>>,
+    load: Res<TotalLoad>,
+    platform: Res<Platform>,
+    mut tilts: ResMut<Tilts>,
+    mut finished: EventWriter<AnimationFinished>,
 ) {
-    if keys.just_released(KeyCode::Space) {
-        if state.get() == &Simulation::Paused {
-            next.set(Simulation::Playing);
-        }
-        if state.get() == &Simulation::Playing {
-            next.set(Simulation::Paused);
-        }
+    tilts.0 += 1;
+    if tilts.0 == 1 && *current.get() == Tilt::North {
+        finished.send(
+            AnimationFinished::new(load.0, tilts.0)
+                .with("Round rocks", platform.round_rocks().len()),
+        );
     }
 }
 
@@ -320,7 +362,7 @@ fn change_gravity(
     let direction = next_cycle(current.get()).unwrap();
     next.set(direction);
     config.gravity = Vec2::from(&direction) * config.gravity.length();
-    println!("Gravity: {:?}", direction);
+    info!("Gravity: {:?}", direction);
 }
 
 fn stabilize_on_rows(mut balls: Query<(&Transform, &Velocity, &mut ExternalForce), With<Ball>>) {
@@ -351,101 +393,94 @@ fn track_ball_columns(
     }
 }
 
-fn stress_test_n(
+/// Loads on each wall of the balls, where they are right now
+fn stress_test(
     mut load: ResMut<TotalLoad>,
     platform: Res<Platform>,
-    max_load: Res<MaxLoad>,
     balls: Query<&Transform, With<Ball>>,
-    mut texts: Query<(&Index, &mut Text)>,
-    mut sprites: Query<(&Index, &mut Sprite)>,
+    mut charts: Query<(&Wall, &mut BarChart)>,
 ) {
-    load.0 = 0;
-    for (i, mut text) in texts.iter_mut().filter(|(i, _)| i.0 .1 == platform.nrows) {
-        let stress = balls
+    let mut live = platform.clone();
+    live.set_round_rocks(
+        balls
             .iter()
-            .map(|tf| Index::from(tf.translation))
-            .filter(|index| index.0 .0 == i.0 .0)
-            .map(|index| index.0 .1 + 1)
-            .sum::<i32>();
-
-        load.0 += stress;
-
-        text.sections[0].value = stress.to_string();
-        for (_, mut sprite) in sprites.iter_mut().filter(|(si, _)| *si == i) {
-            sprite.color = Color::hsl(lerp(180., 0., stress as f32 / max_load.0), 0.5, 0.4);
-        }
+            .map(|tf| cell(&platform, tf.translation.truncate())),
+    );
+    load.0 = live.total_north_load();
+    for (wall, mut chart) in charts.iter_mut() {
+        chart.values = live
+            .column_loads(wall.0)
+            .into_iter()
+            .map(|load| load as f32)
+            .collect();
     }
 }
 
-fn stress_test_s(
+/// Shows the total load, colored from cyan (none) to red (all rocks stacked against the wall)
+fn update_total(
+    load: Res<TotalLoad>,
     platform: Res<Platform>,
-    max_load: Res<MaxLoad>,
-    balls: Query<&Transform, With<Ball>>,
-    mut texts: Query<(&Index, &mut Text)>,
-    mut sprites: Query<(&Index, &mut Sprite)>,
+    mut totals: Query<&mut Text, With<Total>>,
 ) {
-    for (i, mut text) in texts.iter_mut().filter(|(i, _)| i.0 .1 == -1) {
-        let stress = balls
-            .iter()
-            .map(|tf| Index::from(tf.translation))
-            .filter(|index| index.0 .0 == i.0 .0)
-            .map(|index| platform.nrows - index.0 .1)
-            .sum::<i32>();
-        text.sections[0].value = stress.to_string();
-        for (_, mut sprite) in sprites.iter_mut().filter(|(si, _)| *si == i) {
-            sprite.color = Color::hsl(lerp(180., 0., stress as f32 / max_load.0), 0.5, 0.4);
-        }
-    }
+    let Ok(mut text) = totals.get_single_mut() else {
+        return;
+    };
+    let max = platform.max_possible_load(NORTH).max(1);
+    let heat = (load.0 as f32 / max as f32).clamp(0., 1.);
+    text.sections[1].value = load.0.to_string();
+    text.sections[1].style.color = Color::hsl(lerp(180., 0., heat), 0.5, 0.6);
 }
 
-fn stress_test_w(
-    platform: Res<Platform>,
-    max_load: Res<MaxLoad>,
-    balls: Query<&Transform, With<Ball>>,
-    mut texts: Query<(&Index, &mut Text)>,
-    mut sprites: Query<(&Index, &mut Sprite)>,
-) {
-    for (i, mut text) in texts.iter_mut().filter(|(i, _)| i.0 .0 == -1) {
-        let stress = balls
-            .iter()
-            .map(|tf| Index::from(tf.translation))
-            .filter(|index| index.0 .1 == i.0 .1)
-            .map(|index| platform.nrows - index.0 .0)
-            .sum::<i32>();
-        text.sections[0].value = stress.to_string();
-        for (_, mut sprite) in sprites.iter_mut().filter(|(si, _)| *si == i) {
-            sprite.color = Color::hsl(lerp(180., 0., stress as f32 / max_load.0), 0.5, 0.4);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HeadlessPlugins;
+    use std::str::FromStr;
+
+    #[test]
+    fn smoke() {
+        let input = include_str!("../../sample/fourteenth.txt");
+        let platform = Platform::from_str(input).expect("parsing");
+        let mut app = app(platform, HeadlessPlugins);
+        for _ in 0..3 {
+            app.update();
         }
     }
-}
 
-fn stress_test_e(
-    platform: Res<Platform>,
-    max_load: Res<MaxLoad>,
-    balls: Query<&Transform, With<Ball>>,
-    mut texts: Query<(&Index, &mut Text)>,
-    mut sprites: Query<(&Index, &mut Sprite)>,
-) {
-    for (i, mut text) in texts.iter_mut().filter(|(i, _)| i.0 .0 == platform.nrows) {
-        let stress = balls
-            .iter()
-            .map(|tf| Index::from(tf.translation))
-            .filter(|index| index.0 .1 == i.0 .1)
-            .map(|index| index.0 .0 + 1)
-            .sum::<i32>();
-        text.sections[0].value = stress.to_string();
-        for (_, mut sprite) in sprites.iter_mut().filter(|(si, _)| *si == i) {
-            sprite.color = Color::hsl(lerp(180., 0., stress as f32 / max_load.0), 0.5, 0.4);
-        }
+    #[test]
+    fn explains_picked_cells() {
+        let input = include_str!("../../sample/fourteenth.txt");
+        let platform = Platform::from_str(input).expect("parsing");
+
+        // The top left cell of the sample holds a round rock
+        let top_left = Vec2::new(0., (platform.nrows() - 1) as f32 * SIZE);
+        assert_eq!(platform.pick(top_left), Some(Coord::new(0, 0)));
+        assert_eq!(
+            platform.explain(Coord::new(0, 0)),
+            vec!["Round rock, load 10", "Column load 31"]
+        );
+        assert_eq!(platform.pick(Vec2::new(-SIZE, 0.)), None);
     }
-}
 
-fn update_total(load: Res<TotalLoad>, mut totals: Query<&mut Text, With<Total>>) {
-    totals.get_single_mut().unwrap().sections[1].value = load.0.to_string()
-}
+    #[test]
+    fn smoke_with_missing_and_duplicated_total() {
+        let input = include_str!("../../sample/fourteenth.txt");
+        let platform = Platform::from_str(input).expect("parsing");
+        let mut app = app(platform, HeadlessPlugins);
+        app.update();
+
+        let totals = app
+            .world
+            .query_filtered::<Entity, With<Total>>()
+            .iter(&app.world)
+            .collect::<Vec<_>>();
+        for id in totals {
+            app.world.despawn(id);
+        }
+        app.update();
 
-fn update(keys: Res<Input<KeyCode>>, mut exit: ResMut<Events<bevy::app::AppExit>>) {
-    if keys.just_pressed(KeyCode::Q) {
-        exit.send(bevy::app::AppExit);
+        app.world.spawn((Total, Text::default()));
+        app.world.spawn((Total, Text::default()));
+        app.update();
     }
 }
@@ -0,0 +1,174 @@
+//! Dense bit mask representation of a [`Platform`] for spinning it many times. The animation keeps
+//! using the `HashMap` based one, which knows about each rock
+
+use std::{collections::HashSet, iter::successors};
+
+use anyhow::anyhow;
+
+use super::{Platform, Rock, CYCLE, EAST, NORTH, SOUTH, WEST};
+use crate::{cycle, Coord};
+
+/// One bit mask per row for the round and for the square rocks, in which all rocks of a row
+/// (or between two rows) are moved at once until none of them can move any further
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitPlatform {
+    round: Vec<u128>,
+    square: Vec<u128>,
+    ncols: i32,
+}
+
+/// Fails for platforms wider than the bit masks
+impl TryFrom<&Platform> for BitPlatform {
+    type Error = anyhow::Error;
+
+    fn try_from(platform: &Platform) -> Result<Self, Self::Error> {
+        if platform.ncols() > u128::BITS as i32 {
+            return Err(anyhow!(
+                "Platform with {} columns is too wide for a bit mask",
+                platform.ncols()
+            ));
+        }
+        let mask = |y, kind| {
+            (0..platform.ncols())
+                .filter(|x| platform.get(Coord::new(*x, y)) == kind)
+                .fold(0u128, |mask, x| mask | 1 << x)
+        };
+        Ok(Self {
+            round: (0..platform.nrows())
+                .map(|y| mask(y, Rock::Round))
+                .collect(),
+            square: (0..platform.nrows())
+                .map(|y| mask(y, Rock::Square))
+                .collect(),
+            ncols: platform.ncols(),
+        })
+    }
+}
+
+impl BitPlatform {
+    fn full(&self) -> u128 {
+        u128::MAX >> (u128::BITS as i32 - self.ncols)
+    }
+
+    fn free(&self, y: usize) -> u128 {
+        !(self.round[y] | self.square[y]) & self.full()
+    }
+
+    pub fn tilt(&mut self, dir: Coord) {
+        if dir == NORTH || dir == SOUTH {
+            let n = self.round.len();
+            let mut moved = true;
+            while moved {
+                moved = false;
+                for y in 1..n {
+                    let (from, to) = if dir == NORTH {
+                        (y, y - 1)
+                    } else {
+                        (n - 1 - y, n - y)
+                    };
+                    let movers = self.round[from] & self.free(to);
+                    if movers != 0 {
+                        self.round[from] &= !movers;
+                        self.round[to] |= movers;
+                        moved = true;
+                    }
+                }
+            }
+        } else if dir == WEST || dir == EAST {
+            for y in 0..self.round.len() {
+                loop {
+                    let free = self.free(y);
+                    let movers = if dir == WEST {
+                        self.round[y] & free << 1
+                    } else {
+                        self.round[y] & free >> 1
+                    };
+                    if movers == 0 {
+                        break;
+                    }
+                    self.round[y] &= !movers;
+                    self.round[y] |= if dir == WEST {
+                        movers >> 1
+                    } else {
+                        movers << 1
+                    };
+                }
+            }
+        } else {
+            panic!("Only N,S,W or E directions supported")
+        }
+    }
+
+    /// Tilt the platform into all directions of a [`CYCLE`] once
+    pub fn spin_cycle(&mut self) {
+        for dir in CYCLE {
+            self.tilt(dir);
+        }
+    }
+
+    /// Spin `n` cycles, but only as many as it takes to end up in the same state, once the rocks
+    /// are found to repeat themselves
+    pub fn run_cycles(&mut self, n: usize) {
+        let states = successors(Some(self.clone()), |platform| {
+            let mut next = platform.clone();
+            next.spin_cycle();
+            Some(next)
+        });
+        // `states` never ends, and there are only finitely many of them, so there is a cycle
+        let (mu, lambda) = cycle(states).expect("Spinning never repeats itself");
+        let n = if n <= mu { n } else { mu + (n - mu) % lambda };
+        for _ in 0..n {
+            self.spin_cycle();
+        }
+    }
+
+    pub fn total_north_load(&self) -> i32 {
+        let nrows = self.round.len() as i32;
+        self.round
+            .iter()
+            .enumerate()
+            .map(|(y, mask)| mask.count_ones() as i32 * (nrows - y as i32))
+            .sum()
+    }
+
+    pub fn round_rocks(&self) -> HashSet<Coord> {
+        self.round
+            .iter()
+            .enumerate()
+            .flat_map(|(y, mask)| {
+                (0..self.ncols)
+                    .filter(move |x| mask & 1 << x != 0)
+                    .map(move |x| Coord::new(x, y as i32))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fourteenth::SPIN_CYCLES;
+    use rstest::rstest;
+    use std::str::FromStr;
+
+    #[rstest]
+    #[case(0)]
+    #[case(1)]
+    #[case(3)]
+    #[case(10)]
+    #[case(SPIN_CYCLES)]
+    fn run_cycles_like_platform(#[case] n: usize) {
+        let mut platform =
+            Platform::from_str(include_str!("../../sample/fourteenth.txt")).expect("parsing");
+        let mut bits = BitPlatform::try_from(&platform).expect("bits");
+        bits.run_cycles(n);
+        platform.spin(platform.equivalent_cycles(n as u64) as usize);
+        assert_eq!(bits.round_rocks(), platform.round_rocks());
+    }
+
+    #[rstest]
+    fn rejects_wide_platforms() {
+        let platform = Platform::from_str(&".".repeat(129)).expect("parsing");
+        assert!(BitPlatform::try_from(&platform).is_err());
+    }
+}
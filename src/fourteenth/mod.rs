@@ -1,17 +1,25 @@
+#[cfg(any(test, feature = "bench"))]
+pub mod alternatives;
+#[cfg(feature = "animations")]
 pub mod animation;
+pub mod bits;
 
 use anyhow::anyhow;
+#[cfg(feature = "animations")]
 use bevy::ecs::system::Resource;
 use itertools::Itertools;
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{HashMap, HashSet, VecDeque},
     fmt::Display,
     ops::Not,
     str::FromStr,
 };
 use termion::color::{Fg, Reset, Rgb, Yellow};
 
-use crate::Coord;
+use crate::{grid::Grid, render::Diagram, tui::Stepper, Coord, Part, Solver, Strategy};
+use bits::BitPlatform;
 
 pub const NORTH: Coord = Coord::new(0, -1);
 pub const SOUTH: Coord = Coord::new(0, 1);
@@ -19,23 +27,23 @@ pub const EAST: Coord = Coord::new(1, 0);
 pub const WEST: Coord = Coord::new(-1, 0);
 
 pub const CYCLE: [Coord; 4] = [NORTH, WEST, SOUTH, EAST];
+pub const SPIN_CYCLES: usize = 1_000_000_000;
 
-#[derive(Debug, Clone, Resource)]
+#[derive(Debug, Clone, Eq)]
+#[cfg_attr(feature = "animations", derive(Resource))]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct Platform {
-    rocks: HashMap<Coord, Rock>,
-    nrows: i32,
-    ncols: i32,
+    rocks: Grid<Rock>,
 }
 
 impl PartialEq for Platform {
     fn eq(&self, other: &Self) -> bool {
-        self.ncols == other.ncols
-            && self.nrows == other.nrows
-            && self.round_rocks() == other.round_rocks()
+        self.rocks.size() == other.rocks.size() && self.round_rocks() == other.round_rocks()
     }
 }
 
 #[derive(Default, Debug, PartialEq, Copy, Clone, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub enum Rock {
     #[default]
     None,
@@ -44,32 +52,41 @@ pub enum Rock {
 }
 
 impl Platform {
+    pub fn nrows(&self) -> i32 {
+        self.rocks.nrows()
+    }
+
+    pub fn ncols(&self) -> i32 {
+        self.rocks.ncols()
+    }
+
+    /// Rock at `c`, everything outside of the platform counts as a wall of square rocks
     pub(crate) fn get(&self, c: Coord) -> Rock {
-        if c.x < 0 || self.ncols <= c.x || c.y < 0 || self.nrows <= c.y {
+        if !self.rocks.contains(c) {
             return Rock::Square;
         }
-        self.rocks.get(&c).copied().unwrap_or_default()
+        self.rocks.get(c).copied().unwrap_or_default()
     }
 
     fn outer(&self, dir: Coord) -> i32 {
         if dir == NORTH || dir == SOUTH {
-            return self.ncols;
+            return self.ncols();
         }
         if dir == EAST || dir == WEST {
-            return self.nrows;
+            return self.nrows();
         }
         panic!("Only N,S,W or E directions supported")
     }
 
     fn inner_iter(&self, dir: Coord) -> Box<dyn Iterator<Item = i32>> {
         if dir == NORTH {
-            Box::new(-1..=self.nrows)
+            Box::new(-1..=self.nrows())
         } else if dir == SOUTH {
-            Box::new((-1..=self.nrows).rev())
+            Box::new((-1..=self.nrows()).rev())
         } else if dir == EAST {
-            Box::new((-1..=self.ncols).rev())
+            Box::new((-1..=self.ncols()).rev())
         } else if dir == WEST {
-            Box::new(-1..=self.ncols)
+            Box::new(-1..=self.ncols())
         } else {
             panic!("Only N,S,W or E directions supported")
         }
@@ -114,19 +131,159 @@ impl Platform {
         self.rocks.extend(rocks);
     }
 
+    /// Moves of the round rocks while tilting into `dir`, one cell per step, until they all came to
+    /// rest like after [`Platform::tilt`]
+    pub fn tilt_steps(&self, dir: Coord) -> impl Iterator<Item = Vec<(Coord, Coord)>> {
+        let mut platform = self.clone();
+        std::iter::from_fn(move || {
+            let moves = platform.roll(dir);
+            moves.is_empty().not().then_some(moves)
+        })
+    }
+
+    /// Move each round rock one cell into `dir`, if there is space, starting with the ones closest
+    /// to the wall, so that rows of rocks roll together
+    fn roll(&mut self, dir: Coord) -> Vec<(Coord, Coord)> {
+        let mut moves = Vec::new();
+        for rock in self
+            .round_rocks()
+            .into_iter()
+            .sorted_by_key(|rock| (-rock.dot(dir), rock.x, rock.y))
+        {
+            let target = rock + dir;
+            if self.get(target) != Rock::None {
+                continue;
+            }
+            self.rocks
+                .insert(rock, Rock::None)
+                .expect("Rock to be on the platform");
+            self.rocks
+                .insert(target, Rock::Round)
+                .expect("Free cell to be on the platform");
+            moves.push((rock, target));
+        }
+        moves
+    }
+
+    /// Tilt the platform `n` times into all directions of a [`CYCLE`]
+    pub fn spin(&mut self, n: usize) {
+        for _ in 0..n {
+            for dir in CYCLE {
+                self.tilt(dir);
+            }
+        }
+    }
+
+    /// Number of spin cycles, after which the platform is in the same state as after `n` cycles
+    pub fn equivalent_cycles(&self, n: u64) -> u64 {
+        // Key on the whole state, since different platforms can carry the same load
+        let mut seen = HashMap::new();
+        let mut platform = self.clone();
+        for i in 0..n {
+            if let Some(start) = seen.insert(platform.state(), i) {
+                return start + (n - start) % (i - start);
+            }
+            platform.spin(1);
+        }
+        n
+    }
+
+    /// Spin `n` cycles, skipping over the repetitions once the rocks are found in a state they
+    /// have been in before, and return the [`Platform::total_north_load`] afterwards
+    pub fn run_cycles(&mut self, n: u64) -> i32 {
+        let n = self.equivalent_cycles(n);
+        self.spin(n as usize);
+        self.total_north_load()
+    }
+
+    /// Positions of the round rocks in a stable order, the square ones never move
+    fn state(&self) -> Vec<(i32, i32)> {
+        self.round_rocks()
+            .into_iter()
+            .map(|coord| (coord.y, coord.x))
+            .sorted()
+            .collect()
+    }
+
     pub fn total_north_load(&self) -> i32 {
         self.rocks
             .iter()
             .filter(|(_, item)| item == &&Rock::Round)
-            .map(|(coord, _)| self.nrows - coord.y)
+            .map(|(coord, _)| self.nrows() - coord.y)
+            .sum()
+    }
+    /// Share of [`Platform::total_north_load`] caused by the round rocks in column `x`
+    pub fn column_north_load(&self, x: i32) -> i32 {
+        self.rocks
+            .iter()
+            .filter(|(coord, rock)| coord.x == x && rock == &&Rock::Round)
+            .map(|(coord, _)| self.nrows() - coord.y)
             .sum()
     }
+
+    /// Load on the wall in direction `dir` of each column (north & south) or row (west & east),
+    /// i.e. how far the round rocks in there are from the opposite wall
+    pub fn column_loads(&self, dir: Coord) -> Vec<i32> {
+        let lines = if dir == NORTH || dir == SOUTH {
+            self.ncols()
+        } else {
+            self.nrows()
+        };
+        let mut loads = vec![0; lines as usize];
+        for rock in self.round_rocks() {
+            let (line, load) = if dir == NORTH {
+                (rock.x, self.nrows() - rock.y)
+            } else if dir == SOUTH {
+                (rock.x, rock.y + 1)
+            } else if dir == WEST {
+                (rock.y, self.ncols() - rock.x)
+            } else if dir == EAST {
+                (rock.y, rock.x + 1)
+            } else {
+                panic!("Only N,S,W or E directions supported")
+            };
+            loads[line as usize] += load;
+        }
+        loads
+    }
+
+    /// Largest [`Platform::column_loads`] sum on the wall in direction `dir`, which the round
+    /// rocks could cause if they were all stacked against it with no square rocks in their way
+    pub fn max_possible_load(&self, dir: Coord) -> i32 {
+        self.stacked_load(dir, self.outer(dir))
+    }
+
+    /// Largest single value of [`Platform::column_loads`] in direction `dir`, i.e. as many round
+    /// rocks as fit stacked into one column (north & south) or row (west & east)
+    pub fn max_possible_line_load(&self, dir: Coord) -> i32 {
+        self.stacked_load(dir, 1)
+    }
+
+    /// Load of the round rocks filling the `width` cells closest to the wall in direction `dir`
+    /// first, then the ones next to them and so on
+    fn stacked_load(&self, dir: Coord, width: i32) -> i32 {
+        // How far the opposite wall is away
+        let depth = if dir == NORTH || dir == SOUTH {
+            self.nrows()
+        } else {
+            self.ncols()
+        };
+        let rocks = (self.round_rocks().len() as i32).min(width * depth);
+        (0..rocks).map(|i| depth - i / width).sum()
+    }
+
+    /// Move all round rocks to `coords`, e.g. where a simulation let them roll to
+    pub fn set_round_rocks(&mut self, coords: impl IntoIterator<Item = Coord>) {
+        self.rocks.retain(|_, rock| rock != &Rock::Round);
+        self.rocks
+            .extend(coords.into_iter().map(|coord| (coord, Rock::Round)));
+    }
+
     pub fn round_rocks(&self) -> HashSet<Coord> {
         self.rocks
             .iter()
             .filter(|(_, rock)| rock == &&Rock::Round)
             .map(|(coord, _)| coord)
-            .copied()
             .collect()
     }
 }
@@ -134,60 +291,80 @@ impl Platform {
 impl FromStr for Platform {
     type Err = anyhow::Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let s = s.trim();
-        let rocks = s
-            .lines()
-            .enumerate()
-            .flat_map(|(y, line)| {
-                line.trim().chars().enumerate().map(move |(x, c)| {
-                    Ok::<(Coord, Rock), anyhow::Error>((
-                        Coord::new(x as i32, y as i32),
-                        Rock::try_from(c)?,
-                    ))
-                })
-            })
-            .process_results(|iter| iter.collect::<HashMap<_, _>>())?;
-        if rocks.is_empty() {
-            return Err(anyhow!("Empty platforms not allowed"));
-        }
-        let ncols = rocks.keys().map(|i| i.x).max().unwrap_or_default() + 1;
-        let nrows = rocks.keys().map(|i| i.y).max().unwrap_or_default() + 1;
         Ok(Self {
-            rocks,
-            ncols,
-            nrows,
+            rocks: Grid::from_str(s.trim())?,
         })
     }
 }
 
 impl Display for Platform {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "╭")?;
-        for _ in 0..self.ncols + 2 {
-            write!(f, "─")?;
-        }
-        writeln!(f, "╮")?;
-        for y in -1..=self.nrows {
-            write!(f, "│")?;
-            for x in -1..=self.ncols {
-                let coord = Coord::new(x, y);
-                let rock = self.get(coord);
-                if rock == Rock::Square {
-                    write!(f, "{}", Fg(Rgb(160, 160, 160)))?;
-                } else if rock == Rock::Round {
-                    write!(f, "{}", Fg(Yellow))?;
-                }
-                write!(f, "{}", rock)?;
-                write!(f, "{}", Fg(Reset))?;
-            }
-            writeln!(f, "│")?;
+        let pretty = self.rocks.pretty(|coord, _| {
+            let rock = self.get(coord);
+            let color = match rock {
+                Rock::Square => Fg(Rgb(160, 160, 160)).to_string(),
+                Rock::Round => Fg(Yellow).to_string(),
+                Rock::None => String::new(),
+            };
+            format!("{color}{rock}{}", Fg(Reset))
+        });
+        write!(f, "{}", pretty.with_margin(1).bordered())
+    }
+}
+
+impl Diagram for Platform {
+    fn size(&self) -> Coord {
+        self.rocks.size()
+    }
+
+    fn color(&self, cell: Coord) -> Option<[u8; 3]> {
+        match self.get(cell) {
+            Rock::None => None,
+            Rock::Round => Some([255, 215, 0]),
+            Rock::Square => Some([160, 160, 160]),
         }
-        write!(f, "╰")?;
-        for _ in 0..self.ncols + 2 {
-            write!(f, "─")?;
+    }
+
+    fn symbol(&self, cell: Coord) -> char {
+        self.get(cell).to_string().chars().next().unwrap_or(' ')
+    }
+}
+
+/// Tilts a [`Platform`] into one direction after the other in the terminal, rolling the rocks one
+/// cell per step
+#[derive(Debug, Clone)]
+pub struct Tilting {
+    platform: Platform,
+    pending: VecDeque<Coord>,
+}
+
+impl Tilting {
+    pub fn new(platform: Platform, directions: impl IntoIterator<Item = Coord>) -> Self {
+        Self {
+            platform,
+            pending: directions.into_iter().collect(),
         }
-        write!(f, "╯")?;
-        Ok(())
+    }
+}
+
+impl Stepper for Tilting {
+    fn step(&mut self) -> bool {
+        while let Some(dir) = self.pending.front().copied() {
+            if !self.platform.roll(dir).is_empty() {
+                return true;
+            }
+            self.pending.pop_front();
+        }
+        false
+    }
+
+    fn frame(&self) -> String {
+        format!(
+            "{}\nNorth load: {}, tilts left: {}",
+            self.platform,
+            self.platform.total_north_load(),
+            self.pending.len()
+        )
     }
 }
 
@@ -216,3 +393,162 @@ impl Display for Rock {
         )
     }
 }
+
+pub fn solve(part: Part, input: &str) -> anyhow::Result<i32> {
+    let mut platform = Platform::from_str(input)?;
+    match part {
+        Part::One => {
+            platform.tilt(NORTH);
+            Ok(platform.total_north_load())
+        }
+        Part::Two => {
+            let mut bits = BitPlatform::try_from(&platform)?;
+            bits.run_cycles(SPIN_CYCLES);
+            Ok(bits.total_north_load())
+        }
+    }
+}
+
+/// Day 14: Parabolic Reflector Dish
+#[derive(Debug, Clone, Copy)]
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    fn name(&self) -> &'static str {
+        "fourteenth"
+    }
+
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::One, input).map(|x| x.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::Two, input).map(|x| x.to_string())
+    }
+
+    fn strategies(&self) -> Vec<Strategy> {
+        vec![Strategy {
+            name: "hashmap",
+            about: "Spin the round rocks as a set of coordinates instead of bitsets",
+            solve: |part, input| {
+                let mut platform = Platform::from_str(input)?;
+                let load = match part {
+                    Part::One => {
+                        platform.tilt(NORTH);
+                        platform.total_north_load()
+                    }
+                    Part::Two => platform.run_cycles(SPIN_CYCLES as u64),
+                };
+                Ok(load.to_string())
+            },
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0, 104)]
+    #[case(1, 87)]
+    #[case(3, 69)]
+    #[case(SPIN_CYCLES as u64, 64)]
+    fn run_cycles_sample(#[case] n: u64, #[case] load: i32) {
+        let mut platform =
+            Platform::from_str(include_str!("../../sample/fourteenth.txt")).expect("parsing");
+        assert_eq!(platform.run_cycles(n), load);
+    }
+
+    #[rstest]
+    #[case(NORTH, vec![31, 14, 14, 9, 7, 5, 4, 9, 0, 11])]
+    #[case(SOUTH, vec![13, 19, 19, 2, 4, 6, 7, 13, 0, 11])]
+    #[case(WEST, vec![10, 25, 0, 26, 12, 15, 13, 3, 0, 17])]
+    #[case(EAST, vec![1, 8, 0, 18, 10, 7, 20, 8, 0, 5])]
+    fn column_loads_sample(#[case] dir: Coord, #[case] expected: Vec<i32>) {
+        let platform =
+            Platform::from_str(include_str!("../../sample/fourteenth.txt")).expect("parsing");
+        assert_eq!(platform.column_loads(dir), expected);
+    }
+
+    #[rstest]
+    fn column_loads_add_up_to_total() {
+        let mut platform =
+            Platform::from_str(include_str!("../../sample/fourteenth.txt")).expect("parsing");
+        platform.tilt(NORTH);
+        assert_eq!(platform.column_loads(NORTH).iter().sum::<i32>(), 136);
+    }
+
+    #[rstest]
+    #[case(NORTH)]
+    #[case(SOUTH)]
+    #[case(WEST)]
+    #[case(EAST)]
+    fn max_possible_load_sample(#[case] dir: Coord) {
+        let mut platform =
+            Platform::from_str(include_str!("../../sample/fourteenth.txt")).expect("parsing");
+        // 18 round rocks: 10 against the wall, 8 in the line next to it
+        assert_eq!(platform.max_possible_load(dir), 10 * 10 + 8 * 9);
+        assert_eq!(platform.max_possible_line_load(dir), (1..=10).sum::<i32>());
+        platform.tilt(dir);
+        let loads = platform.column_loads(dir);
+        assert!(loads.iter().sum::<i32>() <= platform.max_possible_load(dir));
+        assert!(loads
+            .into_iter()
+            .all(|load| load <= platform.max_possible_line_load(dir)));
+    }
+
+    #[rstest]
+    #[case(NORTH)]
+    #[case(SOUTH)]
+    #[case(WEST)]
+    #[case(EAST)]
+    fn tilt_steps_end_like_tilt(#[case] dir: Coord) {
+        let platform =
+            Platform::from_str(include_str!("../../sample/fourteenth.txt")).expect("parsing");
+        let mut rolled = platform.clone();
+        for moves in platform.tilt_steps(dir) {
+            assert!(moves.iter().all(|(from, to)| *from + dir == *to));
+            rolled.set_round_rocks(rolled.round_rocks().into_iter().map(|rock| {
+                moves
+                    .iter()
+                    .find(|(from, _)| *from == rock)
+                    .map_or(rock, |(_, to)| *to)
+            }));
+        }
+        let mut tilted = platform.clone();
+        tilted.tilt(dir);
+        assert_eq!(rolled, tilted);
+    }
+
+    #[rstest]
+    fn tilting_rolls_one_cell_per_step() {
+        let platform = Platform::from_str("O\n.\n.\nO").expect("parsing");
+        let steps = platform.tilt_steps(NORTH).collect::<Vec<_>>();
+        assert_eq!(
+            steps,
+            vec![
+                vec![(Coord::new(0, 3), Coord::new(0, 2))],
+                vec![(Coord::new(0, 2), Coord::new(0, 1))],
+            ]
+        );
+        let mut tilting = Tilting::new(platform, [NORTH]);
+        assert!(tilting.step());
+        assert!(tilting.step());
+        assert!(!tilting.step());
+    }
+
+    #[rstest]
+    #[case(2)]
+    #[case(10)]
+    #[case(25)]
+    fn run_cycles_like_spin(#[case] n: u64) {
+        let mut platform =
+            Platform::from_str(include_str!("../../sample/fourteenth.txt")).expect("parsing");
+        let mut expected = platform.clone();
+        expected.spin(n as usize);
+        assert_eq!(platform.run_cycles(n), expected.total_north_load());
+        assert_eq!(platform, expected);
+    }
+}
@@ -0,0 +1,261 @@
+use std::collections::HashSet;
+
+use anyhow::anyhow;
+use nom::Finish;
+use serde::Serialize;
+
+use self::parser::cards;
+use crate::{anyhowing, export::Row, Part, Solver};
+
+mod parser;
+
+/// Characters filling an eighth more of a bar each, the last one a whole character
+const BARS: [char; 8] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scratchcard {
+    pub id: u32,
+    pub winners: Vec<u32>,
+    pub numbers: Vec<u32>,
+}
+
+impl Scratchcard {
+    /// How many of our numbers are winning ones
+    pub fn matches(&self) -> u32 {
+        let winners = self.winners.iter().collect::<HashSet<_>>();
+        self.numbers.iter().filter(|n| winners.contains(n)).count() as u32
+    }
+
+    /// One point for the first match, doubled by each further one. Fails for more than 64 matches,
+    /// whose points don't fit into a `u64`
+    pub fn points(&self) -> anyhow::Result<u64> {
+        match self.matches() {
+            0 => Ok(0),
+            n => 1_u64
+                .checked_shl(n - 1)
+                .ok_or(anyhow!("Card {} has too many points, {n} matches", self.id)),
+        }
+    }
+}
+
+/// All cards of the input, in the order of the pile
+pub fn scratchcards(input: &str) -> anyhow::Result<Vec<Scratchcard>> {
+    Ok(cards(input).finish().map_err(anyhowing)?.1)
+}
+
+/// How many of each card we end up with, its original included, if each one wins copies of as
+/// many cards below it as it has matches
+pub fn copies(cards: &[Scratchcard]) -> Vec<u64> {
+    let mut copies = vec![1; cards.len()];
+    for (i, card) in cards.iter().enumerate() {
+        let won = (i + 1)..(i + 1 + card.matches() as usize).min(cards.len());
+        let n = copies[i];
+        for copy in &mut copies[won] {
+            *copy += n;
+        }
+    }
+    copies
+}
+
+/// What one card scores, a row of the CSV export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CardStats {
+    pub card: u32,
+    pub matches: u32,
+    pub points: u64,
+    pub copies: u64,
+}
+
+impl Row for CardStats {
+    const HEADER: &'static [&'static str] = &["card", "matches", "points", "copies"];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.card.to_string(),
+            self.matches.to_string(),
+            self.points.to_string(),
+            self.copies.to_string(),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stats {
+    /// How many cards have as many matches as the index, up to the most any card has
+    pub histogram: Vec<usize>,
+    pub cards: Vec<CardStats>,
+}
+
+/// Matches, points & copies of each card and how the matches are distributed
+pub fn stats(cards: &[Scratchcard]) -> anyhow::Result<Stats> {
+    let cards = cards
+        .iter()
+        .zip(copies(cards))
+        .map(|(card, copies)| {
+            Ok(CardStats {
+                card: card.id,
+                matches: card.matches(),
+                points: card.points()?,
+                copies,
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let most = cards.iter().map(|card| card.matches).max().unwrap_or(0);
+    let mut histogram = vec![0; most as usize + 1];
+    for card in &cards {
+        histogram[card.matches as usize] += 1;
+    }
+    Ok(Stats { histogram, cards })
+}
+
+impl Stats {
+    /// Histogram of the matches, one line per number of matches with a bar `width` characters
+    /// long for the most frequent one
+    pub fn bars(&self, width: usize) -> String {
+        let most = self.histogram.iter().copied().max().unwrap_or(0).max(1);
+        self.histogram
+            .iter()
+            .enumerate()
+            .map(|(matches, n)| format!("{matches:>2} │{} {n}\n", bar(*n, most, width)))
+            .collect()
+    }
+}
+
+/// `value` as a bar of eighth characters, `width` characters being `max`
+fn bar(value: usize, max: usize, width: usize) -> String {
+    let eighths = value * width * BARS.len() / max;
+    let (full, rest) = (eighths / BARS.len(), eighths % BARS.len());
+    let mut bar = BARS[BARS.len() - 1].to_string().repeat(full);
+    if rest > 0 {
+        bar.push(BARS[rest - 1]);
+    }
+    bar
+}
+
+pub fn solve(part: Part, input: &str) -> anyhow::Result<u64> {
+    let cards = scratchcards(input)?;
+    Ok(match part {
+        Part::One => cards
+            .iter()
+            .map(Scratchcard::points)
+            .sum::<anyhow::Result<_>>()?,
+        Part::Two => copies(&cards).iter().sum(),
+    })
+}
+
+/// Day 4: Scratchcards
+#[derive(Debug, Clone, Copy)]
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    fn name(&self) -> &'static str {
+        "fourth"
+    }
+
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::One, input).map(|x| x.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::Two, input).map(|x| x.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::csv;
+    use rstest::rstest;
+
+    const SAMPLE: &str = include_str!("../../sample/fourth.txt");
+
+    #[rstest]
+    #[case(Part::One, 13)]
+    #[case(Part::Two, 30)]
+    fn sample(#[case] part: Part, #[case] expected: u64) {
+        assert_eq!(solve(part, SAMPLE).expect("solving"), expected);
+    }
+
+    #[rstest]
+    fn sample_stats() {
+        let stats = stats(&scratchcards(SAMPLE).expect("parsing")).expect("stats");
+        assert_eq!(stats.histogram, vec![2, 1, 2, 0, 1]);
+        let per_card = stats
+            .cards
+            .iter()
+            .map(|card| (card.matches, card.points, card.copies))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            per_card,
+            vec![
+                (4, 8, 1),
+                (2, 2, 2),
+                (2, 2, 4),
+                (1, 1, 8),
+                (0, 0, 14),
+                (0, 0, 1)
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case(64, Some(1 << 63))]
+    #[case(65, None)]
+    fn points_fail_beyond_u64(#[case] matches: u32, #[case] expected: Option<u64>) {
+        let numbers = (1..=matches).collect::<Vec<_>>();
+        let card = Scratchcard {
+            id: 1,
+            winners: numbers.clone(),
+            numbers,
+        };
+        assert_eq!(card.points().ok(), expected);
+    }
+
+    #[rstest]
+    fn copies_stop_at_the_end_of_the_pile() {
+        let cards = scratchcards("Card 1: 1 2 | 1 2\nCard 2: 3 | 3").expect("parsing");
+        assert_eq!(copies(&cards), vec![1, 2]);
+    }
+
+    #[rstest]
+    fn exports_one_row_per_card() {
+        let stats = stats(&scratchcards(SAMPLE).expect("parsing")).expect("stats");
+        let csv = csv(&stats.cards);
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("card,matches,points,copies"));
+        assert_eq!(lines.next(), Some("1,4,8,1"));
+        assert_eq!(lines.count(), 5);
+    }
+
+    #[rstest]
+    #[case(0, 4, 2, "")]
+    #[case(4, 4, 2, "██")]
+    #[case(1, 4, 2, "▌")]
+    #[case(3, 4, 2, "█▌")]
+    #[case(1, 8, 1, "▏")]
+    fn bars_in_eighths(
+        #[case] value: usize,
+        #[case] max: usize,
+        #[case] width: usize,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(bar(value, max, width), expected);
+    }
+
+    #[rstest]
+    fn histogram_has_a_line_per_number_of_matches() {
+        let stats = stats(&scratchcards(SAMPLE).expect("parsing")).expect("stats");
+        assert_eq!(
+            stats.bars(4),
+            " 0 │████ 2\n 1 │██ 1\n 2 │████ 2\n 3 │ 0\n 4 │██ 1\n"
+        );
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("Card 1: 1 2")]
+    #[case("Card x: 1 | 2")]
+    fn invalid_cards(#[case] input: &str) {
+        assert!(scratchcards(input).is_err());
+    }
+}
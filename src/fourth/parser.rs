@@ -0,0 +1,36 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, line_ending, multispace0, space1},
+    multi::separated_list1,
+    sequence::preceded,
+    IResult, Parser as NomParser,
+};
+use nom_supreme::ParserExt;
+
+use super::Scratchcard;
+use crate::parse::common::{integer, labelled_list, list, ws};
+
+/// Winning numbers & the ones we have, e.g. `Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53`
+pub(crate) fn card(s: &str) -> IResult<&str, Scratchcard> {
+    let (s, (id, winners)) = labelled_list(
+        preceded(tag("Card").and(space1), integer::<u32>),
+        integer::<u32>,
+    )(s)?;
+    let (s, numbers) = preceded(ws(char('|')), list(integer::<u32>))(s)?;
+    Ok((
+        s,
+        Scratchcard {
+            id,
+            winners,
+            numbers,
+        },
+    ))
+}
+
+pub(crate) fn cards(s: &str) -> IResult<&str, Vec<Scratchcard>> {
+    multispace0
+        .precedes(separated_list1(line_ending, card))
+        .terminated(multispace0)
+        .all_consuming()
+        .parse(s)
+}
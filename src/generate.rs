@@ -0,0 +1,105 @@
+//! Random puzzle inputs, e.g. for the benchmarks or the fixtures (see [`crate::fixtures`]). They
+//! are drawn from a given generator, so that one of [`crate::rng::stream`] repeats them
+
+use std::collections::HashSet;
+
+use rand::{seq::SliceRandom, Rng};
+
+/// `rows` lines of `cols` characters each, drawn from `chars`. Repeating a character in there
+/// makes it more likely
+pub fn grid(rng: &mut impl Rng, chars: &[char], cols: usize, rows: usize) -> String {
+    (0..rows)
+        .map(|_| {
+            let mut row = (0..cols)
+                .map(|_| *chars.choose(rng).expect("some chars"))
+                .collect::<String>();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
+
+/// `n` Day 12 reports of up to `len` springs each, whose clues are those of a random row of
+/// springs, of which some are then hidden as unknown again. So each has at least one arrangement
+pub fn springs(rng: &mut impl Rng, n: usize, len: usize) -> String {
+    (0..n)
+        .map(|_| {
+            let mut row = (0..rng.gen_range(1..=len))
+                .map(|_| rng.gen_bool(0.5))
+                .collect::<Vec<_>>();
+            // The puzzle has no reports without any damaged springs
+            if !row.contains(&true) {
+                row.push(true);
+            }
+            let clues = row
+                .split(|damaged| !damaged)
+                .filter(|group| !group.is_empty())
+                .map(|group| group.len().to_string())
+                .collect::<Vec<_>>();
+            let pattern = row
+                .iter()
+                .map(|damaged| match (rng.gen_bool(0.5), damaged) {
+                    (true, _) => '?',
+                    (false, true) => '#',
+                    (false, false) => '.',
+                })
+                .collect::<String>();
+            format!("{pattern} {}\n", clues.join(","))
+        })
+        .collect()
+}
+
+/// `n` Day 22 bricks, one to three cubes long, in a 3x3 column up to a height of 13. None of them
+/// overlap, but their two ends come in any order
+pub fn bricks(rng: &mut impl Rng, n: usize) -> String {
+    let mut taken = HashSet::new();
+    let mut bricks = String::new();
+    let mut count = 0;
+    while count < n {
+        let start = [
+            rng.gen_range(0..3),
+            rng.gen_range(0..3),
+            rng.gen_range(1..12),
+        ];
+        let mut end = start;
+        end[rng.gen_range(0..3)] += rng.gen_range(1..3);
+        if end[0] > 2 || end[1] > 2 {
+            continue;
+        }
+        let cubes = (start[0]..=end[0])
+            .flat_map(|x| (start[1]..=end[1]).map(move |y| (x, y)))
+            .flat_map(|(x, y)| (start[2]..=end[2]).map(move |z| (x, y, z)))
+            .collect::<Vec<_>>();
+        if cubes.iter().any(|cube| taken.contains(cube)) {
+            continue;
+        }
+        taken.extend(cubes);
+
+        let (a, b) = if rng.gen_bool(0.5) {
+            (end, start)
+        } else {
+            (start, end)
+        };
+        bricks += &format!("{},{},{}~{},{},{}\n", a[0], a[1], a[2], b[0], b[1], b[2]);
+        count += 1;
+    }
+    bricks
+}
+
+/// `n` Day 24 hailstones around the test area of part one, which one rock thrown from within it
+/// hits all
+pub fn hailstones(rng: &mut impl Rng, n: usize) -> String {
+    let rock = [(); 3].map(|_| rng.gen_range(200_000_000_000_000_i64..=400_000_000_000_000));
+    let throw = [(); 3].map(|_| rng.gen_range(-200_i64..=200));
+    (0..n)
+        .map(|_| {
+            let time = rng.gen_range(100_000_000_000_i64..=1_000_000_000_000);
+            let velocity = [(); 3].map(|_| rng.gen_range(-400_i64..=400));
+            let position = [0, 1, 2].map(|i| rock[i] + (throw[i] - velocity[i]) * time);
+            format!(
+                "{}, {}, {} @ {}, {}, {}\n",
+                position[0], position[1], position[2], velocity[0], velocity[1], velocity[2]
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,152 @@
+//! Writing networks of named nodes in Graphviz' DOT language, to inspect them with external tools
+
+use std::collections::HashSet;
+
+use itertools::Itertools;
+
+use crate::intern::Interner;
+
+/// Directed edge between two nodes (ids of an [`Interner`]), optionally labelled (e.g. with its
+/// direction or weight)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Edge {
+    pub from: u32,
+    pub to: u32,
+    pub label: Option<String>,
+}
+
+impl Edge {
+    pub fn new(from: u32, to: u32) -> Self {
+        Self {
+            from,
+            to,
+            label: None,
+        }
+    }
+
+    pub fn labelled(self, label: impl ToString) -> Self {
+        Self {
+            label: Some(label.to_string()),
+            ..self
+        }
+    }
+}
+
+/// DOT attributes as pairs of name & value, e.g. `("shape", "box")`
+pub type Attributes = Vec<(&'static str, String)>;
+
+/// Hooks for a day's own attributes, e.g. node shapes per kind of module. Comes on top of the
+/// labels & highlights, which [`to_dot`] sets anyway
+pub trait Style {
+    fn node(&self, _label: &str) -> Attributes {
+        Vec::new()
+    }
+
+    fn edge(&self, _edge: &Edge) -> Attributes {
+        Vec::new()
+    }
+}
+
+/// Nothing but labels & highlights
+impl Style for () {}
+
+/// How [`to_dot`] draws the highlighted edges
+const HIGHLIGHT: [(&str, &str); 2] = [("color", "blue"), ("penwidth", "2")];
+
+/// Directed graph of all `names` as nodes (in the order they got interned) and the `edges` between
+/// them, of which the `highlights` stand out
+pub fn to_dot(
+    names: &Interner,
+    edges: impl IntoIterator<Item = Edge>,
+    highlights: &HashSet<Edge>,
+    style: &impl Style,
+) -> String {
+    let mut dot = String::from("digraph {\n");
+    for (_, label) in names.iter() {
+        dot += &format!("    {}{};\n", quote(label), attributes(style.node(label)));
+    }
+    for edge in edges {
+        let mut attrs = Attributes::new();
+        if let Some(label) = &edge.label {
+            attrs.push(("label", label.clone()));
+        }
+        if highlights.contains(&edge) {
+            attrs.extend(HIGHLIGHT.map(|(name, value)| (name, value.to_string())));
+        }
+        attrs.extend(style.edge(&edge));
+        dot += &format!(
+            "    {} -> {}{};\n",
+            quote(names.resolve(edge.from)),
+            quote(names.resolve(edge.to)),
+            attributes(attrs)
+        );
+    }
+    dot + "}\n"
+}
+
+fn quote(id: &str) -> String {
+    format!("\"{}\"", id.replace('"', "\\\""))
+}
+
+/// Plain identifiers & numbers stay as they are, anything else gets quoted
+fn value(value: &str) -> String {
+    let identifier = value.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if identifier || value.parse::<f64>().is_ok() {
+        value.to_string()
+    } else {
+        quote(value)
+    }
+}
+
+fn attributes(attrs: Attributes) -> String {
+    if attrs.is_empty() {
+        return String::new();
+    }
+    let attrs = attrs
+        .iter()
+        .map(|(name, v)| format!("{name}={}", value(v)))
+        .join(", ");
+    format!(" [{attrs}]")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    /// Flip-flops as boxes, everything else stays an ellipse
+    struct FlipFlops;
+
+    impl Style for FlipFlops {
+        fn node(&self, label: &str) -> Attributes {
+            match label.starts_with('%') {
+                true => vec![("shape", "box".into())],
+                false => Vec::new(),
+            }
+        }
+    }
+
+    #[rstest]
+    fn writes_nodes_and_edges() {
+        let names = ["broadcaster", "%a", "&inv"]
+            .into_iter()
+            .collect::<Interner>();
+        let edges = [Edge::new(0, 1).labelled(3), Edge::new(1, 2)];
+        let highlights = HashSet::from([edges[1].clone()]);
+        let dot = to_dot(&names, edges, &highlights, &FlipFlops);
+        assert_eq!(
+            dot,
+            "digraph {\n    \"broadcaster\";\n    \"%a\" [shape=box];\n    \"&inv\";\n    \"broadcaster\" -> \"%a\" [label=3];\n    \"%a\" -> \"&inv\" [color=blue, penwidth=2];\n}\n"
+        );
+    }
+
+    #[rstest]
+    #[case("box", "box")]
+    #[case("2.5", "2.5")]
+    #[case("a b", "\"a b\"")]
+    #[case("say \"hi\"", "\"say \\\"hi\\\"\"")]
+    fn quotes_only_when_needed(#[case] raw: &str, #[case] expected: &str) {
+        assert_eq!(value(raw), expected);
+    }
+}
@@ -0,0 +1,312 @@
+//! Rectangular grid of cells as most days' puzzle inputs are, parsed from one character per cell
+
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use anyhow::anyhow;
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+use crate::{neighbors4, Coord};
+
+/// Character, which marks an empty cell in the puzzle inputs
+pub const EMPTY: char = '.';
+
+/// Grid of `ncols` x `nrows` cells, which only stores the non-empty ones
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serialize",
+    serde(bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>"))
+)]
+pub struct Grid<T> {
+    #[cfg_attr(feature = "serialize", serde(with = "crate::serialize::pairs"))]
+    cells: HashMap<Coord, T>,
+    nrows: i32,
+    ncols: i32,
+}
+
+impl<T> Grid<T> {
+    /// Grid of the given size without any cells
+    pub fn new(ncols: i32, nrows: i32) -> Self {
+        Self {
+            cells: HashMap::new(),
+            nrows,
+            ncols,
+        }
+    }
+
+    pub fn nrows(&self) -> i32 {
+        self.nrows
+    }
+
+    pub fn ncols(&self) -> i32 {
+        self.ncols
+    }
+
+    /// Number of columns (x) and rows (y)
+    pub fn size(&self) -> Coord {
+        Coord::new(self.ncols, self.nrows)
+    }
+
+    /// Whether `coord` lies within the grid's bounds
+    pub fn contains(&self, coord: Coord) -> bool {
+        (0..self.ncols).contains(&coord.x) && (0..self.nrows).contains(&coord.y)
+    }
+
+    /// Cell at `coord`, `None` if it is empty or out of bounds
+    pub fn get(&self, coord: Coord) -> Option<&T> {
+        self.cells.get(&coord)
+    }
+
+    /// Put `value` into the cell at `coord`, returning what was there before
+    pub fn insert(&mut self, coord: Coord, value: T) -> anyhow::Result<Option<T>> {
+        if !self.contains(coord) {
+            return Err(anyhow!(
+                "{coord:?} is outside of the {}x{} grid",
+                self.ncols,
+                self.nrows
+            ));
+        }
+        Ok(self.cells.insert(coord, value))
+    }
+
+    /// Keep only the cells for which `f` returns true
+    pub fn retain(&mut self, mut f: impl FnMut(Coord, &T) -> bool) {
+        self.cells.retain(|coord, value| f(*coord, value));
+    }
+
+    /// All non-empty cells, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.cells.iter().map(|(coord, value)| (*coord, value))
+    }
+
+    /// Coordinates of all cells (empty or not), row by row
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let (ncols, nrows) = (self.ncols, self.nrows);
+        (0..nrows).flat_map(move |y| (0..ncols).map(move |x| Coord::new(x, y)))
+    }
+
+    /// All cells of row `y` from left to right
+    pub fn row(&self, y: i32) -> impl Iterator<Item = (Coord, Option<&T>)> {
+        (0..self.ncols)
+            .map(move |x| Coord::new(x, y))
+            .map(|c| (c, self.get(c)))
+    }
+
+    /// All cells of column `x` from top to bottom
+    pub fn column(&self, x: i32) -> impl Iterator<Item = (Coord, Option<&T>)> {
+        (0..self.nrows)
+            .map(move |y| Coord::new(x, y))
+            .map(|c| (c, self.get(c)))
+    }
+
+    /// Coordinates of the (up to four) cells next to `coord` within the grid's bounds
+    pub fn neighbors(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        neighbors4(coord).filter(|c| self.contains(*c))
+    }
+
+    /// Draw this grid with the string `cell` returns for each coordinate (and its value, if any)
+    pub fn pretty<F>(&self, cell: F) -> Pretty<'_, T, F>
+    where
+        F: Fn(Coord, Option<&T>) -> String,
+    {
+        Pretty {
+            grid: self,
+            cell,
+            border: false,
+            margin: 0,
+        }
+    }
+}
+
+impl<T> Extend<(Coord, T)> for Grid<T> {
+    /// Cells outside of the grid's bounds are dropped
+    fn extend<I: IntoIterator<Item = (Coord, T)>>(&mut self, iter: I) {
+        let (ncols, nrows) = (self.ncols, self.nrows);
+        self.cells.extend(
+            iter.into_iter()
+                .filter(|(c, _)| (0..ncols).contains(&c.x) && (0..nrows).contains(&c.y)),
+        );
+    }
+}
+
+/// One cell per character, `.` for empty ones. All lines must be equally long
+impl<T> FromStr for Grid<T>
+where
+    T: TryFrom<char>,
+    anyhow::Error: From<T::Error>,
+{
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines = s.trim_end().lines().map(str::trim).collect::<Vec<_>>();
+        let ncols = lines
+            .first()
+            .filter(|line| !line.is_empty())
+            .ok_or(anyhow!("Grid must contain at least one cell"))?
+            .chars()
+            .count();
+        let mut grid = Self::new(ncols as i32, lines.len() as i32);
+        for (y, line) in lines.into_iter().enumerate() {
+            if line.chars().count() != ncols {
+                return Err(anyhow!(
+                    "Line {y} of the grid is not {ncols} characters long: {line:?}"
+                ));
+            }
+            for (x, c) in line.chars().enumerate().filter(|(_, c)| *c != EMPTY) {
+                grid.cells
+                    .insert(Coord::new(x as i32, y as i32), T::try_from(c)?);
+            }
+        }
+        Ok(grid)
+    }
+}
+
+/// Printable view of a [`Grid`], see [`Grid::pretty`]
+pub struct Pretty<'a, T, F> {
+    grid: &'a Grid<T>,
+    cell: F,
+    border: bool,
+    margin: i32,
+}
+
+impl<T, F> Pretty<'_, T, F> {
+    /// Surround the grid with a box
+    pub fn bordered(mut self) -> Self {
+        self.border = true;
+        self
+    }
+
+    /// Also draw `margin` cells outside of the grid's bounds on every side
+    pub fn with_margin(mut self, margin: i32) -> Self {
+        self.margin = margin;
+        self
+    }
+}
+
+/// Same as [`Display`], the closure formatting the cells can't be debugged anyway
+impl<T, F> std::fmt::Debug for Pretty<'_, T, F>
+where
+    F: Fn(Coord, Option<&T>) -> String,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl<T, F> Display for Pretty<'_, T, F>
+where
+    F: Fn(Coord, Option<&T>) -> String,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (m, grid) = (self.margin, self.grid);
+        let width = (grid.ncols + 2 * m) as usize;
+        if self.border {
+            writeln!(f, "╭{}╮", "─".repeat(width))?;
+        }
+        for y in -m..grid.nrows + m {
+            if self.border {
+                write!(f, "│")?;
+            }
+            for x in -m..grid.ncols + m {
+                let coord = Coord::new(x, y);
+                write!(f, "{}", (self.cell)(coord, grid.get(coord)))?;
+            }
+            if self.border {
+                write!(f, "│")?;
+            }
+            if y < grid.nrows + m - 1 || self.border {
+                writeln!(f)?;
+            }
+        }
+        if self.border {
+            write!(f, "╰{}╯", "─".repeat(width))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Wall;
+
+    impl TryFrom<char> for Wall {
+        type Error = anyhow::Error;
+        fn try_from(c: char) -> Result<Self, Self::Error> {
+            match c {
+                '#' => Ok(Wall),
+                _ => Err(anyhow!("Unknown cell {c:?}")),
+            }
+        }
+    }
+
+    fn grid() -> Grid<Wall> {
+        Grid::from_str("#..\n.#.\r\n").expect("parsing")
+    }
+
+    #[rstest]
+    fn parses_non_empty_cells() {
+        let grid = grid();
+        assert_eq!(grid.size(), Coord::new(3, 2));
+        assert_eq!(grid.get(Coord::new(0, 0)), Some(&Wall));
+        assert_eq!(grid.get(Coord::new(1, 0)), None);
+        assert_eq!(grid.iter().count(), 2);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("#.\n#")]
+    #[case("#?")]
+    fn rejects_invalid_grids(#[case] input: &str) {
+        assert!(Grid::<Wall>::from_str(input).is_err());
+    }
+
+    #[rstest]
+    #[case(Coord::new(0, 0), true)]
+    #[case(Coord::new(2, 1), true)]
+    #[case(Coord::new(3, 1), false)]
+    #[case(Coord::new(0, -1), false)]
+    fn checks_bounds(#[case] coord: Coord, #[case] inside: bool) {
+        let mut grid = grid();
+        assert_eq!(grid.contains(coord), inside);
+        assert_eq!(grid.insert(coord, Wall).is_ok(), inside);
+    }
+
+    #[rstest]
+    fn iterates_rows_and_columns() {
+        let grid = grid();
+        assert_eq!(grid.coords().count(), 6);
+        assert_eq!(grid.row(1).filter(|(_, c)| c.is_some()).count(), 1);
+        assert_eq!(
+            grid.column(0).map(|(c, _)| c).collect::<Vec<_>>(),
+            vec![Coord::new(0, 0), Coord::new(0, 1)]
+        );
+    }
+
+    #[rstest]
+    #[case(Coord::new(0, 0), 2)]
+    #[case(Coord::new(1, 0), 3)]
+    #[case(Coord::new(1, 1), 3)]
+    fn neighbors_within_bounds(#[case] coord: Coord, #[case] n: usize) {
+        assert_eq!(grid().neighbors(coord).count(), n);
+    }
+
+    #[rstest]
+    fn pretty_prints() {
+        let grid = grid();
+        let cell = |_, wall: Option<&Wall>| String::from(if wall.is_some() { "#" } else { " " });
+        assert_eq!(grid.pretty(cell).to_string(), "#  \n # ");
+        assert_eq!(
+            grid.pretty(cell).bordered().to_string(),
+            "╭───╮\n│#  │\n│ # │\n╰───╯"
+        );
+        assert_eq!(
+            grid.pretty(cell).with_margin(1).to_string().lines().count(),
+            4
+        );
+    }
+}
@@ -0,0 +1,98 @@
+//! Downloading personal puzzle inputs from adventofcode.com, cached under [`INPUT_DIR`]. The
+//! binaries fall back to them for missing input files
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::Day;
+
+/// Where downloaded inputs are cached, one `<day>.txt` per day, e.g. `input/fifth.txt`
+pub const INPUT_DIR: &str = "input";
+
+/// Environment variable with the session cookie of adventofcode.com
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+const YEAR: u16 = 2023;
+
+const USER_AGENT: &str = concat!("aoc23/", env!("CARGO_PKG_VERSION"));
+
+/// `$XDG_CONFIG_HOME/aoc23/session`, falling back to `~/.config/aoc23/session`
+fn config() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| Some(PathBuf::from(std::env::var_os("HOME")?).join(".config")))
+        .map(|dir| dir.join("aoc23").join("session"))
+}
+
+/// Session cookie of adventofcode.com, from [`SESSION_VAR`] or the config file
+pub fn session() -> Result<String> {
+    if let Ok(session) = std::env::var(SESSION_VAR) {
+        return Ok(session.trim().to_string());
+    }
+    let path = config().ok_or(anyhow!("${SESSION_VAR} is not set and there is no $HOME"))?;
+    let session = std::fs::read_to_string(&path)
+        .with_context(|| format!("${SESSION_VAR} is not set and {path:?} not readable"))?;
+    Ok(session.trim().to_string())
+}
+
+/// Where to download the input of `day` from
+pub fn url(day: Day) -> String {
+    format!("https://adventofcode.com/{YEAR}/day/{day}/input")
+}
+
+pub fn download(day: Day, session: &str) -> Result<String> {
+    let response = ureq::get(&url(day))
+        .set("Cookie", &format!("session={session}"))
+        .set("User-Agent", USER_AGENT)
+        .call()
+        .with_context(|| format!("Downloading the input of day {day}"))?;
+    Ok(response.into_string()?)
+}
+
+/// Path to the input of a day in `dir`, downloading it first unless it is cached there already
+pub fn fetch(day: Day, dir: &Path, force: bool) -> Result<PathBuf> {
+    let path = dir.join(format!("{}.txt", day.name()));
+    if path.exists() && !force {
+        return Ok(path);
+    }
+    let input = download(day, &session()?)?;
+    std::fs::create_dir_all(dir)?;
+    std::fs::write(&path, input)?;
+    Ok(path)
+}
+
+/// Reads the input file at `path`, or the (downloaded) input of the `day`th, if there is no such
+/// file
+pub fn read(path: impl AsRef<Path>, day: u8) -> Result<String> {
+    let path = path.as_ref();
+    if path.exists() {
+        return Ok(std::fs::read_to_string(path)?);
+    }
+    let day = Day::try_from(day)?;
+    eprintln!("{path:?} does not exist, using the input of day {day} instead");
+    let path = fetch(day, Path::new(INPUT_DIR), false)?;
+    Ok(std::fs::read_to_string(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0)]
+    #[case(26)]
+    fn rejects_days_beyond_advent(#[case] day: u8) {
+        assert!(read("does/not/exist.txt", day).is_err());
+    }
+
+    #[rstest]
+    fn cached_inputs_are_not_downloaded() {
+        let dir = std::env::temp_dir().join("aoc23-cached-inputs");
+        std::fs::create_dir_all(&dir).expect("creating");
+        std::fs::write(dir.join("fifth.txt"), "cached").expect("writing");
+        let path = fetch(Day::Fifth, &dir, false).expect("fetching");
+        assert_eq!(std::fs::read_to_string(path).expect("reading"), "cached");
+    }
+}
@@ -0,0 +1,91 @@
+//! Interning of strings as small ids, e.g. node labels, so that types holding them need neither
+//! lifetimes nor clones of the strings
+
+use std::collections::HashMap;
+
+/// Two-way mapping between strings and the ids they got interned as, handed out from 0 upwards
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Interner {
+    ids: HashMap<String, u32>,
+    names: Vec<String>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Id of `name`, which gets interned first if it is new
+    pub fn intern(&mut self, name: &str) -> u32 {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+        let id = self.names.len() as u32;
+        self.ids.insert(name.to_owned(), id);
+        self.names.push(name.to_owned());
+        id
+    }
+
+    /// Id of `name`, if it was interned before
+    pub fn get(&self, name: &str) -> Option<u32> {
+        self.ids.get(name).copied()
+    }
+
+    /// Name interned as `id`. Panics for ids, which were not handed out by this interner
+    pub fn resolve(&self, id: u32) -> &str {
+        &self.names[id as usize]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// All ids with their names, in the order they got interned
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &str)> {
+        self.names
+            .iter()
+            .enumerate()
+            .map(|(id, name)| (id as u32, name.as_str()))
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for Interner {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut interner = Self::new();
+        for name in iter {
+            interner.intern(name.as_ref());
+        }
+        interner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    fn interns_each_name_once() {
+        let mut interner = Interner::new();
+        assert_eq!(interner.intern("AAA"), 0);
+        assert_eq!(interner.intern("BBB"), 1);
+        assert_eq!(interner.intern("AAA"), 0);
+        assert_eq!(interner.len(), 2);
+        assert_eq!(interner.get("BBB"), Some(1));
+        assert_eq!(interner.get("ZZZ"), None);
+        assert_eq!(interner.resolve(1), "BBB");
+    }
+
+    #[rstest]
+    fn iterates_in_interning_order() {
+        let interner = ["b", "a", "b", "c"].into_iter().collect::<Interner>();
+        assert_eq!(
+            interner.iter().collect::<Vec<_>>(),
+            vec![(0, "b"), (1, "a"), (2, "c")]
+        );
+    }
+}
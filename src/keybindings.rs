@@ -0,0 +1,170 @@
+//! Keys controlling the animations, remappable e.g. for non-QWERTY layouts through a TOML file
+
+use std::path::Path;
+
+use anyhow::Context;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Optional file in the working directory, overriding (some of) the default [`Keybindings`], e.g.
+/// `faster = "N"` and `slower = "P"`
+pub const FILE: &str = "keybindings.toml";
+
+/// Something the user can do in an animation with a key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Pause,
+    Step,
+    Faster,
+    Slower,
+    Quit,
+    /// One of the first four things to choose from, e.g. seed ranges for Day 5
+    Select(usize),
+    /// The next thing to choose from, or the previous one with Shift held
+    Cycle,
+    Left,
+    Right,
+    /// Starts the animation over, see [`crate::reset`]
+    Restart,
+    /// Switches to another input, see [`crate::samples`]
+    PreviousSample,
+    NextSample,
+}
+
+#[derive(Debug, Clone, PartialEq, Resource, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Keybindings {
+    /// Pauses & resumes the animation
+    pub pause: KeyCode,
+    /// Advances by a single step, also while paused
+    pub step: KeyCode,
+    /// Doubles the animation's frequency
+    pub faster: KeyCode,
+    /// Halves the animation's frequency
+    pub slower: KeyCode,
+    pub quit: KeyCode,
+    pub select: [KeyCode; 4],
+    /// Selects the next of any number of things, the previous one together with Shift
+    pub cycle: KeyCode,
+    pub left: KeyCode,
+    pub right: KeyCode,
+    pub restart: KeyCode,
+    pub previous_sample: KeyCode,
+    pub next_sample: KeyCode,
+}
+
+impl Default for Keybindings {
+    fn default() -> Self {
+        Self {
+            pause: KeyCode::Space,
+            step: KeyCode::Tab,
+            faster: KeyCode::J,
+            slower: KeyCode::K,
+            quit: KeyCode::Q,
+            select: [KeyCode::Key1, KeyCode::Key2, KeyCode::Key3, KeyCode::Key4],
+            cycle: KeyCode::Tab,
+            left: KeyCode::H,
+            right: KeyCode::L,
+            restart: KeyCode::R,
+            previous_sample: KeyCode::BracketLeft,
+            next_sample: KeyCode::BracketRight,
+        }
+    }
+}
+
+impl Keybindings {
+    /// Binds `action` to `key` instead
+    pub fn bind(mut self, action: Action, key: KeyCode) -> Self {
+        *self.key_mut(action) = key;
+        self
+    }
+
+    pub fn key(&self, action: Action) -> KeyCode {
+        match action {
+            Action::Pause => self.pause,
+            Action::Step => self.step,
+            Action::Faster => self.faster,
+            Action::Slower => self.slower,
+            Action::Quit => self.quit,
+            Action::Select(i) => self.select[i],
+            Action::Cycle => self.cycle,
+            Action::Left => self.left,
+            Action::Right => self.right,
+            Action::Restart => self.restart,
+            Action::PreviousSample => self.previous_sample,
+            Action::NextSample => self.next_sample,
+        }
+    }
+
+    fn key_mut(&mut self, action: Action) -> &mut KeyCode {
+        match action {
+            Action::Pause => &mut self.pause,
+            Action::Step => &mut self.step,
+            Action::Faster => &mut self.faster,
+            Action::Slower => &mut self.slower,
+            Action::Quit => &mut self.quit,
+            Action::Select(i) => &mut self.select[i],
+            Action::Cycle => &mut self.cycle,
+            Action::Left => &mut self.left,
+            Action::Right => &mut self.right,
+            Action::Restart => &mut self.restart,
+            Action::PreviousSample => &mut self.previous_sample,
+            Action::NextSample => &mut self.next_sample,
+        }
+    }
+
+    /// Whether the key of `action` was released this frame
+    pub fn released(&self, keys: &Input<KeyCode>, action: Action) -> bool {
+        keys.just_released(self.key(action))
+    }
+
+    /// Whether the key of `action` was pressed this frame
+    pub fn pressed(&self, keys: &Input<KeyCode>, action: Action) -> bool {
+        keys.just_pressed(self.key(action))
+    }
+
+    /// Bindings from the TOML file at `path`, or the default ones if there is none
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path).with_context(|| format!("Reading {path:?}"))?;
+        toml::from_str(&content).with_context(|| format!("Parsing {path:?}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    fn file_overrides_some_keys() {
+        let path = std::env::temp_dir().join("aoc23-keybindings.toml");
+        std::fs::write(
+            &path,
+            "faster = \"N\"\nselect = [\"A\", \"S\", \"D\", \"F\"]\n",
+        )
+        .expect("writing");
+        let bindings = Keybindings::load(&path).expect("loading");
+        assert_eq!(bindings.key(Action::Faster), KeyCode::N);
+        assert_eq!(bindings.key(Action::Select(2)), KeyCode::D);
+        assert_eq!(bindings.key(Action::Slower), KeyCode::K);
+    }
+
+    #[rstest]
+    fn missing_file_binds_defaults() {
+        let bindings = Keybindings::load("does/not/exist.toml").expect("loading");
+        assert_eq!(bindings, Keybindings::default());
+    }
+
+    #[rstest]
+    #[case(Action::Pause, KeyCode::P)]
+    #[case(Action::Select(3), KeyCode::Key0)]
+    fn builder_rebinds(#[case] action: Action, #[case] key: KeyCode) {
+        let bindings = Keybindings::default().bind(action, key);
+        assert_eq!(bindings.key(action), key);
+        assert_eq!(bindings.key(Action::Quit), KeyCode::Q);
+    }
+}
@@ -5,26 +5,144 @@
     let_chains,
     iter_array_chunks
 )]
-
+#![deny(missing_debug_implementations)]
+
+pub mod cache;
+#[cfg(feature = "animations")]
+pub mod camera;
+#[cfg(feature = "animations")]
+pub mod capture;
+#[cfg(feature = "animations")]
+pub mod crt;
+pub mod day;
+#[cfg(feature = "animations")]
+pub mod demo;
+#[cfg(feature = "day18")]
+pub mod eighteenth;
+#[cfg(feature = "day08")]
+pub mod eighth;
+#[cfg(feature = "day11")]
+pub mod eleventh;
+pub mod export;
+#[cfg(feature = "day15")]
 pub mod fifteenth;
+#[cfg(feature = "day05")]
 pub mod fifth;
+pub mod fixtures;
+#[cfg(feature = "day14")]
 pub mod fourteenth;
+#[cfg(feature = "day04")]
+pub mod fourth;
+pub mod generate;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod intern;
+#[cfg(feature = "animations")]
+pub mod keybindings;
+pub mod linalg;
+pub mod minimize;
+#[cfg(feature = "day19")]
+pub mod nineteenth;
+#[cfg(feature = "day09")]
+pub mod ninth;
+pub mod parse;
+#[cfg(feature = "animations")]
+pub mod picking;
+pub mod render;
+pub mod report;
+#[cfg(feature = "animations")]
+pub mod reset;
+pub mod rng;
+#[cfg(feature = "animations")]
+pub mod samples;
+pub mod search;
+#[cfg(feature = "day02")]
 pub mod second;
+#[cfg(feature = "serialize")]
+pub(crate) mod serialize;
+#[cfg(feature = "day17")]
+pub mod seventeenth;
+#[cfg(feature = "day07")]
+pub mod seventh;
+#[cfg(feature = "day16")]
 pub mod sixteenth;
+#[cfg(feature = "day06")]
+pub mod sixth;
+#[cfg(feature = "animations")]
+pub mod spawning;
+#[cfg(feature = "animations")]
+pub mod summary;
+#[cfg(feature = "day10")]
 pub mod ten;
+#[cfg(feature = "day03")]
+pub mod third;
+#[cfg(feature = "day13")]
 pub mod thirteenth;
+pub mod timing;
+pub mod tui;
+#[cfg(feature = "day12")]
+pub mod twelfth;
+#[cfg(feature = "day25")]
+pub mod twentyfifth;
+#[cfg(feature = "day21")]
+pub mod twentyfirst;
+#[cfg(feature = "day24")]
+pub mod twentyfourth;
+#[cfg(feature = "day22")]
+pub mod twentysecond;
+#[cfg(feature = "day23")]
+pub mod twentythird;
+#[cfg(feature = "animations")]
+pub mod widgets;
+
+#[cfg(feature = "alloc-stats")]
+pub mod alloc;
 
 use anyhow::anyhow;
+#[cfg(feature = "animations")]
 use bevy::{
-    input::mouse::{MouseMotion, MouseWheel},
+    app::{AppExit, PluginGroupBuilder},
+    gizmos::GizmoPlugin,
+    input::InputPlugin,
     prelude::*,
-    render::{mesh::Indices, render_resource::PrimitiveTopology},
+    render::{
+        mesh::Indices,
+        render_resource::{PrimitiveTopology, Shader},
+    },
+    utils::Duration,
 };
 use clap::ValueEnum;
-use enum_iterator::{next_cycle, previous_cycle, Sequence};
-use std::{convert::AsRef, fmt::Debug};
+use enum_iterator::{all, next_cycle, previous_cycle, Sequence};
+#[cfg(feature = "animations")]
+use keybindings::{Action, Keybindings};
+use minimize::Structure;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt::Debug,
+    path::{Path, PathBuf},
+};
 
-#[derive(Default, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, ValueEnum)]
+#[cfg(feature = "animations")]
+pub use camera::{mouse, AocCameraPlugin, Scroll};
+pub use day::Day;
+
+#[derive(
+    Default,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Clone,
+    Copy,
+    Hash,
+    ValueEnum,
+    Sequence,
+    Serialize,
+    Deserialize,
+)]
 pub enum Part {
     #[default]
     One,
@@ -32,7 +150,10 @@ pub enum Part {
 }
 
 pub type Coord = euclid::Vector2D<i32, euclid::UnknownUnit>;
+/// Cell in space, e.g. a cube of a brick in Day 22 with `z` growing upwards
+pub type Coord3 = euclid::Vector3D<i32, euclid::UnknownUnit>;
 
+#[cfg(feature = "animations")]
 pub fn coord2vec(coord: Coord) -> Vec2 {
     Vec2::new(coord.x as f32, -coord.y as f32)
 }
@@ -41,6 +162,157 @@ pub fn anyhowing(e: nom::error::Error<&str>) -> anyhow::Error {
     anyhow!("{e}")
 }
 
+/// Error for `--animate` in a build without the `animations` feature
+pub fn animations_unavailable() -> anyhow::Error {
+    anyhow!("Animations are not available in a build without the `animations` feature")
+}
+
+/// Unix line endings and exactly one trailing newline, no matter how the puzzle input was saved
+pub fn normalize_input(input: &str) -> String {
+    let mut input = input.replace("\r\n", "\n").trim_end().to_owned();
+    input.push('\n');
+    input
+}
+
+/// Both parts of one day's puzzle
+pub trait Solver {
+    /// Name of the day's binary and input file, e.g. `fifth` for `sample/fifth.txt`
+    fn name(&self) -> &'static str;
+
+    /// Where the input of this day lives within `dir`, e.g. `input/fifth.txt`
+    fn input(&self, dir: &Path) -> PathBuf {
+        dir.join(format!("{}.txt", self.name()))
+    }
+
+    fn part_one(&self, input: &str) -> anyhow::Result<String>;
+
+    fn part_two(&self, input: &str) -> anyhow::Result<String>;
+
+    /// Pieces the input can be reduced by, when minimizing it
+    fn structure(&self) -> Structure {
+        Structure::Lines
+    }
+
+    fn solve(&self, part: Part, input: &str) -> anyhow::Result<String> {
+        match part {
+            Part::One => self.part_one(input),
+            Part::Two => self.part_two(input),
+        }
+    }
+
+    /// Other ways of solving the day next to [`Solver::solve`], e.g. a slower but simpler one to
+    /// cross-check it with
+    fn strategies(&self) -> Vec<Strategy> {
+        Vec::new()
+    }
+
+    /// Names of all ways of solving the day, starting with [`DEFAULT_STRATEGY`]
+    fn strategy_names(&self) -> Vec<&'static str> {
+        std::iter::once(DEFAULT_STRATEGY)
+            .chain(self.strategies().iter().map(|strategy| strategy.name))
+            .collect()
+    }
+
+    /// Solves `part` the way of the strategy called `name`, see [`Solver::strategy_names`]
+    fn solve_with(&self, name: &str, part: Part, input: &str) -> anyhow::Result<String> {
+        if name == DEFAULT_STRATEGY {
+            return self.solve(part, input);
+        }
+        let strategy = self
+            .strategies()
+            .into_iter()
+            .find(|strategy| strategy.name == name)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Day {} has no strategy {name:?}, only {}",
+                    self.name(),
+                    self.strategy_names().join(", ")
+                )
+            })?;
+        (strategy.solve)(part, input)
+    }
+}
+
+/// Name of [`Solver::solve`] among a day's [`Strategy`]s
+pub const DEFAULT_STRATEGY: &str = "default";
+
+/// Alternative way of solving a day, which a [`Solver`] lists in [`Solver::strategies`]
+#[derive(Debug, Clone, Copy)]
+pub struct Strategy {
+    /// Short & unique per day, e.g. `flood-fill`
+    pub name: &'static str,
+    /// What the strategy does differently
+    pub about: &'static str,
+    pub solve: fn(Part, &str) -> anyhow::Result<String>,
+}
+
+/// Solvers of all days, which live in this library (and whose `dayNN` feature is enabled), by
+/// their number. All other days are binary-only
+pub fn solvers() -> BTreeMap<Day, Box<dyn Solver>> {
+    #[allow(unused_mut)]
+    let mut solvers = BTreeMap::<Day, Box<dyn Solver>>::new();
+    #[cfg(feature = "day02")]
+    solvers.insert(Day::Second, Box::new(second::Puzzle));
+    #[cfg(feature = "day03")]
+    solvers.insert(Day::Third, Box::new(third::Puzzle));
+    #[cfg(feature = "day04")]
+    solvers.insert(Day::Fourth, Box::new(fourth::Puzzle));
+    #[cfg(feature = "day05")]
+    solvers.insert(Day::Fifth, Box::new(fifth::Puzzle));
+    #[cfg(feature = "day06")]
+    solvers.insert(Day::Sixth, Box::new(sixth::Puzzle));
+    #[cfg(feature = "day07")]
+    solvers.insert(Day::Seventh, Box::new(seventh::Puzzle));
+    #[cfg(feature = "day08")]
+    solvers.insert(Day::Eighth, Box::new(eighth::Puzzle));
+    #[cfg(feature = "day09")]
+    solvers.insert(Day::Ninth, Box::new(ninth::Puzzle));
+    #[cfg(feature = "day10")]
+    solvers.insert(Day::Tenth, Box::new(ten::Puzzle));
+    #[cfg(feature = "day11")]
+    solvers.insert(Day::Eleventh, Box::new(eleventh::Puzzle));
+    #[cfg(feature = "day12")]
+    solvers.insert(Day::Twelfth, Box::new(twelfth::Puzzle));
+    #[cfg(feature = "day13")]
+    solvers.insert(Day::Thirteenth, Box::new(thirteenth::Puzzle));
+    #[cfg(feature = "day14")]
+    solvers.insert(Day::Fourteenth, Box::new(fourteenth::Puzzle));
+    #[cfg(feature = "day15")]
+    solvers.insert(Day::Fifteenth, Box::new(fifteenth::Puzzle));
+    #[cfg(feature = "day16")]
+    solvers.insert(Day::Sixteenth, Box::new(sixteenth::Puzzle));
+    #[cfg(feature = "day17")]
+    solvers.insert(Day::Seventeenth, Box::new(seventeenth::Puzzle));
+    #[cfg(feature = "day18")]
+    solvers.insert(Day::Eighteenth, Box::new(eighteenth::Puzzle));
+    #[cfg(feature = "day19")]
+    solvers.insert(Day::Nineteenth, Box::new(nineteenth::Puzzle));
+    #[cfg(feature = "day21")]
+    solvers.insert(Day::TwentyFirst, Box::new(twentyfirst::Puzzle));
+    #[cfg(feature = "day22")]
+    solvers.insert(Day::TwentySecond, Box::new(twentysecond::Puzzle));
+    #[cfg(feature = "day23")]
+    solvers.insert(Day::TwentyThird, Box::new(twentythird::Puzzle));
+    #[cfg(feature = "day24")]
+    solvers.insert(Day::TwentyFourth, Box::new(twentyfourth::Puzzle));
+    #[cfg(feature = "day25")]
+    solvers.insert(Day::TwentyFifth, Box::new(twentyfifth::Puzzle));
+    solvers
+}
+
+/// Solve one `part` of `day` for the given puzzle `input`, see [`solvers`] for which days can be
+pub fn solve(day: Day, part: Part, input: &str) -> anyhow::Result<String> {
+    solve_with(day, DEFAULT_STRATEGY, part, input)
+}
+
+/// Like [`solve`], but the way of the day's strategy called `strategy`
+pub fn solve_with(day: Day, strategy: &str, part: Part, input: &str) -> anyhow::Result<String> {
+    solvers()
+        .get(&day)
+        .ok_or(anyhow!("Day {day} cannot be solved by the library"))?
+        .solve_with(strategy, part, input)
+}
+
 #[derive(PartialEq, Eq, Clone, Copy, Hash, Sequence)]
 pub enum Direction {
     Up,
@@ -56,6 +328,69 @@ impl Direction {
     pub fn ccw(&self) -> Self {
         previous_cycle(self).unwrap()
     }
+    pub fn opposite(&self) -> Self {
+        self.cw().cw()
+    }
+    pub fn turn(&self, turn: Turn) -> Self {
+        match turn {
+            Turn::Left => self.ccw(),
+            Turn::Straight => *self,
+            Turn::Right => self.cw(),
+            Turn::Back => self.opposite(),
+        }
+    }
+}
+
+/// Which way to turn, relative to the current heading
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Sequence)]
+pub enum Turn {
+    Left,
+    Straight,
+    Right,
+    Back,
+}
+
+/// All eight directions, clockwise starting north. The cardinal ones are the [`Direction`]s
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Sequence)]
+pub enum Compass {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Compass {
+    /// Next direction clockwise, i.e. 45° to the right
+    pub fn cw(&self) -> Self {
+        next_cycle(self).unwrap()
+    }
+    /// Next direction counter-clockwise, i.e. 45° to the left
+    pub fn ccw(&self) -> Self {
+        previous_cycle(self).unwrap()
+    }
+    pub fn opposite(&self) -> Self {
+        self.cw().cw().cw().cw()
+    }
+    /// Turning left or right is by 90°, like for a [`Direction`]
+    pub fn turn(&self, turn: Turn) -> Self {
+        match turn {
+            Turn::Left => self.ccw().ccw(),
+            Turn::Straight => *self,
+            Turn::Right => self.cw().cw(),
+            Turn::Back => self.opposite(),
+        }
+    }
+    pub fn is_diagonal(&self) -> bool {
+        Direction::try_from(*self).is_err()
+    }
+    /// NE, SE, SW & NW
+    pub fn diagonals() -> impl Iterator<Item = Self> {
+        all::<Self>().filter(Self::is_diagonal)
+    }
 }
 
 impl Debug for Direction {
@@ -79,9 +414,56 @@ impl From<Direction> for Coord {
     }
 }
 
+impl From<Direction> for Compass {
+    fn from(dir: Direction) -> Self {
+        match dir {
+            Direction::Up => Self::N,
+            Direction::Right => Self::E,
+            Direction::Down => Self::S,
+            Direction::Left => Self::W,
+        }
+    }
+}
+
+impl TryFrom<Compass> for Direction {
+    type Error = anyhow::Error;
+
+    fn try_from(dir: Compass) -> Result<Self, Self::Error> {
+        match dir {
+            Compass::N => Ok(Self::Up),
+            Compass::E => Ok(Self::Right),
+            Compass::S => Ok(Self::Down),
+            Compass::W => Ok(Self::Left),
+            diagonal => Err(anyhow!("{diagonal:?} is not a cardinal direction")),
+        }
+    }
+}
+
+impl From<Compass> for Coord {
+    fn from(dir: Compass) -> Self {
+        match Direction::try_from(dir) {
+            Ok(cardinal) => cardinal.into(),
+            // Diagonals are the sum of their cardinal neighbors
+            Err(_) => Coord::from(dir.ccw()) + Coord::from(dir.cw()),
+        }
+    }
+}
+
+/// The four coordinates sharing an edge with `coord`
+pub fn neighbors4(coord: Coord) -> impl Iterator<Item = Coord> {
+    all::<Direction>().map(move |dir| coord + Coord::from(dir))
+}
+
+/// The eight coordinates sharing an edge or a corner with `coord`
+pub fn neighbors8(coord: Coord) -> impl Iterator<Item = Coord> {
+    all::<Compass>().map(move |dir| coord + Coord::from(dir))
+}
+
+#[cfg(any(feature = "animations", feature = "day16"))]
 pub(crate) fn lerp(a: f32, b: f32, t: f32) -> f32 {
     a + (b - a) * t
 }
+#[cfg(feature = "animations")]
 pub(crate) fn lerprgb(a: Color, b: Color, t: f32) -> Color {
     Color::rgba(
         lerp(a.r(), b.r(), t),
@@ -90,6 +472,7 @@ pub(crate) fn lerprgb(a: Color, b: Color, t: f32) -> Color {
         lerp(a.a(), b.a(), t),
     )
 }
+#[cfg(feature = "animations")]
 pub fn lerphsl(a: Color, b: Color, t: f32) -> Color {
     Color::hsla(
         lerp(a.h(), b.h(), t),
@@ -99,21 +482,58 @@ pub fn lerphsl(a: Color, b: Color, t: f32) -> Color {
     )
 }
 
-#[derive(Resource)]
+/// Window-less set of plugins, which still provides everything the animation systems rely on
+/// (input, time, assets & gizmos), so that an animation's `App` can be updated in tests
+#[cfg(feature = "animations")]
+#[derive(Debug)]
+pub struct HeadlessPlugins;
+
+#[cfg(feature = "animations")]
+impl PluginGroup for HeadlessPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        MinimalPlugins
+            .build()
+            .add(AssetPlugin::default())
+            .add(InputPlugin)
+            .add(HeadlessAssets)
+            .add(GizmoPlugin)
+    }
+}
+
+#[cfg(feature = "animations")]
+struct HeadlessAssets;
+
+#[cfg(feature = "animations")]
+impl Plugin for HeadlessAssets {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Shader>()
+            .init_asset::<Image>()
+            .init_asset::<Mesh>()
+            .init_asset::<ColorMaterial>()
+            .init_asset::<TextureAtlas>()
+            .init_asset::<Font>();
+    }
+}
+
+#[cfg(feature = "animations")]
+#[derive(Debug, Clone, Resource)]
 pub struct Tick {
     timer: Timer,
     f: f32,
 }
 
-#[derive(Default, Resource, Debug)]
+#[cfg(feature = "animations")]
+#[derive(Default, Resource, Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Running(bool);
 
+#[cfg(feature = "animations")]
 impl Running {
     pub fn inner(&self) -> bool {
         self.0
     }
 }
 
+#[cfg(feature = "animations")]
 impl Tick {
     pub fn new(f: f32) -> Self {
         Self {
@@ -129,62 +549,123 @@ impl Tick {
     pub fn frequency(&self) -> f32 {
         self.f
     }
+    /// Keeps how far the current tick got, so that gradual changes don't keep resetting it
     pub fn set_frequency(&mut self, f: f32) {
-        self.timer = Timer::from_seconds(1. / f, TimerMode::Repeating);
+        self.timer.set_duration(Duration::from_secs_f32(1. / f));
         self.f = f;
     }
 }
 
+#[cfg(feature = "animations")]
 impl AsRef<Timer> for Tick {
     fn as_ref(&self) -> &Timer {
         &self.timer
     }
 }
 
-pub fn frequency_increaser(keys: Res<Input<KeyCode>>, mut timer: ResMut<Tick>) {
+#[cfg(feature = "animations")]
+pub fn frequency_increaser(
+    keys: Res<Input<KeyCode>>,
+    bindings: Res<Keybindings>,
+    mut timer: ResMut<Tick>,
+) {
     let f = timer.frequency();
-    if keys.just_released(KeyCode::J) {
+    if bindings.released(&keys, Action::Faster) {
         timer.set_frequency(f * 2.);
     }
-    if keys.just_released(KeyCode::K) {
+    if bindings.released(&keys, Action::Slower) {
         timer.set_frequency(f / 2.);
     }
 }
 
-#[derive(Debug, Component)]
-pub struct Scroll(pub f32);
+#[cfg(feature = "animations")]
+pub fn toggle_running(
+    keys: Res<Input<KeyCode>>,
+    bindings: Res<Keybindings>,
+    mut run: ResMut<Running>,
+) {
+    if bindings.released(&keys, Action::Pause) {
+        run.0 ^= true;
+    }
+}
 
-const ZOOM_SPEED: f32 = 4.0;
+#[cfg(feature = "animations")]
+pub fn quit(keys: Res<Input<KeyCode>>, bindings: Res<Keybindings>, mut exit: EventWriter<AppExit>) {
+    if bindings.pressed(&keys, Action::Quit) {
+        exit.send(AppExit);
+    }
+}
 
-const ZOOM_SENSITIVITY: f32 = 0.1;
-pub fn mouse(
-    time: Res<Time>,
-    mouse: Res<Input<MouseButton>>,
-    mut motion: EventReader<MouseMotion>,
-    mut scroll: EventReader<MouseWheel>,
-    mut query: Query<(&mut Scroll, &mut Transform), With<Camera>>,
-) {
-    let pressed = mouse.any_pressed([MouseButton::Left, MouseButton::Right]);
-    let motion = motion.read().map(|ev| ev.delta).sum::<Vec2>();
-    let delta = scroll.read().map(|ev| ev.y).sum::<f32>();
-
-    for (mut scroll, mut tf) in query.iter_mut() {
-        scroll.0 += delta * ZOOM_SENSITIVITY;
-        let mut s = tf.scale.x;
-        s += ZOOM_SPEED * (scroll.0.exp() - s) * time.delta_seconds();
-        tf.scale = Vec3::splat(s);
-        if pressed {
-            tf.translation += Vec3::new(-motion.x, motion.y, 0.) * s;
+/// Keys shared by all animations, by default: space toggles [`Running`], J/K double/halve the frequency of the
+/// [`Tick`] (if there is one) and Q quits. The [`Keybindings`] come from [`keybindings::FILE`],
+/// unless inserted before adding this plugin. R starts the animation over (see [`reset`]). Also
+/// plays the [`demo::Script`] inserted before adding this plugin, if any, and shows a [`summary`]
+/// card once the animation is finished
+#[cfg(feature = "animations")]
+#[derive(Debug)]
+pub struct AocControlPlugin;
+
+#[cfg(feature = "animations")]
+impl Plugin for AocControlPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.world.contains_resource::<Keybindings>() {
+            let bindings = Keybindings::load(keybindings::FILE).unwrap_or_else(|e| {
+                error!("{e:#}, using the default keys");
+                Keybindings::default()
+            });
+            app.insert_resource(bindings);
         }
+        if let Some(script) = app.world.get_resource::<demo::Script>().cloned() {
+            app.add_plugins(demo::DemoPlugin(script));
+        }
+        app.add_plugins(summary::SummaryPlugin);
+        app.init_resource::<Running>()
+            .add_event::<reset::Reset>()
+            .init_schedule(reset::OnReset)
+            .init_schedule(reset::OnLoad)
+            .add_systems(PreStartup, reset::load)
+            .add_systems(
+                Update,
+                (
+                    toggle_running,
+                    frequency_increaser.run_if(resource_exists::<Tick>()),
+                    quit,
+                    (
+                        reset::request,
+                        reset::reset.run_if(on_event::<reset::Reset>()),
+                    )
+                        .chain(),
+                ),
+            );
     }
 }
 
-pub fn toggle_running(keys: Res<Input<KeyCode>>, mut run: ResMut<Running>) {
-    if keys.just_released(KeyCode::Space) {
-        run.0 ^= true;
+/// How the binaries present an animation, as given on the command line: the settings of the camera,
+/// a demo script to play and where to record the frames to. Has to be added before the other
+/// plugins of the animation, which read these resources while being built, e.g. as part of the
+/// [`DefaultPlugins`]: `DefaultPlugins.build().add(presentation)`
+#[cfg(feature = "animations")]
+#[derive(Debug, Default, Clone)]
+pub struct Presentation {
+    pub camera: camera::CameraSettings,
+    pub demo: Option<demo::Script>,
+    pub recording: Option<capture::Recording>,
+}
+
+#[cfg(feature = "animations")]
+impl Plugin for Presentation {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.camera);
+        if let Some(script) = &self.demo {
+            app.insert_resource(script.clone());
+        }
+        if let Some(recording) = &self.recording {
+            app.insert_resource(recording.clone());
+        }
     }
 }
 
+#[cfg(feature = "animations")]
 pub(crate) fn rect(x: f32, y: f32, z: f32, w: f32, h: f32, color: Color) -> SpriteBundle {
     SpriteBundle {
         sprite: Sprite {
@@ -197,6 +678,7 @@ pub(crate) fn rect(x: f32, y: f32, z: f32, w: f32, h: f32, color: Color) -> Spri
     }
 }
 
+#[cfg(feature = "animations")]
 pub(crate) fn arc_segment(n: usize, arc: &ArcSegment) -> Mesh {
     let mut vertices = Vec::new();
     let mut faces = Vec::new();
@@ -218,6 +700,7 @@ pub(crate) fn arc_segment(n: usize, arc: &ArcSegment) -> Mesh {
         .with_indices(Some(Indices::U32(faces)))
 }
 
+#[cfg(feature = "animations")]
 #[derive(Default, Debug, Component, Clone, PartialEq)]
 pub(crate) struct ArcSegment {
     /// Offset
@@ -230,6 +713,7 @@ pub(crate) struct ArcSegment {
     ro: f32,
 }
 
+#[cfg(feature = "animations")]
 pub(crate) fn in_states<S>(states: &'static [S]) -> impl Condition<()>
 where
     S: States,
@@ -273,8 +757,165 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(feature = "animations")]
+    use bevy::input::{keyboard::KeyboardInput, ButtonState};
     use rstest::rstest;
-    use std::iter::{empty, once};
+    use std::{
+        collections::HashSet,
+        iter::{empty, once},
+    };
+
+    fn unix(input: &str) -> String {
+        input.to_owned()
+    }
+    fn crlf(input: &str) -> String {
+        input.replace('\n', "\r\n")
+    }
+    fn trailing_blank_lines(input: &str) -> String {
+        format!("{input}\n\n\n")
+    }
+
+    #[rstest]
+    #[case(2, Part::One, include_str!("../sample/second.txt"), "8")]
+    #[case(2, Part::Two, include_str!("../sample/second.txt"), "2286")]
+    #[case(3, Part::One, include_str!("../sample/third.txt"), "4361")]
+    #[case(3, Part::Two, include_str!("../sample/third.txt"), "467835")]
+    #[case(4, Part::One, include_str!("../sample/fourth.txt"), "13")]
+    #[case(4, Part::Two, include_str!("../sample/fourth.txt"), "30")]
+    #[case(5, Part::One, include_str!("../sample/fifth.txt"), "35")]
+    #[case(5, Part::Two, include_str!("../sample/fifth.txt"), "46")]
+    #[case(6, Part::One, include_str!("../sample/sixth.txt"), "288")]
+    #[case(6, Part::Two, include_str!("../sample/sixth.txt"), "71503")]
+    #[case(7, Part::One, include_str!("../sample/seventh.txt"), "6440")]
+    #[case(7, Part::Two, include_str!("../sample/seventh.txt"), "5905")]
+    #[case(8, Part::One, include_str!("../sample/eighth.txt"), "6")]
+    #[case(9, Part::One, include_str!("../sample/ninth.txt"), "114")]
+    #[case(9, Part::Two, include_str!("../sample/ninth.txt"), "2")]
+    #[case(10, Part::One, include_str!("../sample/tenth-b.txt"), "8")]
+    #[case(10, Part::Two, include_str!("../sample/tenth-e.txt"), "8")]
+    #[case(10, Part::Two, include_str!("../sample/tenth-f.txt"), "35")]
+    #[case(11, Part::One, include_str!("../sample/eleventh.txt"), "374")]
+    #[case(11, Part::Two, include_str!("../sample/eleventh.txt"), "82000210")]
+    #[case(12, Part::One, include_str!("../sample/twelfth.txt"), "21")]
+    #[case(12, Part::Two, include_str!("../sample/twelfth.txt"), "525152")]
+    #[case(13, Part::One, include_str!("../sample/thirteenth.txt"), "405")]
+    #[case(13, Part::Two, include_str!("../sample/thirteenth.txt"), "400")]
+    #[case(14, Part::One, include_str!("../sample/fourteenth.txt"), "136")]
+    #[case(14, Part::Two, include_str!("../sample/fourteenth.txt"), "64")]
+    #[case(15, Part::One, include_str!("../sample/fifteenth.txt"), "1320")]
+    #[case(15, Part::Two, include_str!("../sample/fifteenth.txt"), "145")]
+    #[case(16, Part::One, include_str!("../sample/sixteenth.txt"), "46")]
+    #[case(16, Part::Two, include_str!("../sample/sixteenth.txt"), "51")]
+    #[case(17, Part::One, include_str!("../sample/seventeenth.txt"), "102")]
+    #[case(17, Part::Two, include_str!("../sample/seventeenth.txt"), "94")]
+    #[case(18, Part::One, include_str!("../sample/eighteenth.txt"), "62")]
+    #[case(18, Part::Two, include_str!("../sample/eighteenth.txt"), "952408144115")]
+    #[case(19, Part::One, include_str!("../sample/nineteenth.txt"), "19114")]
+    #[case(19, Part::Two, include_str!("../sample/nineteenth.txt"), "167409079868000")]
+    #[case(21, Part::One, include_str!("../sample/twentyfirst.txt"), "42")]
+    #[case(22, Part::One, include_str!("../sample/twentysecond.txt"), "5")]
+    #[case(22, Part::Two, include_str!("../sample/twentysecond.txt"), "7")]
+    #[case(23, Part::One, include_str!("../sample/twentythird.txt"), "94")]
+    #[case(23, Part::Two, include_str!("../sample/twentythird.txt"), "154")]
+    #[case(24, Part::Two, include_str!("../sample/twentyfourth.txt"), "47")]
+    #[case(25, Part::One, include_str!("../sample/twentyfifth.txt"), "54")]
+    fn solve_samples(
+        #[case] day: u8,
+        #[case] part: Part,
+        #[case] input: &str,
+        #[case] expected: &str,
+        #[values(unix, crlf, trailing_blank_lines)] variant: fn(&str) -> String,
+    ) {
+        assert_eq!(
+            solve(Day::try_from(day).expect("day"), part, &variant(input)).expect("solving"),
+            expected
+        );
+    }
+
+    #[rstest]
+    fn solve_fixtures() {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(fixtures::FIXTURES_DIR);
+        let answers =
+            std::fs::read_to_string(dir.join(fixtures::ANSWERS_FILE)).expect("reading answers");
+        let solvers = solvers();
+        for line in answers.lines().filter(|line| !line.starts_with('#')) {
+            let [day, file, one, two] = line.split_whitespace().collect::<Vec<_>>()[..] else {
+                panic!("Expected day, file and both answers, got {line:?}");
+            };
+            let Some(solver) = solvers.get(&day.parse().expect("day")) else {
+                continue;
+            };
+            let input = std::fs::read_to_string(dir.join(file)).expect("reading fixture");
+            for (part, expected) in [(Part::One, one), (Part::Two, two)] {
+                let solution = solver.solve(part, &normalize_input(&input));
+                match expected {
+                    fixtures::FAILS => {
+                        assert!(solution.is_err(), "{file} part {part:?}: {solution:?}")
+                    }
+                    _ => assert_eq!(solution.expect("solving"), expected, "{file} part {part:?}"),
+                }
+            }
+        }
+    }
+
+    #[rstest]
+    #[case("a\r\nb\r\n", "a\nb\n")]
+    #[case("a\nb", "a\nb\n")]
+    #[case("a\nb\n\n\n", "a\nb\n")]
+    #[case("a\r\n\r\nb\r\n\r\n", "a\n\nb\n")]
+    fn normalized_inputs(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(normalize_input(input), expected);
+    }
+
+    #[rstest]
+    fn registry_lists_library_days() {
+        assert_eq!(
+            solvers().keys().map(Day::number).collect::<Vec<_>>(),
+            vec![
+                2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 21, 22, 23, 24, 25
+            ]
+        );
+    }
+
+    #[rstest]
+    fn every_solver_has_a_sample() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("sample");
+        for (day, solver) in solvers() {
+            let path = solver.input(&dir);
+            assert!(path.exists(), "Day {day} has no sample at {path:?}");
+        }
+    }
+
+    #[rstest]
+    fn solve_binary_only_day() {
+        assert!(solve(Day::First, Part::One, "").is_err());
+    }
+
+    #[rstest]
+    fn strategies_agree_on_samples() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        for (day, solver) in solvers() {
+            let Ok(input) = std::fs::read_to_string(solver.input(&root.join("sample"))) else {
+                continue;
+            };
+            for part in all::<Part>() {
+                let Ok(expected) = solver.solve(part, &input) else {
+                    continue;
+                };
+                for name in solver.strategy_names() {
+                    // Some strategies give up on inputs too large for them
+                    if let Ok(answer) = solver.solve_with(name, part, &input) {
+                        assert_eq!(answer, expected, "Day {day} part {part:?} by {name}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[rstest]
+    fn unknown_strategy() {
+        assert!(solve_with(Day::Sixteenth, "guessing", Part::One, "").is_err());
+    }
 
     #[rstest]
     #[case(None, empty())]
@@ -288,4 +929,66 @@ mod tests {
     ) {
         assert_eq!(expected, cycle(xs));
     }
+
+    /// Presses & releases `key` within the next update
+    #[cfg(feature = "animations")]
+    fn tap(app: &mut App, key: KeyCode) {
+        for state in [ButtonState::Pressed, ButtonState::Released] {
+            app.world
+                .resource_mut::<Events<KeyboardInput>>()
+                .send(KeyboardInput {
+                    scan_code: 0,
+                    key_code: Some(key),
+                    state,
+                    window: Entity::PLACEHOLDER,
+                });
+        }
+        app.update();
+    }
+
+    #[cfg(feature = "animations")]
+    #[rstest]
+    fn controls_toggle_running_and_quit() {
+        let mut app = App::new();
+        app.add_plugins((HeadlessPlugins, AocControlPlugin))
+            .insert_resource(Tick::new(2.));
+        app.update();
+
+        tap(&mut app, KeyCode::Space);
+        assert!(app.world.resource::<Running>().inner());
+        tap(&mut app, KeyCode::J);
+        assert_eq!(app.world.resource::<Tick>().frequency(), 4.);
+        assert!(app.world.resource::<Events<AppExit>>().is_empty());
+        tap(&mut app, KeyCode::Q);
+        assert!(!app.world.resource::<Events<AppExit>>().is_empty());
+    }
+
+    #[rstest]
+    #[case(Direction::Up, Turn::Left, Direction::Left)]
+    #[case(Direction::Up, Turn::Back, Direction::Down)]
+    #[case(Direction::Left, Turn::Right, Direction::Up)]
+    #[case(Direction::Down, Turn::Straight, Direction::Down)]
+    fn turning(#[case] dir: Direction, #[case] turn: Turn, #[case] expected: Direction) {
+        assert_eq!(dir.turn(turn), expected);
+        assert_eq!(Compass::from(dir).turn(turn), Compass::from(expected));
+    }
+
+    #[rstest]
+    #[case(Compass::NE, Coord::new(1, -1))]
+    #[case(Compass::SW, Coord::new(-1, 1))]
+    #[case(Compass::W, Coord::new(-1, 0))]
+    fn compass_offsets(#[case] dir: Compass, #[case] expected: Coord) {
+        assert_eq!(Coord::from(dir), expected);
+        assert_eq!(Coord::from(dir.opposite()), -expected);
+    }
+
+    #[rstest]
+    fn neighbors_surround_coord() {
+        let coord = Coord::new(3, 5);
+        let ring = neighbors8(coord).collect::<HashSet<_>>();
+        assert_eq!(ring.len(), 8);
+        assert!(!ring.contains(&coord));
+        assert!(neighbors4(coord).all(|n| ring.contains(&n)));
+        assert_eq!(Compass::diagonals().count(), 4);
+    }
 }
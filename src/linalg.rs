@@ -0,0 +1,140 @@
+//! Exact vector math in 3D on integers too large for floats to stay exact, e.g. the positions of
+//! Day 24's hailstones in the hundreds of trillions
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// Vector of three `i128`s. Only [`Vector::cross`] checks for overflows, since its products are
+/// the only ones to get near the limits on puzzle inputs
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Vector(pub [i128; 3]);
+
+impl Vector {
+    pub const ZERO: Self = Self([0; 3]);
+
+    pub fn new(x: i128, y: i128, z: i128) -> Self {
+        Self([x, y, z])
+    }
+
+    pub fn x(&self) -> i128 {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> i128 {
+        self.0[1]
+    }
+
+    pub fn z(&self) -> i128 {
+        self.0[2]
+    }
+
+    pub fn dot(&self, other: &Self) -> i128 {
+        self.0.iter().zip(other.0).map(|(a, b)| a * b).sum()
+    }
+
+    /// Vector perpendicular to both, `None` if it doesn't fit into `i128`s
+    pub fn cross(&self, other: &Self) -> Option<Self> {
+        let [a, b] = [self.0, other.0];
+        let component =
+            |i: usize, j: usize| a[i].checked_mul(b[j])?.checked_sub(a[j].checked_mul(b[i])?);
+        Some(Self([component(1, 2)?, component(2, 0)?, component(0, 1)?]))
+    }
+
+    /// Whether both point along the same line, in the same or opposite direction
+    pub fn is_parallel(&self, other: &Self) -> Option<bool> {
+        Some(self.cross(other)? == Self::ZERO)
+    }
+
+    /// Shortest vector of the same direction with integer components, i.e. divided by the greatest
+    /// common divisor of its components
+    pub fn primitive(&self) -> Self {
+        match self.0.into_iter().fold(0, gcd) {
+            0 => *self,
+            divisor => Self(self.0.map(|c| c / divisor)),
+        }
+    }
+
+    /// Exact quotient of each component by `divisor`, `None` unless all of them are multiples
+    pub fn div_exact(&self, divisor: i128) -> Option<Self> {
+        (divisor != 0 && self.0.iter().all(|c| c % divisor == 0))
+            .then(|| Self(self.0.map(|c| c / divisor)))
+    }
+}
+
+/// Greatest common divisor, never negative
+pub fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Add for Vector {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self([0, 1, 2].map(|i| self.0[i] + other.0[i]))
+    }
+}
+
+impl Sub for Vector {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self([0, 1, 2].map(|i| self.0[i] - other.0[i]))
+    }
+}
+
+impl Mul<i128> for Vector {
+    type Output = Self;
+
+    fn mul(self, factor: i128) -> Self {
+        Self(self.0.map(|c| c * factor))
+    }
+}
+
+impl Neg for Vector {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(self.0.map(|c| -c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Vector::new(1, 0, 0), Vector::new(0, 1, 0), Vector::new(0, 0, 1))]
+    #[case(Vector::new(2, 3, 4), Vector::new(5, 6, 7), Vector::new(-3, 6, -3))]
+    #[case(Vector::new(2, 3, 4), Vector::new(4, 6, 8), Vector::ZERO)]
+    fn cross_products(#[case] a: Vector, #[case] b: Vector, #[case] expected: Vector) {
+        assert_eq!(a.cross(&b), Some(expected));
+        assert_eq!(b.cross(&a), Some(-expected));
+        assert_eq!(expected.dot(&a), 0);
+    }
+
+    #[rstest]
+    fn cross_product_overflows() {
+        let huge = Vector::new(i128::MAX / 2, 1, 0);
+        assert_eq!(huge.cross(&Vector::new(0, 4, 0)), None);
+    }
+
+    #[rstest]
+    #[case(Vector::new(6, -9, 12), Vector::new(2, -3, 4))]
+    #[case(Vector::new(0, 0, -5), Vector::new(0, 0, -1))]
+    #[case(Vector::ZERO, Vector::ZERO)]
+    fn primitives(#[case] v: Vector, #[case] expected: Vector) {
+        assert_eq!(v.primitive(), expected);
+    }
+
+    #[rstest]
+    fn divides_exactly() {
+        let v = Vector::new(6, -9, 12);
+        assert_eq!(v.div_exact(3), Some(Vector::new(2, -3, 4)));
+        assert_eq!(v.div_exact(2), None);
+        assert_eq!(v.div_exact(0), None);
+    }
+}
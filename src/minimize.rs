@@ -0,0 +1,111 @@
+//! Delta debugging of puzzle inputs: shrink an input, on which a solver misbehaves, to a minimal
+//! one which still shows the same misbehaviour
+
+use anyhow::anyhow;
+
+/// Pieces an input can be reduced by, without making each piece unparsable on its own
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Structure {
+    /// Single lines
+    #[default]
+    Lines,
+    /// Groups of lines, separated by blank lines
+    Blocks,
+}
+
+impl Structure {
+    fn split(self, input: &str) -> Vec<&str> {
+        match self {
+            Self::Lines => input.lines().collect(),
+            Self::Blocks => input
+                .split("\n\n")
+                .map(str::trim_end)
+                .filter(|block| !block.is_empty())
+                .collect(),
+        }
+    }
+
+    fn join(self, chunks: &[&str]) -> String {
+        let separator = match self {
+            Self::Lines => "\n",
+            Self::Blocks => "\n\n",
+        };
+        let mut input = chunks.join(separator);
+        input.push('\n');
+        input
+    }
+}
+
+/// Smallest input built from `input`'s chunks, for which `failing` still holds. Every single
+/// chunk of the result is needed to make it fail (1-minimal), but not necessarily the fewest
+pub fn minimize(
+    input: &str,
+    structure: Structure,
+    mut failing: impl FnMut(&str) -> bool,
+) -> anyhow::Result<String> {
+    if !failing(input) {
+        return Err(anyhow!("The input does not fail to begin with"));
+    }
+    let mut chunks = structure.split(input);
+    let mut failing = |chunks: &[&str]| failing(&structure.join(chunks));
+    let mut n = 2;
+    while chunks.len() >= 2 {
+        let size = chunks.len().div_ceil(n);
+        let starts = (0..chunks.len()).step_by(size);
+
+        let subset = starts
+            .clone()
+            .map(|start| chunks[start..(start + size).min(chunks.len())].to_vec())
+            .find(|subset| failing(subset));
+        if let Some(subset) = subset {
+            chunks = subset;
+            n = 2;
+            continue;
+        }
+
+        let complement = starts
+            .map(|start| {
+                let end = (start + size).min(chunks.len());
+                [&chunks[..start], &chunks[end..]].concat()
+            })
+            .find(|complement| failing(complement));
+        if let Some(complement) = complement {
+            chunks = complement;
+            n = (n - 1).max(2);
+            continue;
+        }
+
+        if n >= chunks.len() {
+            break;
+        }
+        n = (2 * n).min(chunks.len());
+    }
+    Ok(structure.join(&chunks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Structure::Lines, "a\nb\nx\nc\nd\ne\ny\nf\n", "x\ny\n")]
+    #[case(Structure::Lines, "x\ny\n", "x\ny\n")]
+    #[case(Structure::Blocks, "a\nb\n\nx\nc\n\nd\n\ny\n\n\n", "x\nc\n\ny\n")]
+    fn keeps_only_needed_chunks(
+        #[case] structure: Structure,
+        #[case] input: &str,
+        #[case] expected: &str,
+    ) {
+        let failing = |input: &str| input.contains('x') && input.contains('y');
+        assert_eq!(
+            minimize(input, structure, failing).expect("minimizing"),
+            expected
+        );
+    }
+
+    #[rstest]
+    fn needs_failing_input() {
+        assert!(minimize("a\nb\n", Structure::Lines, |input| input.contains('x')).is_err());
+    }
+}
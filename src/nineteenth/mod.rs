@@ -0,0 +1,318 @@
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    ops::Range,
+    str::FromStr,
+};
+
+use anyhow::{anyhow, bail};
+use nom::Finish;
+
+use self::parser::system;
+use crate::{anyhowing, Part, Solver};
+
+mod parser;
+
+/// Name of the workflow, where all parts start
+pub const START: &str = "in";
+/// Ratings a part can have in each category (part two)
+pub const RATINGS: Range<u64> = 1..4001;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+    /// Extremely cool looking
+    X,
+    /// Musical
+    M,
+    /// Aerodynamic
+    A,
+    /// Shiny
+    S,
+}
+
+/// A machine part with its rating in each [`Category`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MachinePart(pub [u64; 4]);
+
+impl MachinePart {
+    pub fn rating(&self, category: Category) -> u64 {
+        self.0[category as usize]
+    }
+
+    /// Sum of its ratings in all categories
+    pub fn total(&self) -> u64 {
+        self.0.iter().sum()
+    }
+}
+
+/// All parts with their rating of each [`Category`] in the respective range
+pub type Hypercube = [Range<u64>; 4];
+
+/// Number of distinct parts in the `cube`
+pub fn combinations(cube: &Hypercube) -> u64 {
+    cube.iter()
+        .map(|range| range.end.saturating_sub(range.start))
+        .product()
+}
+
+/// Where a part goes next
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Target {
+    Accept,
+    Reject,
+    Workflow(String),
+}
+
+/// A part's rating in `category` compared to `value` has to be `ordering`, e.g. `a<2006`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Condition {
+    pub category: Category,
+    pub ordering: Ordering,
+    pub value: u64,
+}
+
+impl Condition {
+    pub fn matches(&self, part: &MachinePart) -> bool {
+        part.rating(self.category).cmp(&self.value) == self.ordering
+    }
+
+    /// The parts of `cube` matching the condition and the rest of them, `None` where there are no
+    /// such parts
+    pub fn split(&self, cube: Hypercube) -> (Option<Hypercube>, Option<Hypercube>) {
+        let i = self.category as usize;
+        let range = cube[i].clone();
+        let (matching, rest) = match self.ordering {
+            Ordering::Less => (
+                range.start..range.end.min(self.value),
+                range.start.max(self.value)..range.end,
+            ),
+            Ordering::Greater => (
+                range.start.max(self.value + 1)..range.end,
+                range.start..range.end.min(self.value + 1),
+            ),
+            Ordering::Equal => (range, 0..0),
+        };
+        let with = |range: Range<u64>| {
+            let mut cube = cube.clone();
+            cube[i] = range;
+            (!cube[i].is_empty()).then_some(cube)
+        };
+        (with(matching), with(rest))
+    }
+}
+
+/// Rules tried one after the other, sending a part to the target of the first one it matches and
+/// to the `fallback` if it matches none
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workflow {
+    pub rules: Vec<(Condition, Target)>,
+    pub fallback: Target,
+}
+
+impl Workflow {
+    pub fn route(&self, part: &MachinePart) -> &Target {
+        self.rules
+            .iter()
+            .find(|(condition, _)| condition.matches(part))
+            .map_or(&self.fallback, |(_, target)| target)
+    }
+
+    /// Splits `cube` by the rules into the parts going to each target
+    pub fn route_all(&self, cube: Hypercube) -> Vec<(&Target, Hypercube)> {
+        let mut routes = Vec::new();
+        let mut rest = Some(cube);
+        for (condition, target) in &self.rules {
+            let Some(cube) = rest else {
+                break;
+            };
+            let (matching, unmatched) = condition.split(cube);
+            routes.extend(matching.map(|cube| (target, cube)));
+            rest = unmatched;
+        }
+        routes.extend(rest.map(|cube| (&self.fallback, cube)));
+        routes
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct System {
+    workflows: HashMap<String, Workflow>,
+    parts: Vec<MachinePart>,
+}
+
+impl FromStr for System {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(system(s).finish().map_err(anyhowing)?.1)
+    }
+}
+
+impl System {
+    pub fn parts(&self) -> &[MachinePart] {
+        &self.parts
+    }
+
+    pub fn workflow(&self, name: &str) -> anyhow::Result<&Workflow> {
+        self.workflows
+            .get(name)
+            .ok_or(anyhow!("There is no workflow {name}"))
+    }
+
+    /// Whether `part` ends up accepted, when following the workflows from [`START`]
+    pub fn accepts(&self, part: &MachinePart) -> anyhow::Result<bool> {
+        let mut visited = HashSet::new();
+        let mut name = START;
+        while visited.insert(name) {
+            match self.workflow(name)?.route(part) {
+                Target::Accept => return Ok(true),
+                Target::Reject => return Ok(false),
+                Target::Workflow(next) => name = next,
+            }
+        }
+        bail!("{part:?} runs in circles through workflow {name}")
+    }
+
+    /// Sum of the total ratings of all accepted parts (part one)
+    pub fn accepted_ratings(&self) -> anyhow::Result<u64> {
+        self.parts.iter().try_fold(0, |sum, part| {
+            Ok(sum + if self.accepts(part)? { part.total() } else { 0 })
+        })
+    }
+
+    /// Disjoint hypercubes of all the parts in `cube`, which end up accepted. Like pushing ranges
+    /// of seeds through the almanac of Day 5, just in four dimensions and along a tree of workflows
+    pub fn accepted(&self, cube: Hypercube) -> anyhow::Result<Vec<Hypercube>> {
+        let mut accepted = Vec::new();
+        let mut open = vec![(START, cube, 0)];
+        while let Some((name, cube, depth)) = open.pop() {
+            // Without circles no path visits more workflows than there are
+            if depth > self.workflows.len() {
+                bail!("Parts run in circles through workflow {name}");
+            }
+            for (target, cube) in self.workflow(name)?.route_all(cube) {
+                match target {
+                    Target::Accept => accepted.push(cube),
+                    Target::Reject => {}
+                    Target::Workflow(next) => open.push((next, cube, depth + 1)),
+                }
+            }
+        }
+        Ok(accepted)
+    }
+
+    /// How many distinct parts with [`RATINGS`] get accepted (part two)
+    pub fn accepted_combinations(&self) -> anyhow::Result<u64> {
+        let cubes = self.accepted([RATINGS; 4])?;
+        Ok(cubes.iter().map(combinations).sum())
+    }
+}
+
+pub fn solve(part: Part, input: &str) -> anyhow::Result<u64> {
+    let system = System::from_str(input)?;
+    match part {
+        Part::One => system.accepted_ratings(),
+        Part::Two => system.accepted_combinations(),
+    }
+}
+
+/// Day 19: Aplenty
+#[derive(Debug, Clone, Copy)]
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    fn name(&self) -> &'static str {
+        "nineteenth"
+    }
+
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::One, input).map(|x| x.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::Two, input).map(|x| x.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const SAMPLE: &str = include_str!("../../sample/nineteenth.txt");
+
+    #[rstest]
+    #[case(Part::One, 19114)]
+    #[case(Part::Two, 167409079868000)]
+    fn sample(#[case] part: Part, #[case] expected: u64) {
+        assert_eq!(solve(part, SAMPLE).expect("solving"), expected);
+    }
+
+    #[rstest]
+    fn routes_sample_parts() {
+        let system = System::from_str(SAMPLE).expect("parsing");
+        let accepted = system
+            .parts()
+            .iter()
+            .map(|part| system.accepts(part).expect("routing"))
+            .collect::<Vec<_>>();
+        assert_eq!(accepted, vec![true, false, true, false, true]);
+    }
+
+    #[rstest]
+    #[case(Ordering::Less, 10, Some(1..10), Some(10..21))]
+    #[case(Ordering::Greater, 10, Some(11..21), Some(1..11))]
+    #[case(Ordering::Less, 1, None, Some(1..21))]
+    #[case(Ordering::Greater, 20, None, Some(1..21))]
+    #[case(Ordering::Less, 30, Some(1..21), None)]
+    fn splits_cubes(
+        #[case] ordering: Ordering,
+        #[case] value: u64,
+        #[case] matching: Option<Range<u64>>,
+        #[case] rest: Option<Range<u64>>,
+    ) {
+        let condition = Condition {
+            category: Category::M,
+            ordering,
+            value,
+        };
+        let cube = [5..6, 1..21, 7..8, 9..10];
+        let with = |range: Range<u64>| [5..6, range, 7..8, 9..10];
+        assert_eq!(condition.split(cube), (matching.map(with), rest.map(with)));
+    }
+
+    #[rstest]
+    fn accepted_cubes_are_disjoint() {
+        let system = System::from_str(SAMPLE).expect("parsing");
+        let cubes = system
+            .accepted([1..201, 1..201, 1..201, 1..201])
+            .expect("accepting");
+        let overlap = |a: &Hypercube, b: &Hypercube| {
+            a.iter()
+                .zip(b)
+                .all(|(a, b)| a.start < b.end && b.start < a.end)
+        };
+        for (i, a) in cubes.iter().enumerate() {
+            assert!(cubes[i + 1..].iter().all(|b| !overlap(a, b)));
+        }
+    }
+
+    #[rstest]
+    #[case("in{x<10:aa,R}\naa{in}\n\n{x=1,m=1,a=1,s=1}")]
+    #[case("in{x<10:aa,R}\n\n{x=1,m=1,a=1,s=1}")]
+    fn broken_workflows(#[case] input: &str) {
+        let system = System::from_str(input).expect("parsing");
+        assert!(system.accepts(&system.parts()[0]).is_err());
+        assert!(system.accepted_combinations().is_err());
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("in{A}")]
+    #[case("in{x<10:A}\n\n{x=1,m=1,a=1,s=1}")]
+    #[case("in{A}\n\n{x=1,m=1,a=1}")]
+    #[case("in{y<10:A,R}\n\n{x=1,m=1,a=1,s=1}")]
+    fn invalid_systems(#[case] input: &str) {
+        assert!(System::from_str(input).is_err());
+    }
+}
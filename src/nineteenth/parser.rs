@@ -0,0 +1,96 @@
+use std::cmp::Ordering;
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, char, line_ending, multispace0, multispace1},
+    multi::{many0, separated_list1},
+    sequence::{delimited, separated_pair, terminated, tuple},
+    IResult, Parser as NomParser,
+};
+use nom_supreme::ParserExt;
+
+use super::{Category, Condition, MachinePart, System, Target, Workflow};
+use crate::parse::common::integer;
+
+pub(crate) fn category(s: &str) -> IResult<&str, Category> {
+    alt((
+        char('x').value(Category::X),
+        char('m').value(Category::M),
+        char('a').value(Category::A),
+        char('s').value(Category::S),
+    ))
+    .parse(s)
+}
+
+pub(crate) fn target(s: &str) -> IResult<&str, Target> {
+    alpha1
+        .map(|name| match name {
+            "A" => Target::Accept,
+            "R" => Target::Reject,
+            name => Target::Workflow(name.to_owned()),
+        })
+        .parse(s)
+}
+
+/// E.g. `a<2006`
+pub(crate) fn condition(s: &str) -> IResult<&str, Condition> {
+    tuple((
+        category,
+        alt((
+            char('<').value(Ordering::Less),
+            char('>').value(Ordering::Greater),
+        )),
+        integer,
+    ))
+    .map(|(category, ordering, value)| Condition {
+        category,
+        ordering,
+        value,
+    })
+    .parse(s)
+}
+
+/// E.g. `px{a<2006:qkq,m>2090:A,rfg}`
+pub(crate) fn workflow(s: &str) -> IResult<&str, (String, Workflow)> {
+    let rule = separated_pair(condition, char(':'), target);
+    tuple((
+        alpha1.map(String::from),
+        delimited(
+            char('{'),
+            tuple((many0(terminated(rule, char(','))), target)),
+            char('}'),
+        ),
+    ))
+    .map(|(name, (rules, fallback))| (name, Workflow { rules, fallback }))
+    .parse(s)
+}
+
+/// E.g. `{x=787,m=2655,a=1222,s=2876}`
+pub(crate) fn part(s: &str) -> IResult<&str, MachinePart> {
+    tuple((
+        integer.preceded_by(tag("{x=")),
+        integer.preceded_by(tag(",m=")),
+        integer.preceded_by(tag(",a=")),
+        integer.preceded_by(tag(",s=")),
+    ))
+    .terminated(char('}'))
+    .map(|(x, m, a, s)| MachinePart([x, m, a, s]))
+    .parse(s)
+}
+
+pub(crate) fn system(s: &str) -> IResult<&str, System> {
+    multispace0
+        .precedes(separated_pair(
+            separated_list1(line_ending, workflow),
+            multispace1,
+            separated_list1(line_ending, part),
+        ))
+        .terminated(multispace0)
+        .all_consuming()
+        .map(|(workflows, parts)| System {
+            workflows: workflows.into_iter().collect(),
+            parts,
+        })
+        .parse(s)
+}
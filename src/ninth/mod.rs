@@ -0,0 +1,131 @@
+use std::str::FromStr;
+
+use nom::{Finish, Parser};
+use nom_supreme::ParserExt;
+
+use self::parser::{history, report};
+use crate::{anyhowing, Part, Solver};
+
+mod parser;
+
+/// Readings of one value of the OASIS over time, oldest first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct History(pub Vec<i64>);
+
+impl FromStr for History {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(history
+            .all_consuming()
+            .parse(s.trim())
+            .finish()
+            .map_err(anyhowing)?
+            .1)
+    }
+}
+
+impl History {
+    /// Difference table, starting with the history itself, each row the differences between the
+    /// neighbors of the one before, down to the first one of all zeros
+    pub fn differences(&self) -> Vec<Vec<i64>> {
+        let mut rows = vec![self.0.clone()];
+        while let Some(row) = rows.last().filter(|row| row.iter().any(|x| *x != 0)) {
+            let next = row.windows(2).map(|w| w[1] - w[0]).collect();
+            rows.push(next);
+        }
+        rows
+    }
+
+    /// Value the history extrapolates to next, the sum of the last values of all differences
+    pub fn next_value(&self) -> i64 {
+        self.differences().iter().filter_map(|row| row.last()).sum()
+    }
+
+    /// Value the history extrapolates to before its first one
+    pub fn previous_value(&self) -> i64 {
+        self.differences().iter().rev().fold(0, |below, row| {
+            row.first().map_or(below, |first| first - below)
+        })
+    }
+}
+
+/// All histories of the input, one per line
+pub fn histories(input: &str) -> anyhow::Result<Vec<History>> {
+    Ok(report(input).finish().map_err(anyhowing)?.1)
+}
+
+pub fn solve(part: Part, input: &str) -> anyhow::Result<i64> {
+    let histories = histories(input)?;
+    Ok(histories
+        .iter()
+        .map(|history| match part {
+            Part::One => history.next_value(),
+            Part::Two => history.previous_value(),
+        })
+        .sum())
+}
+
+/// Day 9: Mirage Maintenance
+#[derive(Debug, Clone, Copy)]
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    fn name(&self) -> &'static str {
+        "ninth"
+    }
+
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::One, input).map(|x| x.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::Two, input).map(|x| x.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Part::One, 114)]
+    #[case(Part::Two, 2)]
+    fn sample(#[case] part: Part, #[case] expected: i64) {
+        let input = include_str!("../../sample/ninth.txt");
+        assert_eq!(solve(part, input).expect("solving"), expected);
+    }
+
+    #[rstest]
+    #[case("0 3 6 9 12 15", 18, -3, &[15, 3, 0])]
+    #[case("1 3 6 10 15 21", 28, 0, &[21, 6, 1, 0])]
+    #[case("10 13 16 21 30 45", 68, 5, &[45, 15, 6, 2, 0])]
+    #[case("-4 -4 -4", -4, -4, &[-4, 0])]
+    #[case("7", 7, 7, &[7])]
+    fn extrapolates(
+        #[case] history: History,
+        #[case] next: i64,
+        #[case] previous: i64,
+        #[case] lasts: &[i64],
+    ) {
+        let table = history.differences();
+        assert_eq!(
+            table
+                .iter()
+                .filter_map(|row| row.last().copied())
+                .collect::<Vec<_>>(),
+            lasts
+        );
+        assert_eq!(history.next_value(), next);
+        assert_eq!(history.previous_value(), previous);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("1 2 x")]
+    #[case("1 2\n\n3 4")]
+    fn invalid_reports(#[case] input: &str) {
+        assert!(histories(input).is_err());
+    }
+}
@@ -0,0 +1,22 @@
+use nom::{
+    character::complete::{line_ending, multispace0},
+    multi::separated_list1,
+    IResult, Parser as NomParser,
+};
+use nom_supreme::ParserExt;
+
+use super::History;
+use crate::parse::common::{integer, list};
+
+/// Values of one history, oldest first, e.g. `0 3 6 9 12 15`
+pub(crate) fn history(s: &str) -> IResult<&str, History> {
+    list(integer::<i64>).map(History).parse(s)
+}
+
+pub(crate) fn report(s: &str) -> IResult<&str, Vec<History>> {
+    multispace0
+        .precedes(separated_list1(line_ending, history))
+        .terminated(multispace0)
+        .all_consuming()
+        .parse(s)
+}
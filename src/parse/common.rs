@@ -0,0 +1,125 @@
+//! Small [`nom`] combinators for the primitives most puzzle inputs are made of
+
+use std::str::FromStr;
+
+use nom::{
+    character::complete::{char, digit1, line_ending, multispace0, space0, space1},
+    combinator::{opt, recognize},
+    multi::{many1, separated_list1},
+    sequence::{delimited, pair, separated_pair, terminated},
+    IResult, Parser,
+};
+use nom_supreme::ParserExt;
+
+use crate::{Coord, Coord3};
+
+/// Run `inner`, ignoring any spaces or tabs around it (but no line endings)
+pub fn ws<'a, O>(
+    inner: impl Parser<&'a str, O, nom::error::Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, O> {
+    delimited(space0, inner, space0)
+}
+
+/// An optionally negative decimal number, e.g. `42` or `-7`
+pub fn integer<T: FromStr>(s: &str) -> IResult<&str, T> {
+    recognize(pair(opt(char('-')), digit1))
+        .map_res(str::parse)
+        .parse(s)
+}
+
+/// Two integers separated by a comma, e.g. `3,-4` or `3, -4`
+pub fn coord(s: &str) -> IResult<&str, Coord> {
+    separated_pair(integer::<i32>, ws(char(',')), integer::<i32>)
+        .map(|(x, y)| Coord::new(x, y))
+        .parse(s)
+}
+
+/// Three integers separated by commas, e.g. `1,0,1`
+pub fn coord3(s: &str) -> IResult<&str, Coord3> {
+    separated_pair(
+        integer::<i32>,
+        ws(char(',')),
+        separated_pair(integer::<i32>, ws(char(',')), integer::<i32>),
+    )
+    .map(|(x, (y, z))| Coord3::new(x, y, z))
+    .parse(s)
+}
+
+/// A list of `item`s separated by spaces, e.g. `41 48 83 86 17`
+pub fn list<'a, O>(
+    item: impl Parser<&'a str, O, nom::error::Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(space1, item)
+}
+
+/// A `label`, a colon and then a [`list`] of `item`s, e.g. `seeds: 79 14 55 13`
+pub fn labelled_list<'a, L, O>(
+    label: impl Parser<&'a str, L, nom::error::Error<&'a str>>,
+    item: impl Parser<&'a str, O, nom::error::Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (L, Vec<O>)> {
+    pair(terminated(label, ws(char(':'))), list(item))
+}
+
+/// Rows of `cell`s, one row per line. Trailing line endings are consumed as well
+pub fn grid_of<'a, O>(
+    cell: impl Parser<&'a str, O, nom::error::Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<Vec<O>>> {
+    terminated(separated_list1(line_ending, many1(cell)), multispace0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::{bytes::complete::tag, character::complete::one_of};
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("42", Ok(("", 42)))]
+    #[case("-7 rest", Ok((" rest", -7)))]
+    #[case("007,", Ok((",", 7)))]
+    fn integers(#[case] input: &str, #[case] expected: IResult<&str, i32>) {
+        assert_eq!(expected, integer(input));
+    }
+
+    #[rstest]
+    fn unsigned_integer_rejects_sign() {
+        assert!(integer::<u32>("-1").is_err());
+    }
+
+    #[rstest]
+    #[case("3,4", Coord::new(3, 4))]
+    #[case("3, -4", Coord::new(3, -4))]
+    #[case("-1 ,2", Coord::new(-1, 2))]
+    fn coords(#[case] input: &str, #[case] expected: Coord) {
+        assert_eq!(Ok(("", expected)), coord(input));
+    }
+
+    #[rstest]
+    #[case("1,0,1", Coord3::new(1, 0, 1))]
+    #[case("0, -2 ,10", Coord3::new(0, -2, 10))]
+    fn coord3s(#[case] input: &str, #[case] expected: Coord3) {
+        assert_eq!(Ok(("", expected)), coord3(input));
+    }
+
+    #[rstest]
+    #[case("seeds: 79 14 55 13")]
+    #[case("seeds:79 14 55 13")]
+    #[case("seeds  :  79 14 55 13")]
+    fn labelled_lists(#[case] input: &str) {
+        assert_eq!(
+            Ok(("", ("seeds", vec![79, 14, 55, 13]))),
+            labelled_list(tag("seeds"), integer::<u32>)(input)
+        );
+    }
+
+    #[rstest]
+    #[case("#.\n.#")]
+    #[case("#.\n.#\n")]
+    #[case("#.\r\n.#\r\n\r\n")]
+    fn grids(#[case] input: &str) {
+        assert_eq!(
+            Ok(("", vec![vec!['#', '.'], vec!['.', '#']])),
+            grid_of(one_of("#."))(input)
+        );
+    }
+}
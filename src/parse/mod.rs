@@ -0,0 +1,3 @@
+//! Parsers shared by more than one day
+
+pub mod common;
@@ -0,0 +1,98 @@
+//! Hovering a grid cell while an animation is paused shows a tooltip explaining that cell
+
+use std::marker::PhantomData;
+
+use bevy::{prelude::*, sprite::Anchor, window::PrimaryWindow};
+
+use crate::{Coord, Running};
+
+const FONT_SIZE: f32 = 24.;
+
+/// Day specific knowledge about the cells of a grid, which [`PickingPlugin`] shows in a tooltip
+pub trait CellInfo: Resource {
+    /// Cell drawn at the world `position`, if there is any
+    fn pick(&self, position: Vec2) -> Option<Coord>;
+
+    /// What is going on in `cell`, one line per fact
+    fn explain(&self, cell: Coord) -> Vec<String>;
+}
+
+/// Shows a tooltip with [`CellInfo::explain`] for the hovered cell of the `R` resource. Only
+/// while the animation is paused, i.e. its [`Running`] resource is off (or missing)
+#[derive(Debug)]
+pub struct PickingPlugin<R>(PhantomData<R>);
+
+impl<R> Default for PickingPlugin<R> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<R: CellInfo> Plugin for PickingPlugin<R> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_tooltip)
+            .add_systems(Update, explain_hovered_cell::<R>);
+    }
+}
+
+#[derive(Debug, Component)]
+struct Tooltip;
+
+fn spawn_tooltip(mut cmd: Commands) {
+    cmd.spawn((
+        Tooltip,
+        Text2dBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font_size: FONT_SIZE,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ),
+            text_anchor: Anchor::BottomLeft,
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+    ));
+}
+
+/// World position below the mouse cursor, if it hovers the primary window
+fn cursor_position(
+    windows: &Query<&Window, With<PrimaryWindow>>,
+    cameras: &Query<(&Camera, &GlobalTransform)>,
+) -> Option<Vec2> {
+    let cursor = windows.get_single().ok()?.cursor_position()?;
+    let (camera, transform) = cameras.get_single().ok()?;
+    camera.viewport_to_world_2d(transform, cursor)
+}
+
+fn explain_hovered_cell<R: CellInfo>(
+    grid: Res<R>,
+    running: Option<Res<Running>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut tooltips: Query<(&mut Text, &mut Transform, &mut Visibility), With<Tooltip>>,
+) {
+    let Ok((mut text, mut tf, mut visibility)) = tooltips.get_single_mut() else {
+        return;
+    };
+    let paused = running.map_or(true, |running| !running.inner());
+    let hovered = cursor_position(&windows, &cameras)
+        .filter(|_| paused)
+        .and_then(|position| Some((position, grid.pick(position)?)));
+    let Some((position, cell)) = hovered else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+
+    let lines = grid.explain(cell);
+    text.sections[0].value = format!("({}, {})\n{}", cell.x, cell.y, lines.join("\n"));
+    // Keep the tooltip's size on screen independent of the camera's zoom
+    let scale = cameras
+        .get_single()
+        .map(|(_, camera)| camera.compute_transform().scale)
+        .unwrap_or(Vec3::ONE);
+    *tf = Transform::from_translation(position.extend(100.)).with_scale(scale);
+    *visibility = Visibility::Visible;
+}
@@ -0,0 +1,257 @@
+//! Drawing a day's parsed puzzle input as a picture, without solving it, e.g. to check by eye
+//! whether parsing a new input matches what the puzzle shows
+
+use std::{fmt::Write, io::Cursor};
+
+use anyhow::anyhow;
+use clap::ValueEnum;
+use image::{ImageOutputFormat, Rgb, RgbImage};
+use termion::color::{Fg, Reset, Rgb as TermRgb};
+
+use crate::{Coord, Day};
+
+/// Color of empty cells in the images
+const BACKGROUND: [u8; 3] = [30, 30, 30];
+
+/// Edge length of one cell in pixels
+const CELL_SIZE: u32 = 10;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Colored characters for the terminal
+    #[default]
+    Term,
+    /// One filled square per cell
+    Png,
+    /// One colored character per cell
+    Svg,
+}
+
+/// Grid of cells, as which a day's puzzle input can be drawn
+pub trait Diagram {
+    /// Number of columns (x) and rows (y)
+    fn size(&self) -> Coord;
+
+    /// Color `cell` is drawn with, `None` if there is nothing to draw
+    fn color(&self, cell: Coord) -> Option<[u8; 3]>;
+
+    /// Character `cell` is drawn as
+    fn symbol(&self, cell: Coord) -> char;
+}
+
+/// Several diagrams drawn below each other, separated by an empty row
+#[derive(Debug, Clone)]
+pub struct Stacked<D>(pub Vec<D>);
+
+impl<D: Diagram> Stacked<D> {
+    /// Diagram drawn at `cell` and that cell within it, `None` for the gaps in between
+    fn locate(&self, cell: Coord) -> Option<(&D, Coord)> {
+        let mut y = cell.y;
+        for diagram in &self.0 {
+            let size = diagram.size();
+            if y < size.y {
+                return (0 <= y && cell.x < size.x).then_some((diagram, Coord::new(cell.x, y)));
+            }
+            y -= size.y + 1;
+        }
+        None
+    }
+}
+
+impl<D: Diagram> Diagram for Stacked<D> {
+    fn size(&self) -> Coord {
+        let width = self.0.iter().map(|d| d.size().x).max().unwrap_or_default();
+        let height = self.0.iter().map(|d| d.size().y + 1).sum::<i32>();
+        Coord::new(width, (height - 1).max(0))
+    }
+
+    fn color(&self, cell: Coord) -> Option<[u8; 3]> {
+        let (diagram, cell) = self.locate(cell)?;
+        diagram.color(cell)
+    }
+
+    fn symbol(&self, cell: Coord) -> char {
+        self.locate(cell)
+            .map_or(' ', |(diagram, cell)| diagram.symbol(cell))
+    }
+}
+
+fn cells(diagram: &dyn Diagram) -> impl Iterator<Item = Coord> {
+    let size = diagram.size();
+    (0..size.y).flat_map(move |y| (0..size.x).map(move |x| Coord::new(x, y)))
+}
+
+pub fn term(diagram: &dyn Diagram) -> String {
+    let mut out = String::new();
+    for cell in cells(diagram) {
+        let symbol = diagram.symbol(cell);
+        match diagram.color(cell) {
+            Some([r, g, b]) => write!(out, "{}{symbol}{}", Fg(TermRgb(r, g, b)), Fg(Reset)),
+            None => write!(out, "{symbol}"),
+        }
+        .expect("writing to a string");
+        if cell.x == diagram.size().x - 1 {
+            out.push('\n');
+        }
+    }
+    out
+}
+
+pub fn svg(diagram: &dyn Diagram) -> String {
+    let size = diagram.size() * CELL_SIZE as i32;
+    let [r, g, b] = BACKGROUND;
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"rgb({r},{g},{b})\"/>\n",
+        size.x, size.y
+    );
+    for cell in cells(diagram) {
+        let Some([r, g, b]) = diagram.color(cell) else {
+            continue;
+        };
+        let symbol = match diagram.symbol(cell) {
+            '&' => String::from("&amp;"),
+            '<' => String::from("&lt;"),
+            '>' => String::from("&gt;"),
+            c => c.to_string(),
+        };
+        let center = cell * CELL_SIZE as i32 + Coord::splat(CELL_SIZE as i32 / 2);
+        writeln!(
+            out,
+            "<text x=\"{}\" y=\"{}\" font-size=\"{CELL_SIZE}\" font-family=\"monospace\" \
+             text-anchor=\"middle\" dominant-baseline=\"central\" fill=\"rgb({r},{g},{b})\">{symbol}</text>",
+            center.x, center.y
+        )
+        .expect("writing to a string");
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+pub fn png(diagram: &dyn Diagram) -> anyhow::Result<Vec<u8>> {
+    let size = diagram.size();
+    let image = RgbImage::from_fn(
+        size.x as u32 * CELL_SIZE,
+        size.y as u32 * CELL_SIZE,
+        |x, y| {
+            let cell = Coord::new((x / CELL_SIZE) as i32, (y / CELL_SIZE) as i32);
+            Rgb(diagram.color(cell).unwrap_or(BACKGROUND))
+        },
+    );
+    let mut bytes = Cursor::new(Vec::new());
+    image.write_to(&mut bytes, ImageOutputFormat::Png)?;
+    Ok(bytes.into_inner())
+}
+
+pub fn render(diagram: &dyn Diagram, format: Format) -> anyhow::Result<Vec<u8>> {
+    Ok(match format {
+        Format::Term => term(diagram).into_bytes(),
+        Format::Svg => svg(diagram).into_bytes(),
+        Format::Png => png(diagram)?,
+    })
+}
+
+/// The parsed puzzle `input` of `day`, as it is before solving. Only days which live in this
+/// library (and whose `dayNN` feature is enabled) can be drawn
+#[cfg_attr(
+    not(any(
+        feature = "day03",
+        feature = "day10",
+        feature = "day13",
+        feature = "day14",
+        feature = "day16"
+    )),
+    allow(unused_variables)
+)]
+pub fn diagram(day: Day, input: &str) -> anyhow::Result<Box<dyn Diagram>> {
+    #[cfg(any(
+        feature = "day03",
+        feature = "day10",
+        feature = "day14",
+        feature = "day16"
+    ))]
+    use std::str::FromStr;
+
+    match day {
+        #[cfg(feature = "day03")]
+        Day::Third => Ok(Box::new(crate::third::Schematic::from_str(input)?)),
+        #[cfg(feature = "day10")]
+        Day::Tenth => Ok(Box::new(crate::ten::Maze::from_str(input)?)),
+        #[cfg(feature = "day13")]
+        Day::Thirteenth => Ok(Box::new(Stacked(crate::thirteenth::parse_grids(input)?))),
+        #[cfg(feature = "day14")]
+        Day::Fourteenth => Ok(Box::new(crate::fourteenth::Platform::from_str(input)?)),
+        #[cfg(feature = "day16")]
+        Day::Sixteenth => Ok(Box::new(crate::sixteenth::Contraption::from_str(input)?)),
+        _ => Err(anyhow!("Day {day} has no diagram of its input")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    /// Diagonal of `n` colored cells
+    struct Diagonal(i32);
+
+    impl Diagram for Diagonal {
+        fn size(&self) -> Coord {
+            Coord::new(self.0, self.0)
+        }
+        fn color(&self, cell: Coord) -> Option<[u8; 3]> {
+            (cell.x == cell.y).then_some([255, 0, 0])
+        }
+        fn symbol(&self, cell: Coord) -> char {
+            if cell.x == cell.y {
+                '\\'
+            } else {
+                '.'
+            }
+        }
+    }
+
+    #[rstest]
+    fn stacks_with_gap() {
+        let stacked = Stacked(vec![Diagonal(2), Diagonal(3)]);
+        assert_eq!(stacked.size(), Coord::new(3, 6));
+        let symbols = cells(&stacked)
+            .map(|cell| stacked.symbol(cell))
+            .collect::<String>();
+        assert_eq!(symbols, "\\. .\\    \\...\\...\\");
+        assert_eq!(stacked.color(Coord::new(1, 4)), Some([255, 0, 0]));
+        assert_eq!(stacked.color(Coord::new(1, 2)), None);
+    }
+
+    #[rstest]
+    fn svg_has_one_text_per_colored_cell() {
+        assert_eq!(svg(&Diagonal(4)).matches("<text").count(), 4);
+    }
+
+    #[rstest]
+    fn png_has_cell_sized_pixels() {
+        let bytes = png(&Diagonal(3)).expect("encoding");
+        let image = image::load_from_memory(&bytes).expect("decoding").to_rgb8();
+        assert_eq!(image.dimensions(), (3 * CELL_SIZE, 3 * CELL_SIZE));
+        assert_eq!(
+            image.get_pixel(CELL_SIZE + 1, CELL_SIZE + 1),
+            &Rgb([255, 0, 0])
+        );
+        assert_eq!(image.get_pixel(CELL_SIZE + 1, 1), &Rgb(BACKGROUND));
+    }
+
+    #[rstest]
+    #[case(Day::Third, include_str!("../sample/third.txt"), Coord::new(10, 10))]
+    #[case(Day::Tenth, include_str!("../sample/tenth-a.txt"), Coord::new(5, 5))]
+    #[case(Day::Thirteenth, include_str!("../sample/thirteenth.txt"), Coord::new(9, 15))]
+    #[case(Day::Fourteenth, include_str!("../sample/fourteenth.txt"), Coord::new(10, 10))]
+    #[case(Day::Sixteenth, include_str!("../sample/sixteenth.txt"), Coord::new(10, 10))]
+    fn diagrams_span_input(#[case] day: Day, #[case] input: &str, #[case] size: Coord) {
+        assert_eq!(diagram(day, input).expect("parsing").size(), size);
+    }
+
+    #[rstest]
+    fn no_diagram_for_binary_only_day() {
+        assert!(diagram(Day::First, "").is_err());
+    }
+}
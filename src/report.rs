@@ -0,0 +1,368 @@
+//! Answers & timings of solving several days in one go, and how two such reports differ, so
+//! that refactorings can be checked for changed answers or slowdowns
+
+use std::{collections::BTreeMap, fmt::Display};
+
+use enum_iterator::all;
+use serde::{Deserialize, Serialize};
+
+use crate::{cache::Run, Day, Part};
+
+/// Relative slowdown of a solve, above which [`diff`] flags it as a regression by default
+pub const REGRESSION_THRESHOLD: f64 = 0.2;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub day: Day,
+    pub part: Part,
+    pub answer: String,
+    /// Wall time the solve took
+    pub seconds: f64,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+    pub entries: Vec<Entry>,
+}
+
+impl Report {
+    pub fn get(&self, day: Day, part: Part) -> Option<&Entry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.day == day && entry.part == part)
+    }
+
+    /// Wall time all solves took together
+    pub fn seconds(&self) -> f64 {
+        self.entries.iter().map(|entry| entry.seconds).sum()
+    }
+}
+
+/// One line per solve with its answer & time, and the total time below
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let width = self
+            .entries
+            .iter()
+            .map(|entry| entry.answer.len())
+            .max()
+            .unwrap_or_default()
+            .max("Answer".len());
+        writeln!(f, "Day Part {:>width$} {:>9}", "Answer", "Time")?;
+        for entry in &self.entries {
+            writeln!(
+                f,
+                "{:>3} {:<4} {:>width$} {:>8.3}s",
+                entry.day,
+                format!("{:?}", entry.part),
+                entry.answer,
+                entry.seconds
+            )?;
+        }
+        writeln!(f, "{:>w$} {:>8.3}s", "Total", self.seconds(), w = 9 + width)
+    }
+}
+
+/// Difference of one solve between an old and a new [`Report`]
+#[derive(Debug, PartialEq)]
+pub enum Change<'a> {
+    Answer { old: &'a Entry, new: &'a Entry },
+    Regression { old: &'a Entry, new: &'a Entry },
+    Removed(&'a Entry),
+    Added(&'a Entry),
+}
+
+impl Change<'_> {
+    /// Whether this change means the new version got worse
+    pub fn is_failure(&self) -> bool {
+        !matches!(self, Self::Added(_))
+    }
+}
+
+impl Display for Change<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Answer { old, new } => write!(
+                f,
+                "Day {:>2} part {:?}: answer changed from {} to {}",
+                new.day, new.part, old.answer, new.answer
+            ),
+            Self::Regression { old, new } => write!(
+                f,
+                "Day {:>2} part {:?}: {:.3}s -> {:.3}s ({:+.0}%)",
+                new.day,
+                new.part,
+                old.seconds,
+                new.seconds,
+                100. * (new.seconds / old.seconds - 1.)
+            ),
+            Self::Removed(old) => write!(f, "Day {:>2} part {:?}: missing", old.day, old.part),
+            Self::Added(new) => write!(f, "Day {:>2} part {:?}: new", new.day, new.part),
+        }
+    }
+}
+
+/// All changes from `old` to `new`, counting solves which got slower by more than `threshold`
+/// (relative to the old time) as regressions
+pub fn diff<'a>(old: &'a Report, new: &'a Report, threshold: f64) -> Vec<Change<'a>> {
+    let mut changes = Vec::new();
+    for o in &old.entries {
+        match new.get(o.day, o.part) {
+            None => changes.push(Change::Removed(o)),
+            Some(n) if n.answer != o.answer => changes.push(Change::Answer { old: o, new: n }),
+            Some(n) if n.seconds > o.seconds * (1. + threshold) => {
+                changes.push(Change::Regression { old: o, new: n })
+            }
+            Some(_) => {}
+        }
+    }
+    changes.extend(
+        new.entries
+            .iter()
+            .filter(|n| old.get(n.day, n.part).is_none())
+            .map(Change::Added),
+    );
+    changes
+}
+
+/// What the runs of one day came to, see [`Summary`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DaySummary {
+    pub day: Day,
+    /// Parts with at least one run
+    pub solved: Vec<Part>,
+    /// Fastest run of each solved part, added up
+    pub fastest: f64,
+    /// Timestamp of the latest run
+    pub last: u64,
+    pub runs: usize,
+}
+
+/// Dashboard of personal runs across the month, one row per day and some totals below
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Summary {
+    pub days: Vec<DaySummary>,
+}
+
+impl Summary {
+    pub fn new<'a>(runs: impl IntoIterator<Item = &'a Run>) -> Self {
+        let mut days = BTreeMap::<Day, Vec<&Run>>::new();
+        for run in runs {
+            days.entry(run.entry.day).or_default().push(run);
+        }
+        let days = days
+            .into_iter()
+            .map(|(day, runs)| {
+                let fastest = |part| {
+                    runs.iter()
+                        .filter(|run| run.entry.part == part)
+                        .map(|run| run.entry.seconds)
+                        .reduce(f64::min)
+                };
+                let solved = all::<Part>()
+                    .filter(|part| fastest(*part).is_some())
+                    .collect::<Vec<_>>();
+                DaySummary {
+                    day,
+                    fastest: solved.iter().filter_map(|part| fastest(*part)).sum(),
+                    solved,
+                    last: runs
+                        .iter()
+                        .map(|run| run.timestamp)
+                        .max()
+                        .unwrap_or_default(),
+                    runs: runs.len(),
+                }
+            })
+            .collect();
+        Self { days }
+    }
+
+    /// Solved parts of all days
+    pub fn stars(&self) -> usize {
+        self.days.iter().map(|day| day.solved.len()).sum()
+    }
+
+    pub fn runs(&self) -> usize {
+        self.days.iter().map(|day| day.runs).sum()
+    }
+
+    /// Wall time of all days' fastest runs together
+    pub fn seconds(&self) -> f64 {
+        self.days.iter().map(|day| day.fastest).sum()
+    }
+
+    /// Day taking the longest even at its fastest
+    pub fn slowest(&self) -> Option<&DaySummary> {
+        self.days
+            .iter()
+            .max_by(|a, b| a.fastest.total_cmp(&b.fastest))
+    }
+}
+
+/// One line per day with its stars, fastest time, last run & number of runs, the totals below
+impl Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Day Stars   Fastest Last run    Runs")?;
+        for day in &self.days {
+            writeln!(
+                f,
+                "{:>3} {:<5} {:>8.3}s {} {:>5}",
+                day.day,
+                "*".repeat(day.solved.len()),
+                day.fastest,
+                date(day.last),
+                day.runs
+            )?;
+        }
+        writeln!(
+            f,
+            "{} days, {}/50 stars, {} runs, {:.3}s at the fastest",
+            self.days.len(),
+            self.stars(),
+            self.runs(),
+            self.seconds()
+        )?;
+        if let Some(slowest) = self.slowest() {
+            writeln!(
+                f,
+                "Slowest is day {} with {:.3}s",
+                slowest.day, slowest.fastest
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// `YYYY-MM-DD` in UTC of a timestamp in seconds since the unix epoch
+fn date(timestamp: u64) -> String {
+    // Howard Hinnant's `civil_from_days`, with eras of 400 years starting on the 1st of March
+    let days = (timestamp / 86400) as i64 + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let m = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * m + 2) / 5 + 1;
+    let month = if m < 10 { m + 3 } else { m - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn entry(day: u8, part: Part, answer: &str, seconds: f64) -> Entry {
+        Entry {
+            day: Day::try_from(day).expect("day"),
+            part,
+            answer: answer.to_owned(),
+            seconds,
+        }
+    }
+
+    #[rstest]
+    #[case(entry(2, Part::One, "8", 1.), entry(2, Part::One, "8", 1.1), 0)]
+    #[case(entry(2, Part::One, "8", 1.), entry(2, Part::One, "8", 0.1), 0)]
+    #[case(entry(2, Part::One, "8", 1.), entry(2, Part::One, "8", 1.5), 1)]
+    #[case(entry(2, Part::One, "8", 1.), entry(2, Part::One, "9", 1.), 1)]
+    #[case(entry(2, Part::One, "8", 1.), entry(2, Part::Two, "8", 1.), 1)]
+    fn diffs(#[case] old: Entry, #[case] new: Entry, #[case] failures: usize) {
+        let old = Report { entries: vec![old] };
+        let new = Report { entries: vec![new] };
+        let changes = diff(&old, &new, REGRESSION_THRESHOLD);
+        assert_eq!(
+            changes.iter().filter(|c| c.is_failure()).count(),
+            failures,
+            "{changes:?}"
+        );
+    }
+
+    #[rstest]
+    fn answer_change_wins_over_regression() {
+        let old = Report {
+            entries: vec![entry(5, Part::Two, "46", 1.)],
+        };
+        let new = Report {
+            entries: vec![entry(5, Part::Two, "47", 3.)],
+        };
+        assert_eq!(
+            diff(&old, &new, REGRESSION_THRESHOLD),
+            vec![Change::Answer {
+                old: &old.entries[0],
+                new: &new.entries[0]
+            }]
+        );
+    }
+
+    #[rstest]
+    fn table_aligns_answers() {
+        let report = Report {
+            entries: vec![
+                entry(2, Part::One, "8", 0.25),
+                entry(16, Part::Two, "51", 0.5),
+            ],
+        };
+        assert_eq!(
+            report.to_string().lines().collect::<Vec<_>>(),
+            [
+                "Day Part Answer      Time",
+                "  2 One       8    0.250s",
+                " 16 Two      51    0.500s",
+                "          Total    0.750s",
+            ]
+        );
+    }
+
+    #[rstest]
+    fn round_trips_through_json() {
+        let report = Report {
+            entries: vec![entry(16, Part::Two, "51", 0.25)],
+        };
+        let json = serde_json::to_string(&report).expect("serializing");
+        assert_eq!(
+            serde_json::from_str::<Report>(&json).expect("deserializing"),
+            report
+        );
+    }
+
+    #[rstest]
+    #[case(0, "1970-01-01")]
+    #[case(951782400, "2000-02-29")]
+    #[case(1701734400, "2023-12-05")]
+    #[case(1703980799, "2023-12-30")]
+    fn dates(#[case] timestamp: u64, #[case] expected: &str) {
+        assert_eq!(date(timestamp), expected);
+    }
+
+    #[rstest]
+    fn summarizes_runs_per_day() {
+        let run = |day, part, seconds, timestamp| Run {
+            entry: entry(day, part, "8", seconds),
+            timestamp,
+        };
+        let runs = [
+            run(16, Part::One, 0.5, 1702684800),
+            run(2, Part::One, 0.25, 1701475200),
+            run(16, Part::One, 0.25, 1702771200),
+            run(16, Part::Two, 1., 1702771200),
+        ];
+        let summary = Summary::new(&runs);
+        assert_eq!(summary.stars(), 3);
+        assert_eq!(summary.runs(), 4);
+        assert_eq!(summary.slowest().map(|day| day.day), Some(Day::Sixteenth));
+        assert_eq!(
+            summary.to_string().lines().collect::<Vec<_>>(),
+            [
+                "Day Stars   Fastest Last run    Runs",
+                "  2 *        0.250s 2023-12-02     1",
+                " 16 **       1.250s 2023-12-17     3",
+                "2 days, 3/50 stars, 4 runs, 1.500s at the fastest",
+                "Slowest is day 16 with 1.250s",
+            ]
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! Starting an animation over without closing its window: on [`Action::Restart`] all entities get
+//! despawned, the resources of each [`ResetPlugin`] restored and the `Startup` systems run again
+
+use std::marker::PhantomData;
+
+use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
+
+use crate::keybindings::{Action, Keybindings};
+
+/// Runs on each [`Reset`], after the entities are gone and before `Startup` runs again
+#[derive(Debug, Clone, PartialEq, Eq, Hash, ScheduleLabel)]
+pub struct OnReset;
+
+/// Runs before `Startup` and whenever another input got loaded (see [`crate::samples`]), to
+/// remember the resources of each [`ResetPlugin`] as they are now
+#[derive(Debug, Clone, PartialEq, Eq, Hash, ScheduleLabel)]
+pub struct OnLoad;
+
+/// Starts the animation over, sent by the [`crate::AocControlPlugin`] for [`Action::Restart`]
+#[derive(Debug, Clone, Copy, Event)]
+pub struct Reset;
+
+/// How the resource `R` was, when it got loaded last
+#[derive(Debug, Resource)]
+struct Snapshot<R>(R);
+
+/// Restores the resource `R` on each [`Reset`] to how it was inserted into the app, i.e. before
+/// any system changed it (see [`OnLoad`])
+#[derive(Debug)]
+pub struct ResetPlugin<R>(PhantomData<R>);
+
+impl<R> Default for ResetPlugin<R> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<R: Resource + Clone> Plugin for ResetPlugin<R> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnLoad, remember::<R>)
+            .add_systems(OnReset, restore::<R>);
+    }
+}
+
+fn remember<R: Resource + Clone>(world: &mut World) {
+    let resource = world.resource::<R>().clone();
+    world.insert_resource(Snapshot(resource));
+}
+
+fn restore<R: Resource + Clone>(mut resource: ResMut<R>, snapshot: Res<Snapshot<R>>) {
+    *resource = snapshot.0.clone();
+}
+
+pub(crate) fn request(
+    keys: Res<Input<KeyCode>>,
+    bindings: Res<Keybindings>,
+    mut resets: EventWriter<Reset>,
+) {
+    if bindings.released(&keys, Action::Restart) {
+        resets.send(Reset);
+    }
+}
+
+pub(crate) fn load(world: &mut World) {
+    world.run_schedule(OnLoad);
+}
+
+/// Everything but the windows goes, the days' `Startup` systems spawn it anew
+pub(crate) fn reset(world: &mut World) {
+    let entities = world
+        .query_filtered::<Entity, Without<Window>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+    for entity in entities {
+        world.despawn(entity);
+    }
+    world.run_schedule(OnReset);
+    // Only exists once any system got added to it, which animations without a setup don't
+    let _ = world.try_run_schedule(Startup);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AocControlPlugin, HeadlessPlugins};
+    use rstest::rstest;
+
+    #[derive(Debug, Clone, PartialEq, Resource)]
+    struct Counter(usize);
+
+    #[derive(Debug, Component)]
+    struct Thing;
+
+    fn setup(mut cmd: Commands) {
+        cmd.spawn(Thing);
+    }
+
+    fn count(mut counter: ResMut<Counter>) {
+        counter.0 += 1;
+    }
+
+    #[rstest]
+    fn starts_over_from_the_beginning() {
+        let mut app = App::new();
+        app.add_plugins((
+            HeadlessPlugins,
+            AocControlPlugin,
+            ResetPlugin::<Counter>::default(),
+        ))
+        .insert_resource(Counter(0))
+        .add_systems(Startup, setup)
+        .add_systems(Update, count);
+        for _ in 0..5 {
+            app.update();
+        }
+        app.world.spawn(Thing);
+        assert_eq!(app.world.resource::<Counter>(), &Counter(5));
+
+        app.world.send_event(Reset);
+        app.update();
+
+        let things = app.world.query::<&Thing>().iter(&app.world).count();
+        assert_eq!(things, 1);
+        assert!(app.world.resource::<Counter>().0 <= 1);
+    }
+}
@@ -0,0 +1,124 @@
+//! All randomness of the crate derives from one seed, so that runs can be repeated: it comes from
+//! `--seed` (see [`RngArgs`]), the `AOC23_SEED` environment variable or else is picked at random
+
+use std::{env::VarError, sync::OnceLock};
+
+use anyhow::{anyhow, Context};
+use clap::Args;
+use rand::{rngs::StdRng, SeedableRng};
+use tracing::{info, warn};
+
+/// Environment variable with the seed, unless `--seed` is given
+pub const SEED_VAR: &str = "AOC23_SEED";
+
+static SEED: OnceLock<u64> = OnceLock::new();
+
+/// Command line flag for the seed, to `#[clap(flatten)]` into a binary's options
+#[derive(Debug, Default, Args)]
+pub struct RngArgs {
+    /// Seed for everything random, e.g. the hues of beams, to repeat a run exactly
+    #[clap(long)]
+    seed: Option<u64>,
+}
+
+impl RngArgs {
+    /// Seeds all randomness of this process with the one given on the command line or else in
+    /// [`SEED_VAR`], if any. Fails if the latter is set, but no seed
+    pub fn install(&self) -> anyhow::Result<()> {
+        let seed = match self.seed {
+            Some(seed) => Some(seed),
+            None => env_seed()?,
+        };
+        seed.map_or(Ok(()), install)
+    }
+}
+
+/// Seed in [`SEED_VAR`], if it is set
+fn env_seed() -> anyhow::Result<Option<u64>> {
+    parse_seed(std::env::var(SEED_VAR))
+}
+
+fn parse_seed(var: Result<String, VarError>) -> anyhow::Result<Option<u64>> {
+    match var {
+        Ok(seed) => seed
+            .trim()
+            .parse()
+            .map(Some)
+            .with_context(|| format!("${SEED_VAR} is set to {seed:?}, which is no seed")),
+        Err(VarError::NotPresent) => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("${SEED_VAR} is no seed")),
+    }
+}
+
+/// Seed all randomness of this process with `seed`, before anything random happened
+pub fn install(seed: u64) -> anyhow::Result<()> {
+    SEED.set(seed)
+        .map_err(|_| anyhow!("A seed is installed already"))
+}
+
+/// Seed of this process, which stays the same once anything asked for it. Binaries taking
+/// [`RngArgs`] fail on a [`SEED_VAR`] which is no seed, everything else only warns about it
+pub fn seed() -> u64 {
+    *SEED.get_or_init(|| {
+        let seed = env_seed()
+            .unwrap_or_else(|e| {
+                warn!("{e:#}, picking one at random instead");
+                None
+            })
+            .unwrap_or_else(rand::random);
+        info!("Seeding randomness with {seed} (set {SEED_VAR} to repeat)");
+        seed
+    })
+}
+
+/// Crate-wide generator, which all other ones derive from
+pub fn rng() -> StdRng {
+    StdRng::seed_from_u64(seed())
+}
+
+/// Generator of its own for `subsystem`, e.g. `"beam hues"`, so that drawing numbers in one doesn't
+/// change the ones of another
+pub fn stream(subsystem: &str) -> StdRng {
+    derive(seed(), subsystem)
+}
+
+fn derive(seed: u64, subsystem: &str) -> StdRng {
+    // FNV-1a, since it has to stay the same across builds & platforms unlike `DefaultHasher`
+    let hash = subsystem
+        .bytes()
+        .fold(0xcbf29ce484222325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+    StdRng::seed_from_u64(seed ^ hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+    use rstest::rstest;
+
+    fn draw(mut rng: StdRng) -> Vec<u32> {
+        (0..8).map(|_| rng.gen()).collect()
+    }
+
+    #[rstest]
+    #[case(Ok("42".to_owned()), Some(Some(42)))]
+    #[case(Ok(" 7\n".to_owned()), Some(Some(7)))]
+    #[case(Err(VarError::NotPresent), Some(None))]
+    #[case(Ok("forty-two".to_owned()), None)]
+    #[case(Ok("-1".to_owned()), None)]
+    fn seeds_from_env(
+        #[case] var: Result<String, VarError>,
+        #[case] expected: Option<Option<u64>>,
+    ) {
+        assert_eq!(parse_seed(var).ok(), expected);
+    }
+
+    #[rstest]
+    fn streams_repeat_per_seed_and_subsystem() {
+        assert_eq!(draw(derive(7, "beam hues")), draw(derive(7, "beam hues")));
+        assert_ne!(draw(derive(7, "beam hues")), draw(derive(8, "beam hues")));
+        assert_ne!(draw(derive(7, "beam hues")), draw(derive(7, "demo")));
+    }
+}
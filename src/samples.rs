@@ -0,0 +1,138 @@
+//! Flipping through several inputs of a day within one window, e.g. the samples of Day 10: the
+//! keys of [`Action::PreviousSample`] & [`Action::NextSample`] load another one and start the
+//! animation over with it (see [`crate::reset`])
+
+use bevy::prelude::*;
+
+use crate::{
+    keybindings::{Action, Keybindings},
+    normalize_input,
+    reset::{self, OnLoad},
+};
+
+/// Parses an input and inserts the resources an animation needs of it into the world
+pub type Loader = fn(&mut World, &str) -> anyhow::Result<()>;
+
+/// Loads the previous/next of its inputs on the press of a key
+#[derive(Clone, Resource)]
+pub struct SamplesPlugin {
+    inputs: Vec<String>,
+    load: Loader,
+    /// Which of the inputs is loaded, none until flipping for the first time
+    current: Option<usize>,
+}
+
+impl std::fmt::Debug for SamplesPlugin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SamplesPlugin")
+            .field("inputs", &self.inputs.len())
+            .field("current", &self.current)
+            .finish()
+    }
+}
+
+impl SamplesPlugin {
+    pub fn new(inputs: impl IntoIterator<Item = impl AsRef<str>>, load: Loader) -> Self {
+        Self {
+            inputs: inputs
+                .into_iter()
+                .map(|input| normalize_input(input.as_ref()))
+                .collect(),
+            load,
+            current: None,
+        }
+    }
+}
+
+impl Plugin for SamplesPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.clone())
+            .add_event::<Flipped>()
+            .add_systems(Update, (flip, switch.run_if(on_event::<Flipped>())).chain());
+    }
+}
+
+#[derive(Debug, Clone, Copy, Event)]
+struct Flipped;
+
+fn flip(
+    keys: Res<Input<KeyCode>>,
+    bindings: Res<Keybindings>,
+    mut samples: ResMut<SamplesPlugin>,
+    mut flipped: EventWriter<Flipped>,
+) {
+    let n = samples.inputs.len();
+    if n == 0 {
+        return;
+    }
+    let current = if bindings.released(&keys, Action::NextSample) {
+        samples.current.map_or(0, |i| (i + 1) % n)
+    } else if bindings.released(&keys, Action::PreviousSample) {
+        samples.current.map_or(n - 1, |i| (i + n - 1) % n)
+    } else {
+        return;
+    };
+    samples.current = Some(current);
+    flipped.send(Flipped);
+}
+
+fn switch(world: &mut World) {
+    let samples = world.resource::<SamplesPlugin>();
+    let Some(current) = samples.current else {
+        return;
+    };
+    let (load, input) = (samples.load, samples.inputs[current].clone());
+    if let Err(e) = load(world, &input) {
+        error!("Loading sample {}: {e:#}", current + 1);
+        return;
+    }
+    world.run_schedule(OnLoad);
+    reset::reset(world);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{reset::ResetPlugin, AocControlPlugin, HeadlessPlugins};
+    use rstest::rstest;
+
+    #[derive(Debug, Clone, PartialEq, Resource)]
+    struct Words(Vec<String>);
+
+    fn load(world: &mut World, input: &str) -> anyhow::Result<()> {
+        if input.trim().is_empty() {
+            anyhow::bail!("No words");
+        }
+        world.insert_resource(Words(input.split_whitespace().map(str::to_owned).collect()));
+        Ok(())
+    }
+
+    fn forget(mut words: ResMut<Words>) {
+        words.0.clear();
+    }
+
+    #[rstest]
+    fn flips_through_the_inputs() {
+        let mut app = App::new();
+        app.add_plugins((
+            HeadlessPlugins,
+            AocControlPlugin,
+            ResetPlugin::<Words>::default(),
+            SamplesPlugin::new(["a b", "", "c"], load),
+        ))
+        .insert_resource(Words(vec!["start".to_owned()]))
+        .add_systems(Update, forget.before(flip));
+        app.update();
+
+        let mut flip_to = |current| {
+            app.world.resource_mut::<SamplesPlugin>().current = Some(current);
+            app.world.send_event(Flipped);
+            app.update();
+            app.world.resource::<Words>().clone()
+        };
+        assert_eq!(flip_to(2), Words(vec!["c".to_owned()]));
+        assert_eq!(flip_to(0), Words(vec!["a".to_owned(), "b".to_owned()]));
+        // Inputs failing to load leave the animation as it is
+        assert_eq!(flip_to(1), Words(vec![]));
+    }
+}
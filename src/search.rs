@@ -0,0 +1,214 @@
+//! Weighted shortest paths over states of a day's own, e.g. position, direction & run length of a
+//! crucible, without pulling in a graph library
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    hash::Hash,
+    ops::Add,
+};
+
+/// Cheapest path from `start` to the first state passing `is_goal`, as all its states (both ends
+/// included) and its total cost. The `successors` of a state are the ones reachable in one step,
+/// each with the cost of that step
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<(Vec<S>, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], but trying states with the lowest cost so far plus `heuristic` first. It
+/// estimates the cost from a state to the goal and must never overestimate it, e.g. the manhattan
+/// distance on a grid where each step costs at least one
+pub fn astar<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<(Vec<S>, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    // States get an index each, so that the queue doesn't need them to be ordered
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), 0))]);
+    let mut indices = HashMap::from([(start.clone(), 0)]);
+    let mut states = vec![State::new(start, C::default(), None)];
+
+    while let Some(Reverse((_, i))) = queue.pop() {
+        if states[i].closed {
+            continue;
+        }
+        states[i].closed = true;
+        if is_goal(&states[i].state) {
+            return Some((path(&states, i), states[i].cost));
+        }
+        for (next, step) in successors(&states[i].state) {
+            let cost = states[i].cost + step;
+            let j = match indices.get(&next) {
+                Some(&j) if states[j].closed || states[j].cost <= cost => continue,
+                Some(&j) => {
+                    states[j].cost = cost;
+                    states[j].parent = Some(i);
+                    j
+                }
+                None => {
+                    indices.insert(next.clone(), states.len());
+                    states.push(State::new(next, cost, Some(i)));
+                    states.len() - 1
+                }
+            };
+            queue.push(Reverse((cost + heuristic(&states[j].state), j)));
+        }
+    }
+    None
+}
+
+/// A state found so far, with the cheapest way there
+#[derive(Debug)]
+struct State<S, C> {
+    state: S,
+    cost: C,
+    /// Index of the state before this one on the cheapest way
+    parent: Option<usize>,
+    /// Whether its cost can't get any lower
+    closed: bool,
+}
+
+impl<S, C> State<S, C> {
+    fn new(state: S, cost: C, parent: Option<usize>) -> Self {
+        Self {
+            state,
+            cost,
+            parent,
+            closed: false,
+        }
+    }
+}
+
+/// States from the start to the `i`th one
+fn path<S: Clone, C>(states: &[State<S, C>], i: usize) -> Vec<S> {
+    let mut path = Vec::new();
+    let mut current = Some(i);
+    while let Some(i) = current {
+        path.push(states[i].state.clone());
+        current = states[i].parent;
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Coord, Direction};
+    use enum_iterator::all;
+    use rstest::rstest;
+
+    /// Sample of Day 17: Clumsy Crucible
+    const CITY: &str = "
+2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533";
+
+    fn heat_losses() -> Vec<Vec<u32>> {
+        CITY.trim()
+            .lines()
+            .map(|line| line.chars().filter_map(|c| c.to_digit(10)).collect())
+            .collect()
+    }
+
+    fn heat_loss(city: &[Vec<u32>], c: Coord) -> Option<u32> {
+        city.get(usize::try_from(c.y).ok()?)?
+            .get(usize::try_from(c.x).ok()?)
+            .copied()
+    }
+
+    /// Position, heading & how many cells the crucible moved straight into that heading
+    type Crucible = (Coord, Option<Direction>, u32);
+
+    /// Least heat loss of a crucible moving at least `min` & at most `max` cells straight
+    fn crucible(min: u32, max: u32, informed: bool) -> Option<u32> {
+        let city = heat_losses();
+        let goal = Coord::new(city[0].len() as i32 - 1, city.len() as i32 - 1);
+        let successors = |(pos, dir, run): &Crucible| {
+            all::<Direction>()
+                .filter(|next| *dir != Some(next.opposite()))
+                .filter(|next| match dir {
+                    Some(dir) if dir == next => *run < max,
+                    Some(_) => *run >= min,
+                    None => true,
+                })
+                .filter_map(|next| {
+                    let pos = *pos + Coord::from(next);
+                    let run = if *dir == Some(next) { run + 1 } else { 1 };
+                    Some(((pos, Some(next), run), heat_loss(&city, pos)?))
+                })
+                .collect::<Vec<_>>()
+        };
+        let is_goal = |(pos, _, run): &Crucible| *pos == goal && *run >= min;
+        let start = (Coord::new(0, 0), None, 0);
+        let (path, cost) = if informed {
+            let manhattan =
+                |(pos, _, _): &Crucible| ((goal.x - pos.x).abs() + (goal.y - pos.y).abs()) as u32;
+            astar(start, successors, manhattan, is_goal)?
+        } else {
+            dijkstra(start, successors, is_goal)?
+        };
+        assert_eq!(path.first().map(|(pos, _, _)| *pos), Some(Coord::new(0, 0)));
+        assert_eq!(path.last().map(|(pos, _, _)| *pos), Some(goal));
+        let losses = path[1..].iter().map(|(pos, _, _)| heat_loss(&city, *pos));
+        assert_eq!(losses.sum::<Option<u32>>(), Some(cost));
+        Some(cost)
+    }
+
+    #[rstest]
+    #[case(0, 3, 102)]
+    #[case(4, 10, 94)]
+    fn clumsy_crucible(#[case] min: u32, #[case] max: u32, #[case] expected: u32) {
+        assert_eq!(crucible(min, max, false), Some(expected));
+        assert_eq!(crucible(min, max, true), Some(expected));
+    }
+
+    #[rstest]
+    fn start_can_be_the_goal() {
+        let path = dijkstra(3, |n: &i32| [(n + 1, 1)], |n| *n == 3);
+        assert_eq!(path, Some((vec![3], 0)));
+    }
+
+    #[rstest]
+    fn unreachable_goal() {
+        let path = dijkstra(0, |n: &i32| (*n < 10).then_some((n + 1, 1)), |n| *n < 0);
+        assert_eq!(path, None);
+    }
+
+    #[rstest]
+    fn prefers_cheaper_detours() {
+        // 0 -> 3 directly costs 10, the way over 1 & 2 only 3
+        let edges = |n: &u8| match n {
+            0 => vec![(3, 10), (1, 1)],
+            1 => vec![(2, 1)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        };
+        assert_eq!(dijkstra(0, edges, |n| *n == 3), Some((vec![0, 1, 2, 3], 3)));
+    }
+}
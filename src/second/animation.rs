@@ -1,7 +1,10 @@
 use crate::{
-    mouse,
+    capture::CapturePlugin,
+    reset::ResetPlugin,
     second::{Color as C, Game},
-    toggle_running, Part, Running, Scroll, Tick,
+    summary::AnimationFinished,
+    widgets::{ScorePopup, WidgetsPlugin},
+    AocCameraPlugin, AocControlPlugin, Part, Presentation, Running, Tick,
 };
 
 use bevy::{
@@ -17,14 +20,20 @@ use super::BAG;
 #[derive(Debug, Resource)]
 struct Games(Vec<Game>);
 
-#[derive(Debug, Default, Resource)]
+#[derive(Debug, Default, Clone, Resource)]
 struct GameState {
+    part: Part,
+    /// Cubes of each color drawn right now (part one) or at most within the game (part two)
     bag: [usize; 3],
     game: u32,
     round: usize,
     draw: C,
     step: Step,
     checked_games: HashMap<u32, bool>,
+    /// Sum of the ids of possible games (part one) or of the games' powers (part two)
+    total: u32,
+    /// Ticks taken until now
+    steps: usize,
 }
 
 #[derive(Debug, Default, Clone, Copy)]
@@ -59,6 +68,9 @@ struct RoundId(usize);
 struct Label;
 #[derive(Debug, Component)]
 struct Sum;
+/// Power of the cubes in the bag, part two only
+#[derive(Debug, Component)]
+struct Power;
 
 #[derive(Debug, Default, Component)]
 struct List;
@@ -74,10 +86,14 @@ impl From<&Draw> for Color {
     }
 }
 
-pub fn run(input: &str, frequency: f32, part: Part) {
-    if part == Part::Two {
-        unimplemented!("Animation for Part 2");
-    }
+pub fn run(input: &str, frequency: f32, part: Part, presentation: Presentation) {
+    let plugins = DefaultPlugins.build().add(presentation);
+    app(input, frequency, part, plugins)
+        .add_plugins(CapturePlugin)
+        .run()
+}
+
+pub fn app(input: &str, frequency: f32, part: Part, plugins: impl PluginGroup) -> App {
     let games = Games(
         input
             .lines()
@@ -85,31 +101,36 @@ pub fn run(input: &str, frequency: f32, part: Part) {
             .collect(),
     );
 
-    App::new()
-        .add_plugins(DefaultPlugins)
-        .insert_resource(games)
-        .insert_resource(Tick::new(frequency))
-        .insert_resource(Running::default())
-        .insert_resource(GameState {
-            game: 1,
-            ..default()
-        })
-        .add_systems(Startup, setup)
-        .add_systems(
-            Update,
-            (
-                update,
-                mouse,
-                draw_color,
-                draw_bag,
-                move_list,
-                update_sum,
-                toggle_running,
-                highlight_draw,
-                highlight_game_result,
-            ),
-        )
-        .run()
+    let mut app = App::new();
+    app.add_plugins((
+        plugins,
+        AocCameraPlugin::new(0.1).at(Vec3::new(200., 0., 0.)),
+        AocControlPlugin,
+        WidgetsPlugin,
+    ))
+    .insert_resource(games)
+    .insert_resource(Tick::new(frequency))
+    .insert_resource(GameState {
+        part,
+        game: 1,
+        ..default()
+    })
+    .add_plugins(ResetPlugin::<GameState>::default())
+    .add_systems(Startup, setup)
+    .add_systems(
+        Update,
+        (
+            update,
+            draw_color,
+            draw_bag,
+            move_list,
+            update_sum,
+            update_power,
+            highlight_draw,
+            highlight_game_result,
+        ),
+    );
+    app
 }
 
 const CIRCLE_RADIUS: f32 = 25.;
@@ -131,15 +152,8 @@ fn setup(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
     games: Res<Games>,
+    state: Res<GameState>,
 ) {
-    commands.spawn((
-        Scroll(0.1),
-        Camera2dBundle {
-            transform: Transform::from_xyz(200., 0., 0.),
-            ..default()
-        },
-    ));
-
     // Right panel - Bag
     let bag_gap = 10.;
     let bag_start_y = 100.;
@@ -231,6 +245,22 @@ fn setup(
         },
     ));
 
+    if state.part == Part::Two {
+        commands.spawn((
+            Power,
+            Text2dBundle {
+                text: Text::from_section("---", STYLE.clone()),
+                transform: Transform::from_xyz(
+                    red_start_x - CIRCLE_RADIUS,
+                    bag_start_y - 2.1 * 5. * CIRCLE_RADIUS - 2. * bag_gap,
+                    0.,
+                ),
+                text_anchor: Anchor::TopLeft,
+                ..default()
+            },
+        ));
+    }
+
     // Left Panel
     commands.spawn((
         Sum,
@@ -274,10 +304,7 @@ fn setup(
                     .with_children(|parent| {
                         for (ri, round) in game.rounds.iter().enumerate() {
                             let mut offset2 = 0;
-                            for (color, draw) in [C::Red, C::Green, C::Blue]
-                                .into_iter()
-                                .filter_map(|key| round.0.get(&key).map(|value| (key, value)))
-                            {
+                            for (color, draw) in round.draws() {
                                 let label = format!("{draw} {color:?} ");
                                 parent.spawn((
                                     Draw::default(),
@@ -362,11 +389,13 @@ fn highlight_draw(state: Res<GameState>, mut query: Query<(&mut Draw, &GameId, &
         .filter(|(_, _, rid, _)| state.round == rid.0)
         .filter(|(_, _, _, c)| state.draw == **c)
     {
-        *draw = match state.step {
-            Step::Done => *draw,
-            Step::BagUpdate => Draw::Checking,
-            Step::ShowingResult(false) => Draw::Fail,
-            Step::ShowingResult(true) => Draw::Success,
+        *draw = match (state.step, state.part) {
+            (Step::Done, _) => *draw,
+            (Step::BagUpdate, _) => Draw::Checking,
+            (Step::ShowingResult(false), Part::One) => Draw::Fail,
+            // Draws, which didn't grow the bag, don't count for its power
+            (Step::ShowingResult(false), Part::Two) => Draw::Unchecked,
+            (Step::ShowingResult(true), _) => Draw::Success,
         };
     }
 }
@@ -386,50 +415,73 @@ fn highlight_game_result(
 
 fn update_sum(state: Res<GameState>, mut query: Query<&mut Text, With<Sum>>) {
     for mut text in query.iter_mut() {
-        text.sections[0].value = format!(
-            "{sum}",
-            sum = state
-                .checked_games
-                .iter()
-                .filter(|(_, v)| **v)
-                .map(|(k, _)| k)
-                .sum::<u32>()
-        );
+        text.sections[0].value = format!("{}", state.total);
+    }
+}
+
+fn update_power(state: Res<GameState>, mut query: Query<&mut Text, With<Power>>) {
+    let [r, g, b] = state.bag;
+    for mut text in query.iter_mut() {
+        text.sections[0].value = format!("{r} x {g} x {b} = {}", r * g * b);
     }
 }
 
 fn update(
+    mut cmd: Commands,
     mut state: ResMut<GameState>,
     running: Res<Running>,
     games: Res<Games>,
     mut timer: ResMut<Tick>,
     time: Res<Time>,
+    mut finished: EventWriter<AnimationFinished>,
 ) {
-    if !running.inner() {
+    if !running.inner() || matches!(state.step, Step::Done) {
         return;
     }
     if !timer.inner().tick(time.delta()).just_finished() {
         return;
     }
-    println!("State: {:?}", state);
+    debug!("State: {:?}", state);
     let game = games
         .0
         .iter()
         .find(|g| g.id == state.game)
         .unwrap_or_else(|| panic!("Game #{} to exist", state.game));
     let round = &game.rounds[state.round];
-    state.step = match (state.step, round.0.get(&state.draw).as_ref()) {
+    state.step = match (state.step, round.get(state.draw)) {
         (Step::Done, _) => Step::Done,
-        (Step::BagUpdate, Some(&d)) => {
-            let idx = match state.draw {
-                C::Red => 0,
-                C::Green => 1,
-                C::Blue => 2,
-            };
-            state.bag[idx] = *d as usize;
-            Step::ShowingResult(d <= BAG.get(&state.draw).unwrap())
+        (Step::BagUpdate, Some(d)) => {
+            let (i, d) = (state.draw as usize, d as usize);
+            match state.part {
+                Part::One => {
+                    state.bag[i] = d;
+                    let violation = game
+                        .first_violation(&BAG)
+                        .map(|(round, color, _, _)| (round, color));
+                    Step::ShowingResult(violation != Some((state.round, state.draw)))
+                }
+                Part::Two => {
+                    let grows = d > state.bag[i];
+                    state.bag[i] = state.bag[i].max(d);
+                    Step::ShowingResult(grows)
+                }
+            }
+        }
+        (Step::ShowingResult(false), _) if state.part == Part::One => {
+            state.draw = C::default();
+            let gid = state.game;
+            state.checked_games.insert(gid, false);
+            state.game += 1;
+            if state.game > games.0.len() as u32 {
+                state.game = games.0.len() as u32;
+                Step::Done
+            } else {
+                state.round = 0;
+                state.bag = [0, 0, 0];
+                Step::BagUpdate
+            }
         }
-        (Step::ShowingResult(true), _) | (Step::BagUpdate, None) => {
+        (Step::ShowingResult(_), _) | (Step::BagUpdate, None) => {
             let mut result = Step::BagUpdate;
             match next(&state.draw) {
                 Some(n) => {
@@ -440,11 +492,29 @@ fn update(
                     // Round finished
                     state.draw = C::default();
                     state.round += 1;
-                    state.bag = [0, 0, 0];
+                    if state.part == Part::One {
+                        state.bag = [0, 0, 0];
+                    }
                     if state.round >= game.rounds.len() {
                         // Game finished
                         let gid = state.game;
+                        let score = match state.part {
+                            Part::One => gid,
+                            Part::Two => game.power(),
+                        };
                         state.checked_games.insert(gid, true);
+                        state.total += score;
+                        state.bag = [0, 0, 0];
+                        cmd.spawn(ScorePopup::bundle(
+                            format!("+{score}"),
+                            TextStyle {
+                                font_size: FONT_SIZE * 0.8,
+                                color: Color::GRAY,
+                                ..default()
+                            },
+                            Vec3::new(PROMPT_X - CHAR_SIZE, FONT_SIZE, 1.),
+                            FONT_SIZE / 2.,
+                        ));
                         state.game += 1;
                         if state.game > games.0.len() as u32 {
                             state.game = games.0.len() as u32;
@@ -458,19 +528,84 @@ fn update(
             }
             result
         }
-        (Step::ShowingResult(false), _) => {
-            state.draw = C::default();
-            let gid = state.game;
-            state.checked_games.insert(gid, false);
-            state.game += 1;
-            if state.game > games.0.len() as u32 {
-                state.game = games.0.len() as u32;
-                Step::Done
-            } else {
-                state.round = 0;
-                state.bag = [0, 0, 0];
-                Step::BagUpdate
+    };
+    state.steps += 1;
+    if matches!(state.step, Step::Done) {
+        let possible = state.checked_games.values().filter(|possible| **possible);
+        finished.send(
+            AnimationFinished::new(state.total, state.steps)
+                .with("Possible games", possible.count()),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HeadlessPlugins;
+    use bevy::{ecs::event::Events, time::TimeUpdateStrategy};
+    use std::time::Duration;
+
+    #[test]
+    fn smoke() {
+        let input = include_str!("../../sample/second.txt");
+        let mut app = app(input, 1., Part::One, HeadlessPlugins);
+        for _ in 0..3 {
+            app.update();
+        }
+    }
+
+    #[test]
+    fn sums_up_the_powers() {
+        let input = include_str!("../../sample/second.txt");
+        let mut app = app(input, 10., Part::Two, HeadlessPlugins);
+        app.insert_resource(Running(true))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )));
+        for _ in 0..1000 {
+            app.update();
+            if matches!(app.world.resource::<GameState>().step, Step::Done) {
+                break;
             }
         }
-    };
+        let state = app.world.resource::<GameState>();
+        assert!(matches!(state.step, Step::Done), "{state:?}");
+        assert_eq!(state.checked_games.len(), 5);
+        assert_eq!(state.total, 2286);
+
+        let events = app.world.resource::<Events<AnimationFinished>>();
+        let finished = events
+            .get_reader()
+            .read(events)
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(finished.len(), 1);
+        assert_eq!(finished[0].answer, "2286");
+    }
+
+    #[test]
+    fn fails_games_at_their_first_violation() {
+        let input = include_str!("../../sample/second.txt");
+        let mut app = app(input, 10., Part::One, HeadlessPlugins);
+        app.insert_resource(Running(true))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )));
+        for _ in 0..1000 {
+            app.update();
+            if matches!(app.world.resource::<GameState>().step, Step::Done) {
+                break;
+            }
+        }
+        let state = app.world.resource::<GameState>();
+        assert!(matches!(state.step, Step::Done), "{state:?}");
+        assert_eq!(
+            state.checked_games,
+            [(1, true), (2, true), (3, false), (4, false), (5, true)]
+                .into_iter()
+                .collect()
+        );
+        assert_eq!(state.total, 8);
+    }
 }
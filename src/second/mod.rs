@@ -1,13 +1,16 @@
+#[cfg(feature = "animations")]
 pub mod animation;
 pub mod parser;
 
-use crate::second::parser::parse_game;
+use crate::{second::parser::parse_game, Part, Solver};
 use anyhow::anyhow;
+#[cfg(feature = "animations")]
 use bevy::prelude::Component;
-use enum_iterator::Sequence;
+use enum_iterator::{all, Sequence};
 use lazy_static::lazy_static;
-use nom::Finish;
+use nom::{error::ErrorKind, Finish};
 use std::collections::HashMap;
+use std::fmt::Display;
 use std::str::FromStr;
 
 lazy_static! {
@@ -17,7 +20,8 @@ lazy_static! {
             .collect();
 }
 
-#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, Component, Default, Sequence)]
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, Default, Sequence)]
+#[cfg_attr(feature = "animations", derive(Component))]
 pub enum Color {
     #[default]
     Red,
@@ -25,7 +29,7 @@ pub enum Color {
     Blue,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Game {
     id: u32,
     rounds: Vec<Round>,
@@ -33,54 +37,196 @@ pub struct Game {
 
 impl Game {
     pub fn possible(&self, bag: &HashMap<Color, u32>) -> bool {
-        self.rounds.iter().all(|round| {
-            round
-                .0
-                .iter()
-                .all(|(color, n)| n <= bag.get(color).unwrap_or(&0))
+        self.first_violation(bag).is_none()
+    }
+
+    /// First draw, which needs more cubes of a color than the bag has, as the index of its round,
+    /// its color, how many it needs and how many are available. Draws within a round are checked
+    /// red, green, blue
+    pub fn first_violation(&self, bag: &HashMap<Color, u32>) -> Option<(usize, Color, u32, u32)> {
+        self.rounds.iter().enumerate().find_map(|(i, round)| {
+            all::<Color>().find_map(|color| {
+                let needed = round.get(color)?;
+                let available = bag.get(&color).copied().unwrap_or(0);
+                (needed > available).then_some((i, color, needed, available))
+            })
         })
     }
-    pub fn fewest(&self) -> HashMap<Color, u32> {
-        self.rounds.iter().fold(HashMap::new(), |mut a, round| {
-            for (color, n) in round.0.iter() {
-                let x = a.entry(*color).or_insert(0);
-                *x = *n.max(x);
+
+    /// Fewest cubes of each color, which make this game possible
+    pub fn fewest(&self) -> Round {
+        self.rounds.iter().fold(Round::default(), |mut a, round| {
+            for (color, n) in round.draws() {
+                a.set(color, n.max(a.get(color).unwrap_or(0)));
             }
             a
         })
     }
 
+    /// Product of the [`Game::fewest`] cubes of each color
+    pub fn power(&self) -> u32 {
+        self.fewest().0.iter().product()
+    }
+
     pub fn id(&self) -> u32 {
         self.id
     }
+
+    /// Parses a game like [`FromStr`], handling colors drawn more than once in a round as
+    /// `duplicates` says
+    pub fn parse(s: &str, duplicates: Duplicates) -> anyhow::Result<Self> {
+        let (_, game) = parse_game(s, duplicates)
+            .finish()
+            .map_err(|e| match e.code {
+                ErrorKind::Verify => anyhow!("Color drawn again in the same round: {:?}", e.input),
+                _ => anyhow!("{e}"),
+            })?;
+        Ok(game)
+    }
+
+    /// Parses a game, failing on colors drawn more than once in a round (see [`Duplicates::Reject`])
+    pub fn strict(s: &str) -> anyhow::Result<Self> {
+        Self::parse(s, Duplicates::Reject)
+    }
 }
+
+impl Display for Game {
+    /// Canonical form of the puzzle input, with the draws of each round ordered red, green, blue
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rounds = self.rounds.iter().map(Round::to_string);
+        write!(
+            f,
+            "Game {}: {}",
+            self.id,
+            rounds.collect::<Vec<_>>().join("; ")
+        )
+    }
+}
+
 impl FromStr for Game {
     type Err = anyhow::Error;
 
+    /// Sums up colors drawn more than once in a round, see [`Duplicates::Sum`]
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(parse_game(s).finish().map_err(|e| anyhow!("{e}"))?.1)
+        Self::parse(s, Duplicates::Sum)
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct Round(HashMap<Color, u32>);
+/// What to make of a color drawn more than once within a round, e.g. "3 blue, 4 blue", which the
+/// puzzle never does, but hand-made inputs might
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Duplicates {
+    /// Add the cubes up, i.e. 7 blue, with a warning in the log
+    #[default]
+    Sum,
+    /// Fail to parse the game
+    Reject,
+}
+
+/// Cubes drawn per color, indexed by `Color as usize`. No cubes means the color wasn't drawn
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Round([u32; 3]);
+
+impl Round {
+    /// Cubes of `color` drawn, `None` if there were none
+    pub fn get(&self, color: Color) -> Option<u32> {
+        Some(self.0[color as usize]).filter(|n| *n > 0)
+    }
+
+    pub fn set(&mut self, color: Color, n: u32) {
+        self.0[color as usize] = n;
+    }
+
+    /// Colors drawn and how many cubes of each, in the order red, green, blue
+    pub fn draws(&self) -> impl Iterator<Item = Draw> + '_ {
+        all::<Color>().filter_map(|color| Some((color, self.get(color)?)))
+    }
+}
+
+impl FromIterator<Draw> for Round {
+    fn from_iter<T: IntoIterator<Item = Draw>>(draws: T) -> Self {
+        let mut round = Self::default();
+        for (color, n) in draws {
+            round.set(color, n);
+        }
+        round
+    }
+}
+
+impl Display for Round {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let draws = self.draws().map(|(color, n)| format!("{n} {color}"));
+        write!(f, "{}", draws.collect::<Vec<_>>().join(", "))
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Red => write!(f, "red"),
+            Self::Green => write!(f, "green"),
+            Self::Blue => write!(f, "blue"),
+        }
+    }
+}
 
 pub type Draw = (Color, u32);
 
+pub fn possible_game_ids(input: &str) -> impl Iterator<Item = u32> + '_ {
+    input
+        .lines()
+        .filter_map(|line| Game::from_str(line).ok())
+        .filter(|game| game.possible(&BAG))
+        .map(|game| game.id())
+}
+
+pub fn powers(input: &str) -> impl Iterator<Item = u32> + '_ {
+    input
+        .lines()
+        .filter_map(|line| Game::from_str(line).ok())
+        .map(|game| game.power())
+}
+
+pub fn solve(part: Part, input: &str) -> anyhow::Result<u32> {
+    Ok(match part {
+        Part::One => possible_game_ids(input).sum(),
+        Part::Two => powers(input).sum(),
+    })
+}
+
+/// Day 2: Cube Conundrum
+#[derive(Debug, Clone, Copy)]
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    fn name(&self) -> &'static str {
+        "second"
+    }
+
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::One, input).map(|x| x.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::Two, input).map(|x| x.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::{collection::vec, prelude::*};
     use rstest::rstest;
 
     #[rstest]
-    #[case("Game 1: ", Game { id: 1, rounds: vec![Round(HashMap::new())] })]
-    #[case("Game 2: 3 blue", Game { id: 2, rounds: vec![Round([(Color::Blue, 3)].into_iter().collect())] })]
-    #[case("Game 3: 5 blue, 1 green", Game { id: 3, rounds: vec![Round([(Color::Blue, 5), (Color::Green, 1)].into_iter().collect())] })]
-    #[case("Game 4: 8 blue, 3 green, 2 red", Game { id: 4, rounds: vec![Round([(Color::Blue, 8), (Color::Green, 3), (Color::Red, 2)].into_iter().collect())] })]
+    #[case("Game 1: ", Game { id: 1, rounds: vec![Round::default()] })]
+    #[case("Game 2: 3 blue", Game { id: 2, rounds: vec![[(Color::Blue, 3)].into_iter().collect::<Round>()] })]
+    #[case("Game 3: 5 blue, 1 green", Game { id: 3, rounds: vec![[(Color::Blue, 5), (Color::Green, 1)].into_iter().collect::<Round>()] })]
+    #[case("Game 4: 8 blue, 3 green, 2 red", Game { id: 4, rounds: vec![[(Color::Blue, 8), (Color::Green, 3), (Color::Red, 2)].into_iter().collect::<Round>()] })]
     #[case("Game 5: 8 blue; 3 green; 2 red", Game { id: 5, rounds: vec![
-        Round([(Color::Blue, 8)].into_iter().collect()),
-        Round([(Color::Green, 3)].into_iter().collect()),
-        Round([(Color::Red, 2)].into_iter().collect()),
+        [(Color::Blue, 8)].into_iter().collect::<Round>(),
+        [(Color::Green, 3)].into_iter().collect::<Round>(),
+        [(Color::Red, 2)].into_iter().collect::<Round>(),
     ]})]
     fn game_fromstr(#[case] s: &str, #[case] expected: Game) {
         assert_eq!(expected, Game::from_str(s).unwrap());
@@ -92,9 +238,84 @@ mod tests {
     #[case("Game 1: 4 green; 2 green", &[(Color::Green, 4)])]
     #[case("Game 1: 7 blue, 2 green; 2 blue; 2 red, 12 green", &[(Color::Blue, 7), (Color::Green, 12), (Color::Red, 2)])]
     fn fewest(#[case] game: Game, #[case] expected: &[(Color, u32)]) {
+        assert_eq!(expected.iter().copied().collect::<Round>(), game.fewest());
+    }
+
+    #[rstest]
+    #[case("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green", None)]
+    #[case("Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red", Some((0, Color::Red, 20, 12)))]
+    #[case("Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red", Some((2, Color::Red, 14, 12)))]
+    #[case("Game 6: 15 blue, 14 green", Some((0, Color::Green, 14, 13)))]
+    fn first_violation(#[case] game: Game, #[case] expected: Option<(usize, Color, u32, u32)>) {
+        assert_eq!(expected, game.first_violation(&BAG));
+        assert_eq!(expected.is_none(), game.possible(&BAG));
+    }
+
+    #[rstest]
+    #[case("Game 1: 3 blue, 4 red", "4 red, 3 blue")]
+    #[case("Game 1: 2 green, 1 blue, 1 green", "3 green, 1 blue")]
+    #[case("Game 1: ", "")]
+    fn round_display(#[case] game: Game, #[case] expected: &str) {
+        assert_eq!(game.rounds[0].to_string(), expected);
+    }
+
+    #[rstest]
+    #[case(
+        "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+        "Game 1: 4 red, 3 blue; 1 red, 2 green, 6 blue; 2 green"
+    )]
+    #[case("Game 7: 1 blue, 1 blue", "Game 7: 2 blue")]
+    #[case("Game 2: ", "Game 2: ")]
+    fn game_display(#[case] game: Game, #[case] expected: &str) {
+        assert_eq!(game.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case("Game 1: 3 blue, 4 blue", &[(Color::Blue, 7)])]
+    #[case("Game 1: 1 red, 2 green, 3 red, 4 red", &[(Color::Red, 8), (Color::Green, 2)])]
+    #[case("Game 1: 0 blue, 5 blue", &[(Color::Blue, 5)])]
+    fn sums_up_duplicates(#[case] s: &str, #[case] expected: &[(Color, u32)]) {
+        let game = Game::from_str(s).expect("parsing");
+        assert_eq!(
+            game.rounds,
+            vec![expected.iter().copied().collect::<Round>()]
+        );
+        assert!(Game::strict(s).is_err());
+    }
+
+    #[rstest]
+    #[case("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green")]
+    #[case("Game 2: 3 blue; 4 blue")]
+    #[case("Game 3: ")]
+    fn strict_accepts_distinct_colors(#[case] s: &str) {
+        assert_eq!(
+            Game::strict(s).expect("parsing"),
+            Game::from_str(s).expect("parsing")
+        );
+    }
+
+    #[rstest]
+    fn strict_points_at_the_duplicate() {
+        let e = Game::strict("Game 4: 1 green; 2 red, 5 blue, 3 red; 1 blue").unwrap_err();
         assert_eq!(
-            expected.iter().copied().collect::<HashMap<_, _>>(),
-            game.fewest()
+            e.to_string(),
+            "Color drawn again in the same round: \"3 red\""
         );
     }
+
+    fn color() -> impl Strategy<Value = Color> {
+        prop_oneof![Just(Color::Red), Just(Color::Green), Just(Color::Blue)]
+    }
+
+    fn game() -> impl Strategy<Value = Game> {
+        let round = vec((color(), 1..100u32), 1..4).prop_map(|draws| draws.into_iter().collect());
+        (0..1000u32, vec(round, 1..6)).prop_map(|(id, rounds)| Game { id, rounds })
+    }
+
+    proptest! {
+        #[test]
+        fn display_round_trips(game in game()) {
+            prop_assert_eq!(Game::from_str(&game.to_string()).expect("parsing"), game);
+        }
+    }
 }
@@ -1,29 +1,56 @@
-use crate::second::{Color, Draw, Game, Round};
+use crate::{
+    parse::common::{integer, ws},
+    second::{Color, Draw, Duplicates, Game, Round},
+};
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{space1, u32},
-    combinator::{map, value},
+    character::complete::{char, space1},
+    combinator::{consumed, map, value},
+    error::{Error, ErrorKind},
     multi::separated_list0,
     sequence::{preceded, terminated},
     IResult, Parser as NomParser,
 };
+use tracing::warn;
 
-pub(crate) fn parse_game(s: &str) -> IResult<&str, Game> {
-    let (s, id) = preceded(tag("Game "), terminated(u32, tag(": ")))(s)?;
-    let (s, rounds) = separated_list0(tag("; "), parse_round)(s)?;
+/// Game with the draws of each round, summing up or rejecting colors drawn more than once within
+/// the same round. A rejected draw fails with [`ErrorKind::Verify`] pointing at it
+pub(crate) fn parse_game(s: &str, duplicates: Duplicates) -> IResult<&str, Game> {
+    let (s, id) = preceded(
+        tag("Game").and(space1),
+        terminated(integer::<u32>, ws(char(':'))),
+    )(s)?;
+    let (s, rounds) = separated_list0(ws(char(';')), |s| parse_round(s, id, duplicates))(s)?;
     Ok((s, Game { id, rounds }))
 }
 
-fn parse_round(s: &str) -> IResult<&str, Round> {
-    map(separated_list0(tag(", "), parse_draw), |xs| {
-        Round(xs.into_iter().collect())
-    })(s)
+fn parse_round(s: &str, id: u32, duplicates: Duplicates) -> IResult<&str, Round> {
+    let (rest, draws) = separated_list0(ws(char(',')), consumed(parse_draw))(s)?;
+    let mut round = Round::default();
+    let mut drawn = [false; 3];
+    for (text, (color, n)) in draws {
+        if !std::mem::replace(&mut drawn[color as usize], true) {
+            round.set(color, n);
+            continue;
+        }
+        match duplicates {
+            Duplicates::Reject => {
+                return Err(nom::Err::Failure(Error::new(text, ErrorKind::Verify)));
+            }
+            Duplicates::Sum => {
+                let before = round.get(color).unwrap_or(0);
+                warn!("Game {id} draws {color} twice in one round, summing up {before} & {n}");
+                round.set(color, before + n);
+            }
+        }
+    }
+    Ok((rest, round))
 }
 
 fn parse_draw(s: &str) -> IResult<&str, Draw> {
     map(
-        u32.and(preceded(
+        integer::<u32>.and(preceded(
             space1,
             alt((
                 value(Color::Blue, tag("blue")),
@@ -0,0 +1,204 @@
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use enum_iterator::all;
+
+use crate::{grid::Grid, search::astar, Coord, Direction, Part, Solver};
+
+/// How many blocks a regular crucible moves at least & at most in a straight line (part one)
+pub const CRUCIBLE: (u32, u32) = (1, 3);
+/// Same for an ultra crucible (part two)
+pub const ULTRA_CRUCIBLE: (u32, u32) = (4, 10);
+
+/// Heat lost when entering a city block, 1 to 9
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeatLoss(pub u32);
+
+impl TryFrom<char> for HeatLoss {
+    type Error = anyhow::Error;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        c.to_digit(10)
+            .filter(|loss| *loss > 0)
+            .map(Self)
+            .ok_or(anyhow!("Unknown heat loss: {c}"))
+    }
+}
+
+/// Where a crucible is, where it heads to & how many blocks it moved into that direction already
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Crucible {
+    pub pos: Coord,
+    /// `None` before its first move
+    pub heading: Option<Direction>,
+    pub run: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct City {
+    blocks: Grid<HeatLoss>,
+}
+
+impl FromStr for City {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self {
+            blocks: Grid::from_str(s.trim())?,
+        })
+    }
+}
+
+impl City {
+    pub fn ncols(&self) -> i32 {
+        self.blocks.ncols()
+    }
+
+    pub fn nrows(&self) -> i32 {
+        self.blocks.nrows()
+    }
+
+    /// Heat lost when entering the block at `c`, `None` outside of the city
+    pub fn heat_loss(&self, c: Coord) -> Option<u32> {
+        self.blocks.get(c).map(|loss| loss.0)
+    }
+
+    /// Machine parts factory in the bottom right, where the crucibles have to go
+    pub fn factory(&self) -> Coord {
+        Coord::new(self.ncols() - 1, self.nrows() - 1)
+    }
+
+    /// Where the `crucible` can go next and how much heat that costs, if it has to move at least
+    /// `min` & at most `max` blocks straight before turning
+    pub fn successors(&self, crucible: &Crucible, (min, max): (u32, u32)) -> Vec<(Crucible, u32)> {
+        all::<Direction>()
+            .filter_map(|heading| {
+                let run = match crucible.heading {
+                    None => 1,
+                    Some(h) if h == heading => (crucible.run < max).then_some(crucible.run + 1)?,
+                    Some(h) if h == heading.opposite() => return None,
+                    Some(_) => (crucible.run >= min).then_some(1)?,
+                };
+                let pos = crucible.pos + Coord::from(heading);
+                let crucible = Crucible {
+                    pos,
+                    heading: Some(heading),
+                    run,
+                };
+                Some((crucible, self.heat_loss(pos)?))
+            })
+            .collect()
+    }
+
+    /// Way of the least heat loss from the top left to the [`City::factory`] and how much heat
+    /// gets lost on it, for crucibles moving `runs` (min & max) blocks straight
+    pub fn best_path(&self, runs: (u32, u32)) -> Option<(Vec<Crucible>, u32)> {
+        let factory = self.factory();
+        let start = Crucible {
+            pos: Coord::new(0, 0),
+            heading: None,
+            run: 0,
+        };
+        astar(
+            start,
+            |crucible| self.successors(crucible, runs),
+            // Each block costs at least one
+            |crucible| ((factory.x - crucible.pos.x) + (factory.y - crucible.pos.y)) as u32,
+            |crucible| {
+                crucible.pos == factory && (crucible.run >= runs.0 || crucible.heading.is_none())
+            },
+        )
+    }
+
+    pub fn least_heat_loss(&self, runs: (u32, u32)) -> Option<u32> {
+        self.best_path(runs).map(|(_, loss)| loss)
+    }
+}
+
+pub fn solve(part: Part, input: &str) -> anyhow::Result<u32> {
+    let city = City::from_str(input)?;
+    let runs = match part {
+        Part::One => CRUCIBLE,
+        Part::Two => ULTRA_CRUCIBLE,
+    };
+    city.least_heat_loss(runs)
+        .ok_or(anyhow!("No crucible can reach the factory"))
+}
+
+/// Day 17: Clumsy Crucible
+#[derive(Debug, Clone, Copy)]
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    fn name(&self) -> &'static str {
+        "seventeenth"
+    }
+
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::One, input).map(|x| x.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::Two, input).map(|x| x.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Part::One, include_str!("../../sample/seventeenth.txt"), 102)]
+    #[case(Part::Two, include_str!("../../sample/seventeenth.txt"), 94)]
+    #[case(Part::One, include_str!("../../sample/seventeenth-b.txt"), 59)]
+    #[case(Part::Two, include_str!("../../sample/seventeenth-b.txt"), 71)]
+    fn samples(#[case] part: Part, #[case] input: &str, #[case] expected: u32) {
+        assert_eq!(solve(part, input).expect("solving"), expected);
+    }
+
+    #[rstest]
+    #[case(CRUCIBLE)]
+    #[case(ULTRA_CRUCIBLE)]
+    fn paths_keep_to_the_runs(#[case] runs: (u32, u32)) {
+        let (min, max) = runs;
+        let city = City::from_str(include_str!("../../sample/seventeenth.txt")).expect("parsing");
+        let (path, loss) = city.best_path((min, max)).expect("path");
+        assert_eq!(
+            path.last().map(|crucible| crucible.pos),
+            Some(city.factory())
+        );
+        assert!(path.iter().all(|crucible| crucible.run <= max));
+        assert!(path
+            .windows(2)
+            .filter(|w| w[0].heading.is_some() && w[0].heading != w[1].heading)
+            .all(|w| w[0].run >= min));
+        let losses = path[1..]
+            .iter()
+            .map(|crucible| city.heat_loss(crucible.pos));
+        assert_eq!(losses.sum::<Option<u32>>(), Some(loss));
+    }
+
+    #[rstest]
+    #[case(0, 4)]
+    #[case(1, 1)]
+    #[case(4, 3)]
+    #[case(10, 2)]
+    fn turns_only_after_min_run(#[case] run: u32, #[case] n: usize) {
+        let city = City::from_str(include_str!("../../sample/seventeenth.txt")).expect("parsing");
+        let crucible = Crucible {
+            pos: Coord::new(5, 5),
+            heading: (run > 0).then_some(Direction::Right),
+            run,
+        };
+        assert_eq!(city.successors(&crucible, ULTRA_CRUCIBLE).len(), n);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("12\n3")]
+    #[case("1a")]
+    fn invalid_cities(#[case] input: &str) {
+        assert!(City::from_str(input).is_err());
+    }
+}
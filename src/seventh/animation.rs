@@ -0,0 +1,299 @@
+use bevy::{prelude::*, sprite::Anchor};
+
+use crate::{
+    lerp,
+    reset::ResetPlugin,
+    summary::AnimationFinished,
+    widgets::{ScorePopup, WidgetsPlugin},
+    AocCameraPlugin, AocControlPlugin, Presentation, Running, Tick,
+};
+
+use super::{Bid, Game, Hand};
+
+pub fn run(game: Game, frequency: f32, presentation: Presentation) {
+    let plugins = DefaultPlugins.build().add(presentation);
+    app(game, frequency, plugins).run()
+}
+
+pub fn app(game: Game, frequency: f32, plugins: impl PluginGroup) -> App {
+    let mut app = App::new();
+    let state = GameState::new(&game);
+    let height = game.rounds.len() as f32 * ROW_HEIGHT;
+    let camera = AocCameraPlugin::new(1.).at(Vec3::new(0., -height / 2., 0.));
+    app.add_plugins((plugins, camera, AocControlPlugin, WidgetsPlugin))
+        .insert_resource(game)
+        .insert_resource(state)
+        .insert_resource(Tick::new(frequency))
+        .add_plugins(ResetPlugin::<GameState>::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, (update, round_mover, round_colorer, totaller));
+    app
+}
+
+const FONT_SIZE: f32 = 32.;
+const ROW_HEIGHT: f32 = FONT_SIZE * 1.2;
+const MOTION: f32 = 10.;
+const JOKER_COLOR: Color = Color::FUCHSIA;
+const SWAP_COLOR: Color = Color::ORANGE;
+const PAID_COLOR: Color = Color::GOLD;
+/// Index of the first text section after the five cards of a round
+const CLASS_SECTION: usize = 5;
+
+#[derive(Debug, Clone, Resource)]
+struct GameState {
+    /// Index of the round shown in each slot, from top to bottom
+    slots: Vec<usize>,
+    /// Insertion sort of the rounds, as swaps of the slot with the one below it
+    swaps: Vec<usize>,
+    swapped: usize,
+    /// How many rounds (from the top) got paid their winnings already
+    paid: usize,
+    total: u32,
+}
+
+impl GameState {
+    fn new(game: &Game) -> Self {
+        let hands = game.rounds.iter().map(|(hand, _)| hand).collect::<Vec<_>>();
+        Self {
+            slots: (0..hands.len()).collect(),
+            swaps: insertion_sort(&hands),
+            swapped: 0,
+            paid: 0,
+            total: 0,
+        }
+    }
+
+    /// Slot, in which each round is shown right now
+    fn positions(&self) -> Vec<usize> {
+        let mut positions = vec![0; self.slots.len()];
+        for (slot, round) in self.slots.iter().enumerate() {
+            positions[*round] = slot;
+        }
+        positions
+    }
+
+    /// Slots swapped most recently, until the paying starts
+    fn swapping(&self) -> Option<(usize, usize)> {
+        if self.paid > 0 {
+            return None;
+        }
+        let slot = *self.swaps.get(self.swapped.checked_sub(1)?)?;
+        Some((slot, slot + 1))
+    }
+}
+
+/// Adjacent swaps, which sort `hands` from the weakest to the strongest, keeping equal hands in
+/// their order like [`Game::ranking`] does
+fn insertion_sort(hands: &[&Hand]) -> Vec<usize> {
+    let mut order = (0..hands.len()).collect::<Vec<_>>();
+    let mut swaps = Vec::new();
+    for i in 1..hands.len() {
+        let mut j = i;
+        while j > 0 && hands[order[j - 1]] > hands[order[j]] {
+            order.swap(j - 1, j);
+            swaps.push(j - 1);
+            j -= 1;
+        }
+    }
+    swaps
+}
+
+#[derive(Debug, Component)]
+struct Round(usize);
+
+#[derive(Debug, Component)]
+struct Total;
+
+fn slot_y(slot: usize) -> f32 {
+    -(slot as f32) * ROW_HEIGHT
+}
+
+fn round(hand: &Hand, bid: Bid) -> Text {
+    let style = |color| TextStyle {
+        font_size: FONT_SIZE,
+        color,
+        ..default()
+    };
+    let cards = hand.cards.iter().map(|card| {
+        let color = if card.is_joker() {
+            JOKER_COLOR
+        } else {
+            Color::WHITE
+        };
+        TextSection::new(card.face.to_string(), style(color))
+    });
+    Text::from_sections(cards.chain([
+        TextSection::new(
+            format!(" {:<13}", format!("{:?}", hand.rank)),
+            style(Color::GRAY),
+        ),
+        TextSection::new(format!("{bid:>4}$"), style(Color::GRAY)),
+    ]))
+}
+
+fn setup(mut cmd: Commands, game: Res<Game>) {
+    for (i, (hand, bid)) in game.rounds.iter().enumerate() {
+        cmd.spawn((
+            Round(i),
+            Text2dBundle {
+                text: round(hand, *bid),
+                transform: Transform::from_xyz(0., slot_y(i), 0.),
+                text_anchor: Anchor::CenterLeft,
+                ..default()
+            },
+        ));
+    }
+
+    let style = TextStyle {
+        font_size: FONT_SIZE,
+        color: PAID_COLOR,
+        ..default()
+    };
+    cmd.spawn((
+        Total,
+        Text2dBundle {
+            text: Text::from_sections([
+                TextSection::new("Winnings: ", style.clone()),
+                TextSection::new("---", style),
+            ]),
+            transform: Transform::from_xyz(0., 2. * ROW_HEIGHT, 1.),
+            text_anchor: Anchor::CenterLeft,
+            ..default()
+        },
+    ));
+}
+
+fn update(
+    mut cmd: Commands,
+    running: Res<Running>,
+    time: Res<Time>,
+    mut timer: ResMut<Tick>,
+    mut state: ResMut<GameState>,
+    game: Res<Game>,
+    mut finished: EventWriter<AnimationFinished>,
+) {
+    if !running.inner() {
+        return;
+    }
+    if !timer.inner().tick(time.delta()).just_finished() {
+        return;
+    }
+
+    if let Some(&slot) = state.swaps.get(state.swapped) {
+        state.slots.swap(slot, slot + 1);
+        state.swapped += 1;
+        return;
+    }
+
+    // Sorted, now pay each round its bid times its rank, starting with the weakest
+    let Some(&round) = state.slots.get(state.paid) else {
+        return;
+    };
+    state.paid += 1;
+    let winnings = game.rounds[round].1 * state.paid as u32;
+    state.total += winnings;
+    cmd.spawn(ScorePopup::bundle(
+        format!("+{winnings}"),
+        TextStyle {
+            font_size: FONT_SIZE * 0.8,
+            color: PAID_COLOR,
+            ..default()
+        },
+        Vec3::new(-FONT_SIZE, slot_y(state.paid - 1), 2.),
+        ROW_HEIGHT,
+    ));
+    if state.paid == state.slots.len() {
+        finished.send(
+            AnimationFinished::new(state.total, state.swapped + state.paid)
+                .with("Swaps", state.swapped),
+        );
+    }
+}
+
+fn round_mover(
+    time: Res<Time>,
+    state: Res<GameState>,
+    mut rounds: Query<(&Round, &mut Transform)>,
+) {
+    let dt = time.delta_seconds();
+    let positions = state.positions();
+    for (round, mut tf) in rounds.iter_mut() {
+        let target = slot_y(positions[round.0]);
+        tf.translation.y = lerp(tf.translation.y, target, MOTION * dt);
+    }
+}
+
+fn round_colorer(state: Res<GameState>, mut rounds: Query<(&Round, &mut Text)>) {
+    let positions = state.positions();
+    let swapping = state.swapping();
+    for (round, mut text) in rounds.iter_mut() {
+        let slot = positions[round.0];
+        let color = if slot < state.paid {
+            PAID_COLOR
+        } else if swapping.is_some_and(|(a, b)| slot == a || slot == b) {
+            SWAP_COLOR
+        } else {
+            Color::GRAY
+        };
+        for section in text.sections[CLASS_SECTION..].iter_mut() {
+            section.style.color = color;
+        }
+    }
+}
+
+fn totaller(state: Res<GameState>, mut totals: Query<&mut Text, With<Total>>) {
+    if state.total > 0 {
+        for mut text in totals.iter_mut() {
+            text.sections[1].value = state.total.to_string();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HeadlessPlugins;
+    use bevy::time::TimeUpdateStrategy;
+    use std::{str::FromStr, time::Duration};
+
+    fn game() -> Game {
+        let input = include_str!("../../sample/seventh.txt").replace('J', "*");
+        Game::from_str(&input).expect("parsing")
+    }
+
+    #[test]
+    fn smoke() {
+        let mut app = app(game(), 5., HeadlessPlugins);
+        for _ in 0..3 {
+            app.update();
+        }
+    }
+
+    #[test]
+    fn insertion_sort_ranks_like_game() {
+        let mut game = game();
+        let mut state = GameState::new(&game);
+        for slot in state.swaps.clone() {
+            state.slots.swap(slot, slot + 1);
+        }
+        let sorted = state
+            .slots
+            .iter()
+            .map(|round| game.rounds[*round].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(sorted, game.ranking().cloned().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn pays_total_winnings() {
+        let mut app = app(game(), 100., HeadlessPlugins);
+        app.insert_resource(Running(true))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )));
+        for _ in 0..200 {
+            app.update();
+        }
+        assert_eq!(app.world.resource::<GameState>().total, 5905);
+    }
+}
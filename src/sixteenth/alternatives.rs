@@ -0,0 +1,89 @@
+//! A plain flood fill over (cell, direction) states, as alternative to advancing all [`Beam`]s
+//! step by step, and finding the best entry by flooding from every single one of them instead of
+//! sharing the runs between mirrors. Both lost in the benchmarks, but are kept around to compare
+//! against
+//!
+//! [`Beam`]: super::Beam
+
+use std::collections::HashSet;
+
+use super::{Contraption, Mirror, Ray};
+use crate::{Coord, Direction};
+
+impl Contraption {
+    /// Number of cells energized by a beam entering at `entry`, see [`Contraption::set_entry`]
+    pub fn sweep(&self, entry: (Direction, i32)) -> usize {
+        use Direction::{Down, Left, Right, Up};
+
+        let mut visited = HashSet::new();
+        let mut open = vec![(self.entry_cell(entry), entry.0)];
+        while let Some((coord, direction)) = open.pop() {
+            if Ray::new(coord, direction, 0.).is_out_of_bounds(self.ncols(), self.nrows())
+                || !visited.insert((coord, direction))
+            {
+                continue;
+            }
+            let next = match (self.cells.get(coord), direction) {
+                (None, _)
+                | (Some(Mirror::SplitterUD), Up | Down)
+                | (Some(Mirror::SplitterLR), Left | Right) => vec![direction],
+                (Some(Mirror::Slash), Right | Left) | (Some(Mirror::Backslash), Up | Down) => {
+                    vec![direction.ccw()]
+                }
+                (Some(Mirror::Slash), Up | Down) | (Some(Mirror::Backslash), Right | Left) => {
+                    vec![direction.cw()]
+                }
+                (Some(Mirror::SplitterUD), Left | Right)
+                | (Some(Mirror::SplitterLR), Up | Down) => {
+                    vec![direction.cw(), direction.ccw()]
+                }
+            };
+            open.extend(next.into_iter().map(|dir| (coord + Coord::from(dir), dir)));
+        }
+        visited
+            .into_iter()
+            .map(|(coord, _)| coord)
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    /// Same as [`Contraption::best_entry`], but sweeping each entry on its own
+    pub fn sweep_best_entry(&self) -> Option<((Direction, i32), usize)> {
+        self.entries()
+            .map(|entry| (entry, self.sweep(entry)))
+            .max_by_key(|(_, energized_cells)| *energized_cells)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::str::FromStr;
+
+    #[rstest]
+    fn sweep_energizes_like_beams() {
+        let input = include_str!("../../sample/sixteenth.txt");
+        let contraption = Contraption::from_str(input).expect("parsing");
+        for entry in contraption.entries() {
+            let mut beams = Contraption::from_str(input).expect("parsing");
+            beams.set_entry(entry).expect("entry");
+            beams.run_to_equilibrium().expect("running");
+            assert_eq!(
+                beams.energized_cells().len(),
+                contraption.sweep(entry),
+                "{entry:?}"
+            );
+        }
+    }
+
+    #[rstest]
+    fn sweeping_finds_the_best_entry() {
+        let input = include_str!("../../sample/sixteenth.txt");
+        let contraption = Contraption::from_str(input).expect("parsing");
+        assert_eq!(
+            contraption.best_entry().map(|(_, n)| n),
+            contraption.sweep_best_entry().map(|(_, n)| n)
+        );
+    }
+}
@@ -1,44 +1,61 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, sprite::Anchor};
+use enum_iterator::all;
 
 use crate::{
-    coord2vec, frequency_increaser, lerprgb, mouse, toggle_running, Running, Scroll, Tick,
+    capture::CapturePlugin,
+    coord2vec,
+    keybindings::{Action, Keybindings},
+    lerprgb,
+    picking::{CellInfo, PickingPlugin},
+    reset::ResetPlugin,
+    summary::AnimationFinished,
+    AocCameraPlugin, AocControlPlugin, Coord, Direction, Presentation, Running, Tick,
 };
 
-use super::{Contraption, Mirror};
+use super::{Contraption, Mirror, Ray};
 
 const TILE: f32 = 40.;
-const COLOR_FADE_RAYS_AFTER_SECS: f32 = 4.;
-
-pub fn run(machine: Contraption, frequency: f32) {
-    App::new()
-        .add_plugins(DefaultPlugins)
-        .insert_resource(machine)
-        .insert_resource(Tick::new(frequency))
-        .insert_resource(Running::default())
-        .add_systems(Startup, setup)
-        .add_systems(
-            Update,
-            (
-                update,
-                mouse,
-                toggle_running,
-                frequency_increaser,
-                draw_beams,
-            ),
-        )
+const TRAIL_HEAD: Color = Color::WHITE;
+const LEGEND_FONT_SIZE: f32 = 30.;
+
+/// Panel next to the contraption, explaining each kind of mirror and how often it got hit
+#[derive(Debug, Component)]
+struct Legend;
+
+/// How many seconds a freshly energized cell takes to fade from bright white to its beam's color
+#[derive(Debug, Resource)]
+struct Trail(f32);
+
+pub fn run(machine: Contraption, frequency: f32, trail: f32, presentation: Presentation) {
+    let plugins = DefaultPlugins.build().add(presentation);
+    app(machine, frequency, trail, plugins)
+        .add_plugins(CapturePlugin)
         .run()
 }
 
+pub fn app(machine: Contraption, frequency: f32, trail: f32, plugins: impl PluginGroup) -> App {
+    let center = Vec3::new(
+        machine.ncols() as f32 * TILE / 2.,
+        -machine.nrows() as f32 * TILE / 2.,
+        10.,
+    );
+    let mut app = App::new();
+    app.add_plugins((
+        plugins,
+        AocCameraPlugin::new(1.7).at(center),
+        AocControlPlugin,
+        PickingPlugin::<Contraption>::default(),
+    ))
+    .insert_resource(machine)
+    .insert_resource(Trail(trail))
+    .insert_resource(Tick::new(frequency))
+    .add_plugins(ResetPlugin::<Contraption>::default())
+    .add_systems(Startup, setup)
+    .add_systems(Update, (update, draw_beams, update_legend));
+    app
+}
+
 fn setup(mut cmd: Commands, machine: Res<Contraption>) {
-    cmd.spawn(Camera2dBundle {
-        transform: Transform::from_xyz(
-            machine.ncols as f32 * TILE / 2.,
-            -machine.nrows as f32 * TILE / 2.,
-            10.,
-        ),
-        ..default()
-    })
-    .insert(Scroll(1.7));
     for (coord, mirror) in machine.mirrors() {
         cmd.spawn(SpriteBundle {
             sprite: Sprite {
@@ -59,18 +76,89 @@ fn setup(mut cmd: Commands, machine: Res<Contraption>) {
             ..default()
         });
     }
+
+    cmd.spawn((
+        Legend,
+        Text2dBundle {
+            text: Text::from_sections(all::<Mirror>().map(|mirror| {
+                TextSection::new(
+                    legend_entry(mirror, 0),
+                    TextStyle {
+                        font_size: LEGEND_FONT_SIZE,
+                        color: Color::GRAY,
+                        ..default()
+                    },
+                )
+            })),
+            text_anchor: Anchor::TopLeft,
+            transform: Transform::from_xyz(TILE * (machine.ncols() as f32 + 1.), 0., 1.),
+            ..default()
+        },
+    ));
+}
+
+/// One line of the [`Legend`]
+fn legend_entry(mirror: Mirror, hits: usize) -> String {
+    format!(
+        "{}  {:<42} {hits:>6} hits\n",
+        mirror.symbol(),
+        mirror.meaning()
+    )
+}
+
+fn update_legend(machine: Res<Contraption>, mut legend: Query<&mut Text, With<Legend>>) {
+    let Ok(mut text) = legend.get_single_mut() else {
+        return;
+    };
+    for (section, mirror) in text.sections.iter_mut().zip(all::<Mirror>()) {
+        section.value = legend_entry(mirror, machine.hits(mirror));
+    }
+}
+
+impl CellInfo for Contraption {
+    fn pick(&self, position: Vec2) -> Option<Coord> {
+        let cell = Coord::new(
+            (position.x / TILE).round() as i32,
+            (-position.y / TILE).round() as i32,
+        );
+        self.cells.contains(cell).then_some(cell)
+    }
+
+    fn explain(&self, cell: Coord) -> Vec<String> {
+        let mirror = match self.cells.get(cell) {
+            Some(mirror) => format!("{} {}", mirror.symbol(), mirror.meaning()),
+            None => String::from("Empty space"),
+        };
+        let beams = self.beams().filter_map(|beam| {
+            let directions = all::<Direction>()
+                .filter(|dir| beam.contains(&Ray::new(cell, *dir, f32::NAN)))
+                .map(|dir| format!("{dir:?}"))
+                .collect::<Vec<_>>();
+            (!directions.is_empty()).then(|| format!("Beam passing {}", directions.join(" ")))
+        });
+        [mirror].into_iter().chain(beams).collect()
+    }
 }
 
-fn draw_beams(machine: Res<Contraption>, mut gizmos: Gizmos, time: Res<Time>) {
+/// Color of a ray `age` seconds after its stamp
+fn trail_color(base: Color, age: f32, trail: f32) -> Color {
+    let t = if trail > 0. { age / trail } else { 1. };
+    lerprgb(TRAIL_HEAD, base, t.clamp(0., 1.))
+}
+
+fn draw_beams(machine: Res<Contraption>, trail: Res<Trail>, mut gizmos: Gizmos, time: Res<Time>) {
     for beam in machine.beams() {
-        gizmos.linestrip_gradient_2d(beam.rays().map(|ray| {
+        // Straight segments only need their two ends, the gradient fills in the cells between
+        let ends = beam
+            .segments()
+            .flat_map(|segment| [segment.first().clone(), segment.last()]);
+        gizmos.linestrip_gradient_2d(ends.map(|ray| {
             (
                 coord2vec(ray.coord) * TILE,
-                lerprgb(
-                    beam.color,
-                    Color::WHITE.with_a(0.75),
-                    ((time.elapsed_seconds() - ray.stamp) / COLOR_FADE_RAYS_AFTER_SECS)
-                        .clamp(0., 1.),
+                trail_color(
+                    Color::hsl(beam.hue, 1., 0.5),
+                    time.elapsed_seconds() - ray.stamp,
+                    trail.0,
                 ),
             )
         }));
@@ -79,17 +167,15 @@ fn draw_beams(machine: Res<Contraption>, mut gizmos: Gizmos, time: Res<Time>) {
 
 fn update(
     keys: Res<Input<KeyCode>>,
+    bindings: Res<Keybindings>,
     running: Res<Running>,
     time: Res<Time>,
     mut timer: ResMut<Tick>,
-    mut exit: ResMut<Events<bevy::app::AppExit>>,
     mut machine: ResMut<Contraption>,
+    mut steps: Local<usize>,
+    mut finished: EventWriter<AnimationFinished>,
 ) {
-    if keys.just_pressed(KeyCode::Q) {
-        exit.send(bevy::app::AppExit);
-    }
-
-    let trigger = keys.just_released(KeyCode::Tab)
+    let trigger = bindings.released(&keys, Action::Step)
         || running.inner() && timer.inner().tick(time.delta()).just_finished();
 
     if !trigger {
@@ -97,6 +183,96 @@ fn update(
     }
 
     if !machine.is_in_equilibrium() {
-        machine.advance(time.elapsed_seconds());
+        if let Err(e) = machine.advance_stamped(time.elapsed_seconds()) {
+            error!("{e}");
+            return;
+        }
+        *steps += 1;
+        if machine.is_in_equilibrium() {
+            let summary = all::<Mirror>().fold(
+                AnimationFinished::new(machine.energized_cells().len(), *steps),
+                |summary, mirror| {
+                    summary.with(format!("{} hits", mirror.symbol()), machine.hits(mirror))
+                },
+            );
+            finished.send(summary);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sixteenth::PART_ONE_ENTRY, HeadlessPlugins};
+    use rstest::rstest;
+    use std::str::FromStr;
+
+    #[test]
+    fn smoke() {
+        let input = include_str!("../../sample/sixteenth.txt");
+        let mut machine = Contraption::from_str(input).expect("parsing");
+        machine.set_entry(PART_ONE_ENTRY).expect("setting entry");
+
+        let mut app = app(machine, 50., 4., HeadlessPlugins);
+        for _ in 0..3 {
+            app.update();
+        }
+    }
+
+    #[rstest]
+    #[case(0., TRAIL_HEAD)]
+    #[case(4., Color::RED)]
+    #[case(10., Color::RED)]
+    #[case(-1., TRAIL_HEAD)]
+    fn trail_fades_towards_beam_color(#[case] age: f32, #[case] expected: Color) {
+        assert_eq!(trail_color(Color::RED, age, 4.), expected);
+    }
+
+    #[test]
+    fn legend_counts_hits() {
+        let input = include_str!("../../sample/sixteenth.txt");
+        let mut machine = Contraption::from_str(input).expect("parsing");
+        machine.set_entry(PART_ONE_ENTRY).expect("setting entry");
+        machine.run_to_equilibrium().expect("running");
+        let hits = machine.hits(Mirror::SplitterUD);
+
+        let mut app = app(machine, 50., 4., HeadlessPlugins);
+        app.update();
+        let text = app
+            .world
+            .query_filtered::<&Text, With<Legend>>()
+            .single(&app.world);
+        let line = all::<Mirror>()
+            .position(|mirror| mirror == Mirror::SplitterUD)
+            .expect("splitter in legend");
+        assert!(hits > 0);
+        assert_eq!(
+            text.sections[line].value,
+            legend_entry(Mirror::SplitterUD, hits)
+        );
+    }
+
+    #[test]
+    fn explains_picked_cells() {
+        let input = include_str!("../../sample/sixteenth.txt");
+        let mut machine = Contraption::from_str(input).expect("parsing");
+        machine.set_entry(PART_ONE_ENTRY).expect("setting entry");
+        machine.run_to_equilibrium().expect("running");
+
+        let splitter = machine.pick(Vec2::new(TILE, 0.)).expect("cell");
+        assert_eq!(splitter, Coord::new(1, 0));
+        assert_eq!(
+            machine.explain(splitter)[0],
+            "| splits horizontal beams up & down"
+        );
+        assert!(machine
+            .explain(Coord::new(0, 0))
+            .contains(&String::from("Beam passing →")));
+        assert_eq!(machine.pick(Vec2::new(-TILE, 0.)), None);
+    }
+
+    #[rstest]
+    fn trail_without_duration_shows_beam_color() {
+        assert_eq!(trail_color(Color::RED, 0., 0.), Color::RED);
     }
 }
@@ -6,18 +6,31 @@ use std::{
 };
 
 use anyhow::anyhow;
-use bevy::{ecs::system::Resource, render::color::Color};
-use enum_iterator::all;
-use rand::{thread_rng, Rng};
+#[cfg(feature = "animations")]
+use bevy::ecs::system::Resource;
+use enum_iterator::{all, Sequence};
+use rand::{rngs::StdRng, Rng};
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
 use termion::color::{Fg, Reset, Rgb};
 
-use crate::{lerphsl, Coord, Direction};
+use crate::{
+    grid::Grid, lerp, render::Diagram, tui::Stepper, Coord, Direction, Part, Solver,
+    Strategy,
+};
 
+#[cfg(any(test, feature = "bench"))]
+pub mod alternatives;
+#[cfg(feature = "animations")]
 pub mod animation;
+mod runs;
+
+use runs::Runs;
 
 pub const PART_ONE_ENTRY: (Direction, i32) = (Direction::Right, 0);
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Sequence)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub enum Mirror {
     Slash,
     Backslash,
@@ -25,13 +38,44 @@ pub enum Mirror {
     SplitterUD,
 }
 
-#[derive(Resource)]
+/// Only the mirrors and size of a contraption get serialized, not the beams travelling in it
+#[derive(Clone)]
+#[cfg_attr(feature = "animations", derive(Resource))]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct Contraption {
-    cells: HashMap<Coord, Mirror>,
-    nrows: i32,
-    ncols: i32,
+    cells: Grid<Mirror>,
+    #[cfg_attr(feature = "serialize", serde(skip))]
     active: VecDeque<Beam>,
+    #[cfg_attr(feature = "serialize", serde(skip))]
     closed: Vec<Beam>,
+    /// Every cell & direction any beam travelled through, to tell quickly when a beam is finished
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    visited: HashSet<(Coord, Direction)>,
+    /// How often beams ran into each kind of mirror so far
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    hits: HashMap<Mirror, usize>,
+    /// Picks the hues of split off beams, see [`crate::rng`]
+    #[cfg_attr(feature = "serialize", serde(skip, default = "hues"))]
+    hues: StdRng,
+    /// Where the first beam entered, `None` until [`Contraption::set_entry`]
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    entry: Option<(Direction, i32)>,
+}
+
+/// Advancing a [`Contraption`] without any beam ever entering it, see [`Contraption::set_entry`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntryNotSet;
+
+impl Display for EntryNotSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "No entry set, so no beam would ever energize a cell")
+    }
+}
+
+impl std::error::Error for EntryNotSet {}
+
+fn hues() -> StdRng {
+    crate::rng::stream("beam hues")
 }
 
 #[derive(Debug, Clone)]
@@ -41,11 +85,21 @@ pub struct Ray {
     stamp: f32,
 }
 
-#[derive(Debug)]
+/// Straight run of `length` rays, which all head into the same direction as the `start` ray
+#[derive(Debug, Clone)]
+pub struct Segment {
+    start: Ray,
+    length: i32,
+    /// Stamp of the last ray, the ones in between are interpolated
+    until: f32,
+}
+
+#[derive(Debug, Clone)]
 pub struct Beam {
     latest: Ray,
-    rays: Vec<Ray>,
-    color: Color,
+    segments: Vec<Segment>,
+    /// Of the beam's color, in degrees
+    hue: f32,
     nrows: i32,
     ncols: i32,
 }
@@ -95,36 +149,123 @@ impl PartialEq for Ray {
     }
 }
 
+impl Segment {
+    fn new(start: Ray) -> Self {
+        Self {
+            until: start.stamp,
+            start,
+            length: 1,
+        }
+    }
+
+    fn step(&self) -> Coord {
+        Coord::from(self.start.direction)
+    }
+
+    pub fn first(&self) -> &Ray {
+        &self.start
+    }
+
+    pub fn last(&self) -> Ray {
+        Ray::new(
+            self.start.coord + self.step() * (self.length - 1),
+            self.start.direction,
+            self.until,
+        )
+    }
+
+    /// Grow by `ray`, if it continues this segment straight ahead
+    fn extend(&mut self, ray: &Ray) -> bool {
+        if ray.direction != self.start.direction || ray.coord != self.last().coord + self.step() {
+            return false;
+        }
+        self.length += 1;
+        self.until = ray.stamp;
+        true
+    }
+
+    fn contains(&self, ray: &Ray) -> bool {
+        let offset = ray.coord - self.start.coord;
+        let k = offset.dot(self.step());
+        ray.direction == self.start.direction
+            && offset == self.step() * k
+            && (0..self.length).contains(&k)
+    }
+
+    /// Expand the segment into one ray per cell
+    pub fn rays(&self) -> impl Iterator<Item = Ray> + '_ {
+        (0..self.length).map(|k| {
+            let t = if self.length > 1 {
+                k as f32 / (self.length - 1) as f32
+            } else {
+                0.
+            };
+            Ray::new(
+                self.start.coord + self.step() * k,
+                self.start.direction,
+                lerp(self.start.stamp, self.until, t),
+            )
+        })
+    }
+}
+
 impl Beam {
     fn new(ray: Ray, hue: f32, ncols: i32, nrows: i32) -> Self {
-        let rays = Vec::default();
-        let color = Color::hsl(hue, 1., 0.5);
+        let segments = Vec::default();
         Self {
-            rays,
+            segments,
             latest: ray,
-            color,
+            hue,
             nrows,
             ncols,
         }
     }
 
-    pub(crate) fn rays(&self) -> impl Iterator<Item = &Ray> {
-        self.rays.iter()
+    #[cfg(any(test, feature = "animations"))]
+    pub(crate) fn segments(&self) -> impl Iterator<Item = &Segment> {
+        self.segments.iter()
+    }
+
+    pub(crate) fn rays(&self) -> impl Iterator<Item = Ray> + '_ {
+        self.segments.iter().flat_map(|segment| segment.rays())
+    }
+
+    fn contains(&self, ray: &Ray) -> bool {
+        self.segments.iter().any(|segment| segment.contains(ray))
+    }
+
+    fn push(&mut self, ray: Ray) {
+        if let Some(segment) = self.segments.last_mut() {
+            if segment.extend(&ray) {
+                return;
+            }
+        }
+        self.segments.push(Segment::new(ray));
     }
 
     pub fn tip(&self) -> &Ray {
         &self.latest
     }
 
-    fn is_finished<'a>(&self, mut beams: impl Iterator<Item = &'a [Ray]>) -> bool {
-        beams.any(|beam| beam.contains(&self.latest))
+    /// Whether the tip left the contraption or runs where any beam (this one included) ran before
+    fn is_finished(&self, visited: &HashSet<(Coord, Direction)>) -> bool {
+        visited.contains(&(self.latest.coord, self.latest.direction))
             || self.latest.is_out_of_bounds(self.ncols, self.nrows)
     }
 
-    fn advance(&mut self, cells: &HashMap<Coord, Mirror>, stamp: f32) -> Option<Beam> {
-        self.rays.push(self.latest.clone());
+    /// Moves the tip one cell on, counting into `hits` if a mirror deflected or split it. Splitting
+    /// leaves both beams on the splitter, heading along it, so they don't count again
+    fn advance(
+        &mut self,
+        cells: &Grid<Mirror>,
+        stamp: f32,
+        hues: &mut StdRng,
+        hits: &mut HashMap<Mirror, usize>,
+    ) -> Option<Beam> {
+        self.push(self.latest.clone());
         use Direction::{Down, Left, Right, Up};
-        let (new_beam, next) = match (cells.get(&self.latest.coord), self.latest.direction) {
+        let mirror = cells.get(self.latest.coord);
+        let (new_beam, next) = match (mirror, self.latest.direction) {
             (None, _) => (None, self.latest.cast(stamp)), // empty space, simply cast the ray forward
             (Some(Mirror::Slash), Right | Left) => (None, self.latest.ccw().cast(stamp)),
             (Some(Mirror::Slash), Up | Down) => (None, self.latest.cw().cast(stamp)),
@@ -138,7 +279,7 @@ impl Beam {
                 (
                     Some(Beam::new(
                         other,
-                        (self.color.h() + thread_rng().gen_range(90.0..270.0)) % 360.,
+                        (self.hue + hues.gen_range(90.0..270.0)) % 360.,
                         self.ncols,
                         self.nrows,
                     )),
@@ -146,6 +287,9 @@ impl Beam {
                 )
             }
         };
+        if let Some(mirror) = mirror.filter(|_| next.direction != self.latest.direction) {
+            *hits.entry(*mirror).or_default() += 1;
+        }
         self.latest = next;
         new_beam
     }
@@ -153,18 +297,34 @@ impl Beam {
 
 impl Contraption {
     pub fn ncols(&self) -> i32 {
-        self.ncols
+        self.cells.ncols()
     }
 
     pub fn nrows(&self) -> i32 {
-        self.nrows
+        self.cells.nrows()
     }
 
     pub fn reset(&mut self) {
         self.active.clear();
         self.closed.clear();
+        self.visited.clear();
+        self.hits.clear();
+        self.hues = hues();
+        self.entry = None;
+    }
+
+    /// First cell of a beam entering at the `i`th row/column, on the edge opposite of where it
+    /// heads to
+    pub(crate) fn entry_cell(&self, (dir, i): (Direction, i32)) -> Coord {
+        match dir {
+            Direction::Right => Coord::new(0, i),
+            Direction::Down => Coord::new(i, 0),
+            Direction::Left => Coord::new(self.ncols() - 1, i),
+            Direction::Up => Coord::new(i, self.nrows() - 1),
+        }
     }
 
+    /// Where the beam enters, needed before advancing
     pub fn set_entry(&mut self, (dir, i): (Direction, i32)) -> anyhow::Result<()> {
         if !self.active.is_empty() {
             return Err(anyhow!(
@@ -172,8 +332,9 @@ impl Contraption {
             ));
         }
 
-        let ray = Ray::new(Coord::from(dir.cw()).abs() * i, dir, 0.);
-        self.active = [Beam::new(ray, 0., self.ncols, self.nrows)]
+        self.entry = Some((dir, i));
+        let ray = Ray::new(self.entry_cell((dir, i)), dir, 0.);
+        self.active = [Beam::new(ray, 0., self.ncols(), self.nrows())]
             .into_iter()
             .collect();
         Ok(())
@@ -182,7 +343,7 @@ impl Contraption {
     pub fn energized_cells(&self) -> HashSet<Coord> {
         self.closed
             .iter()
-            .flat_map(|beam| beam.rays.iter().map(|ray| ray.coord))
+            .flat_map(|beam| beam.rays().map(|ray| ray.coord))
             .collect()
     }
 
@@ -190,15 +351,13 @@ impl Contraption {
         self.active.is_empty()
     }
 
-    pub fn mirrors(&self) -> impl Iterator<Item = (&Coord, &Mirror)> {
+    pub fn mirrors(&self) -> impl Iterator<Item = (Coord, &Mirror)> {
         self.cells.iter()
     }
 
-    fn rays_iter(&self) -> impl Iterator<Item = &[Ray]> {
-        self.active
-            .iter()
-            .chain(self.closed.iter())
-            .map(|beam| beam.rays.as_slice())
+    /// How many times any beam has been deflected or split by a `mirror` of that kind
+    pub fn hits(&self, mirror: Mirror) -> usize {
+        self.hits.get(&mirror).copied().unwrap_or_default()
     }
 
     pub(crate) fn beams(&self) -> impl Iterator<Item = &Beam> {
@@ -208,93 +367,172 @@ impl Contraption {
         self.active.iter()
     }
 
-    pub fn advance(&mut self, stamp: f32) {
+    pub fn entry(&self) -> Option<(Direction, i32)> {
+        self.entry
+    }
+
+    /// Let every active beam travel one cell further
+    pub fn advance(&mut self) -> Result<(), EntryNotSet> {
+        self.advance_stamped(0.)
+    }
+
+    /// Like [`Contraption::advance`], but remembers `stamp` (e.g. the current time) in each new
+    /// ray, so that visualizations know when a cell got energized
+    pub fn advance_stamped(&mut self, stamp: f32) -> Result<(), EntryNotSet> {
+        if self.entry.is_none() {
+            return Err(EntryNotSet);
+        }
         let mut n = self.active.len();
         while n > 0 && let Some(mut beam) = self.active.pop_front() {
             n -= 1;
-            if beam.is_finished(self.rays_iter().chain(once(beam.rays.as_slice()))) {
+            if beam.is_finished(&self.visited) {
                 self.closed.push(beam);
                 continue;
             }
-            if let Some(new_beam) = beam.advance(&self.cells, stamp) {
+            self.visited.insert((beam.tip().coord, beam.tip().direction));
+            let new_beam = beam.advance(&self.cells, stamp, &mut self.hues, &mut self.hits);
+            if let Some(new_beam) = new_beam {
                 self.active.push_back(new_beam);
             }
             self.active.push_back(beam);
         }
+        Ok(())
+    }
+
+    /// Advance until all beams are finished and return how many steps that took
+    pub fn run_to_equilibrium(&mut self) -> Result<usize, EntryNotSet> {
+        if self.entry.is_none() {
+            return Err(EntryNotSet);
+        }
+        let mut steps = 0;
+        while !self.is_in_equilibrium() {
+            self.advance()?;
+            steps += 1;
+        }
+        Ok(steps)
+    }
+}
+
+impl Contraption {
+    /// Number of cells energized by a beam entering at `entry`, same as advancing until equilibrium
+    /// but without keeping track of any beams, colors or stamps
+    pub fn energize(&self, entry: (Direction, i32)) -> usize {
+        let mut visited = HashSet::new();
+        let mut open = vec![(self.entry_cell(entry), entry.0)];
+        while let Some((coord, direction)) = open.pop() {
+            if !self.cells.contains(coord) || !visited.insert((coord, direction)) {
+                continue;
+            }
+            let (next, split) = match self.cells.get(coord) {
+                Some(mirror) => mirror.deflect(direction),
+                None => (direction, None),
+            };
+            open.extend(
+                once(next)
+                    .chain(split)
+                    .map(|dir| (coord + Coord::from(dir), dir)),
+            );
+        }
+        visited
+            .into_iter()
+            .map(|(coord, _)| coord)
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    /// All entries along the edges: rightwards along the rows, upwards along the columns, then
+    /// leftwards & downwards back in reverse
+    pub fn entries(&self) -> impl Iterator<Item = (Direction, i32)> {
+        let (ncols, nrows) = (self.ncols(), self.nrows());
+        (0..nrows)
+            .map(|i| (Direction::Right, i))
+            .chain((0..ncols).map(|i| (Direction::Up, i)))
+            .chain((0..nrows).rev().map(|i| (Direction::Left, i)))
+            .chain((0..ncols).rev().map(|i| (Direction::Down, i)))
+    }
+
+    /// Entry along the edges, from which a beam energizes the most cells, and that number of cells.
+    /// The straight runs between mirrors get computed once and are then shared by all entries
+    pub fn best_entry(&self) -> Option<((Direction, i32), usize)> {
+        let starts = |entry: (Direction, i32)| (self.entry_cell(entry), entry.0);
+        let mut runs = Runs::new(self, self.entries().map(starts));
+        self.entries()
+            .map(|entry| {
+                let (coord, direction) = starts(entry);
+                (entry, runs.energize(coord, direction))
+            })
+            .max_by_key(|(_, energized_cells)| *energized_cells)
     }
 }
 
 impl FromStr for Contraption {
     type Err = anyhow::Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let cells = s
-            .lines()
-            .enumerate()
-            .flat_map(|(y, line)| {
-                line.trim()
-                    .chars()
-                    .enumerate()
-                    .filter(|(_, c)| *c != '.')
-                    .map(move |(x, c)| {
-                        (Coord::new(x as i32, y as i32), Mirror::try_from(c).unwrap())
-                    })
-            })
-            .collect::<HashMap<_, _>>();
-        let nrows = s.lines().count() as i32;
-        let ncols = s
-            .lines()
-            .next()
-            .ok_or(anyhow!("Contraption must contain at least one line"))?
-            .trim()
-            .chars()
-            .count() as i32;
         Ok(Self {
-            cells,
-            ncols,
-            nrows,
+            cells: Grid::from_str(s)?,
             active: VecDeque::new(),
             closed: Vec::new(),
+            visited: HashSet::new(),
+            hits: HashMap::new(),
+            hues: hues(),
+            entry: None,
         })
     }
 }
 
+/// Fully saturated color of `hue` (in degrees) at half lightness, like the beams are drawn
+fn rgb(hue: f32) -> [u8; 3] {
+    let channel = |n: f32| {
+        let k = (n + hue / 30.) % 12.;
+        let value = 0.5 - 0.5 * (k - 3.).min(9. - k).clamp(-1., 1.);
+        (value * 255.).round() as u8
+    };
+    [channel(0.), channel(8.), channel(4.)]
+}
+
 impl Debug for Contraption {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let reset = Fg(Reset);
-        write!(f, "╭")?;
-        for _ in 0..self.ncols {
-            write!(f, "─")?;
-        }
-        writeln!(f, "╮")?;
-        for y in 0..self.nrows {
-            write!(f, "│")?;
-            for x in 0..self.ncols {
-                let coord = Coord::new(x, y);
-                let color = self
-                    .beams()
-                    .filter(|beam| {
-                        all::<Direction>()
-                            .any(|dir| beam.rays.contains(&Ray::new(coord, dir, f32::NAN)))
-                    })
-                    .map(|beam| beam.color)
-                    .reduce(|a, b| lerphsl(a, b, 0.5))
-                    .unwrap_or(Color::GRAY);
-                let color = color.as_rgba_u8();
-                let fg = Fg(Rgb(color[0], color[1], color[2]));
-                if let Some(mirror) = self.cells.get(&coord) {
-                    write!(f, "{fg}{}{reset}", mirror)?;
-                } else {
-                    write!(f, "{fg}·{reset}")?;
-                }
+        let pretty = self.cells.pretty(|coord, mirror| {
+            let color = self
+                .beams()
+                .filter(|beam| {
+                    all::<Direction>().any(|dir| beam.contains(&Ray::new(coord, dir, f32::NAN)))
+                })
+                .map(|beam| beam.hue)
+                .reduce(|a, b| lerp(a, b, 0.5))
+                .map_or([127; 3], rgb);
+            let fg = Fg(Rgb(color[0], color[1], color[2]));
+            match mirror {
+                Some(mirror) => format!("{fg}{mirror}{reset}"),
+                None => format!("{fg}·{reset}"),
             }
-            writeln!(f, "│")?;
-        }
-        write!(f, "╰")?;
-        for _ in 0..self.ncols {
-            write!(f, "─")?;
-        }
-        write!(f, "╯")?;
-        Ok(())
+        });
+        write!(f, "{}", pretty.bordered())
+    }
+}
+
+impl Stepper for Contraption {
+    fn step(&mut self) -> bool {
+        !self.is_in_equilibrium() && self.advance().is_ok()
+    }
+
+    fn frame(&self) -> String {
+        format!("{self:?}\nEnergized: {}", self.energized_cells().len())
+    }
+}
+
+impl Diagram for Contraption {
+    fn size(&self) -> Coord {
+        self.cells.size()
+    }
+
+    fn color(&self, cell: Coord) -> Option<[u8; 3]> {
+        self.cells.get(cell).map(|_| [200, 200, 200])
+    }
+
+    fn symbol(&self, cell: Coord) -> char {
+        self.cells.get(cell).map_or('·', Mirror::symbol)
     }
 }
 
@@ -309,6 +547,41 @@ impl Display for Mirror {
     }
 }
 
+impl Mirror {
+    /// Character this mirror is written as in the puzzle input
+    pub fn symbol(&self) -> char {
+        match self {
+            Self::SplitterLR => '-',
+            Self::SplitterUD => '|',
+            Self::Slash => '/',
+            Self::Backslash => '\\',
+        }
+    }
+
+    /// Direction in which a beam heading into `direction` leaves this mirror, and the one of the
+    /// second beam, if it got split
+    pub fn deflect(&self, direction: Direction) -> (Direction, Option<Direction>) {
+        use Direction::{Down, Left, Right, Up};
+        match (self, direction) {
+            (Self::SplitterUD, Up | Down) | (Self::SplitterLR, Left | Right) => (direction, None),
+            (Self::Slash, Right | Left) | (Self::Backslash, Up | Down) => (direction.ccw(), None),
+            (Self::Slash, Up | Down) | (Self::Backslash, Right | Left) => (direction.cw(), None),
+            (Self::SplitterUD, Left | Right) | (Self::SplitterLR, Up | Down) => {
+                (direction.ccw(), Some(direction.cw()))
+            }
+        }
+    }
+
+    /// What this mirror does to a beam running into it
+    pub fn meaning(&self) -> &'static str {
+        match self {
+            Self::Slash | Self::Backslash => "deflects beams by 90°",
+            Self::SplitterLR => "splits vertical beams to the left & right",
+            Self::SplitterUD => "splits horizontal beams up & down",
+        }
+    }
+}
+
 impl TryFrom<char> for Mirror {
     type Error = anyhow::Error;
     fn try_from(value: char) -> Result<Self, Self::Error> {
@@ -321,3 +594,165 @@ impl TryFrom<char> for Mirror {
         }
     }
 }
+
+/// Number of cells a beam energizes, which enters `input`'s contraption at `entry`
+pub fn energize(input: &str, entry: (Direction, i32)) -> anyhow::Result<usize> {
+    Ok(Contraption::from_str(input)?.energize(entry))
+}
+
+/// Entry along the edges, from which a beam energizes the most cells, and that number of cells
+pub fn best_entry(input: &str) -> anyhow::Result<((Direction, i32), usize)> {
+    Contraption::from_str(input)?
+        .best_entry()
+        .ok_or(anyhow!("No best entry found"))
+}
+
+pub fn solve(part: Part, input: &str) -> anyhow::Result<usize> {
+    match part {
+        Part::One => energize(input, PART_ONE_ENTRY),
+        Part::Two => best_entry(input).map(|(_, energized_cells)| energized_cells),
+    }
+}
+
+/// Day 16: The Floor Will Be Lava
+#[derive(Debug, Clone, Copy)]
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    fn name(&self) -> &'static str {
+        "sixteenth"
+    }
+
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::One, input).map(|x| x.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::Two, input).map(|x| x.to_string())
+    }
+
+    fn strategies(&self) -> Vec<Strategy> {
+        vec![Strategy {
+            name: "beams",
+            about: "Advance all beams step by step until they all left, from each entry anew",
+            solve: |part, input| {
+                let mut contraption = Contraption::from_str(input)?;
+                let entries = match part {
+                    Part::One => vec![PART_ONE_ENTRY],
+                    Part::Two => contraption.entries().collect(),
+                };
+                let mut most = 0;
+                for entry in entries {
+                    contraption.reset();
+                    contraption.set_entry(entry)?;
+                    contraption.run_to_equilibrium()?;
+                    most = most.max(contraption.energized_cells().len());
+                }
+                Ok(most.to_string())
+            },
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    fn straight_rays_share_one_segment() {
+        let mut beam = Beam::new(Ray::new(Coord::new(0, 0), Direction::Right, 0.), 0., 5, 5);
+        for x in 0..4 {
+            beam.push(Ray::new(Coord::new(x, 0), Direction::Right, x as f32));
+        }
+        beam.push(Ray::new(Coord::new(3, 1), Direction::Down, 4.));
+
+        assert_eq!(beam.segments().count(), 2);
+        assert!(beam.contains(&Ray::new(Coord::new(2, 0), Direction::Right, f32::NAN)));
+        assert!(!beam.contains(&Ray::new(Coord::new(2, 0), Direction::Left, f32::NAN)));
+        assert!(!beam.contains(&Ray::new(Coord::new(4, 0), Direction::Right, f32::NAN)));
+        assert_eq!(
+            beam.rays()
+                .map(|ray| (ray.coord, ray.stamp))
+                .collect::<Vec<_>>(),
+            vec![
+                (Coord::new(0, 0), 0.),
+                (Coord::new(1, 0), 1.),
+                (Coord::new(2, 0), 2.),
+                (Coord::new(3, 0), 3.),
+                (Coord::new(3, 1), 4.),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case(".\\.\n...", [0, 1, 0, 0])]
+    // Split into up & down, the latter split again into left & right
+    #[case(".|-\n.-.", [0, 0, 1, 1])]
+    // Beams running along a splitter pass it without a hit
+    #[case(".-|\n...", [0, 0, 1, 0])]
+    #[case("-/\n.|", [1, 0, 0, 0])]
+    fn hits_count_mirrors_per_kind(#[case] input: &str, #[case] expected: [usize; 4]) {
+        let kinds = [
+            Mirror::Slash,
+            Mirror::Backslash,
+            Mirror::SplitterUD,
+            Mirror::SplitterLR,
+        ];
+        let mut contraption = Contraption::from_str(input).expect("parsing");
+        contraption.set_entry(PART_ONE_ENTRY).expect("setting entry");
+        contraption.run_to_equilibrium().expect("running");
+        assert_eq!(kinds.map(|kind| contraption.hits(kind)), expected);
+
+        contraption.reset();
+        assert_eq!(kinds.map(|kind| contraption.hits(kind)), [0; 4]);
+    }
+
+    #[rstest]
+    fn advancing_needs_an_entry() {
+        let mut contraption = Contraption::from_str(".\\.\n...").expect("parsing");
+        assert_eq!(contraption.advance(), Err(EntryNotSet));
+        assert_eq!(contraption.run_to_equilibrium(), Err(EntryNotSet));
+
+        contraption.set_entry(PART_ONE_ENTRY).expect("setting entry");
+        assert!(contraption.run_to_equilibrium().expect("running") > 0);
+        assert_eq!(contraption.energized_cells().len(), 3);
+
+        contraption.reset();
+        assert_eq!(contraption.advance(), Err(EntryNotSet));
+    }
+
+    #[rstest]
+    fn energize_matches_beams() {
+        let input = include_str!("../../sample/sixteenth.txt");
+        let contraption = Contraption::from_str(input).expect("parsing");
+        let mut beams = contraption.clone();
+        for entry in contraption.entries() {
+            beams.reset();
+            beams.set_entry(entry).expect("entry");
+            beams.run_to_equilibrium().expect("running");
+            assert_eq!(
+                beams.energized_cells().len(),
+                contraption.energize(entry),
+                "{entry:?}"
+            );
+        }
+    }
+
+    #[rstest]
+    fn symbols_round_trip() {
+        for mirror in all::<Mirror>() {
+            assert_eq!(Mirror::try_from(mirror.symbol()).expect("mirror"), mirror);
+        }
+    }
+
+    #[cfg(feature = "serialize")]
+    #[rstest]
+    fn json_round_trip() {
+        let input = include_str!("../../sample/sixteenth.txt");
+        let contraption = Contraption::from_str(input).expect("parsing");
+        let json = serde_json::to_string(&contraption).expect("serializing");
+        let parsed = serde_json::from_str::<Contraption>(&json).expect("deserializing");
+        assert_eq!(format!("{parsed:?}"), format!("{contraption:?}"));
+    }
+}
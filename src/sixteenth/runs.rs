@@ -0,0 +1,137 @@
+//! Straight runs of cells between the mirrors turning beams, computed once per contraption, so
+//! that beams from every entry can follow them instead of advancing cell by cell
+
+use std::{collections::HashMap, iter::once};
+
+use itertools::Itertools;
+
+use super::Contraption;
+use crate::{Coord, Direction};
+
+/// Cells a beam passes in a straight line, up to (and including) the mirror which turns or splits
+/// it, and the runs it continues on from there
+#[derive(Debug, Clone)]
+struct Run {
+    cells: Vec<usize>,
+    next: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Runs {
+    runs: Vec<Run>,
+    /// Index of the run starting at each cell & direction
+    starts: HashMap<(Coord, Direction), usize>,
+    /// Stamp of the last beam, which passed each run, so nothing needs clearing between beams
+    visited: Vec<u32>,
+    /// Stamp of the last beam, which energized each cell
+    energized: Vec<u32>,
+    stamp: u32,
+}
+
+impl Runs {
+    /// All runs, which beams starting at any of the `starts` (cell & direction) can reach
+    pub(crate) fn new(
+        contraption: &Contraption,
+        starts: impl IntoIterator<Item = (Coord, Direction)>,
+    ) -> Self {
+        let ncols = contraption.ncols();
+        let index = |coord: Coord| (coord.y * ncols + coord.x) as usize;
+        let mut ids = HashMap::new();
+        let mut runs = Vec::new();
+        let mut open = starts.into_iter().collect_vec();
+        while let Some((start, direction)) = open.pop() {
+            if ids.contains_key(&(start, direction)) {
+                continue;
+            }
+            ids.insert((start, direction), runs.len());
+            let (mut coord, mut cells, mut exits) = (start, Vec::new(), Vec::new());
+            while contraption.cells.contains(coord) {
+                cells.push(index(coord));
+                let turns = match contraption.cells.get(coord) {
+                    Some(mirror) => mirror.deflect(direction),
+                    None => (direction, None),
+                };
+                if turns == (direction, None) {
+                    coord += Coord::from(direction);
+                    continue;
+                }
+                exits = once(turns.0)
+                    .chain(turns.1)
+                    .map(|dir| (coord + Coord::from(dir), dir))
+                    .filter(|(next, _)| contraption.cells.contains(*next))
+                    .collect_vec();
+                break;
+            }
+            open.extend(exits.iter().copied());
+            runs.push((cells, exits));
+        }
+
+        let ncells = (contraption.ncols() * contraption.nrows()) as usize;
+        Self {
+            visited: vec![0; runs.len()],
+            energized: vec![0; ncells],
+            stamp: 0,
+            runs: runs
+                .into_iter()
+                .map(|(cells, exits)| Run {
+                    cells,
+                    next: exits.iter().map(|exit| ids[exit]).collect(),
+                })
+                .collect(),
+            starts: ids,
+        }
+    }
+
+    /// Number of cells energized by a beam starting at `coord` into `direction`, which must be one
+    /// of the starts this was created with
+    pub(crate) fn energize(&mut self, coord: Coord, direction: Direction) -> usize {
+        let Some(&start) = self.starts.get(&(coord, direction)) else {
+            return 0;
+        };
+        self.stamp += 1;
+        let mut energized = 0;
+        let mut open = vec![start];
+        while let Some(i) = open.pop() {
+            if self.visited[i] == self.stamp {
+                continue;
+            }
+            self.visited[i] = self.stamp;
+            let run = &self.runs[i];
+            for &cell in &run.cells {
+                if self.energized[cell] != self.stamp {
+                    self.energized[cell] = self.stamp;
+                    energized += 1;
+                }
+            }
+            open.extend(&run.next);
+        }
+        energized
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::str::FromStr;
+
+    #[rstest]
+    #[case(include_str!("../../sample/sixteenth.txt"))]
+    #[case("|....-\n......\n......\n-....|")]
+    #[case("/-\\\n|.|\n\\-/")]
+    fn runs_energize_like_beams(#[case] input: &str) {
+        let contraption = Contraption::from_str(input).expect("parsing");
+        let starts = contraption
+            .entries()
+            .map(|entry| (contraption.entry_cell(entry), entry.0))
+            .collect_vec();
+        let mut runs = Runs::new(&contraption, starts.clone());
+        for (entry, (coord, direction)) in contraption.entries().zip(starts) {
+            assert_eq!(
+                runs.energize(coord, direction),
+                contraption.energize(entry),
+                "{entry:?}"
+            );
+        }
+    }
+}
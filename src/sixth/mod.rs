@@ -0,0 +1,180 @@
+use nom::Finish;
+use num::integer::Roots;
+
+use self::parser::document;
+use crate::{anyhowing, Part, Solver};
+
+mod parser;
+
+/// Race of a toy boat: it goes as many millimeters per millisecond as the button was held down
+/// for at the start, which counts into the race's time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Race {
+    /// Milliseconds the race lasts
+    pub time: u64,
+    /// Millimeters of the record to beat
+    pub distance: u64,
+}
+
+impl Race {
+    pub fn new(time: u64, distance: u64) -> Self {
+        Self { time, distance }
+    }
+
+    /// Millimeters the boat gets, if the button is held down for `hold` milliseconds
+    pub fn travels(&self, hold: u64) -> u128 {
+        let hold = hold.min(self.time);
+        hold as u128 * (self.time - hold) as u128
+    }
+
+    /// Whether holding the button down for `hold` milliseconds beats the record
+    pub fn beats(&self, hold: u64) -> bool {
+        self.travels(hold) > self.distance as u128
+    }
+
+    /// How many ways to hold the button beat the record, trying each of them
+    pub fn ways_to_win(&self) -> u64 {
+        (0..=self.time).filter(|hold| self.beats(*hold)).count() as u64
+    }
+
+    /// Like [`Race::ways_to_win`], but from the roots of `hold * (time - hold) = distance`. The
+    /// winning holds lie between them, symmetric around `time / 2`, where the boat goes farthest
+    pub fn ways_to_win_fast(&self) -> u64 {
+        let (time, distance) = (self.time as u128, self.distance as u128);
+        let Some(discriminant) = (time * time).checked_sub(4 * distance) else {
+            return 0;
+        };
+        // The integer square root may be off by one from the real one, which moves the shortest
+        // winning hold by at most one, too
+        let mut shortest = ((time - discriminant.sqrt()) / 2) as u64;
+        while shortest > 0 && self.beats(shortest - 1) {
+            shortest -= 1;
+        }
+        while shortest <= self.time / 2 && !self.beats(shortest) {
+            shortest += 1;
+        }
+        if shortest > self.time / 2 {
+            return 0;
+        }
+        self.time - 2 * shortest + 1
+    }
+}
+
+/// Races of the document. With `kerning` (part two) it's one long race, whose numbers are all of
+/// the digits of each line
+pub fn races(input: &str, kerning: bool) -> anyhow::Result<Vec<Race>> {
+    let (times, distances) = document(input, kerning).finish().map_err(anyhowing)?.1;
+    if times.len() != distances.len() {
+        anyhow::bail!("{} times, but {} distances", times.len(), distances.len());
+    }
+    Ok(times
+        .into_iter()
+        .zip(distances)
+        .map(|(time, distance)| Race::new(time, distance))
+        .collect())
+}
+
+/// Product of the ways to win each race
+pub fn margin(races: &[Race]) -> u64 {
+    races.iter().map(Race::ways_to_win_fast).product()
+}
+
+pub fn solve(part: Part, input: &str) -> anyhow::Result<u64> {
+    Ok(margin(&races(input, part == Part::Two)?))
+}
+
+/// Day 6: Wait For It
+#[derive(Debug, Clone, Copy)]
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    fn name(&self) -> &'static str {
+        "sixth"
+    }
+
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::One, input).map(|x| x.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::Two, input).map(|x| x.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    const SAMPLE: &str = include_str!("../../sample/sixth.txt");
+
+    #[rstest]
+    #[case(Part::One, 288)]
+    #[case(Part::Two, 71503)]
+    fn sample(#[case] part: Part, #[case] expected: u64) {
+        assert_eq!(solve(part, SAMPLE).expect("solving"), expected);
+    }
+
+    #[rstest]
+    #[case(false, vec![Race::new(7, 9), Race::new(15, 40), Race::new(30, 200)])]
+    #[case(true, vec![Race::new(71530, 940200)])]
+    fn parses_races(#[case] kerning: bool, #[case] expected: Vec<Race>) {
+        assert_eq!(races(SAMPLE, kerning).expect("parsing"), expected);
+    }
+
+    #[rstest]
+    #[case(Race::new(7, 9), 4)]
+    #[case(Race::new(15, 40), 8)]
+    #[case(Race::new(30, 200), 9)]
+    #[case(Race::new(71530, 940200), 71503)]
+    // Exactly matching the record doesn't beat it
+    #[case(Race::new(4, 4), 0)]
+    #[case(Race::new(4, 3), 1)]
+    #[case(Race::new(5, 6), 0)]
+    #[case(Race::new(5, 5), 2)]
+    #[case(Race::new(0, 0), 0)]
+    #[case(Race::new(1, 0), 0)]
+    #[case(Race::new(2, 0), 1)]
+    fn both_ways_agree(#[case] race: Race, #[case] expected: u64) {
+        assert_eq!(race.ways_to_win(), expected);
+        assert_eq!(race.ways_to_win_fast(), expected);
+    }
+
+    #[rstest]
+    fn sample_winning_holds() {
+        let race = Race::new(7, 9);
+        let winning = (0..=race.time)
+            .filter(|hold| race.beats(*hold))
+            .map(|hold| (hold, race.travels(hold)))
+            .collect::<Vec<_>>();
+        assert_eq!(winning, vec![(2, 10), (3, 12), (4, 12), (5, 10)]);
+    }
+
+    #[rstest]
+    fn fast_way_copes_with_huge_races() {
+        // The distances overflow a u64 long before the longest holds
+        let race = Race::new(u64::MAX, u64::MAX);
+        let fast = race.ways_to_win_fast();
+        assert_eq!(fast % 2, 0);
+        assert!(race.beats((u64::MAX - fast) / 2 + 1));
+        assert!(!race.beats((u64::MAX - fast) / 2));
+    }
+
+    #[rstest]
+    #[case("Time: 7 15\nDistance: 9")]
+    #[case("Time: 7\nDistance: x")]
+    #[case("Distance: 9\nTime: 7")]
+    #[case("")]
+    fn invalid_documents(#[case] input: &str) {
+        assert!(races(input, false).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn fast_way_counts_the_same(time in 0..500u64, distance in 0..70_000u64) {
+            let race = Race::new(time, distance);
+            prop_assert_eq!(race.ways_to_win_fast(), race.ways_to_win());
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::{digit1, line_ending, multispace0},
+    sequence::separated_pair,
+    IResult, Parser as NomParser,
+};
+use nom_supreme::ParserExt;
+
+use crate::parse::common::labelled_list;
+
+/// Numbers after `label`, e.g. `Time:      7  15   30`. With `kerning` the digits of all of
+/// them make up a single number instead
+fn numbers<'a>(
+    label: &'static str,
+    kerning: bool,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<u64>> {
+    move |s| {
+        labelled_list(tag(label), digit1)
+            .map_res(|(_, digits)| {
+                if kerning {
+                    digits.concat().parse::<u64>().map(|n| vec![n])
+                } else {
+                    digits.iter().map(|d| d.parse::<u64>()).collect()
+                }
+            })
+            .parse(s)
+    }
+}
+
+/// Times & record distances of the races, both in the order of the document
+pub(crate) fn document(s: &str, kerning: bool) -> IResult<&str, (Vec<u64>, Vec<u64>)> {
+    multispace0
+        .precedes(separated_pair(
+            numbers("Time", kerning),
+            line_ending,
+            numbers("Distance", kerning),
+        ))
+        .terminated(multispace0)
+        .all_consuming()
+        .parse(s)
+}
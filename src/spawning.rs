@@ -0,0 +1,200 @@
+//! Spreads spawning many entities (e.g. one per grid cell) over several frames, so that the
+//! window stays responsive on startup, and shows a loading bar meanwhile
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::reset::OnReset;
+
+type Job = Box<dyn FnOnce(&mut Commands) + Send + Sync>;
+
+/// Entities waiting to be spawned by the [`StreamingPlugin`]. Fill it in a `Startup` system
+/// instead of spawning directly via `Commands`.
+///
+/// Each entity must be spawned exactly once, so the queue can't be cloned:
+/// ```compile_fail
+/// fn clone<T: Clone>(_: &T) {}
+/// clone(&aoc23::spawning::SpawnQueue::default());
+/// ```
+#[derive(Default, Resource)]
+pub struct SpawnQueue {
+    jobs: VecDeque<Job>,
+    total: usize,
+}
+
+impl std::fmt::Debug for SpawnQueue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SpawnQueue")
+            .field("pending", &self.jobs.len())
+            .field("total", &self.total)
+            .finish()
+    }
+}
+
+impl SpawnQueue {
+    /// Queue `bundle` to be spawned as a new entity
+    pub fn spawn(&mut self, bundle: impl Bundle) {
+        self.push(move |cmd| {
+            cmd.spawn(bundle);
+        });
+    }
+
+    /// Queue `bundle` to be spawned as a child of the already existing `parent`
+    pub fn spawn_child(&mut self, parent: Entity, bundle: impl Bundle) {
+        self.push(move |cmd| {
+            let child = cmd.spawn(bundle).id();
+            cmd.entity(parent).add_child(child);
+        });
+    }
+
+    fn push(&mut self, job: impl FnOnce(&mut Commands) + Send + Sync + 'static) {
+        self.jobs.push_back(Box::new(job));
+        self.total += 1;
+    }
+
+    /// Share of all queued entities, which already got spawned, from 0 to 1
+    pub fn progress(&self) -> f32 {
+        if self.total == 0 {
+            return 1.;
+        }
+        1. - self.jobs.len() as f32 / self.total as f32
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.jobs.is_empty()
+    }
+}
+
+/// Spawns at most `budget` entities of the [`SpawnQueue`] per frame
+#[derive(Debug, Resource, Clone, Copy)]
+pub struct StreamingPlugin {
+    pub budget: usize,
+}
+
+impl StreamingPlugin {
+    pub fn new(budget: usize) -> Self {
+        Self { budget }
+    }
+}
+
+impl Plugin for StreamingPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(*self)
+            .init_resource::<SpawnQueue>()
+            .add_systems(Startup, spawn_loading_bar)
+            .add_systems(OnReset, clear_queue)
+            .add_systems(Update, (spawn_queued, update_loading_bar).chain());
+    }
+}
+
+/// Drops whatever is still waiting, since `Startup` queues everything again
+fn clear_queue(mut queue: ResMut<SpawnQueue>) {
+    *queue = SpawnQueue::default();
+}
+
+#[derive(Debug, Component)]
+struct LoadingBar;
+
+#[derive(Debug, Component)]
+struct LoadingProgress;
+
+fn spawn_loading_bar(mut cmd: Commands) {
+    cmd.spawn((
+        LoadingBar,
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(25.),
+                bottom: Val::Px(20.),
+                width: Val::Percent(50.),
+                height: Val::Px(8.),
+                ..default()
+            },
+            background_color: Color::DARK_GRAY.into(),
+            ..default()
+        },
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            LoadingProgress,
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(0.),
+                    height: Val::Percent(100.),
+                    ..default()
+                },
+                background_color: Color::WHITE.into(),
+                ..default()
+            },
+        ));
+    });
+}
+
+fn spawn_queued(mut cmd: Commands, mut queue: ResMut<SpawnQueue>, plugin: Res<StreamingPlugin>) {
+    for _ in 0..plugin.budget {
+        let Some(job) = queue.jobs.pop_front() else {
+            return;
+        };
+        job(&mut cmd);
+    }
+}
+
+fn update_loading_bar(
+    mut cmd: Commands,
+    queue: Res<SpawnQueue>,
+    bars: Query<Entity, With<LoadingBar>>,
+    mut progress: Query<&mut Style, With<LoadingProgress>>,
+) {
+    if queue.is_done() {
+        for bar in bars.iter() {
+            cmd.entity(bar).despawn_recursive();
+        }
+        return;
+    }
+    for mut style in progress.iter_mut() {
+        style.width = Val::Percent(100. * queue.progress());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HeadlessPlugins;
+
+    #[derive(Debug, Component)]
+    struct Dummy;
+
+    #[test]
+    fn spawns_within_budget_per_frame() {
+        let mut app = App::new();
+        app.add_plugins((HeadlessPlugins, StreamingPlugin::new(4)));
+        let parent = app.world.spawn_empty().id();
+        {
+            let mut queue = app.world.resource_mut::<SpawnQueue>();
+            for _ in 0..5 {
+                queue.spawn(Dummy);
+            }
+            for _ in 0..5 {
+                queue.spawn_child(parent, Dummy);
+            }
+        }
+        let count = |app: &mut App| app.world.query::<&Dummy>().iter(&app.world).count();
+        let bars = |app: &mut App| app.world.query::<&LoadingBar>().iter(&app.world).count();
+
+        app.update();
+        assert_eq!(count(&mut app), 4);
+        assert_eq!(bars(&mut app), 1);
+        app.update();
+        assert_eq!(count(&mut app), 8);
+        app.update();
+        assert_eq!(count(&mut app), 10);
+        assert_eq!(bars(&mut app), 0);
+        assert_eq!(
+            app.world
+                .get::<Children>(parent)
+                .map(|children| children.len()),
+            Some(5)
+        );
+    }
+}
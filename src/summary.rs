@@ -0,0 +1,158 @@
+//! Card summing up an animation once it is done, which each day announces with an
+//! [`AnimationFinished`] event. The key of [`Action::Restart`] then starts the animation over
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::keybindings::{Action, Keybindings};
+
+const FONT_SIZE: f32 = 32.;
+
+/// Sent by an animation, when it arrived at its answer
+#[derive(Debug, Clone, PartialEq, Event)]
+pub struct AnimationFinished {
+    pub answer: String,
+    /// How many steps the animation simulated to get there
+    pub steps: usize,
+    /// Further numbers of the day worth mentioning, as pairs of name & value
+    pub stats: Vec<(String, String)>,
+}
+
+impl AnimationFinished {
+    pub fn new(answer: impl ToString, steps: usize) -> Self {
+        Self {
+            answer: answer.to_string(),
+            steps,
+            stats: Vec::new(),
+        }
+    }
+
+    /// Also mention `value` as `name` on the card
+    pub fn with(mut self, name: impl ToString, value: impl ToString) -> Self {
+        self.stats.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Lines of the card, after the animation ran for `elapsed`
+    fn lines(&self, elapsed: Duration, restart: KeyCode) -> Vec<String> {
+        [
+            format!("Answer: {}", self.answer),
+            format!("Steps: {}", self.steps),
+            format!("Time: {:.1?}", elapsed),
+        ]
+        .into_iter()
+        .chain(
+            self.stats
+                .iter()
+                .map(|(name, value)| format!("{name}: {value}")),
+        )
+        .chain([format!("Press {restart:?} to restart")])
+        .collect()
+    }
+}
+
+#[derive(Debug, Component)]
+struct SummaryCard;
+
+/// Shows the card for each [`AnimationFinished`] event, added by the [`crate::AocControlPlugin`]
+#[derive(Debug, Clone, Copy)]
+pub struct SummaryPlugin;
+
+impl Plugin for SummaryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<AnimationFinished>()
+            .add_systems(Update, show);
+    }
+}
+
+fn show(
+    mut cmd: Commands,
+    time: Res<Time>,
+    bindings: Res<Keybindings>,
+    mut finished: EventReader<AnimationFinished>,
+    cards: Query<Entity, With<SummaryCard>>,
+) {
+    let Some(finished) = finished.read().last() else {
+        return;
+    };
+    for card in cards.iter() {
+        cmd.entity(card).despawn_recursive();
+    }
+    let style = TextStyle {
+        font_size: FONT_SIZE,
+        color: Color::WHITE,
+        ..default()
+    };
+    let text = finished
+        .lines(time.elapsed(), bindings.key(Action::Restart))
+        .join("\n");
+    cmd.spawn((
+        SummaryCard,
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        },
+    ))
+    .with_children(|parent| {
+        parent.spawn(TextBundle {
+            text: Text::from_section(text, style),
+            style: Style {
+                padding: UiRect::all(Val::Px(FONT_SIZE)),
+                ..default()
+            },
+            background_color: Color::rgba(0., 0., 0., 0.8).into(),
+            ..default()
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HeadlessPlugins;
+    use rstest::rstest;
+
+    #[rstest]
+    fn lists_answer_and_stats() {
+        let finished = AnimationFinished::new(46, 30).with("Ghosts", 2);
+        assert_eq!(
+            finished.lines(Duration::from_millis(1500), KeyCode::R),
+            vec![
+                "Answer: 46",
+                "Steps: 30",
+                "Time: 1.5s",
+                "Ghosts: 2",
+                "Press R to restart"
+            ]
+        );
+    }
+
+    #[rstest]
+    fn shows_one_card_per_animation() {
+        let mut app = App::new();
+        app.add_plugins((HeadlessPlugins, SummaryPlugin))
+            .insert_resource(Keybindings::default());
+        app.update();
+        app.world.send_event(AnimationFinished::new(6, 3));
+        app.update();
+        app.world.send_event(AnimationFinished::new(7, 4));
+        app.update();
+
+        let texts = app
+            .world
+            .query::<&Text>()
+            .iter(&app.world)
+            .map(|text| text.sections[0].value.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(texts.len(), 1);
+        assert!(texts[0].starts_with("Answer: 7\nSteps: 4\n"), "{texts:?}");
+    }
+}
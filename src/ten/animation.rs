@@ -1,50 +1,155 @@
-use crate::{frequency_increaser, mouse, toggle_running, Running, Scroll, Tick};
+use crate::{
+    capture::CapturePlugin,
+    picking::{CellInfo, PickingPlugin},
+    reset::ResetPlugin,
+    samples::SamplesPlugin,
+    spawning::{SpawnQueue, StreamingPlugin},
+    summary::AnimationFinished,
+    AocCameraPlugin, AocControlPlugin, Presentation, Running, Tick,
+};
 
-use super::{Coord, Maze, Pipe};
+use super::{heat, Coord, Maze, Pipe};
 
 use bevy::{prelude::*, sprite::Anchor};
-use std::collections::HashSet;
-
-pub fn run(maze: Maze, frequency: f32) {
-    App::new()
-        .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest())) // prevents blurry sprites
-        .insert_resource(maze)
-        .insert_resource(GameState::default())
-        .insert_resource(Running::default())
-        .insert_resource(Tick::new(frequency))
-        .add_systems(Startup, setup)
-        .add_systems(
-            Update,
-            (
-                update,
-                mouse,
-                path_counter,
-                area_counter,
-                toggle_running,
-                pipe_colorer,
-                frequency_increaser,
-            ),
-        )
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+/// Inputs to flip through with `[` & `]`
+const SAMPLES: [&str; 6] = [
+    include_str!("../../sample/tenth-a.txt"),
+    include_str!("../../sample/tenth-b.txt"),
+    include_str!("../../sample/tenth-c.txt"),
+    include_str!("../../sample/tenth-d.txt"),
+    include_str!("../../sample/tenth-e.txt"),
+    include_str!("../../sample/tenth-f.txt"),
+];
+
+pub fn run(maze: Maze, frequency: f32, presentation: Presentation) {
+    // nearest sampling prevents blurry sprites
+    let plugins = DefaultPlugins
+        .set(ImagePlugin::default_nearest())
+        .add(presentation);
+    app(maze, frequency, plugins)
+        .add_plugins(CapturePlugin)
         .run()
 }
 
-#[derive(Debug, Default, Resource)]
+pub fn app(maze: Maze, frequency: f32, plugins: impl PluginGroup) -> App {
+    let mut app = App::new();
+    let start = Vec3::new(maze.start.x as f32 * TILE, -maze.start.y as f32 * TILE, 0.);
+    insert(&mut app.world, maze);
+    app.add_plugins((
+        plugins,
+        AocCameraPlugin::new(0.05).at(start),
+        AocControlPlugin,
+        PickingPlugin::<Maze>::default(),
+        StreamingPlugin::new(SPAWN_BUDGET),
+        SamplesPlugin::new(SAMPLES, load),
+    ))
+    .insert_resource(Tick::new(frequency))
+    .add_plugins(ResetPlugin::<GameState>::default())
+    .add_systems(Startup, setup)
+    .add_systems(
+        Update,
+        (
+            update,
+            path_counter,
+            walker_mover,
+            area_counter,
+            pipe_colorer,
+        ),
+    );
+    app
+}
+
+/// The `maze` and everything derived from it, which the animation needs
+fn insert(world: &mut World, maze: Maze) {
+    world.insert_resource(Distances::new(&maze));
+    world.insert_resource(Junk::new(&maze));
+    world.insert_resource(Walk(maze.bidirectional_walk().collect()));
+    world.insert_resource(GameState::default());
+    world.insert_resource(maze);
+}
+
+fn load(world: &mut World, input: &str) -> anyhow::Result<()> {
+    let mut maze = Maze::from_str(input)?;
+    maze.calculate_inside();
+    insert(world, maze);
+    Ok(())
+}
+
+#[derive(Debug, Default, Clone, Resource)]
 struct GameState {
     progress: usize,
 }
 
 impl GameState {
-    fn path(&self, maze: &Maze) -> usize {
-        self.progress.min(maze.path().len())
+    /// Steps both walkers took so far, stops at the Part One answer
+    fn steps(&self, walk: &Walk) -> usize {
+        self.progress.min(walk.0.len())
     }
 
-    fn area(&self, maze: &Maze) -> usize {
+    fn area(&self, walk: &Walk, maze: &Maze) -> usize {
         self.progress
-            .saturating_sub(maze.path().len())
+            .saturating_sub(walk.0.len())
             .min(maze.inside().len())
     }
 }
 
+/// [`Maze::bidirectional_walk`], computed once
+#[derive(Debug, Resource)]
+struct Walk(Vec<(Coord, Coord)>);
+
+/// One of the two walkers, `true` for the one going backwards along the path
+#[derive(Debug, Component)]
+struct Walker(bool);
+
+/// [`Maze::distance_field`], computed once to color the path as a gradient
+#[derive(Debug, Resource)]
+struct Distances {
+    steps: HashMap<Coord, usize>,
+    max: usize,
+}
+
+impl Distances {
+    fn new(maze: &Maze) -> Self {
+        let steps = maze.distance_field();
+        let max = steps.values().copied().max().unwrap_or_default();
+        Self { steps, max }
+    }
+
+    fn color(&self, coord: &Coord) -> Option<Color> {
+        let [r, g, b] = heat(*self.steps.get(coord)? as f32 / self.max.max(1) as f32);
+        Some(Color::rgb_u8(r, g, b))
+    }
+}
+
+/// [`Maze::junk_pipes`] & [`Maze::dead_ends`], computed once to dim them
+#[derive(Debug, Resource)]
+struct Junk {
+    pipes: HashSet<Coord>,
+    dead_ends: HashSet<Coord>,
+}
+
+impl Junk {
+    fn new(maze: &Maze) -> Self {
+        Self {
+            pipes: maze.junk_pipes(),
+            dead_ends: maze.dead_ends(),
+        }
+    }
+
+    fn color(&self, coord: &Coord) -> Option<Color> {
+        if self.dead_ends.contains(coord) {
+            Some(DEAD_END_COLOR)
+        } else {
+            self.pipes.contains(coord).then_some(JUNK_COLOR)
+        }
+    }
+}
+
 #[derive(Debug, Component)]
 struct PathLen;
 
@@ -53,11 +158,17 @@ struct AreaLen;
 
 const TILE: f32 = 64.;
 const FONT_SIZE: f32 = 40.;
+/// Pipes not on the loop fade into the background, the ones leading nowhere even more so
+const JUNK_COLOR: Color = Color::rgb(0.4, 0.4, 0.4);
+const DEAD_END_COLOR: Color = Color::rgb(0.2, 0.2, 0.2);
+/// How many pipes get spawned per frame during startup
+const SPAWN_BUDGET: usize = 1000;
 
 fn setup(
     mut cmd: Commands,
     assets: Res<AssetServer>,
     mut atlases: ResMut<Assets<TextureAtlas>>,
+    mut queue: ResMut<SpawnQueue>,
     maze: Res<Maze>,
 ) {
     let handle = assets.load("pipes.png");
@@ -69,20 +180,24 @@ fn setup(
         None,
         None,
     ));
-    cmd.spawn((
-        Scroll(0.05),
-        Camera2dBundle {
-            transform: Transform::from_xyz(
-                maze.start.x as f32 * TILE,
-                -maze.start.y as f32 * TILE,
-                0.,
-            ),
-            ..default()
-        },
-    ));
-
     for (coord, p) in &maze.pipes {
-        cmd.spawn(pipe(coord, *p, texture.clone()));
+        queue.spawn(pipe(coord, *p, texture.clone()));
+    }
+
+    let start = Transform::from_xyz(maze.start.x as f32 * TILE, -maze.start.y as f32 * TILE, 1.);
+    for backwards in [false, true] {
+        cmd.spawn((
+            Walker(backwards),
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::CYAN,
+                    custom_size: Some(Vec2::splat(TILE / 3.)),
+                    ..default()
+                },
+                transform: start,
+                ..default()
+            },
+        ));
     }
 
     let red_style = TextStyle {
@@ -99,7 +214,7 @@ fn setup(
         PathLen,
         Text2dBundle {
             text: Text::from_sections([
-                TextSection::new("Path: ", red_style.clone()),
+                TextSection::new("Steps: ", red_style.clone()),
                 TextSection::new("---", red_style),
             ])
             .with_alignment(TextAlignment::Right),
@@ -135,17 +250,47 @@ fn pipe(coord: &Coord, pipe: Pipe, texture_atlas: Handle<TextureAtlas>) -> impl
     )
 }
 
-fn path_counter(state: Res<GameState>, maze: Res<Maze>, mut path: Query<&mut Text, With<PathLen>>) {
+impl CellInfo for Maze {
+    fn pick(&self, position: Vec2) -> Option<crate::Coord> {
+        let x = (position.x / TILE).round() as i32;
+        let y = (-position.y / TILE).round() as i32;
+        ((0..=self.size.x).contains(&x) && (0..=self.size.y).contains(&y))
+            .then_some(crate::Coord::new(x, y))
+    }
+
+    fn explain(&self, cell: crate::Coord) -> Vec<String> {
+        let coord = Coord::new(cell.x, cell.y);
+        let pipe = match self.pipes.get(&coord) {
+            Some(pipe) => format!("Pipe {pipe:?}"),
+            None => String::from("Ground"),
+        };
+        let location = if self.path.contains(&coord) {
+            "on the loop"
+        } else if self.inside.contains(&coord) {
+            "inside the loop"
+        } else {
+            "outside the loop"
+        };
+        vec![pipe, String::from(location)]
+    }
+}
+
+fn path_counter(state: Res<GameState>, walk: Res<Walk>, mut path: Query<&mut Text, With<PathLen>>) {
     if let Some(mut text) = path.iter_mut().next() {
-        let count = state.path(&maze);
+        let count = state.steps(&walk);
         if count > 0 {
             text.sections[1].value = format!("{}", count);
         }
     }
 }
-fn area_counter(state: Res<GameState>, maze: Res<Maze>, mut path: Query<&mut Text, With<AreaLen>>) {
+fn area_counter(
+    state: Res<GameState>,
+    walk: Res<Walk>,
+    maze: Res<Maze>,
+    mut path: Query<&mut Text, With<AreaLen>>,
+) {
     if let Some(mut text) = path.iter_mut().next() {
-        let count = state.area(&maze);
+        let count = state.area(&walk, &maze);
         if count > 0 {
             text.sections[1].value = format!("{}", count);
         }
@@ -157,6 +302,9 @@ fn update(
     time: Res<Time>,
     mut timer: ResMut<Tick>,
     mut state: ResMut<GameState>,
+    walk: Res<Walk>,
+    maze: Res<Maze>,
+    mut finished: EventWriter<AnimationFinished>,
 ) {
     if !running.inner() {
         return;
@@ -164,32 +312,104 @@ fn update(
     if !timer.inner().tick(time.delta()).just_finished() {
         return;
     }
+    // Walking the loop, then filling the area inside of it
+    let total = walk.0.len() + maze.inside().len();
+    if state.progress >= total {
+        return;
+    }
 
     state.progress += 1;
+    if state.progress == total {
+        finished.send(
+            AnimationFinished::new(state.area(&walk, &maze), state.progress)
+                .with("Farthest tile", state.steps(&walk)),
+        );
+    }
+}
+
+fn walker_mover(
+    state: Res<GameState>,
+    walk: Res<Walk>,
+    mut walkers: Query<(&Walker, &mut Transform)>,
+) {
+    // Before the first step both walkers wait on the start
+    let Some((forward, backward)) = state
+        .steps(&walk)
+        .checked_sub(1)
+        .and_then(|step| walk.0.get(step))
+    else {
+        return;
+    };
+    for (walker, mut transform) in walkers.iter_mut() {
+        let coord = if walker.0 { backward } else { forward };
+        transform.translation.x = coord.x as f32 * TILE;
+        transform.translation.y = -coord.y as f32 * TILE;
+    }
 }
 
 fn pipe_colorer(
     maze: Res<Maze>,
+    distances: Res<Distances>,
+    junk: Res<Junk>,
+    walk: Res<Walk>,
     mut pipes: Query<(&Coord, &mut TextureAtlasSprite)>,
     state: Res<GameState>,
 ) {
-    let path = maze
-        .path()
+    let path = walk
+        .0
         .iter()
-        .take(state.progress)
+        .take(state.steps(&walk))
+        .flat_map(|(a, b)| [a, b])
+        .chain([&maze.start])
         .collect::<HashSet<_>>();
     let inside = maze
         .inside()
         .iter()
-        .take(state.progress.saturating_sub(maze.path().len()))
+        .take(state.area(&walk, &maze))
         .collect::<HashSet<_>>();
     for (coord, mut sprite) in pipes.iter_mut() {
         sprite.color = if path.contains(coord) {
-            Color::RED
+            distances.color(coord).unwrap_or(Color::RED)
         } else if inside.contains(coord) {
             Color::YELLOW
         } else {
-            Color::WHITE
+            junk.color(coord).unwrap_or(Color::WHITE)
         };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HeadlessPlugins;
+    use std::str::FromStr;
+
+    #[test]
+    fn smoke() {
+        let input = include_str!("../../sample/tenth-c.txt");
+        let mut maze = Maze::from_str(input).expect("parsing");
+        maze.calculate_inside();
+        let mut app = app(maze, 5., HeadlessPlugins);
+        for _ in 0..3 {
+            app.update();
+        }
+    }
+
+    #[test]
+    fn explains_picked_cells() {
+        let input = include_str!("../../sample/tenth-c.txt");
+        let mut maze = Maze::from_str(input).expect("parsing");
+        maze.calculate_inside();
+
+        let start = maze.pick(Vec2::new(
+            maze.start.x as f32 * TILE,
+            -maze.start.y as f32 * TILE,
+        ));
+        assert_eq!(start, Some(crate::Coord::new(maze.start.x, maze.start.y)));
+        assert_eq!(
+            maze.explain(start.expect("start")),
+            vec!["Pipe Start", "on the loop"]
+        );
+        assert_eq!(maze.pick(Vec2::new(-TILE, TILE)), None);
+    }
+}
@@ -1,3 +1,4 @@
+#[cfg(feature = "animations")]
 pub mod animation;
 
 use std::{
@@ -9,21 +10,27 @@ use std::{
 };
 
 use anyhow::anyhow;
+#[cfg(feature = "animations")]
 use bevy::prelude::{Component, Resource};
 use enum_iterator::all;
 use itertools::Itertools;
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
 use termion::color::{Fg, LightYellow, Red, Reset, Rgb};
 
-use crate::Direction;
+use crate::{render::Diagram, tui::Stepper, Direction, Part, Solver};
 
-#[derive(Debug, Default, PartialEq, Eq, Clone, Hash, Component)]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Hash)]
+#[cfg_attr(feature = "animations", derive(Component))]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct Coord {
     x: i32,
     y: i32,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub(crate) enum Pipe {
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum Pipe {
     NS,
     EW,
     NW,
@@ -33,8 +40,11 @@ pub(crate) enum Pipe {
     Start,
 }
 
-#[derive(Resource)]
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "animations", derive(Resource))]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct Maze {
+    #[cfg_attr(feature = "serialize", serde(with = "crate::serialize::pairs"))]
     pipes: HashMap<Coord, Pipe>,
     start: Coord,
     size: Coord,
@@ -76,9 +86,28 @@ impl Maze {
         })
     }
 
+    /// The actual pipe under the start, i.e. the first one whose ends both lead into neighbors
+    /// connecting back to it
+    pub fn infer_start_pipe(&self) -> Option<Pipe> {
+        let connected = |d: Direction| {
+            self.pipes
+                .get(&(&self.start + d))
+                .is_some_and(|pipe| pipe.connections().contains(&d.opposite()))
+        };
+        [Pipe::NS, Pipe::EW, Pipe::NW, Pipe::NE, Pipe::SW, Pipe::SE]
+            .into_iter()
+            .find(|pipe| pipe.connections().iter().all(|d| connected(*d)))
+    }
+
+    /// Which way to leave the start along the loop, see [`Maze::infer_start_pipe`]
+    fn start_direction(&self) -> Direction {
+        self.infer_start_pipe()
+            .map_or(Direction::Right, |pipe| pipe.connections()[0])
+    }
+
     pub fn calculate_path(&mut self) {
         self.path = self
-            .follow(&self.start, Direction::Right)
+            .follow(&self.start, self.start_direction())
             .take_while_inclusive(|c| *c != self.start)
             .collect();
     }
@@ -89,16 +118,114 @@ impl Maze {
         &self.inside
     }
 
-    pub fn calculate_inside(&mut self, ccw: bool) {
+    /// Pipes not on the loop (see [`Maze::calculate_path`]), which just lie around
+    pub fn junk_pipes(&self) -> HashSet<Coord> {
+        let path = self.path.iter().collect::<HashSet<_>>();
+        self.pipes
+            .keys()
+            .filter(|c| !path.contains(c))
+            .cloned()
+            .collect()
+    }
+
+    /// Whether the pipe at `coord` has an end leading nowhere: into the ground, out of the maze or
+    /// into a pipe not connecting back. Such a pipe can't be on the loop
+    pub fn is_dead_end(&self, coord: &Coord) -> bool {
+        let Some(pipe) = self.pipes.get(coord).filter(|pipe| **pipe != Pipe::Start) else {
+            return false;
+        };
+        pipe.connections().iter().any(|d| {
+            !self
+                .pipes
+                .get(&(coord + *d))
+                .is_some_and(|next| next.connections().contains(&d.opposite()))
+        })
+    }
+
+    /// All pipes, which are [dead ends](Maze::is_dead_end)
+    pub fn dead_ends(&self) -> HashSet<Coord> {
+        self.pipes
+            .keys()
+            .filter(|c| self.is_dead_end(c))
+            .cloned()
+            .collect()
+    }
+
+    /// Steps from the start to each tile of the path (see [`Maze::calculate_path`]), walking
+    /// whichever way round the loop is shorter. The farthest tile is Part One's answer
+    pub fn distance_field(&self) -> HashMap<Coord, usize> {
+        // The path starts one step after the start and ends on the start itself
+        let n = self.path.len();
+        self.path
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (c.clone(), (i + 1).min(n - 1 - i)))
+            .collect()
+    }
+
+    /// Positions of two walkers, which leave the start along both ends of the path (see
+    /// [`Maze::calculate_path`]) at once, one pair per step until they meet on the farthest tile
+    pub fn bidirectional_walk(&self) -> impl Iterator<Item = (Coord, Coord)> + '_ {
+        let forward = self.path.iter();
+        // The path ends on the start, so walking it backwards begins one before that
+        let backward = self.path.iter().rev().skip(1);
+        forward
+            .zip(backward)
+            .take(self.path.len() / 2)
+            .map(|(a, b)| (a.clone(), b.clone()))
+    }
+
+    /// Diagram coloring the path by its [`Maze::distance_field`]
+    pub fn heat_map(&self) -> HeatMap<'_> {
+        let distances = self.distance_field();
+        let max = distances.values().copied().max().unwrap_or_default();
+        HeatMap {
+            maze: self,
+            distances,
+            max,
+        }
+    }
+
+    /// Twice the area enclosed by the path (see [`Maze::calculate_path`]) after the shoelace
+    /// formula, negative if it winds counter-clockwise on screen
+    fn signed_double_area(&self) -> i32 {
+        self.path
+            .iter()
+            .zip(self.path.iter().cycle().skip(1))
+            .map(|(a, b)| a.x * b.y - b.x * a.y)
+            .sum()
+    }
+
+    /// Whether the path (see [`Maze::calculate_path`]) winds counter-clockwise on screen, i.e.
+    /// its inside lies left of the direction of travel. Uses the sign of the shoelace formula
+    pub fn is_counter_clockwise(&self) -> bool {
+        self.signed_double_area() < 0
+    }
+
+    /// Number of tiles inside the path (see [`Maze::calculate_path`]) without filling them: Pick's
+    /// theorem relates them to its shoelace area and the tiles on the path itself
+    pub fn inside_area_shoelace(&self) -> usize {
+        let boundary = self.path.len() as i32;
+        ((self.signed_double_area().abs() - boundary) / 2 + 1).max(0) as usize
+    }
+
+    /// Fills the tiles inside the path, on whichever side of it that is
+    pub fn calculate_inside(&mut self) {
         self.calculate_path();
+        self.inside.clear();
+        let ccw = self.is_counter_clockwise();
 
-        let mut d = Direction::Right;
+        let mut d = self.start_direction();
+        let start = self.infer_start_pipe().unwrap_or(Pipe::Start);
         let pathset = self.path.iter().collect::<HashSet<_>>();
 
         // Find all neighbors on one side (cw or ccw) of the path
         let mut queue = VecDeque::new();
         for c in &self.path {
-            let pipe = self.pipes.get(c).unwrap();
+            let pipe = match self.pipes.get(c).unwrap() {
+                Pipe::Start => &start,
+                pipe => pipe,
+            };
             let neighbors = pipe.unconnected(d, ccw);
             for n in neighbors
                 .into_iter()
@@ -150,6 +277,24 @@ impl Add<Direction> for &Coord {
 }
 
 impl Pipe {
+    /// Sides of the tile this pipe leads to, all of them for the start
+    fn connections(&self) -> &'static [Direction] {
+        match self {
+            Self::NS => &[Direction::Up, Direction::Down],
+            Self::EW => &[Direction::Left, Direction::Right],
+            Self::NW => &[Direction::Up, Direction::Left],
+            Self::NE => &[Direction::Up, Direction::Right],
+            Self::SW => &[Direction::Down, Direction::Left],
+            Self::SE => &[Direction::Down, Direction::Right],
+            Self::Start => &[
+                Direction::Up,
+                Direction::Down,
+                Direction::Left,
+                Direction::Right,
+            ],
+        }
+    }
+
     fn follow(&self, d: Direction) -> Option<Direction> {
         match (d, *self) {
             (_, Pipe::NS | Pipe::EW | Pipe::Start) => Some(d),
@@ -170,36 +315,19 @@ impl Pipe {
         }
     }
 
+    /// Sides of this pipe, which lie on the right (or with `ccw` the left) of a path entering it
+    /// going `d`. Empty for the start or a pipe, which cannot be entered that way
     fn unconnected(&self, d: Direction, ccw: bool) -> Vec<Direction> {
-        // TODO: also handle ccw
-        match (d, *self, ccw) {
-            (_, Self::Start, _) => vec![],
-            (Direction::Up, Self::NS, _)
-            | (Direction::Down, Self::NS, _)
-            | (Direction::Left, Self::EW, _)
-            | (Direction::Right, Self::EW, _) => vec![if ccw { d.ccw() } else { d.cw() }],
-
-            (Direction::Down, Self::NW, false) | (Direction::Right, Self::NW, true) => vec![],
-            (Direction::Down, Self::NW, true) | (Direction::Right, Self::NW, false) => {
-                vec![Direction::Right, Direction::Down]
-            }
-
-            (Direction::Down, Self::NE, true) | (Direction::Left, Self::NE, false) => vec![],
-            (Direction::Down, Self::NE, false) | (Direction::Left, Self::NE, true) => {
-                vec![Direction::Down, Direction::Left]
-            }
-
-            (Direction::Up, Self::SW, true) | (Direction::Right, Self::SW, false) => vec![],
-            (Direction::Up, Self::SW, false) | (Direction::Right, Self::SW, true) => {
-                vec![Direction::Right, Direction::Up]
-            }
-
-            (Direction::Up, Self::SE, false) | (Direction::Left, Self::SE, true) => vec![],
-            (Direction::Up, Self::SE, true) | (Direction::Left, Self::SE, false) => {
-                vec![Direction::Up, Direction::Left]
-            }
-
-            (d, p, _) => panic!("Unsupported, cannot go {d:?} within pipe {p:?}"),
+        if *self == Self::Start {
+            return vec![];
+        }
+        let side = if ccw { d.ccw() } else { d.cw() };
+        match self.follow(d) {
+            None => vec![],
+            Some(next) if next == d => vec![side],
+            // Bending towards the side leaves no room on it
+            Some(next) if next == side => vec![],
+            Some(next) => vec![next.opposite(), d],
         }
     }
 }
@@ -271,15 +399,29 @@ impl From<&Pipe> for char {
 
 impl Debug for Maze {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let path = self.path.iter().collect::<HashSet<_>>();
+        self.draw(f, &self.path.iter().collect(), true)
+    }
+}
+
+impl Maze {
+    /// Pipes with termion colors, highlighting the tiles of `path` and, if `inside` is set, the
+    /// tiles within the loop
+    fn draw(
+        &self,
+        f: &mut impl std::fmt::Write,
+        path: &HashSet<&Coord>,
+        inside: bool,
+    ) -> std::fmt::Result {
         for y in 0..=self.size.y {
             for x in 0..=self.size.x {
                 let c = Coord::new(x, y);
                 let sym = self.pipes.get(&c).map(char::from).unwrap_or('·');
                 if path.contains(&c) {
                     write!(f, "{}{sym}{}", Fg(Red), Fg(Reset))?;
-                } else if self.inside.contains(&c) {
+                } else if inside && self.inside.contains(&c) {
                     write!(f, "{}{sym}{}", Fg(LightYellow), Fg(Reset))?;
+                } else if self.is_dead_end(&c) {
+                    write!(f, "{}{sym}{}", Fg(Rgb(60, 60, 60)), Fg(Reset))?;
                 } else {
                     write!(f, "{}{sym}{}", Fg(Rgb(100, 100, 100)), Fg(Reset))?;
                 }
@@ -289,3 +431,128 @@ impl Debug for Maze {
         Ok(())
     }
 }
+
+impl Diagram for Maze {
+    fn size(&self) -> crate::Coord {
+        crate::Coord::new(self.size.x + 1, self.size.y + 1)
+    }
+
+    fn color(&self, cell: crate::Coord) -> Option<[u8; 3]> {
+        let coord = Coord::new(cell.x, cell.y);
+        match self.pipes.get(&coord)? {
+            Pipe::Start => Some([255, 0, 0]),
+            _ if self.is_dead_end(&coord) => Some([80, 80, 80]),
+            _ => Some([200, 200, 200]),
+        }
+    }
+
+    fn symbol(&self, cell: crate::Coord) -> char {
+        self.pipes
+            .get(&Coord::new(cell.x, cell.y))
+            .map(char::from)
+            .unwrap_or('·')
+    }
+}
+
+/// Walks a solved [`Maze`] in the terminal, from the start along both ends of its loop until they
+/// meet, then shows its inside
+#[derive(Debug, Clone)]
+pub struct Tracing {
+    maze: Maze,
+    walked: usize,
+}
+
+impl From<Maze> for Tracing {
+    fn from(maze: Maze) -> Self {
+        Self { maze, walked: 0 }
+    }
+}
+
+impl Stepper for Tracing {
+    fn step(&mut self) -> bool {
+        let done = self.walked >= self.maze.path.len() / 2;
+        if !done {
+            self.walked += 1;
+        }
+        !done
+    }
+
+    fn frame(&self) -> String {
+        let walk = self.maze.bidirectional_walk().take(self.walked);
+        let tiles = walk.flat_map(|(a, b)| [a, b]).collect::<Vec<_>>();
+        let path = tiles.iter().chain(iter::once(&self.maze.start)).collect();
+        let mut frame = String::new();
+        let done = self.walked >= self.maze.path.len() / 2;
+        self.maze
+            .draw(&mut frame, &path, done)
+            .expect("writing to a string");
+        frame
+            + &format!(
+                "Farthest: {}, inside: {}",
+                self.walked,
+                self.maze.inside.len()
+            )
+    }
+}
+
+/// Color of a tile `t` of the way (0..=1) from the start to the farthest tile of the loop
+pub fn heat(t: f32) -> [u8; 3] {
+    let t = t.clamp(0., 1.);
+    [(255. * t) as u8, 0, (255. * (1. - t)) as u8]
+}
+
+/// See [`Maze::heat_map`]
+#[derive(Debug, Clone)]
+pub struct HeatMap<'a> {
+    maze: &'a Maze,
+    distances: HashMap<Coord, usize>,
+    max: usize,
+}
+
+impl Diagram for HeatMap<'_> {
+    fn size(&self) -> crate::Coord {
+        self.maze.size()
+    }
+
+    fn color(&self, cell: crate::Coord) -> Option<[u8; 3]> {
+        let coord = Coord::new(cell.x, cell.y);
+        match self.distances.get(&coord) {
+            Some(&d) => Some(heat(d as f32 / self.max.max(1) as f32)),
+            None => self.maze.pipes.get(&coord).map(|_| [80, 80, 80]),
+        }
+    }
+
+    fn symbol(&self, cell: crate::Coord) -> char {
+        self.maze.symbol(cell)
+    }
+}
+
+pub fn solve(part: Part, input: &str) -> anyhow::Result<usize> {
+    let mut maze = Maze::from_str(input)?;
+    maze.calculate_path();
+    Ok(match part {
+        Part::One => maze.path().len() / 2,
+        Part::Two => {
+            maze.calculate_inside();
+            maze.inside().len()
+        }
+    })
+}
+
+/// Day 10: Pipe Maze
+#[derive(Debug, Clone, Copy)]
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    fn name(&self) -> &'static str {
+        "tenth"
+    }
+
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::One, input).map(|x| x.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::Two, input).map(|x| x.to_string())
+    }
+}
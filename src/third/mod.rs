@@ -0,0 +1,290 @@
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+use anyhow::anyhow;
+use itertools::Itertools;
+
+use crate::{neighbors8, render::Diagram, Coord, Part, Solver};
+
+/// Part number of the engine, as many digits wide as it was written with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Number {
+    pub value: u32,
+    pub len: i32,
+}
+
+impl Number {
+    /// Cells the number covers, if its first digit is at `start`
+    pub fn cells(&self, start: Coord) -> impl Iterator<Item = Coord> {
+        (0..self.len).map(move |x| start + Coord::new(x, 0))
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Schematic {
+    symbols: HashMap<Coord, char>,
+    /// Cells next to a symbol, with the symbol
+    near_symbols: HashMap<Coord, char>,
+    gears: HashSet<Coord>,
+    /// Numbers by the cell of their first digit
+    numbers: HashMap<Coord, Number>,
+    /// How many characters each row has
+    widths: Vec<i32>,
+}
+
+impl FromStr for Schematic {
+    type Err = anyhow::Error;
+
+    /// Each row on its own, i.e. a number at the end of one row never goes on in the next
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut schematic = Self::default();
+        for (y, line) in s.trim_end().lines().enumerate() {
+            let chars = line.chars().collect::<Vec<_>>();
+            let mut x = 0;
+            while x < chars.len() {
+                let c = Coord::new(x as i32, y as i32);
+                match chars[x] {
+                    '.' => x += 1,
+                    '0'..='9' => {
+                        let digits = chars[x..]
+                            .iter()
+                            .take_while(|c| c.is_ascii_digit())
+                            .collect::<String>();
+                        let value = digits
+                            .parse()
+                            .map_err(|e| anyhow!("Number {digits} in row {y}: {e}"))?;
+                        let len = digits.len() as i32;
+                        schematic.numbers.insert(c, Number { value, len });
+                        x += digits.len();
+                    }
+                    symbol => {
+                        schematic.symbols.insert(c, symbol);
+                        schematic
+                            .near_symbols
+                            .extend(neighbors8(c).map(|c| (c, symbol)));
+                        if symbol == '*' {
+                            schematic.gears.insert(c);
+                        }
+                        x += 1;
+                    }
+                }
+            }
+            schematic.widths.push(chars.len() as i32);
+        }
+        Ok(schematic)
+    }
+}
+
+impl Schematic {
+    /// Numbers and where they start, top to bottom & left to right
+    pub fn numbers(&self) -> impl Iterator<Item = (Coord, Number)> + '_ {
+        self.numbers
+            .iter()
+            .map(|(c, n)| (*c, *n))
+            .sorted_by_key(|(c, _)| (c.y, c.x))
+    }
+
+    /// Symbol next to the cell `c`, if any
+    pub fn symbol_near(&self, c: Coord) -> Option<char> {
+        self.near_symbols.get(&c).copied()
+    }
+
+    /// Number covering the cell `c` and where it starts. None is wider than a `u32`'s ten digits
+    fn number_at(&self, c: Coord) -> Option<(Coord, Number)> {
+        (0..10)
+            .map(|dx| c - Coord::new(dx, 0))
+            .find_map(|start| Some((start, *self.numbers.get(&start)?)))
+            .filter(|(start, n)| c.x < start.x + n.len)
+    }
+
+    /// Part numbers, i.e. the ones with a symbol next to any of their digits
+    pub fn numbers_touching_symbol(&self) -> impl Iterator<Item = u32> + '_ {
+        self.numbers()
+            .filter(|(c, n)| n.cells(*c).any(|c| self.near_symbols.contains_key(&c)))
+            .map(|(_, n)| n.value)
+    }
+
+    /// Both numbers next to each `*` with exactly two of them
+    pub fn gear_ratios(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.gears
+            .iter()
+            .sorted_by_key(|c| (c.y, c.x))
+            .filter_map(|gear| {
+                let near = neighbors8(*gear).collect::<HashSet<_>>();
+                self.numbers()
+                    .filter(|(c, n)| n.cells(*c).any(|c| near.contains(&c)))
+                    .map(|(_, n)| n.value)
+                    .collect_tuple()
+            })
+    }
+
+    /// Numbers ending at the right edge of a row, right above a number starting at the left edge
+    /// of the next one, by where each starts. They count as two numbers, but look like one which
+    /// got wrapped, i.e. the input might have been saved with the wrong line width
+    pub fn wrapped_numbers(&self) -> Vec<(Coord, Coord)> {
+        self.numbers()
+            .filter_map(|(c, n)| {
+                let width = *self.widths.get(c.y as usize)?;
+                let next = Coord::new(0, c.y + 1);
+                (c.x + n.len == width && self.numbers.contains_key(&next)).then_some((c, next))
+            })
+            .collect()
+    }
+}
+
+/// Part numbers in green, the other numbers in gray, gears in gold & all other symbols in white
+impl Diagram for Schematic {
+    fn size(&self) -> Coord {
+        let width = self.widths.iter().max().copied().unwrap_or_default();
+        Coord::new(width, self.widths.len() as i32)
+    }
+
+    fn color(&self, cell: Coord) -> Option<[u8; 3]> {
+        if let Some(symbol) = self.symbols.get(&cell) {
+            return Some(if *symbol == '*' {
+                [255, 215, 0]
+            } else {
+                [230, 230, 230]
+            });
+        }
+        let (start, n) = self.number_at(cell)?;
+        if n.cells(start).any(|c| self.near_symbols.contains_key(&c)) {
+            Some([0, 200, 0])
+        } else {
+            Some([110, 110, 110])
+        }
+    }
+
+    fn symbol(&self, cell: Coord) -> char {
+        if let Some(symbol) = self.symbols.get(&cell) {
+            return *symbol;
+        }
+        self.number_at(cell)
+            .and_then(|(start, n)| {
+                let digits = format!("{:0width$}", n.value, width = n.len as usize);
+                digits.chars().nth((cell.x - start.x) as usize)
+            })
+            .unwrap_or('.')
+    }
+}
+
+pub fn solve(part: Part, input: &str) -> anyhow::Result<u32> {
+    let schematic = Schematic::from_str(input)?;
+    Ok(match part {
+        Part::One => schematic.numbers_touching_symbol().sum(),
+        Part::Two => schematic.gear_ratios().map(|(a, b)| a * b).sum(),
+    })
+}
+
+/// Day 3: Gear Ratios
+#[derive(Debug, Clone, Copy)]
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    fn name(&self) -> &'static str {
+        "third"
+    }
+
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::One, input).map(|x| x.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::Two, input).map(|x| x.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    /// Where a number ending at the right edge and the one it seems to go on with start
+    type Wrap = ((i32, i32), (i32, i32));
+
+    #[rstest]
+    #[case(Part::One, include_str!("../../sample/third.txt"), 4361)]
+    #[case(Part::Two, include_str!("../../sample/third.txt"), 467835)]
+    // Rows don't go on in the next one, so 1234 isn't a number touching the `#`
+    #[case(Part::One, "..12\n34..\n...#", 0)]
+    #[case(Part::One, "..12\n34.#", 12)]
+    #[case(Part::One, "..12\n34#.", 46)]
+    // Symbols in the corners reach the numbers diagonally next to them
+    #[case(Part::One, "#...$\n.1.2.\n.....\n.3.4.\n%...&", 10)]
+    #[case(Part::One, "*..\n...\n..5", 0)]
+    #[case(Part::Two, "*.\n.2\n3.", 0)]
+    #[case(Part::Two, "2*\n.3", 6)]
+    #[case(Part::Two, "1.1\n.*.\n1.1", 0)]
+    #[case(Part::One, "", 0)]
+    fn samples(#[case] part: Part, #[case] input: &str, #[case] expected: u32) {
+        assert_eq!(solve(part, input).expect("solving"), expected);
+    }
+
+    #[rstest]
+    #[case("467..114..\n...*......", &[(0, 0, 467, 3), (5, 0, 114, 3)])]
+    #[case("..12\n34..", &[(2, 0, 12, 2), (0, 1, 34, 2)])]
+    #[case("007*1", &[(0, 0, 7, 3), (4, 0, 1, 1)])]
+    #[case("9\n9\n9", &[(0, 0, 9, 1), (0, 1, 9, 1), (0, 2, 9, 1)])]
+    fn numbers_keep_to_their_row(#[case] input: &str, #[case] expected: &[(i32, i32, u32, i32)]) {
+        let schematic = Schematic::from_str(input).expect("parsing");
+        let numbers = schematic
+            .numbers()
+            .map(|(c, n)| (c.x, c.y, n.value, n.len))
+            .collect::<Vec<_>>();
+        assert_eq!(numbers, expected);
+    }
+
+    #[rstest]
+    #[case("..12\n34..", &[((2, 0), (0, 1))])]
+    #[case("..12\n.34.", &[])]
+    #[case(".12.\n34..", &[])]
+    #[case("9\n9\n9", &[((0, 0), (0, 1)), ((0, 1), (0, 2))])]
+    #[case(include_str!("../../sample/third.txt"), &[])]
+    fn flags_wrapped_numbers(#[case] input: &str, #[case] expected: &[Wrap]) {
+        let schematic = Schematic::from_str(input).expect("parsing");
+        let wrapped = schematic
+            .wrapped_numbers()
+            .into_iter()
+            .map(|(a, b)| ((a.x, a.y), (b.x, b.y)))
+            .collect::<Vec<_>>();
+        assert_eq!(wrapped, expected);
+    }
+
+    #[rstest]
+    #[case("#..\n...\n..#", Coord::new(1, 1), Some('#'))]
+    #[case("#..\n...\n..#", Coord::new(-1, -1), Some('#'))]
+    #[case("#..\n...\n..#", Coord::new(0, 2), None)]
+    #[case("#..\n...\n..#", Coord::new(0, 0), None)]
+    fn symbols_reach_their_neighbors(
+        #[case] input: &str,
+        #[case] c: Coord,
+        #[case] expected: Option<char>,
+    ) {
+        let schematic = Schematic::from_str(input).expect("parsing");
+        assert_eq!(schematic.symbol_near(c), expected);
+    }
+
+    #[rstest]
+    #[case(Coord::new(1, 0), '6', Some([0, 200, 0]))]
+    #[case(Coord::new(6, 0), '1', Some([110, 110, 110]))]
+    #[case(Coord::new(3, 1), '*', Some([255, 215, 0]))]
+    #[case(Coord::new(8, 0), '.', None)]
+    #[case(Coord::new(5, 1), '0', Some([0, 200, 0]))]
+    fn diagram_shows_part_numbers(
+        #[case] cell: Coord,
+        #[case] symbol: char,
+        #[case] color: Option<[u8; 3]>,
+    ) {
+        let schematic = Schematic::from_str("467..114..\n...*007...").expect("parsing");
+        assert_eq!(schematic.size(), Coord::new(10, 2));
+        assert_eq!(schematic.symbol(cell), symbol);
+        assert_eq!(schematic.color(cell), color);
+    }
+
+    #[rstest]
+    fn too_large_numbers() {
+        assert!(Schematic::from_str("99999999999").is_err());
+    }
+}
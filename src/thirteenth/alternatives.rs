@@ -0,0 +1,123 @@
+//! Other representations of a [`Grid`], which are not used by the solver, but are kept around to
+//! compare against in the benchmarks. Dropping `ndarray` for them would not save any compile time,
+//! since the eleventh day depends on it as well
+
+use super::{Grid, Reflection};
+use crate::Part;
+
+/// One bit mask per row and one per column, so that comparing two lines across a mirror is a
+/// single XOR, whose ones are the mismatching cells
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitGrid {
+    rows: Vec<u128>,
+    cols: Vec<u128>,
+}
+
+impl From<&Grid> for BitGrid {
+    fn from(grid: &Grid) -> Self {
+        assert!(
+            grid.rows().max(grid.cols()) <= u128::BITS as usize,
+            "Grid too large for a bit mask"
+        );
+        let rows = (0..grid.rows())
+            .map(|y| {
+                (0..grid.cols())
+                    .filter(|x| grid[[y, *x]] == 1)
+                    .fold(0u128, |mask, x| mask | 1 << x)
+            })
+            .collect();
+        let cols = (0..grid.cols())
+            .map(|x| {
+                (0..grid.rows())
+                    .filter(|y| grid[[*y, x]] == 1)
+                    .fold(0u128, |mask, y| mask | 1 << y)
+            })
+            .collect();
+        Self { rows, cols }
+    }
+}
+
+impl BitGrid {
+    /// Rows (for horizontal mirrors) or columns (for vertical ones), which get mirrored
+    fn lines(&self, direction: Reflection) -> &[u128] {
+        match direction {
+            Reflection::Horizontal => &self.rows,
+            Reflection::Vertical => &self.cols,
+        }
+    }
+
+    /// Pairs of lines facing each other across a mirror at `fold`, as the index of the one
+    /// before the mirror and the mismatching cells of both
+    fn mismatches(
+        &self,
+        fold: usize,
+        direction: Reflection,
+    ) -> impl Iterator<Item = (usize, u128)> + '_ {
+        let lines = self.lines(direction);
+        let k = fold.min(lines.len() - fold);
+        (0..k).map(move |i| (fold - 1 - i, lines[fold - 1 - i] ^ lines[fold + i]))
+    }
+
+    pub fn fold_line(&self, direction: Reflection) -> Option<(Reflection, usize)> {
+        (1..self.lines(direction).len())
+            .find(|fold| self.mismatches(*fold, direction).all(|(_, diff)| diff == 0))
+            .map(|fold| (direction, fold))
+    }
+
+    pub fn find_smudge(
+        &self,
+        direction: Reflection,
+    ) -> Option<((usize, usize), usize, Reflection)> {
+        (1..self.lines(direction).len()).find_map(|fold| {
+            let mut smudges = self
+                .mismatches(fold, direction)
+                .filter(|(_, diff)| *diff != 0);
+            let (line, diff) = smudges.next()?;
+            if diff.count_ones() != 1 || smudges.next().is_some() {
+                return None;
+            }
+            let other = diff.trailing_zeros() as usize;
+            let cell = match direction {
+                Reflection::Horizontal => (line, other),
+                Reflection::Vertical => (other, line),
+            };
+            Some((cell, fold, direction))
+        })
+    }
+
+    /// Same as [`Grid::mirror`]
+    pub fn mirror(&self, part: Part) -> Option<(Reflection, usize)> {
+        match part {
+            Part::One => self
+                .fold_line(Reflection::Horizontal)
+                .or(self.fold_line(Reflection::Vertical)),
+            Part::Two => [Reflection::Horizontal, Reflection::Vertical]
+                .into_iter()
+                .find_map(|r| self.find_smudge(r))
+                .map(|(_, fold, dir)| (dir, fold)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::thirteenth::parse_grids;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(include_str!("../../sample/thirteenth.txt"))]
+    #[case(include_str!("../../sample/thirteenth-mixed.txt"))]
+    fn all_strategies_find_alike(#[case] input: &str) {
+        for grid in parse_grids(input).expect("parsing") {
+            let bits = BitGrid::from(&grid);
+            for direction in [Reflection::Horizontal, Reflection::Vertical] {
+                assert_eq!(grid.fold_line(direction), bits.fold_line(direction));
+                assert_eq!(grid.find_smudge(direction), bits.find_smudge(direction));
+            }
+            for part in [Part::One, Part::Two] {
+                assert_eq!(grid.mirror(part), bits.mirror(part), "\n{grid:?}");
+            }
+        }
+    }
+}
@@ -1,10 +1,18 @@
 use std::collections::HashSet;
 
 use crate::{
-    frequency_increaser, lerp, lerprgb, mouse, rect, toggle_running, Part, Running, Scroll, Tick,
+    capture::CapturePlugin,
+    keybindings::{Action, Keybindings},
+    lerp, lerprgb, rect,
+    reset::ResetPlugin,
+    samples::SamplesPlugin,
+    spawning::{SpawnQueue, StreamingPlugin},
+    summary::AnimationFinished,
+    widgets::{BigNumber, ScorePopup, WidgetsPlugin},
+    AocCameraPlugin, AocControlPlugin, Part, Presentation, Running, Tick,
 };
 
-use super::{Grid, Reflection};
+use super::{parse_grids, Grid, Reflection};
 
 use bevy::{prelude::*, sprite::Anchor};
 use lazy_static::lazy_static;
@@ -27,8 +35,10 @@ const CHECK_COLOR: Color = Color::Rgba {
 };
 const FOUND_COLOR: Color = Color::GREEN;
 const SMUDGE_COLOR: Color = Color::PINK;
+/// How many cells get spawned per frame during startup
+const SPAWN_BUDGET: usize = 500;
 
-#[derive(Debug, Resource, Default)]
+#[derive(Debug, Clone, Resource, Default)]
 struct GameState {
     part: Part,
     grids: Vec<Grid>,
@@ -36,7 +46,7 @@ struct GameState {
     split: Reflection,
     fold: usize,
     step: Step,
-    total: usize,
+    total: u64,
 }
 
 #[derive(Default, Debug, Clone, Copy, PartialEq)]
@@ -49,38 +59,64 @@ enum Step {
     Done,
 }
 
-pub fn run(grids: Vec<Grid>, part: Part, frequency: f32) {
-    App::new()
-        .add_plugins(DefaultPlugins)
-        .insert_resource(Running::default())
-        .insert_resource(Tick::new(frequency))
-        .insert_resource(GameState {
-            part,
-            grids,
-            ..default()
-        })
-        .add_systems(Startup, setup)
-        .add_systems(
-            Update,
-            (
-                update,
-                mouse,
-                toggle_running,
-                vertical_mirror,
-                horizontal_mirror,
-                stripe_mover,
-                cell_colorer,
-                totaller,
-                score_fader,
-                score_mover,
-                score_destroyer,
-                counter,
-                frequency_increaser,
-            ),
-        )
+/// Inputs to flip through with `[` & `]`
+const SAMPLES: [&str; 2] = [
+    include_str!("../../sample/thirteenth.txt"),
+    include_str!("../../sample/thirteenth-mixed.txt"),
+];
+
+pub fn run(grids: Vec<Grid>, part: Part, frequency: f32, presentation: Presentation) {
+    let plugins = DefaultPlugins.build().add(presentation);
+    app(grids, part, frequency, plugins)
+        .add_plugins(CapturePlugin)
         .run()
 }
 
+pub fn app(grids: Vec<Grid>, part: Part, frequency: f32, plugins: impl PluginGroup) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        plugins,
+        AocCameraPlugin::new(0.25).at(Vec3::new(10. * TILE_SIZE, -10. * TILE_SIZE, 0.)),
+        AocControlPlugin,
+        WidgetsPlugin,
+        StreamingPlugin::new(SPAWN_BUDGET),
+        SamplesPlugin::new(SAMPLES, load),
+    ))
+    .insert_resource(Tick::new(frequency))
+    .insert_resource(GameState {
+        part,
+        grids,
+        ..default()
+    })
+    .add_plugins(ResetPlugin::<GameState>::default())
+    .add_systems(Startup, setup)
+    .add_systems(
+        Update,
+        (
+            update,
+            vertical_mirror,
+            horizontal_mirror,
+            stripe_mover,
+            cell_colorer,
+            totaller,
+            counter,
+        ),
+    );
+    app
+}
+
+/// Starts over with the grids of `input`, keeping the part
+fn load(world: &mut World, input: &str) -> anyhow::Result<()> {
+    let grids = parse_grids(input)?;
+    let part = world.resource::<GameState>().part;
+    world.insert_resource(GameState {
+        part,
+        grids,
+        ..default()
+    });
+    Ok(())
+}
+
 lazy_static! {
     static ref STYLE: TextStyle = TextStyle {
         font_size: FONT_SIZE,
@@ -121,68 +157,62 @@ enum HorizontalMirrorHighlight {
 #[derive(Debug, Component)]
 struct Total;
 
-#[derive(Debug, Component)]
-struct Score;
-
 #[derive(Debug, Component)]
 struct Counter(Reflection);
 
-fn setup(mut cmd: Commands, state: Res<GameState>) {
-    cmd.spawn((
-        Scroll(0.25),
-        Camera2dBundle {
-            transform: Transform::from_xyz(10. * TILE_SIZE, -10. * TILE_SIZE, 0.),
-            ..default()
-        },
-    ));
-
-    cmd.spawn((GridStripe, SpatialBundle::default()))
-        .with_children(|parent| {
-            let mut last_y = 0.;
-            for (g, grid) in state.grids.iter().enumerate() {
-                parent
-                    .spawn((
-                        GridComponent,
-                        SpatialBundle::from_transform(Transform::from_xyz(0., last_y, 0.)),
-                    ))
-                    .with_children(|parent| {
-                        let rows = grid.rows();
-                        for y in 0..rows {
-                            for x in 0..grid.cols() {
-                                parent.spawn((
-                                    Cell {
-                                        coord: (y, x),
-                                        grid: g,
-                                    },
-                                    Text2dBundle {
-                                        text: Text::from_section(
-                                            if grid[[y, x]] == 1 { "#" } else { "." },
-                                            STYLE.clone(),
-                                        ),
-                                        transform: Transform::from_xyz(
-                                            x as f32 * TILE_SIZE + 3.,
-                                            y as f32 * -TILE_SIZE - 4.,
-                                            0.,
-                                        ),
-                                        text_anchor: Anchor::BottomLeft,
-                                        ..default()
-                                    },
-                                ));
-                            }
-                        }
-                    });
-                last_y -= grid.rows() as f32 * TILE_SIZE;
-                last_y -= GRID_GAP;
+fn setup(mut cmd: Commands, mut queue: ResMut<SpawnQueue>, state: Res<GameState>) {
+    // Only the grids themselves are spawned right away, their (many) cells trickle in
+    let stripe = cmd.spawn((GridStripe, SpatialBundle::default())).id();
+    let mut last_y = 0.;
+    for (g, grid) in state.grids.iter().enumerate() {
+        let parent = cmd
+            .spawn((
+                GridComponent,
+                SpatialBundle::from_transform(Transform::from_xyz(0., last_y, 0.)),
+            ))
+            .set_parent(stripe)
+            .id();
+        for y in 0..grid.rows() {
+            for x in 0..grid.cols() {
+                queue.spawn_child(
+                    parent,
+                    (
+                        Cell {
+                            coord: (y, x),
+                            grid: g,
+                        },
+                        Text2dBundle {
+                            text: Text::from_section(
+                                if grid[[y, x]] == 1 { "#" } else { "." },
+                                STYLE.clone(),
+                            ),
+                            transform: Transform::from_xyz(
+                                x as f32 * TILE_SIZE + 3.,
+                                y as f32 * -TILE_SIZE - 4.,
+                                0.,
+                            ),
+                            text_anchor: Anchor::BottomLeft,
+                            ..default()
+                        },
+                    ),
+                );
             }
-        });
+        }
+        last_y -= grid.rows() as f32 * TILE_SIZE;
+        last_y -= GRID_GAP;
+    }
 
+    // All sizes below only serve as a starting point, the mirror systems keep them in sync
+    // with whichever grid is currently active, since grids may differ in shape
+    let grid = &state.grids[state.grid];
+    let extent = extent(grid);
     let position = 2.;
-    let size = state.grids[0].rows() as f32 * TILE_SIZE;
+    let size = extent.y;
     cmd.spawn((
         VerticalMirror,
         rect(
             position * TILE_SIZE,
-            size / 2.,
+            (TILE_SIZE + MIRROR_LENGTH - size) / 2.,
             2.,
             MIRROR_THICKNESS,
             size + MIRROR_LENGTH,
@@ -195,7 +225,7 @@ fn setup(mut cmd: Commands, state: Res<GameState>) {
             Counter(Reflection::Vertical),
             Text2dBundle {
                 text: Text::from_section("-", STYLE.clone()),
-                transform: Transform::from_xyz(0., size / 2.0 + MIRROR_LENGTH + TILE_SIZE / 2., 0.),
+                transform: Transform::from_translation(counter_offset(Reflection::Vertical, grid)),
                 text_anchor: Anchor::BottomCenter,
                 ..default()
             },
@@ -226,7 +256,7 @@ fn setup(mut cmd: Commands, state: Res<GameState>) {
         ));
     });
 
-    let size = state.grids[0].cols() as f32 * TILE_SIZE;
+    let size = extent.x;
     cmd.spawn((
         HorizontalMirror,
         rect(
@@ -244,7 +274,10 @@ fn setup(mut cmd: Commands, state: Res<GameState>) {
             Counter(Reflection::Horizontal),
             Text2dBundle {
                 text: Text::from_section("-", STYLE.clone()),
-                transform: Transform::from_xyz(size / 2.0 + MIRROR_LENGTH + TILE_SIZE, 0., 0.),
+                transform: Transform::from_translation(counter_offset(
+                    Reflection::Horizontal,
+                    grid,
+                )),
                 text_anchor: Anchor::CenterLeft,
                 ..default()
             },
@@ -300,12 +333,12 @@ fn vertical_mirror(
 ) {
     let active = state.split == Reflection::Vertical && state.step != Step::Done;
     let fold = if active { state.fold } else { 0 };
-    let cols = state.grids[state.grid].cols();
+    let grid = &state.grids[state.grid];
     let dt = time.delta_seconds();
-    let s = state.grids[state.grid].rows() as f32 * TILE_SIZE;
+    let s = extent(grid).y;
     for (mut tf, mut sprite, mut visible) in mirrors.iter_mut() {
         tf.translation.x = lerp(tf.translation.x, fold as f32 * TILE_SIZE, MOTION * dt);
-        tf.translation.y = -(s - TILE_SIZE - MIRROR_LENGTH) / 2.;
+        tf.translation.y = (TILE_SIZE + MIRROR_LENGTH - s) / 2.;
         *visible = if active {
             Visibility::Visible
         } else {
@@ -317,12 +350,7 @@ fn vertical_mirror(
         }
     }
 
-    let target = if fold <= cols / 2 {
-        fold
-    } else {
-        cols.saturating_sub(fold)
-    } as f32
-        * TILE_SIZE;
+    let target = reach(fold, grid.cols());
     for (side, mut sprite, mut tf) in highlights.iter_mut() {
         if let Some(size) = sprite.custom_size.as_mut() {
             size.x = lerp(size.x, target, MOTION * dt);
@@ -351,9 +379,9 @@ fn horizontal_mirror(
 ) {
     let active = state.split == Reflection::Horizontal && state.step != Step::Done;
     let fold = if active { state.fold } else { 0 };
-    let rows = state.grids[state.grid].rows();
+    let grid = &state.grids[state.grid];
     let dt = time.delta_seconds();
-    let s = state.grids[state.grid].cols() as f32 * TILE_SIZE;
+    let s = extent(grid).x;
     for (mut tf, mut sprite, mut visible) in mirrors.iter_mut() {
         tf.translation.x = s / 2.;
         tf.translation.y = lerp(
@@ -370,12 +398,7 @@ fn horizontal_mirror(
             size.x = s + MIRROR_LENGTH;
         }
     }
-    let target = if fold <= rows / 2 {
-        fold
-    } else {
-        rows.saturating_sub(fold)
-    } as f32
-        * TILE_SIZE;
+    let target = reach(fold, grid.rows());
     for (side, mut sprite, mut tf) in highlights.iter_mut() {
         if let Some(size) = sprite.custom_size.as_mut() {
             size.x = s + MIRROR_LENGTH;
@@ -445,6 +468,14 @@ fn cell_colorer(time: Res<Time>, state: Res<GameState>, mut cells: Query<(&Cell,
         .collect::<HashSet<_>>();
 
     for (cell, mut text) in cells.iter_mut().filter(|(cell, _)| cell.grid == state.grid) {
+        let symbol = if grid[[cell.coord.0, cell.coord.1]] == 1 {
+            "#"
+        } else {
+            "."
+        };
+        if text.sections[0].value != symbol {
+            text.sections[0].value = symbol.to_string();
+        }
         let is_same = sames.contains(&cell.coord);
         let is_even = |n| n % 2 == 0;
         let opposite = match state.split {
@@ -503,51 +534,34 @@ fn totaller(state: Res<GameState>, mut totals: Query<&mut Text, With<Total>>) {
     }
 }
 
-fn score_fader(state: Res<GameState>, mut scores: Query<&mut Text, With<Score>>) {
-    if let Step::Scoring(x) = state.step {
-        for mut text in scores.iter_mut() {
-            let color = &mut text.sections[0].style.color;
-            *color = color.with_a(x);
-        }
+fn counter(state: Res<GameState>, mut counters: Query<(&mut Transform, &mut Text, &Counter)>) {
+    for (mut tf, mut text, Counter(r)) in counters.iter_mut() {
+        text.sections[0].value = format!("{:^2}", state.fold);
+        tf.translation = counter_offset(*r, &state.grids[state.grid]);
     }
 }
-fn score_mover(
-    time: Res<Time>,
-    state: Res<GameState>,
-    mut scores: Query<&mut Transform, With<Score>>,
-) {
-    if let Step::Scoring(_) = state.step {
-        let target = TOTAL_Y + 1.5 * TILE_SIZE + TILE_SIZE / 2.;
-        for mut tf in scores.iter_mut() {
-            tf.translation.y = lerp(tf.translation.y, target, MOTION * time.delta_seconds());
-        }
-    }
+
+/// Size of `grid` in world units as (width, height)
+fn extent(grid: &Grid) -> Vec2 {
+    Vec2::new(grid.cols() as f32, grid.rows() as f32) * TILE_SIZE
 }
 
-fn score_destroyer(mut cmd: Commands, state: Res<GameState>, scores: Query<Entity, With<Score>>) {
-    let Step::Scoring(_) = state.step else {
-        for id in scores.iter() {
-            cmd.entity(id).despawn();
-        }
-        return;
+/// Distance from a mirror at `fold` to the closer edge of a grid `n` tiles long
+fn reach(fold: usize, n: usize) -> f32 {
+    let tiles = if fold <= n / 2 {
+        fold
+    } else {
+        n.saturating_sub(fold)
     };
+    tiles as f32 * TILE_SIZE
 }
 
-fn counter(state: Res<GameState>, mut counters: Query<(&mut Transform, &mut Text, &Counter)>) {
-    for (mut tf, mut text, Counter(r)) in counters.iter_mut() {
-        text.sections[0].value = format!("{:^2}", state.fold);
-        match r {
-            Reflection::Vertical => {
-                tf.translation.y = state.grids[state.grid].rows() as f32 * TILE_SIZE / 2.
-                    + MIRROR_LENGTH
-                    - TILE_SIZE / 2.
-            }
-            Reflection::Horizontal => {
-                tf.translation.x = state.grids[state.grid].cols() as f32 * TILE_SIZE / 2.
-                    + MIRROR_LENGTH
-                    + TILE_SIZE / 2.
-            }
-        };
+/// Position of a fold counter relative to its mirror, just beyond the end of `grid`
+fn counter_offset(reflection: Reflection, grid: &Grid) -> Vec3 {
+    let extent = extent(grid);
+    match reflection {
+        Reflection::Vertical => Vec3::new(0., extent.y / 2. + MIRROR_LENGTH - TILE_SIZE / 2., 0.),
+        Reflection::Horizontal => Vec3::new(extent.x / 2. + MIRROR_LENGTH + TILE_SIZE / 2., 0., 0.),
     }
 }
 
@@ -558,13 +572,10 @@ fn update(
     mut timer: ResMut<Tick>,
     mut state: ResMut<GameState>,
     keys: Res<Input<KeyCode>>,
-    mut exit: ResMut<Events<bevy::app::AppExit>>,
+    bindings: Res<Keybindings>,
+    mut steps: Local<usize>,
+    mut finished: EventWriter<AnimationFinished>,
 ) {
-    if keys.just_pressed(KeyCode::Q) {
-        exit.send(bevy::app::AppExit);
-        return;
-    }
-
     if !running.inner() {
         return;
     }
@@ -573,14 +584,15 @@ fn update(
         state.step = Step::Scoring(lerp(x, 0., MOTION * time.delta_seconds()));
     }
 
-    if !timer.inner().tick(time.delta()).just_finished() && !keys.just_released(KeyCode::Tab) {
+    if !timer.inner().tick(time.delta()).just_finished() && !bindings.released(&keys, Action::Step)
+    {
         return;
     }
+    *steps += 1;
 
     state.step = match (state.step, state.part) {
         (Step::Searching, Part::One) => {
-            let (a, b) = state.grids[state.grid].split(state.fold, state.split);
-            if !a.is_empty() && !b.is_empty() && a == b {
+            if state.grids[state.grid].reflects(state.fold, state.split) {
                 Step::Found(FOUND_COLOR_TOGGLE * 2)
             } else {
                 state.fold += 1;
@@ -611,32 +623,29 @@ fn update(
             }
         },
         (Step::Smudge(_), Part::One) => panic!("Smudging should only happen in Part one!"),
-        (Step::Smudge((0, _)), Part::Two) => Step::Found(0),
+        (Step::Smudge((0, (row, col))), Part::Two) => {
+            // Fix the smudge for real, so that the mirror gets checked again on the clean grid
+            let grid = state.grid;
+            state.grids[grid] = state.grids[grid].toggled(row, col);
+            debug_assert!(state.grids[grid].reflects(state.fold, state.split));
+            Step::Found(FOUND_COLOR_TOGGLE * 2)
+        }
         (Step::Smudge((n, i)), Part::Two) => Step::Smudge((n - 1, i)),
         (Step::Found(0), _) => {
-            cmd.spawn((
-                Score,
-                Text2dBundle {
-                    text: Text::from_section(
-                        match state.split {
-                            Reflection::Vertical => format!("+{}", state.fold),
-                            Reflection::Horizontal => format!("+100*{}", state.fold),
-                        },
-                        TextStyle {
-                            font_size: FONT_SIZE * 0.8,
-                            color: Color::GRAY,
-                            ..default()
-                        },
-                    ),
-                    transform: Transform::from_xyz(TOTAL_X, TOTAL_Y + 1.5 * TILE_SIZE, 1.),
-                    text_anchor: Anchor::CenterRight,
+            cmd.spawn(ScorePopup::bundle(
+                match state.split {
+                    Reflection::Vertical => format!("+{}", state.fold),
+                    Reflection::Horizontal => format!("+100*{}", state.fold),
+                },
+                TextStyle {
+                    font_size: FONT_SIZE * 0.8,
+                    color: Color::GRAY,
                     ..default()
                 },
+                Vec3::new(TOTAL_X, TOTAL_Y + 1.5 * TILE_SIZE, 1.),
+                TILE_SIZE / 2.,
             ));
-            state.total += match state.split {
-                Reflection::Vertical => state.fold,
-                Reflection::Horizontal => 100 * state.fold,
-            };
+            state.total += state.split.score(state.fold);
             Step::Scoring(1.)
         }
         (Step::Found(x), _) => Step::Found(x - 1),
@@ -646,6 +655,10 @@ fn update(
             state.grid += 1;
             if state.grid >= state.grids.len() {
                 state.grid = state.grids.len() - 1;
+                finished.send(
+                    AnimationFinished::new(BigNumber::new(state.total), *steps)
+                        .with("Patterns", state.grids.len()),
+                );
                 Step::Done
             } else {
                 Step::Searching
@@ -654,3 +667,46 @@ fn update(
         _ => state.step,
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{thirteenth::parse_grids, HeadlessPlugins};
+    use bevy::time::TimeUpdateStrategy;
+    use rstest::rstest;
+    use std::time::Duration;
+
+    #[test]
+    fn smoke() {
+        let input = include_str!("../../sample/thirteenth.txt");
+        let grids = parse_grids(input).expect("parsing");
+        for part in [Part::One, Part::Two] {
+            let mut app = app(grids.clone(), part, 2., HeadlessPlugins);
+            for _ in 0..3 {
+                app.update();
+            }
+        }
+    }
+
+    #[rstest]
+    #[case(Part::One, 1309)]
+    #[case(Part::Two, 402)]
+    fn mixed_dimensions(#[case] part: Part, #[case] summary: u64) {
+        let input = include_str!("../../sample/thirteenth-mixed.txt");
+        let grids = parse_grids(input).expect("parsing");
+        let mut app = app(grids, part, 100., HeadlessPlugins);
+        app.insert_resource(Running(true))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )));
+        for _ in 0..2000 {
+            app.update();
+            if app.world.resource::<GameState>().step == Step::Done {
+                break;
+            }
+        }
+        let state = app.world.resource::<GameState>();
+        assert_eq!(state.step, Step::Done);
+        assert_eq!(state.total, summary);
+    }
+}
@@ -1,10 +1,18 @@
+#[cfg(any(test, feature = "bench"))]
+pub mod alternatives;
+#[cfg(feature = "animations")]
 pub mod animation;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use itertools::Itertools;
 use ndarray::prelude::*;
+use rayon::prelude::*;
 use std::{fmt::Debug, ops::Index, str::FromStr};
 
+use crate::{minimize::Structure, render::Diagram, Coord, Part, Solver};
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
 #[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
 pub enum Reflection {
     #[default]
@@ -12,7 +20,19 @@ pub enum Reflection {
     Vertical,
 }
 
+impl Reflection {
+    /// Points a mirror of this kind scores: the number of columns left of a vertical mirror at
+    /// `fold`, or 100 times the number of rows above a horizontal one
+    pub fn score(self, fold: usize) -> u64 {
+        match self {
+            Self::Vertical => fold as u64,
+            Self::Horizontal => 100 * fold as u64,
+        }
+    }
+}
+
 #[derive(PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct Grid(Array2<i8>);
 
 impl Grid {
@@ -32,9 +52,32 @@ impl Grid {
         }
     }
 
+    /// Does a mirror at `fold` reflect each row/column (for `direction`) exactly?
+    pub fn reflects(&self, fold: usize, direction: Reflection) -> bool {
+        if fold == 0 || fold >= self.end(direction) {
+            return false;
+        }
+        let (a, b) = self.split(fold, direction);
+        a == b
+    }
+
+    /// Set the cell at `row` & `col` to `v` (1 for rock, 0 for ash)
+    pub fn set(&mut self, row: usize, col: usize, v: i8) {
+        self.0[[row, col]] = v;
+    }
+
+    /// Copy of this grid with the cell at `row` & `col` flipped, e.g. to fix a smudge
+    pub fn toggled(&self, row: usize, col: usize) -> Grid {
+        let mut grid = self.clone();
+        grid.set(row, col, 1 - self.0[[row, col]]);
+        grid
+    }
+
+    #[cfg(any(test, feature = "bench", feature = "animations"))]
     fn rows(&self) -> usize {
         self.0.nrows()
     }
+    #[cfg(any(test, feature = "bench", feature = "animations"))]
     fn cols(&self) -> usize {
         self.0.ncols()
     }
@@ -70,6 +113,19 @@ impl Grid {
         })
     }
 
+    /// Mirror of this grid (preferring horizontal ones), which in part two needs a smudge fixed
+    pub fn mirror(&self, part: Part) -> Option<(Reflection, usize)> {
+        match part {
+            Part::One => self
+                .fold_line(Reflection::Horizontal)
+                .or(self.fold_line(Reflection::Vertical)),
+            Part::Two => [Reflection::Horizontal, Reflection::Vertical]
+                .into_iter()
+                .find_map(|r| self.find_smudge(r))
+                .map(|(_, fold, dir)| (dir, fold)),
+        }
+    }
+
     pub fn fold_line(&self, direction: Reflection) -> Option<(Reflection, usize)> {
         match direction {
             Reflection::Horizontal => self.fold_line_horizontal(),
@@ -78,16 +134,10 @@ impl Grid {
         .map(|i| (direction, i))
     }
     fn fold_line_horizontal(&self) -> Option<usize> {
-        (1..self.0.nrows()).find(|fold| {
-            let (above, below) = self.split(*fold, Reflection::Horizontal);
-            above == below
-        })
+        (1..self.0.nrows()).find(|fold| self.reflects(*fold, Reflection::Horizontal))
     }
     fn fold_line_vertical(&self) -> Option<usize> {
-        (1..self.0.ncols()).find(|fold| {
-            let (left, right) = self.split(*fold, Reflection::Vertical);
-            left == right
-        })
+        (1..self.0.ncols()).find(|fold| self.reflects(*fold, Reflection::Vertical))
     }
 }
 
@@ -107,18 +157,20 @@ impl FromStr for Grid {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let s = s.trim();
         let two_d = (s.lines().count(), s.lines().next().unwrap_or("").len());
-        let grid = Array::from_iter(
-            s.replace('#', &BOX.to_string())
-                .replace('.', &EMPTY.to_string())
-                .lines()
-                .flat_map(|line| {
-                    line.trim().chars().map(|c| match c {
-                        BOX => 1,
-                        EMPTY => 0,
-                        _ => panic!("Unknown character for Grid: {c} only {BOX} & {EMPTY} allowed"),
-                    })
-                }),
-        );
+        let grid = s
+            .replace('#', &BOX.to_string())
+            .replace('.', &EMPTY.to_string())
+            .lines()
+            .flat_map(|line| {
+                line.trim().chars().map(|c| match c {
+                    BOX => Ok(1),
+                    EMPTY => Ok(0),
+                    _ => Err(anyhow!(
+                        "Unknown character for Grid: {c:?} only {BOX} & {EMPTY} allowed"
+                    )),
+                })
+            })
+            .collect::<Result<Array1<_>>>()?;
         Ok(Grid(grid.into_shape(two_d)?))
     }
 }
@@ -137,3 +189,68 @@ impl Debug for Grid {
         Ok(())
     }
 }
+
+impl Diagram for Grid {
+    fn size(&self) -> Coord {
+        Coord::new(self.0.ncols() as i32, self.0.nrows() as i32)
+    }
+
+    fn color(&self, cell: Coord) -> Option<[u8; 3]> {
+        (self.0[[cell.y as usize, cell.x as usize]] == 1).then_some([200, 200, 200])
+    }
+
+    fn symbol(&self, cell: Coord) -> char {
+        if self.0[[cell.y as usize, cell.x as usize]] == 1 {
+            BOX
+        } else {
+            EMPTY
+        }
+    }
+}
+
+/// All grids in `input`, separated by blank lines
+pub fn parse_grids(input: &str) -> Result<Vec<Grid>> {
+    input
+        .replace("\r\n", "\n")
+        .split("\n\n")
+        .filter(|grid| !grid.trim().is_empty())
+        .map(Grid::from_str)
+        .collect()
+}
+
+/// Sum of the [`Reflection::score`]s of each grid's (smudged) mirror, grids without any add nothing.
+/// The grids are independent, so they get scored in parallel on rayon's current thread pool
+pub fn total_score(grids: &[Grid], part: Part) -> u64 {
+    grids
+        .par_iter()
+        .flat_map(|grid| grid.mirror(part))
+        .map(|(dir, fold)| dir.score(fold))
+        .sum()
+}
+
+pub fn solve(part: Part, input: &str) -> Result<u64> {
+    Ok(total_score(&parse_grids(input)?, part))
+}
+
+/// Day 13: Point of Incidence
+#[derive(Debug, Clone, Copy)]
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    fn name(&self) -> &'static str {
+        "thirteenth"
+    }
+
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::One, input).map(|x| x.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::Two, input).map(|x| x.to_string())
+    }
+
+    /// Grids are separated by blank lines
+    fn structure(&self) -> Structure {
+        Structure::Blocks
+    }
+}
@@ -0,0 +1,81 @@
+//! Measures how long the phases of a solve take and, with the `alloc-stats` feature, how much
+//! they allocate
+
+use std::{
+    fmt::{self, Display, Formatter},
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "alloc-stats")]
+use crate::alloc::{stats, Stats};
+
+#[derive(Debug, Clone)]
+struct Phase {
+    name: &'static str,
+    duration: Duration,
+    #[cfg(feature = "alloc-stats")]
+    allocated: Stats,
+}
+
+/// Collects the phases of a solve, see [`Timings::measure`]. Nothing is displayed unless `time`
+/// was requested, allocations only when also being `verbose`
+#[derive(Debug, Default, Clone)]
+pub struct Timings {
+    time: bool,
+    verbose: bool,
+    phases: Vec<Phase>,
+}
+
+impl Timings {
+    pub fn new(time: bool, verbose: bool) -> Self {
+        Self {
+            time,
+            verbose,
+            phases: Vec::new(),
+        }
+    }
+
+    /// Runs `f` as phase `name` and records how long it took
+    pub fn measure<T>(&mut self, name: &'static str, f: impl FnOnce() -> T) -> T {
+        #[cfg(feature = "alloc-stats")]
+        let before = stats();
+        let start = Instant::now();
+        let result = f();
+        self.phases.push(Phase {
+            name,
+            duration: start.elapsed(),
+            #[cfg(feature = "alloc-stats")]
+            allocated: stats() - before,
+        });
+        result
+    }
+}
+
+/// Lists all phases measured so far, one per line, for the binaries to print to stderr
+impl Display for Timings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if !self.time {
+            return Ok(());
+        }
+        for phase in &self.phases {
+            write!(f, "{:>12}: {:>10.3?}", phase.name, phase.duration)?;
+            #[cfg(feature = "alloc-stats")]
+            if self.verbose {
+                write!(
+                    f,
+                    " {:>10} allocations {:>12} bytes",
+                    phase.allocated.allocations, phase.allocated.bytes
+                )?;
+            }
+            writeln!(f)?;
+        }
+        #[cfg(not(feature = "alloc-stats"))]
+        if self.verbose {
+            writeln!(
+                f,
+                "Build with `--features alloc-stats` to count allocations"
+            )?;
+        }
+        Ok(())
+    }
+}
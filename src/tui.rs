@@ -0,0 +1,191 @@
+//! Animating a solution in the terminal, for when there is no GPU (or only SSH) around
+
+use std::{
+    io::{stdout, Write},
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use termion::{
+    clear, cursor, event::Key, input::TermRead, raw::IntoRawMode, screen::IntoAlternateScreen,
+};
+
+/// How often to look for key presses between two steps
+const POLL: Duration = Duration::from_millis(10);
+
+/// Something, which can be animated in the terminal one step at a time
+pub trait Stepper {
+    /// Advances by one step, `false` once there is nothing left to do
+    fn step(&mut self) -> bool;
+
+    /// What to show on screen, may contain termion colors
+    fn frame(&self) -> String;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Toggle,
+    Step,
+    Faster,
+    Slower,
+    Quit,
+}
+
+impl Action {
+    /// Same keys as the bevy animations, where possible
+    fn from_key(key: Key) -> Option<Self> {
+        match key {
+            Key::Char(' ') => Some(Self::Toggle),
+            Key::Char('n') | Key::Right => Some(Self::Step),
+            Key::Char('j') => Some(Self::Faster),
+            Key::Char('k') => Some(Self::Slower),
+            Key::Char('q') | Key::Esc | Key::Ctrl('c') => Some(Self::Quit),
+            _ => None,
+        }
+    }
+}
+
+/// When to step, controlled by the keys
+#[derive(Debug, Clone, PartialEq)]
+struct Player {
+    running: bool,
+    frequency: f32,
+    steps: usize,
+    finished: bool,
+    /// A single step was requested while paused
+    single: bool,
+}
+
+impl Player {
+    fn new(frequency: f32) -> Self {
+        Self {
+            running: true,
+            frequency,
+            steps: 0,
+            finished: false,
+            single: false,
+        }
+    }
+
+    fn apply(&mut self, action: Action) {
+        match action {
+            Action::Toggle => self.running ^= true,
+            Action::Step => self.single = true,
+            Action::Faster => self.frequency *= 2.,
+            Action::Slower => self.frequency /= 2.,
+            Action::Quit => {}
+        }
+    }
+
+    /// Whether to step now, `elapsed` after the last one
+    fn due(&mut self, elapsed: Duration) -> bool {
+        let single = std::mem::take(&mut self.single);
+        !self.finished && (single || (self.running && elapsed.as_secs_f32() * self.frequency >= 1.))
+    }
+
+    fn status(&self) -> String {
+        let state = match (self.finished, self.running) {
+            (true, _) => "finished",
+            (false, true) => "playing",
+            (false, false) => "paused",
+        };
+        format!(
+            "step {steps}, {state} at {f} Hz | space: play/pause, n: step, j/k: faster/slower, q: quit",
+            steps = self.steps,
+            f = self.frequency,
+        )
+    }
+}
+
+/// Shows the `stepper`'s frames in the alternate screen of the terminal, stepping `frequency`
+/// times per second until the user quits
+pub fn run(mut stepper: impl Stepper, frequency: f32) -> anyhow::Result<()> {
+    let mut screen = stdout().into_raw_mode()?.into_alternate_screen()?;
+    let mut keys = termion::async_stdin().keys();
+    let mut player = Player::new(frequency);
+    let mut last = Instant::now();
+    let mut dirty = true;
+    write!(screen, "{}", cursor::Hide)?;
+    loop {
+        for key in keys.by_ref() {
+            match Action::from_key(key?) {
+                Some(Action::Quit) => {
+                    write!(screen, "{}", cursor::Show)?;
+                    return Ok(());
+                }
+                Some(action) => player.apply(action),
+                None => {}
+            }
+            dirty = true;
+        }
+        if player.due(last.elapsed()) {
+            last = Instant::now();
+            if stepper.step() {
+                player.steps += 1;
+            } else {
+                player.finished = true;
+            }
+            dirty = true;
+        }
+        if dirty {
+            let frame = stepper.frame().replace('\n', "\r\n");
+            write!(
+                screen,
+                "{}{}{frame}\r\n{}",
+                clear::All,
+                cursor::Goto(1, 1),
+                player.status()
+            )?;
+            screen.flush()?;
+            dirty = false;
+        }
+        sleep(POLL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const PERIOD: Duration = Duration::from_millis(500);
+
+    #[rstest]
+    fn steps_once_per_period() {
+        let mut player = Player::new(2.);
+        assert!(!player.due(PERIOD / 2));
+        assert!(player.due(PERIOD));
+        player.apply(Action::Faster);
+        assert!(player.due(PERIOD / 2));
+        player.apply(Action::Slower);
+        player.apply(Action::Slower);
+        assert!(!player.due(PERIOD));
+    }
+
+    #[rstest]
+    fn steps_single_while_paused() {
+        let mut player = Player::new(2.);
+        player.apply(Action::Toggle);
+        assert!(!player.due(PERIOD * 10));
+        player.apply(Action::Step);
+        assert!(player.due(Duration::ZERO));
+        assert!(!player.due(Duration::ZERO));
+    }
+
+    #[rstest]
+    fn stops_when_finished() {
+        let mut player = Player::new(2.);
+        player.finished = true;
+        player.apply(Action::Step);
+        assert!(!player.due(PERIOD * 10));
+    }
+
+    #[rstest]
+    #[case(Key::Char(' '), Some(Action::Toggle))]
+    #[case(Key::Right, Some(Action::Step))]
+    #[case(Key::Ctrl('c'), Some(Action::Quit))]
+    #[case(Key::Char('x'), None)]
+    fn maps_keys(#[case] key: Key, #[case] expected: Option<Action>) {
+        assert_eq!(Action::from_key(key), expected);
+    }
+}
@@ -0,0 +1,66 @@
+//! Counting the arrangements of a [`Report`] by trying every way to fill in its unknown
+//! conditions, instead of the memoized recursion. It lost in the benchmarks and can't unfold part
+//! two's reports at all, but is kept around to compare against
+
+use itertools::Itertools;
+
+use super::{Bit, Report};
+
+impl Report {
+    /// Same as [`Report::arrangements`], but enumerating all assignments of the unknown conditions.
+    /// Only feasible for a handful of them, so `None` with more than 32
+    pub fn enumerate_arrangements(&self) -> Option<usize> {
+        let unknowns = self.pattern.0.iter().filter(|b| **b == Bit::X).count();
+        if unknowns > 32 {
+            return None;
+        }
+        let arrangements = (0..1_u64 << unknowns)
+            .filter(|assignment| {
+                let mut next = 0;
+                let groups = self
+                    .pattern
+                    .0
+                    .iter()
+                    .map(|bit| match bit {
+                        Bit::X => {
+                            next += 1;
+                            (assignment >> (next - 1)) & 1 == 1
+                        }
+                        bit => *bit == Bit::I,
+                    })
+                    .dedup_with_count()
+                    .filter(|(_, damaged)| *damaged)
+                    .map(|(n, _)| n as u32);
+                groups.eq(self.clues.iter().copied())
+            })
+            .count();
+        Some(arrangements)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::twelfth::Springs;
+    use rstest::rstest;
+    use std::str::FromStr;
+
+    #[rstest]
+    #[case("??? 1,1")]
+    #[case("?###???????? 3,2,1")]
+    #[case("????.######..#####. 1,6,5")]
+    fn enumerates_like_recursing(#[case] report: Report) {
+        assert_eq!(Some(report.arrangements()), report.enumerate_arrangements());
+    }
+
+    #[rstest]
+    fn sample_a() {
+        let input = include_str!("../../sample/twelfth.txt");
+        let springs = Springs::from_str(input).expect("parsing");
+        let arrangements = springs
+            .reports()
+            .map(|report| report.enumerate_arrangements().expect("few unknowns"))
+            .sum::<usize>();
+        assert_eq!(21, arrangements);
+    }
+}
@@ -0,0 +1,434 @@
+use std::collections::HashMap;
+
+use bevy::{prelude::*, sprite::Anchor};
+
+use crate::{
+    lerp,
+    reset::ResetPlugin,
+    summary::AnimationFinished,
+    widgets::{ScorePopup, WidgetsPlugin},
+    AocCameraPlugin, AocControlPlugin, Presentation, Running, Tick,
+};
+
+use super::{Bit, Report, Springs};
+
+pub fn run(springs: Springs, frequency: f32, presentation: Presentation) {
+    let plugins = DefaultPlugins.build().add(presentation);
+    app(springs, frequency, plugins).run()
+}
+
+pub fn app(springs: Springs, frequency: f32, plugins: impl PluginGroup) -> App {
+    let mut app = App::new();
+    let state = GameState::new(&springs);
+    app.add_plugins((
+        plugins,
+        AocCameraPlugin::new(1.),
+        AocControlPlugin,
+        WidgetsPlugin,
+    ))
+    .insert_resource(springs)
+    .insert_resource(state)
+    .insert_resource(Tick::new(frequency))
+    .add_plugins(ResetPlugin::<GameState>::default())
+    .add_systems(Startup, setup)
+    .add_systems(Update, (update, row_mover, row_colorer, totaller));
+    app
+}
+
+const FONT_SIZE: f32 = 32.;
+const ROW_HEIGHT: f32 = FONT_SIZE * 1.2;
+const MOTION: f32 = 5.;
+const UNKNOWN_COLOR: Color = Color::GRAY;
+const DAMAGED_COLOR: Color = Color::ORANGE;
+const OPERATIONAL_COLOR: Color = Color::CYAN;
+const FITS_COLOR: Color = Color::GREEN;
+const REJECTED_COLOR: Color = Color::RED;
+const COUNT_COLOR: Color = Color::GOLD;
+
+/// What the last step of a [`Search`] did with the clue of `len` damaged springs at `start`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    #[default]
+    Started,
+    /// The clue fits, continue with the next one after it
+    Placed { start: usize, len: usize },
+    /// The clue overlaps an operational spring or touches a damaged one
+    Rejected { start: usize, len: usize },
+    /// The last clue fits and no damaged spring is left after it: one more arrangement
+    Found { start: usize, len: usize },
+    /// The clue fits and the remaining clues were counted before from there on
+    Remembered {
+        start: usize,
+        len: usize,
+        count: usize,
+    },
+    /// All starts of the clue have been tried, back to the one before
+    Backtracked,
+}
+
+/// Clue `k` (its frame's index) gets tried at every `start` from `pos` on
+#[derive(Debug, Clone, Copy)]
+struct Frame {
+    pos: usize,
+    start: usize,
+    /// Arrangements of the clues from `k` on found so far
+    count: usize,
+}
+
+/// Depth first search over the placements of a report's clues, which remembers how many
+/// arrangements the remaining clues have from each position on. One [`Search::step`] at a time, so
+/// it can be animated
+#[derive(Debug, Default, Clone)]
+struct Search {
+    cells: Vec<Bit>,
+    clues: Vec<usize>,
+    stack: Vec<Frame>,
+    memo: HashMap<(usize, usize), usize>,
+    /// Arrangements of all clues, once the search is done
+    count: usize,
+}
+
+impl Search {
+    fn new(report: &Report) -> Self {
+        // Without the operational spring `Report` pads its pattern with
+        let cells = report.pattern.0[..report.pattern.0.len() - 1].to_vec();
+        let clues = report.clues.iter().map(|n| *n as usize).collect::<Vec<_>>();
+        let (stack, count) = if clues.is_empty() {
+            (vec![], usize::from(!cells.contains(&Bit::I)))
+        } else {
+            let root = Frame {
+                pos: 0,
+                start: 0,
+                count: 0,
+            };
+            (vec![root], 0)
+        };
+        Self {
+            cells,
+            clues,
+            stack,
+            memo: HashMap::new(),
+            count,
+        }
+    }
+
+    fn done(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    /// Arrangements found so far
+    fn arrangements(&self) -> usize {
+        self.count + self.stack.iter().map(|frame| frame.count).sum::<usize>()
+    }
+
+    /// Start & length of the clues placed right now
+    fn placements(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let placed = self.stack.len().saturating_sub(1);
+        self.stack[..placed]
+            .iter()
+            .zip(&self.clues)
+            .map(|(frame, len)| (frame.start - 1, *len))
+    }
+
+    /// Cells, whose condition the current placements fix: damaged ones inside a placed clue,
+    /// operational ones in between
+    fn confirmed(&self) -> Vec<Option<Bit>> {
+        let mut confirmed = vec![None; self.cells.len()];
+        let mut pos = 0;
+        for (start, len) in self.placements() {
+            confirmed[pos..start].fill(Some(Bit::O));
+            confirmed[start..start + len].fill(Some(Bit::I));
+            pos = (start + len + 1).min(self.cells.len());
+            confirmed[start + len..pos].fill(Some(Bit::O));
+        }
+        confirmed
+    }
+
+    fn step(&mut self) -> Outcome {
+        let n = self.cells.len();
+        let Some(k) = self.stack.len().checked_sub(1) else {
+            return Outcome::Backtracked;
+        };
+        let len = self.clues[k];
+        let frame = self.stack[k];
+
+        // Skipping a damaged spring would leave it without a clue
+        let skipped_damaged = frame.start > frame.pos && self.cells[frame.start - 1] == Bit::I;
+        if skipped_damaged || frame.start + len > n {
+            self.memo.insert((frame.pos, k), frame.count);
+            self.stack.pop();
+            match self.stack.last_mut() {
+                Some(parent) => parent.count += frame.count,
+                None => self.count = frame.count,
+            }
+            return Outcome::Backtracked;
+        }
+
+        let start = frame.start;
+        let end = start + len;
+        self.stack[k].start += 1;
+        let fits =
+            !self.cells[start..end].contains(&Bit::O) && self.cells.get(end) != Some(&Bit::I);
+        if !fits {
+            return Outcome::Rejected { start, len };
+        }
+
+        let pos = (end + 1).min(n);
+        if k + 1 == self.clues.len() {
+            if self.cells[pos..].contains(&Bit::I) {
+                return Outcome::Rejected { start, len };
+            }
+            self.stack[k].count += 1;
+            return Outcome::Found { start, len };
+        }
+        if let Some(count) = self.memo.get(&(pos, k + 1)).copied() {
+            self.stack[k].count += count;
+            return Outcome::Remembered { start, len, count };
+        }
+        self.stack.push(Frame {
+            pos,
+            start: pos,
+            count: 0,
+        });
+        Outcome::Placed { start, len }
+    }
+}
+
+#[derive(Debug, Default, Clone, Resource)]
+struct GameState {
+    /// Report searched right now
+    row: usize,
+    search: Search,
+    outcome: Outcome,
+    /// Arrangements of each report searched already
+    counts: Vec<usize>,
+    total: usize,
+    /// Ticks taken until now
+    steps: usize,
+}
+
+impl GameState {
+    fn new(springs: &Springs) -> Self {
+        Self {
+            search: springs.0.first().map(Search::new).unwrap_or_default(),
+            ..default()
+        }
+    }
+}
+
+#[derive(Debug, Component)]
+struct Row(usize);
+
+#[derive(Debug, Component)]
+struct Total;
+
+fn slot_y(slot: f32) -> f32 {
+    -slot * ROW_HEIGHT
+}
+
+fn symbol(bit: Bit) -> &'static str {
+    match bit {
+        Bit::I => "#",
+        Bit::O => ".",
+        Bit::X => "?",
+    }
+}
+
+fn setup(mut cmd: Commands, springs: Res<Springs>) {
+    let style = |color| TextStyle {
+        font_size: FONT_SIZE,
+        color,
+        ..default()
+    };
+    for (i, report) in springs.reports().enumerate() {
+        let cells = report.pattern.0[..report.pattern.0.len() - 1]
+            .iter()
+            .map(|bit| TextSection::new(symbol(*bit), style(UNKNOWN_COLOR)));
+        let clues = report
+            .clues
+            .iter()
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>();
+        cmd.spawn((
+            Row(i),
+            Text2dBundle {
+                text: Text::from_sections(cells.chain([
+                    TextSection::new(format!(" {}", clues.join(",")), style(UNKNOWN_COLOR)),
+                    TextSection::new("", style(COUNT_COLOR)),
+                ])),
+                transform: Transform::from_xyz(0., slot_y(i as f32), 0.),
+                text_anchor: Anchor::CenterLeft,
+                ..default()
+            },
+        ));
+    }
+
+    cmd.spawn((
+        Total,
+        Text2dBundle {
+            text: Text::from_sections([
+                TextSection::new("Arrangements: ", style(COUNT_COLOR)),
+                TextSection::new("---", style(COUNT_COLOR)),
+            ]),
+            transform: Transform::from_xyz(0., 2. * ROW_HEIGHT, 1.),
+            text_anchor: Anchor::CenterLeft,
+            ..default()
+        },
+    ));
+}
+
+fn update(
+    mut cmd: Commands,
+    running: Res<Running>,
+    time: Res<Time>,
+    mut timer: ResMut<Tick>,
+    mut state: ResMut<GameState>,
+    springs: Res<Springs>,
+    mut finished: EventWriter<AnimationFinished>,
+) {
+    if !running.inner() {
+        return;
+    }
+    if !timer.inner().tick(time.delta()).just_finished() {
+        return;
+    }
+    if state.row >= springs.0.len() {
+        return;
+    }
+    state.steps += 1;
+
+    if !state.search.done() {
+        state.outcome = state.search.step();
+        return;
+    }
+
+    // Report searched completely, continue with the next one
+    let count = state.search.count;
+    state.counts.push(count);
+    state.total += count;
+    cmd.spawn(ScorePopup::bundle(
+        format!("+{count}"),
+        TextStyle {
+            font_size: FONT_SIZE * 0.8,
+            color: COUNT_COLOR,
+            ..default()
+        },
+        Vec3::new(-FONT_SIZE, 0., 2.),
+        ROW_HEIGHT,
+    ));
+    state.row += 1;
+    state.outcome = Outcome::Started;
+    state.search = springs
+        .0
+        .get(state.row)
+        .map(Search::new)
+        .unwrap_or_default();
+    if state.row == springs.0.len() {
+        finished.send(AnimationFinished::new(state.total, state.steps).with("Reports", state.row));
+    }
+}
+
+fn row_mover(time: Res<Time>, state: Res<GameState>, mut rows: Query<(&Row, &mut Transform)>) {
+    // Keep the current report at the top
+    let dt = time.delta_seconds();
+    for (row, mut tf) in rows.iter_mut() {
+        let target = slot_y(row.0 as f32 - state.row as f32);
+        tf.translation.y = lerp(tf.translation.y, target, MOTION * dt);
+    }
+}
+
+fn row_colorer(state: Res<GameState>, mut rows: Query<(&Row, &mut Text)>) {
+    // Only the current report & the one finished last change
+    for (row, mut text) in rows.iter_mut() {
+        let n = text.sections.len() - 2;
+        if row.0 + 1 == state.row {
+            text.sections[n + 1].value = format!(" = {}", state.counts[row.0]);
+            for section in text.sections[..n].iter_mut() {
+                section.style.color = UNKNOWN_COLOR;
+            }
+        }
+        if row.0 != state.row {
+            continue;
+        }
+
+        let search = &state.search;
+        let confirmed = search.confirmed();
+        let (tried, color) = match state.outcome {
+            Outcome::Placed { start, len }
+            | Outcome::Found { start, len }
+            | Outcome::Remembered { start, len, .. } => (start..start + len, FITS_COLOR),
+            Outcome::Rejected { start, len } => (start..start + len, REJECTED_COLOR),
+            Outcome::Started | Outcome::Backtracked => (0..0, UNKNOWN_COLOR),
+        };
+        for (i, section) in text.sections[..n].iter_mut().enumerate() {
+            let (bit, target) = match confirmed[i] {
+                _ if tried.contains(&i) => (Bit::I, color),
+                Some(Bit::I) => (Bit::I, DAMAGED_COLOR),
+                Some(_) => (Bit::O, OPERATIONAL_COLOR),
+                None => (search.cells[i], UNKNOWN_COLOR),
+            };
+            section.value = symbol(bit).to_owned();
+            section.style.color = target;
+        }
+        text.sections[n + 1].value = format!(" = {}...", search.arrangements());
+    }
+}
+
+fn totaller(state: Res<GameState>, mut totals: Query<&mut Text, With<Total>>) {
+    if state.counts.is_empty() {
+        return;
+    }
+    for mut text in totals.iter_mut() {
+        text.sections[1].value = state.total.to_string();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{twelfth::unfold, HeadlessPlugins};
+    use bevy::time::TimeUpdateStrategy;
+    use rstest::rstest;
+    use std::{str::FromStr, time::Duration};
+
+    fn springs() -> Springs {
+        Springs::from_str(include_str!("../../sample/twelfth.txt")).expect("parsing")
+    }
+
+    #[test]
+    fn smoke() {
+        let mut app = app(springs(), 5., HeadlessPlugins);
+        for _ in 0..3 {
+            app.update();
+        }
+    }
+
+    #[rstest]
+    #[case(include_str!("../../sample/twelfth.txt").to_owned())]
+    #[case(unfold(include_str!("../../sample/twelfth.txt")))]
+    #[case("# 1\n## 1\n?###???????? 3,2,1\n????.######..#####. 1,6,5".to_owned())]
+    fn search_counts_like_recursion(#[case] input: String) {
+        for report in Springs::from_str(&input).expect("parsing").reports() {
+            let mut search = Search::new(report);
+            while !search.done() {
+                search.step();
+            }
+            assert_eq!(search.count, report.arrangements(), "{report:?}");
+        }
+    }
+
+    #[test]
+    fn counts_all_arrangements() {
+        let mut app = app(springs(), 100., HeadlessPlugins);
+        app.insert_resource(Running(true))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )));
+        for _ in 0..500 {
+            app.update();
+        }
+        let state = app.world.resource::<GameState>();
+        assert_eq!(state.counts, vec![1, 4, 1, 1, 4, 10]);
+        assert_eq!(state.total, 21);
+    }
+}
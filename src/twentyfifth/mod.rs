@@ -0,0 +1,187 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, bail};
+use nom::Finish;
+
+use self::parser::diagram;
+use crate::{anyhowing, intern::Interner, Part, Solver};
+
+mod parser;
+
+/// How many wires have to be disconnected to split the components into two groups
+pub const WIRES: usize = 3;
+
+/// Components wired to each other, as an undirected graph
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Wiring {
+    names: Interner,
+    /// Ids of the components wired to each one
+    neighbors: Vec<Vec<u32>>,
+}
+
+impl FromStr for Wiring {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut names = Interner::new();
+        let mut neighbors = Vec::<Vec<u32>>::new();
+        for (component, others) in diagram(s).finish().map_err(anyhowing)?.1 {
+            let a = names.intern(component);
+            for other in others {
+                let b = names.intern(other);
+                neighbors.resize(names.len(), Vec::new());
+                neighbors[a as usize].push(b);
+                neighbors[b as usize].push(a);
+            }
+        }
+        Ok(Self { names, neighbors })
+    }
+}
+
+/// Wires splitting the components into two groups
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cut {
+    /// Pairs of components, the first one in the group
+    pub wires: Vec<(u32, u32)>,
+    /// Components on one side of the cut
+    pub group: HashSet<u32>,
+}
+
+impl Wiring {
+    /// Number of components
+    pub fn len(&self) -> usize {
+        self.neighbors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.neighbors.is_empty()
+    }
+
+    pub fn name(&self, id: u32) -> &str {
+        self.names.resolve(id)
+    }
+
+    /// Smallest cut separating `source` from `sink`, unless that takes more than `max` wires.
+    /// Pushes one unit of flow along a shortest path per wire (Edmonds-Karp), until no path is
+    /// left: the components still reachable then are the `source`'s side of the cut
+    pub fn cut_between(&self, source: u32, sink: u32, max: usize) -> Option<Cut> {
+        let mut flow = HashMap::<(u32, u32), i32>::new();
+        for _ in 0..=max {
+            let mut parents = HashMap::from([(source, source)]);
+            let mut queue = VecDeque::from([source]);
+            while let Some(a) = queue.pop_front() {
+                for &b in &self.neighbors[a as usize] {
+                    let used = flow.get(&(a, b)).copied().unwrap_or_default();
+                    if used < 1 && !parents.contains_key(&b) {
+                        parents.insert(b, a);
+                        queue.push_back(b);
+                    }
+                }
+            }
+            if !parents.contains_key(&sink) {
+                let group = parents.into_keys().collect::<HashSet<_>>();
+                let wires = group
+                    .iter()
+                    .flat_map(|a| self.neighbors[*a as usize].iter().map(move |b| (*a, *b)))
+                    .filter(|(_, b)| !group.contains(b))
+                    .collect();
+                return Some(Cut { wires, group });
+            }
+            let mut b = sink;
+            while b != source {
+                let a = parents[&b];
+                *flow.entry((a, b)).or_default() += 1;
+                *flow.entry((b, a)).or_default() -= 1;
+                b = a;
+            }
+        }
+        None
+    }
+
+    /// Any cut of at most `max` wires. The first component has to be on one side of it, so
+    /// trying all others for the other side finds it
+    pub fn min_cut(&self, max: usize) -> Option<Cut> {
+        (1..self.len() as u32).find_map(|sink| self.cut_between(0, sink, max))
+    }
+}
+
+pub fn solve(part: Part, input: &str) -> anyhow::Result<usize> {
+    if part == Part::Two {
+        bail!("Day 25 has no second part, all it takes is pushing the big red button");
+    }
+    let wiring = Wiring::from_str(input)?;
+    let cut = wiring
+        .min_cut(WIRES)
+        .ok_or(anyhow!("No {WIRES} wires split the components in two"))?;
+    Ok(cut.group.len() * (wiring.len() - cut.group.len()))
+}
+
+/// Day 25: Snowverload
+#[derive(Debug, Clone, Copy)]
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    fn name(&self) -> &'static str {
+        "twentyfifth"
+    }
+
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::One, input).map(|x| x.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::Two, input).map(|x| x.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+    use rstest::rstest;
+
+    const SAMPLE: &str = include_str!("../../sample/twentyfifth.txt");
+
+    #[rstest]
+    fn sample() {
+        assert_eq!(solve(Part::One, SAMPLE).expect("solving"), 54);
+        assert!(solve(Part::Two, SAMPLE).is_err());
+    }
+
+    #[rstest]
+    fn cuts_the_sample_wires() {
+        let wiring = Wiring::from_str(SAMPLE).expect("parsing");
+        assert_eq!(wiring.len(), 15);
+        let cut = wiring.min_cut(WIRES).expect("cutting");
+        let wires = cut
+            .wires
+            .iter()
+            .map(|(a, b)| {
+                [wiring.name(*a), wiring.name(*b)]
+                    .into_iter()
+                    .sorted()
+                    .join("/")
+            })
+            .sorted()
+            .collect::<Vec<_>>();
+        assert_eq!(wires, ["bvb/cmg", "hfx/pzl", "jqt/nvd"]);
+    }
+
+    #[rstest]
+    fn fully_wired_components_need_more_cuts() {
+        let wiring = Wiring::from_str("a: b c d e\nb: c d e\nc: d e\nd: e").expect("parsing");
+        assert_eq!(wiring.min_cut(WIRES), None);
+        assert_eq!(wiring.min_cut(4).map(|cut| cut.wires.len()), Some(4));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("jqt rhn xhk")]
+    #[case("jqt:")]
+    fn invalid_diagrams(#[case] input: &str) {
+        assert!(Wiring::from_str(input).is_err());
+    }
+}
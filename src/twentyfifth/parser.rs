@@ -0,0 +1,22 @@
+use nom::{
+    character::complete::{alpha1, char, line_ending, multispace0, space1},
+    multi::separated_list1,
+    sequence::separated_pair,
+    IResult, Parser as NomParser,
+};
+use nom_supreme::ParserExt;
+
+use crate::parse::common::ws;
+
+/// A component and the ones it is wired to, e.g. `jqt: rhn xhk nvd`
+pub(crate) fn connections(s: &str) -> IResult<&str, (&str, Vec<&str>)> {
+    separated_pair(alpha1, ws(char(':')), separated_list1(space1, alpha1)).parse(s)
+}
+
+pub(crate) fn diagram(s: &str) -> IResult<&str, Vec<(&str, Vec<&str>)>> {
+    multispace0
+        .precedes(separated_list1(line_ending, connections))
+        .terminated(multispace0)
+        .all_consuming()
+        .parse(s)
+}
@@ -0,0 +1,227 @@
+use std::{
+    collections::{hash_map::Entry, HashMap, VecDeque},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, bail};
+use enum_iterator::all;
+
+use crate::{grid::Grid, Coord, Direction, Part, Solver};
+
+/// How many steps the elf wants to walk on the map itself (part one)
+pub const STEPS: u64 = 64;
+/// Same on the infinitely repeating map (part two)
+pub const INFINITE_STEPS: u64 = 26501365;
+
+/// Anything but a garden plot on the map
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Rock,
+    Start,
+}
+
+impl TryFrom<char> for Tile {
+    type Error = anyhow::Error;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '#' => Ok(Self::Rock),
+            'S' => Ok(Self::Start),
+            _ => Err(anyhow!("Unknown tile: {c}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Garden {
+    tiles: Grid<Tile>,
+    start: Coord,
+}
+
+impl FromStr for Garden {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tiles = Grid::<Tile>::from_str(s.trim())?;
+        let starts = tiles
+            .iter()
+            .filter(|(_, tile)| **tile == Tile::Start)
+            .map(|(c, _)| c)
+            .collect::<Vec<_>>();
+        match starts[..] {
+            [start] => Ok(Self { tiles, start }),
+            _ => bail!("Expected exactly one start, found {}", starts.len()),
+        }
+    }
+}
+
+impl Garden {
+    pub fn ncols(&self) -> i32 {
+        self.tiles.ncols()
+    }
+
+    pub fn nrows(&self) -> i32 {
+        self.tiles.nrows()
+    }
+
+    pub fn start(&self) -> Coord {
+        self.start
+    }
+
+    /// Whether there's a rock at `c`, with the map repeating infinitely into all directions
+    pub fn is_rock(&self, c: Coord) -> bool {
+        let c = Coord::new(c.x.rem_euclid(self.ncols()), c.y.rem_euclid(self.nrows()));
+        self.tiles.get(c) == Some(&Tile::Rock)
+    }
+
+    /// Garden plots reachable in exactly `steps` by a breadth first search, either on the map
+    /// itself or (`tiled`) on the infinitely repeating one. Plots reachable in fewer steps count
+    /// too, if the elf can step back & forth to make up for the difference
+    pub fn walk(&self, steps: u64, tiled: bool) -> u64 {
+        let mut distances = HashMap::from([(self.start, 0)]);
+        let mut open = VecDeque::from([self.start]);
+        while let Some(c) = open.pop_front() {
+            let distance = distances[&c];
+            if distance == steps {
+                continue;
+            }
+            for dir in all::<Direction>() {
+                let next = c + Coord::from(dir);
+                if (!tiled && !self.tiles.contains(next)) || self.is_rock(next) {
+                    continue;
+                }
+                if let Entry::Vacant(e) = distances.entry(next) {
+                    e.insert(distance + 1);
+                    open.push_back(next);
+                }
+            }
+        }
+        distances
+            .values()
+            .filter(|distance| *distance % 2 == steps % 2)
+            .count() as u64
+    }
+
+    /// Whether the start is in the middle of a square map with its row & column free of rocks.
+    /// Then the plots reachable grow quadratically with each further map walked into any direction
+    pub fn is_extrapolatable(&self) -> bool {
+        let n = self.ncols();
+        n == self.nrows()
+            && self.start == Coord::new(n / 2, n / 2)
+            && (0..n).all(|i| {
+                !self.is_rock(Coord::new(i, self.start.y))
+                    && !self.is_rock(Coord::new(self.start.x, i))
+            })
+    }
+
+    /// Garden plots reachable in exactly `steps` on the infinitely repeating map. For an
+    /// [extrapolatable](Garden::is_extrapolatable) garden many steps get fitted by a quadratic
+    /// through the plots reachable in `steps % n` plus one, two & three maps' widths `n`. Any
+    /// other garden is walked all the way, which takes forever for too many steps
+    pub fn reachable(&self, steps: u64) -> u64 {
+        let n = self.ncols() as u64;
+        let offset = steps % n;
+        if !self.is_extrapolatable() || steps <= offset + 3 * n {
+            return self.walk(steps, true);
+        }
+        let [y0, y1, y2] = [1, 2, 3].map(|k| self.walk(offset + k * n, true) as i128);
+        let x = (steps / n - 1) as i128;
+        (y0 + x * (y1 - y0) + x * (x - 1) / 2 * (y2 - 2 * y1 + y0)) as u64
+    }
+}
+
+pub fn solve(part: Part, input: &str) -> anyhow::Result<u64> {
+    let garden = Garden::from_str(input)?;
+    match part {
+        Part::One => Ok(garden.walk(STEPS, false)),
+        Part::Two if garden.is_extrapolatable() => Ok(garden.reachable(INFINITE_STEPS)),
+        Part::Two => {
+            bail!("Can only extrapolate square gardens starting in their rock free middle")
+        }
+    }
+}
+
+/// Day 21: Step Counter
+#[derive(Debug, Clone, Copy)]
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    fn name(&self) -> &'static str {
+        "twentyfirst"
+    }
+
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::One, input).map(|x| x.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::Two, input).map(|x| x.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    /// Square with the start in its middle and rocks off its row & column
+    const OPEN: &str = "
+.......
+.#.....
+.....#.
+...S...
+.#.....
+....#..
+.......";
+
+    fn sample() -> Garden {
+        Garden::from_str(include_str!("../../sample/twentyfirst.txt")).expect("parsing")
+    }
+
+    #[rstest]
+    fn sample_part_one() {
+        assert_eq!(
+            solve(Part::One, include_str!("../../sample/twentyfirst.txt")).expect("solving"),
+            42
+        );
+        assert_eq!(sample().walk(6, false), 16);
+    }
+
+    #[rstest]
+    #[case(6, 16)]
+    #[case(10, 50)]
+    #[case(50, 1594)]
+    #[case(100, 6536)]
+    #[case(500, 167004)]
+    fn sample_tiled(#[case] steps: u64, #[case] expected: u64) {
+        let garden = sample();
+        assert!(!garden.is_extrapolatable());
+        assert_eq!(garden.reachable(steps), expected);
+    }
+
+    #[rstest]
+    #[case(38, 1383)]
+    #[case(44, 1875)]
+    #[case(61, 3529)]
+    #[case(66, 4099)]
+    fn extrapolates_like_walking(#[case] steps: u64, #[case] expected: u64) {
+        let garden = Garden::from_str(OPEN).expect("parsing");
+        assert!(garden.is_extrapolatable());
+        assert_eq!(garden.walk(steps, true), expected);
+        assert_eq!(garden.reachable(steps), expected);
+    }
+
+    #[rstest]
+    fn extrapolates_empty_gardens() {
+        let garden = Garden::from_str(".....\n.....\n..S..\n.....\n.....").expect("parsing");
+        assert_eq!(garden.reachable(22), 23 * 23);
+    }
+
+    #[rstest]
+    #[case("...\n...")]
+    #[case("S.S\n...")]
+    #[case(".S.\n.x.")]
+    fn invalid_gardens(#[case] input: &str) {
+        assert!(Garden::from_str(input).is_err());
+    }
+}
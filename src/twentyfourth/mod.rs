@@ -0,0 +1,196 @@
+use std::ops::RangeInclusive;
+
+use anyhow::anyhow;
+use itertools::Itertools;
+use nom::Finish;
+
+use self::parser::hailstones;
+use crate::{anyhowing, linalg::Vector, Part, Solver};
+
+mod parser;
+
+/// Where paths have to cross in X & Y to count for part one
+pub const TEST_AREA: RangeInclusive<i128> = 200_000_000_000_000..=400_000_000_000_000;
+/// Same for the sample
+pub const SAMPLE_TEST_AREA: RangeInclusive<i128> = 7..=27;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hailstone {
+    pub position: Vector,
+    /// Distance moved per nanosecond
+    pub velocity: Vector,
+}
+
+impl Hailstone {
+    /// Where it will be after `t` nanoseconds
+    pub fn at(&self, t: i128) -> Vector {
+        self.position + self.velocity * t
+    }
+
+    /// Whether the paths of both cross within `area` in X & Y (ignoring Z) in the future of
+    /// both, not necessarily at the same time
+    pub fn crosses(&self, other: &Self, area: &RangeInclusive<i128>) -> bool {
+        let (a, b) = (self, other);
+        let det = a.velocity.x() * b.velocity.y() - a.velocity.y() * b.velocity.x();
+        if det == 0 {
+            // Parallel paths never cross
+            return false;
+        }
+        let (dx, dy) = (
+            b.position.x() - a.position.x(),
+            b.position.y() - a.position.y(),
+        );
+        // When each passes the crossing, times `det`
+        let t = dx * b.velocity.y() - dy * b.velocity.x();
+        let s = dx * a.velocity.y() - dy * a.velocity.x();
+        let (det, t, s) = if det < 0 { (-det, -t, -s) } else { (det, t, s) };
+        if t < 0 || s < 0 {
+            return false;
+        }
+        let area = area.start() * det..=area.end() * det;
+        area.contains(&(a.position.x() * det + a.velocity.x() * t))
+            && area.contains(&(a.position.y() * det + a.velocity.y() * t))
+    }
+
+    /// Nanoseconds until it passes the line through the origin along `direction`, if it does so
+    /// at an integer time
+    fn meets_line(&self, direction: &Vector) -> Option<i128> {
+        let (p, v) = (
+            self.position.cross(direction)?,
+            self.velocity.cross(direction)?,
+        );
+        let i = (0..3).find(|i| v.0[*i] != 0)?;
+        let t = -p.0[i] / v.0[i];
+        (p + v * t == Vector::ZERO).then_some(t)
+    }
+
+    /// Same hailstone, as seen by an observer moving along with `frame`
+    fn relative_to(&self, frame: &Self) -> Self {
+        Self {
+            position: self.position - frame.position,
+            velocity: self.velocity - frame.velocity,
+        }
+    }
+
+    /// Whether it ever collides with `other`
+    pub fn collides(&self, other: &Self) -> Option<bool> {
+        let relative = other.relative_to(self);
+        relative.position.is_parallel(&relative.velocity)
+    }
+}
+
+/// Pairs of hailstones, whose paths cross within `area` (part one)
+pub fn crossings(hailstones: &[Hailstone], area: &RangeInclusive<i128>) -> usize {
+    hailstones
+        .iter()
+        .tuple_combinations()
+        .filter(|(a, b)| a.crosses(b, area))
+        .count()
+}
+
+/// Rock thrown from an integer position at an integer velocity, which hits all `hailstones`
+/// (part two). Seen from the first hailstone, the rock has to pass through the origin, and in
+/// doing so lie in the plane through the origin & the path of each other hailstone. Two such
+/// planes meet in the rock's path, on which the times of two hits pin down its velocity
+pub fn throw(hailstones: &[Hailstone]) -> Option<Hailstone> {
+    let (first, others) = hailstones.split_first()?;
+    let others = others
+        .iter()
+        .map(|h| h.relative_to(first))
+        .collect::<Vec<_>>();
+    others.iter().tuple_combinations().find_map(|(a, b)| {
+        let normal = |h: &Hailstone| h.position.cross(&h.velocity);
+        let direction = normal(a)?.cross(&normal(b)?)?.primitive();
+        if direction == Vector::ZERO {
+            return None;
+        }
+        let (ta, tb) = (a.meets_line(&direction)?, b.meets_line(&direction)?);
+        let velocity = (b.at(tb) - a.at(ta)).div_exact(tb - ta)?;
+        let rock = Hailstone {
+            position: a.at(ta) - velocity * ta + first.position,
+            velocity: velocity + first.velocity,
+        };
+        hailstones
+            .iter()
+            .all(|h| rock.collides(h) == Some(true))
+            .then_some(rock)
+    })
+}
+
+pub fn parse(input: &str) -> anyhow::Result<Vec<Hailstone>> {
+    Ok(hailstones(input).finish().map_err(anyhowing)?.1)
+}
+
+pub fn solve(part: Part, input: &str) -> anyhow::Result<i128> {
+    let hailstones = parse(input)?;
+    match part {
+        Part::One => Ok(crossings(&hailstones, &TEST_AREA) as i128),
+        Part::Two => {
+            let rock = throw(&hailstones).ok_or(anyhow!("No throw hits all hailstones"))?;
+            Ok(rock.position.0.iter().sum())
+        }
+    }
+}
+
+/// Day 24: Never Tell Me The Odds
+#[derive(Debug, Clone, Copy)]
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    fn name(&self) -> &'static str {
+        "twentyfourth"
+    }
+
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::One, input).map(|x| x.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::Two, input).map(|x| x.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn sample() -> Vec<Hailstone> {
+        parse(include_str!("../../sample/twentyfourth.txt")).expect("parsing")
+    }
+
+    #[rstest]
+    fn sample_part_one() {
+        assert_eq!(crossings(&sample(), &SAMPLE_TEST_AREA), 2);
+    }
+
+    #[rstest]
+    fn sample_part_two() {
+        let input = include_str!("../../sample/twentyfourth.txt");
+        assert_eq!(solve(Part::Two, input).expect("solving"), 47);
+        let rock = throw(&sample()).expect("throwing");
+        assert_eq!(rock.position, Vector::new(24, 13, 10));
+        assert_eq!(rock.velocity, Vector::new(-3, 1, 2));
+    }
+
+    #[rstest]
+    #[case(0, 1, true)]
+    #[case(0, 4, false)]
+    #[case(1, 2, false)]
+    #[case(1, 4, false)]
+    fn crossing_paths(#[case] a: usize, #[case] b: usize, #[case] expected: bool) {
+        let hailstones = sample();
+        assert_eq!(
+            hailstones[a].crosses(&hailstones[b], &SAMPLE_TEST_AREA),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("19, 13, 30 @ -2, 1")]
+    #[case("19, 13 @ -2, 1, -2")]
+    #[case("19, 13, 30 -2, 1, -2")]
+    fn invalid_hailstones(#[case] input: &str) {
+        assert!(parse(input).is_err());
+    }
+}
@@ -0,0 +1,39 @@
+use nom::{
+    character::complete::{char, line_ending, multispace0},
+    multi::separated_list1,
+    sequence::{separated_pair, tuple},
+    IResult, Parser as NomParser,
+};
+use nom_supreme::ParserExt;
+
+use super::Hailstone;
+use crate::{
+    linalg::Vector,
+    parse::common::{integer, ws},
+};
+
+/// Three integers separated by commas, e.g. `19, 13, 30`
+pub(crate) fn vector(s: &str) -> IResult<&str, Vector> {
+    tuple((
+        ws(integer),
+        char(',').precedes(ws(integer)),
+        char(',').precedes(ws(integer)),
+    ))
+    .map(|(x, y, z)| Vector::new(x, y, z))
+    .parse(s)
+}
+
+/// Position & velocity, e.g. `19, 13, 30 @ -2,  1, -2`
+pub(crate) fn hailstone(s: &str) -> IResult<&str, Hailstone> {
+    separated_pair(vector, char('@'), vector)
+        .map(|(position, velocity)| Hailstone { position, velocity })
+        .parse(s)
+}
+
+pub(crate) fn hailstones(s: &str) -> IResult<&str, Vec<Hailstone>> {
+    multispace0
+        .precedes(separated_list1(line_ending, hailstone))
+        .terminated(multispace0)
+        .all_consuming()
+        .parse(s)
+}
@@ -0,0 +1,244 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    str::FromStr,
+};
+
+use itertools::Itertools;
+use nom::Finish;
+
+use self::parser::bricks;
+use crate::{anyhowing, Coord, Coord3, Part, Solver};
+
+mod parser;
+
+/// Brick of sand cubes between two corner cubes, both included
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Brick {
+    pub min: Coord3,
+    pub max: Coord3,
+}
+
+impl Brick {
+    /// Brick between the corners `a` & `b`, in any order
+    pub fn new(a: Coord3, b: Coord3) -> Self {
+        Self {
+            min: a.min(b),
+            max: a.max(b),
+        }
+    }
+
+    /// Cells of the ground below the brick
+    pub fn footprint(&self) -> impl Iterator<Item = Coord> {
+        let (min, max) = (self.min, self.max);
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Coord::new(x, y)))
+    }
+
+    pub fn cubes(&self) -> impl Iterator<Item = Coord3> {
+        let (min, max) = (self.min, self.max);
+        (min.z..=max.z).flat_map(move |z| {
+            (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Coord3::new(x, y, z)))
+        })
+    }
+
+    /// Same brick moved up or down, so that its lowest cubes are at height `z`
+    pub fn lowered_to(&self, z: i32) -> Self {
+        let dz = Coord3::new(0, 0, z - self.min.z);
+        Self {
+            min: self.min + dz,
+            max: self.max + dz,
+        }
+    }
+}
+
+/// Bricks as they got snapshot while falling towards the ground at `z = 0`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    pub bricks: Vec<Brick>,
+}
+
+impl FromStr for Snapshot {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self {
+            bricks: bricks(s).finish().map_err(anyhowing)?.1,
+        })
+    }
+}
+
+impl Snapshot {
+    /// Lets the bricks fall from the lowest one up, until each rests on the ground or on others
+    pub fn settle(&self) -> Stack {
+        let n = self.bricks.len();
+        let mut stack = Stack {
+            bricks: self.bricks.clone(),
+            supports: vec![Vec::new(); n],
+            supported_by: vec![Vec::new(); n],
+        };
+        // Height of the topmost cube above each cell of the ground & the brick it belongs to
+        let mut heights = HashMap::<Coord, (i32, usize)>::new();
+        for i in (0..n).sorted_by_key(|i| self.bricks[*i].min.z) {
+            let brick = self.bricks[i];
+            let below = brick
+                .footprint()
+                .filter_map(|c| heights.get(&c).copied())
+                .collect::<Vec<_>>();
+            let rest = below.iter().map(|(z, _)| *z).max().unwrap_or(0);
+            let supporters = below
+                .into_iter()
+                .filter(|(z, _)| *z == rest)
+                .map(|(_, j)| j)
+                .unique()
+                .collect::<Vec<_>>();
+            for j in &supporters {
+                stack.supports[*j].push(i);
+            }
+            stack.supported_by[i] = supporters;
+
+            let settled = brick.lowered_to(rest + 1);
+            for c in settled.footprint() {
+                heights.insert(c, (settled.max.z, i));
+            }
+            stack.bricks[i] = settled;
+        }
+        stack
+    }
+}
+
+/// Bricks after they all fell as far as they could, and which of them rest on which
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stack {
+    /// In the order of the [`Snapshot`]
+    pub bricks: Vec<Brick>,
+    /// Indices of the bricks resting directly on top of each brick
+    pub supports: Vec<Vec<usize>>,
+    /// Indices of the bricks each brick rests directly on, none for the ones on the ground
+    pub supported_by: Vec<Vec<usize>>,
+}
+
+impl Stack {
+    /// Whether the `i`th brick can go without any other falling, since all the ones on top of it
+    /// rest on others as well
+    pub fn is_disintegrable(&self, i: usize) -> bool {
+        self.supports[i]
+            .iter()
+            .all(|j| self.supported_by[*j].len() > 1)
+    }
+
+    /// How many bricks can go on their own (part one)
+    pub fn disintegrable(&self) -> usize {
+        (0..self.bricks.len())
+            .filter(|i| self.is_disintegrable(*i))
+            .count()
+    }
+
+    /// Bricks falling one after another when the `i`th one goes, without the `i`th itself
+    pub fn chain_reaction(&self, i: usize) -> Vec<usize> {
+        let mut falling = HashSet::from([i]);
+        let mut fallen = Vec::new();
+        let mut queue = VecDeque::from([i]);
+        while let Some(k) = queue.pop_front() {
+            for &j in &self.supports[k] {
+                if !falling.contains(&j) && self.supported_by[j].iter().all(|s| falling.contains(s))
+                {
+                    falling.insert(j);
+                    fallen.push(j);
+                    queue.push_back(j);
+                }
+            }
+        }
+        fallen
+    }
+
+    /// How many bricks fall in all chain reactions together (part two)
+    pub fn falls(&self) -> usize {
+        (0..self.bricks.len())
+            .map(|i| self.chain_reaction(i).len())
+            .sum()
+    }
+}
+
+pub fn solve(part: Part, input: &str) -> anyhow::Result<usize> {
+    let stack = Snapshot::from_str(input)?.settle();
+    Ok(match part {
+        Part::One => stack.disintegrable(),
+        Part::Two => stack.falls(),
+    })
+}
+
+/// Day 22: Sand Slabs
+#[derive(Debug, Clone, Copy)]
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    fn name(&self) -> &'static str {
+        "twentysecond"
+    }
+
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::One, input).map(|x| x.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::Two, input).map(|x| x.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn sample() -> Stack {
+        Snapshot::from_str(include_str!("../../sample/twentysecond.txt"))
+            .expect("parsing")
+            .settle()
+    }
+
+    #[rstest]
+    #[case(Part::One, 5)]
+    #[case(Part::Two, 7)]
+    fn samples(#[case] part: Part, #[case] expected: usize) {
+        let input = include_str!("../../sample/twentysecond.txt");
+        assert_eq!(solve(part, input).expect("solving"), expected);
+    }
+
+    #[rstest]
+    fn bricks_settle_onto_each_other() {
+        let stack = sample();
+        assert_eq!(
+            stack.bricks.iter().map(|b| b.min.z).collect::<Vec<_>>(),
+            [1, 2, 2, 3, 3, 4, 5]
+        );
+        assert_eq!(stack.bricks[6].max.z, 6);
+        assert_eq!(stack.supports[0], [1, 2]);
+        assert_eq!(stack.supported_by[3], [1, 2]);
+        assert!(stack.supported_by[0].is_empty());
+    }
+
+    #[rstest]
+    #[case(0, 6)]
+    #[case(1, 0)]
+    #[case(5, 1)]
+    #[case(6, 0)]
+    fn chain_reactions(#[case] i: usize, #[case] expected: usize) {
+        assert_eq!(sample().chain_reaction(i).len(), expected);
+    }
+
+    #[rstest]
+    fn corners_in_any_order() {
+        let brick = Brick::new(Coord3::new(2, 0, 5), Coord3::new(0, 0, 5));
+        assert_eq!(brick.min, Coord3::new(0, 0, 5));
+        assert_eq!(brick.footprint().count(), 3);
+        assert_eq!(brick.cubes().count(), 3);
+        assert_eq!(brick.lowered_to(1).max, Coord3::new(2, 0, 1));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("1,0,1~1,2")]
+    #[case("1,0,1-1,2,1")]
+    fn invalid_snapshots(#[case] input: &str) {
+        assert!(Snapshot::from_str(input).is_err());
+    }
+}
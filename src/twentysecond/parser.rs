@@ -0,0 +1,25 @@
+use nom::{
+    character::complete::{char, line_ending, multispace0},
+    multi::separated_list1,
+    sequence::separated_pair,
+    IResult, Parser as NomParser,
+};
+use nom_supreme::ParserExt;
+
+use super::Brick;
+use crate::parse::common::coord3;
+
+/// Both corners of a brick, e.g. `1,0,1~1,2,1`
+pub(crate) fn brick(s: &str) -> IResult<&str, Brick> {
+    separated_pair(coord3, char('~'), coord3)
+        .map(|(a, b)| Brick::new(a, b))
+        .parse(s)
+}
+
+pub(crate) fn bricks(s: &str) -> IResult<&str, Vec<Brick>> {
+    multispace0
+        .precedes(separated_list1(line_ending, brick))
+        .terminated(multispace0)
+        .all_consuming()
+        .parse(s)
+}
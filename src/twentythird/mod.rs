@@ -0,0 +1,253 @@
+use std::{collections::HashMap, str::FromStr};
+
+use anyhow::{anyhow, bail};
+use enum_iterator::all;
+
+use crate::{grid::Grid, Coord, Direction, Part, Solver};
+
+/// Anything but a path on the map of the hiking trails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Forest,
+    /// Icy slope, which can only be walked down into its direction (part one)
+    Slope(Direction),
+}
+
+impl TryFrom<char> for Tile {
+    type Error = anyhow::Error;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '#' => Ok(Self::Forest),
+            '^' => Ok(Self::Slope(Direction::Up)),
+            '>' => Ok(Self::Slope(Direction::Right)),
+            'v' => Ok(Self::Slope(Direction::Down)),
+            '<' => Ok(Self::Slope(Direction::Left)),
+            _ => Err(anyhow!("Unknown tile: {c}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trails {
+    tiles: Grid<Tile>,
+    start: Coord,
+    end: Coord,
+}
+
+impl FromStr for Trails {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tiles = Grid::<Tile>::from_str(s.trim())?;
+        let gap = |y| {
+            let gaps = tiles
+                .row(y)
+                .filter(|(_, tile)| tile.is_none())
+                .map(|(c, _)| c)
+                .collect::<Vec<_>>();
+            match gaps[..] {
+                [gap] => Ok(gap),
+                _ => Err(anyhow!("Row {y} has {} paths instead of one", gaps.len())),
+            }
+        };
+        Ok(Self {
+            start: gap(0)?,
+            end: gap(tiles.nrows() - 1)?,
+            tiles,
+        })
+    }
+}
+
+impl Trails {
+    pub fn start(&self) -> Coord {
+        self.start
+    }
+
+    pub fn end(&self) -> Coord {
+        self.end
+    }
+
+    pub fn is_path(&self, c: Coord) -> bool {
+        self.tiles.contains(c) && self.tiles.get(c) != Some(&Tile::Forest)
+    }
+
+    /// Paths one step away from `c`, only the one down the slope if standing on a `slippery` one
+    pub fn successors(&self, c: Coord, slippery: bool) -> Vec<Coord> {
+        match self.tiles.get(c) {
+            Some(Tile::Slope(dir)) if slippery => vec![c + Coord::from(*dir)],
+            _ => all::<Direction>().map(|dir| c + Coord::from(dir)).collect(),
+        }
+        .into_iter()
+        .filter(|next| self.is_path(*next))
+        .collect()
+    }
+
+    /// Where paths fork, plus the start & the end
+    pub fn is_junction(&self, c: Coord) -> bool {
+        c == self.start
+            || c == self.end
+            || all::<Direction>()
+                .filter(|dir| self.is_path(c + Coord::from(*dir)))
+                .count()
+                >= 3
+    }
+
+    /// Junctions connected by the corridors between them, whose lengths become the edges'
+    /// weights. The start is the first junction & the end the second one
+    pub fn contract(&self, slippery: bool) -> anyhow::Result<Junctions> {
+        let coords = [self.start, self.end]
+            .into_iter()
+            .chain(
+                self.tiles
+                    .coords()
+                    .filter(|c| *c != self.start && *c != self.end)
+                    .filter(|c| self.is_path(*c) && self.is_junction(*c)),
+            )
+            .collect::<Vec<_>>();
+        if coords.len() > u64::BITS as usize {
+            bail!(
+                "Too many junctions to remember in a bitmask: {}",
+                coords.len()
+            );
+        }
+        let indices = coords
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (*c, i))
+            .collect::<HashMap<_, _>>();
+        let edges = coords
+            .iter()
+            .map(|junction| {
+                self.successors(*junction, slippery)
+                    .into_iter()
+                    .filter_map(|first| {
+                        let (mut previous, mut current, mut length) = (*junction, first, 1);
+                        while !indices.contains_key(&current) {
+                            let next = self
+                                .successors(current, slippery)
+                                .into_iter()
+                                .find(|next| *next != previous)?;
+                            (previous, current, length) = (current, next, length + 1);
+                        }
+                        Some((indices[&current], length))
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(Junctions { coords, edges })
+    }
+}
+
+/// Hiking trails contracted to their forks, see [`Trails::contract`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Junctions {
+    pub coords: Vec<Coord>,
+    /// Junctions reachable from each one without passing another, and the steps it takes
+    pub edges: Vec<Vec<(usize, u32)>>,
+}
+
+impl Junctions {
+    /// Steps of the longest hike from the `from`th to the `to`th junction, which visits none
+    /// twice. `None` if there's no way at all
+    pub fn longest_path(&self, from: usize, to: usize) -> Option<u32> {
+        // Once next to a dead end like the exit, turning elsewhere would cut it off for good
+        let into = self
+            .edges
+            .iter()
+            .filter(|edges| edges.iter().any(|(j, _)| *j == to));
+        let last = match into.collect::<Vec<_>>()[..] {
+            [edges] => edges
+                .iter()
+                .find(|(j, _)| *j == to)
+                .map(|(_, length)| *length),
+            _ => None,
+        };
+        self.longest(from, to, 0, last)
+    }
+
+    fn longest(&self, at: usize, to: usize, visited: u64, last: Option<u32>) -> Option<u32> {
+        if at == to {
+            return Some(0);
+        }
+        let visited = visited | 1 << at;
+        if let Some(length) = last.filter(|_| self.edges[at].iter().any(|(j, _)| *j == to)) {
+            return Some(length);
+        }
+        self.edges[at]
+            .iter()
+            .filter(|(j, _)| visited & 1 << j == 0)
+            .filter_map(|(j, length)| Some(length + self.longest(*j, to, visited, last)?))
+            .max()
+    }
+}
+
+pub fn solve(part: Part, input: &str) -> anyhow::Result<u32> {
+    let junctions = Trails::from_str(input)?.contract(part == Part::One)?;
+    junctions
+        .longest_path(0, 1)
+        .ok_or(anyhow!("There is no hike from the start to the end"))
+}
+
+/// Day 23: A Long Walk
+#[derive(Debug, Clone, Copy)]
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    fn name(&self) -> &'static str {
+        "twentythird"
+    }
+
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::One, input).map(|x| x.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        solve(Part::Two, input).map(|x| x.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn sample() -> Trails {
+        Trails::from_str(include_str!("../../sample/twentythird.txt")).expect("parsing")
+    }
+
+    #[rstest]
+    #[case(Part::One, 94)]
+    #[case(Part::Two, 154)]
+    fn samples(#[case] part: Part, #[case] expected: u32) {
+        let input = include_str!("../../sample/twentythird.txt");
+        assert_eq!(solve(part, input).expect("solving"), expected);
+    }
+
+    #[rstest]
+    #[case(true, 12)]
+    #[case(false, 24)]
+    fn contracts_to_junctions(#[case] slippery: bool, #[case] edges: usize) {
+        let trails = sample();
+        let junctions = trails.contract(slippery).expect("contracting");
+        assert_eq!(junctions.coords.len(), 9);
+        assert_eq!(junctions.coords[..2], [trails.start(), trails.end()]);
+        assert_eq!(junctions.edges.iter().map(Vec::len).sum::<usize>(), edges);
+    }
+
+    #[rstest]
+    fn slopes_lead_downhill() {
+        let trails = sample();
+        let slope = Coord::new(10, 3);
+        assert_eq!(trails.successors(slope, true), [Coord::new(11, 3)]);
+        assert_eq!(trails.successors(slope, false).len(), 2);
+    }
+
+    #[rstest]
+    #[case("#..\n#.#")]
+    #[case("#.#\n...\n###")]
+    #[case("#.#\n#x#\n#.#")]
+    fn invalid_trails(#[case] input: &str) {
+        assert!(Trails::from_str(input).is_err());
+    }
+}
@@ -0,0 +1,371 @@
+use std::fmt::Display;
+
+use crate::lerp;
+
+use bevy::{prelude::*, sprite::Anchor};
+
+const MOTION: f32 = 5.;
+const VANISHED: f32 = 0.01;
+const SI_PREFIXES: [char; 8] = ['k', 'M', 'G', 'T', 'P', 'E', 'Z', 'Y'];
+
+/// Reusable UI pieces, which the days' animations share. Add this plugin to an `App` to have
+/// the widgets' lifecycle systems run
+#[derive(Debug)]
+pub struct WidgetsPlugin;
+
+impl Plugin for WidgetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                score_popup_fader,
+                score_popup_mover,
+                bar_chart_drawer,
+                big_number_writer,
+            ),
+        );
+    }
+}
+
+/// Floating text like "+100*4", which drifts upwards from where it was spawned while fading
+/// out and despawns itself once it became invisible
+#[derive(Debug, Component)]
+pub struct ScorePopup {
+    /// Opacity of the popup, decaying from 1 to 0
+    life: f32,
+    /// Height the popup is rising to
+    target: f32,
+}
+
+impl ScorePopup {
+    /// Popup showing `label` at `position`, which will rise by `rise` until it vanishes
+    pub fn bundle(
+        label: impl Into<String>,
+        style: TextStyle,
+        position: Vec3,
+        rise: f32,
+    ) -> (Self, Text2dBundle) {
+        (
+            Self {
+                life: 1.,
+                target: position.y + rise,
+            },
+            Text2dBundle {
+                text: Text::from_section(label, style),
+                transform: Transform::from_translation(position),
+                text_anchor: Anchor::CenterRight,
+                ..default()
+            },
+        )
+    }
+}
+
+fn score_popup_fader(
+    mut cmd: Commands,
+    time: Res<Time>,
+    mut popups: Query<(Entity, &mut ScorePopup, &mut Text)>,
+) {
+    let dt = time.delta_seconds();
+    for (id, mut popup, mut text) in popups.iter_mut() {
+        popup.life = lerp(popup.life, 0., MOTION * dt);
+        if popup.life < VANISHED {
+            cmd.entity(id).despawn_recursive();
+            continue;
+        }
+        for section in text.sections.iter_mut() {
+            section.style.color = section.style.color.with_a(popup.life);
+        }
+    }
+}
+
+fn score_popup_mover(time: Res<Time>, mut popups: Query<(&ScorePopup, &mut Transform)>) {
+    let dt = time.delta_seconds();
+    for (popup, mut tf) in popups.iter_mut() {
+        tf.translation.y = lerp(tf.translation.y, popup.target, MOTION * dt);
+    }
+}
+
+/// Bars side by side, e.g. one per column of a grid, growing from the chart's origin along its
+/// local y axis (rotate or flip its transform for other directions). They are colored from cyan
+/// (empty) to red (full) and scaled to the largest value, unless there is a fixed `scale`
+#[derive(Debug, Clone, Component)]
+pub struct BarChart {
+    pub values: Vec<f32>,
+    /// Value, which fills a whole bar
+    pub scale: Option<f32>,
+    /// Length of a full bar
+    pub length: f32,
+    /// Distance between neighboring bars along the local x axis
+    pub spacing: f32,
+}
+
+/// One of the sprites of a [`BarChart`], by index
+#[derive(Debug, Component)]
+struct Bar(usize);
+
+impl BarChart {
+    /// Chart without any values yet, which will be drawn at `transform`
+    pub fn bundle(length: f32, spacing: f32, transform: Transform) -> (Self, SpatialBundle) {
+        (
+            Self {
+                values: Vec::new(),
+                scale: None,
+                length,
+                spacing,
+            },
+            SpatialBundle::from_transform(transform),
+        )
+    }
+
+    /// Share of a full bar, which the `i`th one fills
+    fn fill(&self, i: usize) -> f32 {
+        let scale = self
+            .scale
+            .unwrap_or_else(|| self.values.iter().copied().fold(0., f32::max));
+        if scale <= 0. {
+            return 0.;
+        }
+        (self.values[i] / scale).clamp(0., 1.)
+    }
+
+    fn bar(&self, i: usize) -> SpriteBundle {
+        let fill = self.fill(i);
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::hsl(lerp(180., 0., fill), 0.5, 0.4),
+                custom_size: Some(Vec2::new(0.8 * self.spacing, fill * self.length)),
+                anchor: Anchor::BottomCenter,
+                ..default()
+            },
+            transform: Transform::from_xyz(i as f32 * self.spacing, 0., 0.),
+            ..default()
+        }
+    }
+}
+
+fn bar_chart_drawer(
+    mut cmd: Commands,
+    charts: Query<(Entity, &BarChart, Option<&Children>), Changed<BarChart>>,
+    mut bars: Query<(&Bar, &mut Sprite, &mut Transform)>,
+) {
+    for (id, chart, children) in charts.iter() {
+        let existing = children
+            .map(|children| {
+                children
+                    .iter()
+                    .copied()
+                    .filter(|child| bars.contains(*child))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        if existing.len() != chart.values.len() {
+            for bar in existing {
+                cmd.entity(bar).despawn_recursive();
+            }
+            cmd.entity(id).with_children(|parent| {
+                for i in 0..chart.values.len() {
+                    parent.spawn((Bar(i), chart.bar(i)));
+                }
+            });
+            continue;
+        }
+        for child in existing {
+            let Ok((bar, mut sprite, mut tf)) = bars.get_mut(child) else {
+                continue;
+            };
+            let new = chart.bar(bar.0);
+            *sprite = new.sprite;
+            *tf = new.transform;
+        }
+    }
+}
+
+/// Base, in which a [`BigNumber`] is written
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Radix {
+    #[default]
+    Decimal,
+    Hex,
+    Binary,
+}
+
+/// Number too long to read at a glance, e.g. a location of Day 5, with its digits grouped like
+/// `4,294,967,296` or `0xdead_beef` or abbreviated like `4.29G`. Also a component, whose entity's
+/// `Text` gets rewritten whenever the number changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct BigNumber {
+    pub value: i128,
+    pub radix: Radix,
+    /// Whether to shorten decimal values from 1000 on to three significant digits & an SI prefix
+    pub abbreviate: bool,
+}
+
+impl BigNumber {
+    pub fn new(value: impl Into<i128>) -> Self {
+        Self {
+            value: value.into(),
+            radix: Radix::Decimal,
+            abbreviate: false,
+        }
+    }
+
+    pub fn radix(mut self, radix: Radix) -> Self {
+        self.radix = radix;
+        self
+    }
+
+    pub fn abbreviated(mut self) -> Self {
+        self.abbreviate = true;
+        self
+    }
+}
+
+impl Display for BigNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.value < 0 { "-" } else { "" };
+        let magnitude = self.value.unsigned_abs();
+        match self.radix {
+            Radix::Decimal if self.abbreviate && magnitude >= 1000 => {
+                let (mut scaled, mut prefix) = (magnitude as f64, 0);
+                // Rounding to three digits could make 999.9k a "1000k" otherwise
+                while scaled >= 999.5 && prefix < SI_PREFIXES.len() {
+                    scaled /= 1000.;
+                    prefix += 1;
+                }
+                let decimals = match scaled {
+                    s if s < 9.995 => 2,
+                    s if s < 99.95 => 1,
+                    _ => 0,
+                };
+                write!(f, "{sign}{scaled:.decimals$}{}", SI_PREFIXES[prefix - 1])
+            }
+            Radix::Decimal => write!(f, "{sign}{}", group(&magnitude.to_string(), 3, ',')),
+            Radix::Hex => write!(f, "{sign}0x{}", group(&format!("{magnitude:x}"), 4, '_')),
+            Radix::Binary => write!(f, "{sign}0b{}", group(&format!("{magnitude:b}"), 4, '_')),
+        }
+    }
+}
+
+/// `digits` with a `separator` between every `n` of them, counting from the right
+fn group(digits: &str, n: usize, separator: char) -> String {
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / n);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % n == 0 {
+            grouped.push(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+fn big_number_writer(mut numbers: Query<(&BigNumber, &mut Text), Changed<BigNumber>>) {
+    for (number, mut text) in numbers.iter_mut() {
+        if let Some(section) = text.sections.first_mut() {
+            section.value = number.to_string();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HeadlessPlugins;
+    use bevy::time::TimeUpdateStrategy;
+    use itertools::Itertools;
+    use rstest::rstest;
+    use std::time::Duration;
+
+    #[test]
+    fn score_popup_rises_fades_and_despawns() {
+        let mut app = App::new();
+        app.add_plugins((HeadlessPlugins, WidgetsPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                50,
+            )));
+        let id = app
+            .world
+            .spawn(ScorePopup::bundle("+1", default(), Vec3::ZERO, 10.))
+            .id();
+
+        // The very first update has no time delta yet
+        app.update();
+        app.update();
+        let y = app.world.get::<Transform>(id).expect("popup").translation.y;
+        let alpha = app.world.get::<Text>(id).expect("popup").sections[0]
+            .style
+            .color
+            .a();
+        assert!(0. < y && y < 10., "{y}");
+        assert!(alpha < 1., "{alpha}");
+
+        for _ in 0..100 {
+            app.update();
+        }
+        assert!(app.world.get_entity(id).is_none());
+    }
+
+    #[test]
+    fn bar_chart_scales_to_largest_value() {
+        let mut app = App::new();
+        app.add_plugins((HeadlessPlugins, WidgetsPlugin));
+        let chart = app
+            .world
+            .spawn(BarChart::bundle(100., 10., Transform::default()))
+            .id();
+        let heights = |app: &mut App| {
+            app.update();
+            app.world
+                .query::<(&Bar, &Sprite)>()
+                .iter(&app.world)
+                .map(|(bar, sprite)| (bar.0, sprite.custom_size.expect("size").y))
+                .sorted_by_key(|(i, _)| *i)
+                .map(|(_, height)| height)
+                .collect::<Vec<_>>()
+        };
+
+        app.world.get_mut::<BarChart>(chart).expect("chart").values = vec![1., 4., 2.];
+        assert_eq!(heights(&mut app), vec![25., 100., 50.]);
+
+        let mut bar_chart = app.world.get_mut::<BarChart>(chart).expect("chart");
+        bar_chart.values = vec![2., 0.];
+        bar_chart.scale = Some(8.);
+        assert_eq!(heights(&mut app), vec![25., 0.]);
+    }
+
+    #[rstest]
+    #[case(BigNumber::new(0), "0")]
+    #[case(BigNumber::new(999), "999")]
+    #[case(BigNumber::new(1000), "1,000")]
+    #[case(BigNumber::new(-4294967296_i64), "-4,294,967,296")]
+    #[case(BigNumber::new(999).abbreviated(), "999")]
+    #[case(BigNumber::new(12345).abbreviated(), "12.3k")]
+    #[case(BigNumber::new(999_999).abbreviated(), "1.00M")]
+    #[case(BigNumber::new(4294967296_i64).abbreviated(), "4.29G")]
+    #[case(BigNumber::new(-123_456_789).abbreviated(), "-123M")]
+    #[case(BigNumber::new(0xdeadbeef_u32).radix(Radix::Hex), "0xdead_beef")]
+    #[case(BigNumber::new(0x1beef).radix(Radix::Hex), "0x1_beef")]
+    #[case(BigNumber::new(37).radix(Radix::Binary), "0b10_0101")]
+    #[case(BigNumber::new(-5).radix(Radix::Binary).abbreviated(), "-0b101")]
+    fn big_numbers(#[case] number: BigNumber, #[case] expected: &str) {
+        assert_eq!(number.to_string(), expected);
+    }
+
+    #[test]
+    fn big_number_rewrites_its_text() {
+        let mut app = App::new();
+        app.add_plugins((HeadlessPlugins, WidgetsPlugin));
+        let id = app
+            .world
+            .spawn((BigNumber::new(1234567), Text::from_section("", default())))
+            .id();
+        let text = |app: &mut App| {
+            app.update();
+            app.world.get::<Text>(id).expect("text").sections[0]
+                .value
+                .clone()
+        };
+        assert_eq!(text(&mut app), "1,234,567");
+        app.world.get_mut::<BigNumber>(id).expect("number").value = 42;
+        assert_eq!(text(&mut app), "42");
+    }
+}